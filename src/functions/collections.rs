@@ -1,18 +1,21 @@
 use std::str::FromStr;
 use regex::{Regex, Captures};
-use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::prelude::{ToPrimitive, Decimal, Zero};
 use std::collections::BTreeMap;
 use serde::{Deserialize, Serialize};
 use lazy_static::lazy_static;
 
 use crate::functions::*;
+use crate::storage::columns::number::{CurrencyAmount, GenerateNumberColumn};
+use crate::storage::columns::structure::TagsColumn;
 
 
 lazy_static! {
     static ref RE_MIN: Regex = Regex::new(r#"^MIN\((?P<sequence>[\d\s,.-]+)\)|MIN\((?P<sequence_ref>\{[\w\s]+\})\)"#).unwrap();
     static ref RE_MAX: Regex = Regex::new(r#"^MAX\((?P<sequence>[\d\s,.-]+)\)|MAX\((?P<sequence_ref>\{[\w\s]+\})\)"#).unwrap();
-    static ref RE_AVG: Regex = Regex::new(r#"^AVG\((?P<sequence>[\d\s,.-]+)\)|MAX\((?P<sequence_ref>\{[\w\s]+\})\)"#).unwrap();
-    static ref RE_SUM: Regex = Regex::new(r#"^SUM\((?P<sequence>[\d\s,.-]+)\)|MAX\((?P<sequence_ref>\{[\w\s]+\})\)"#).unwrap();
+    static ref RE_AVG: Regex = Regex::new(r#"^AVG\((?P<sequence>[\d\s,.-]+)\)|AVG\((?P<sequence_ref>\{[\w\s]+\})([\s]*,[\s]*(?P<currency>"[A-Za-z]{3}"))?\)"#).unwrap();
+    static ref RE_SUM: Regex = Regex::new(r#"^SUM\((?P<sequence>[\d\s,.-]+)\)|SUM\((?P<sequence_ref>\{[\w\s]+\})([\s]*,[\s]*(?P<currency>"[A-Za-z]{3}"))?\)"#).unwrap();
+    static ref RE_HAS_TAG: Regex = Regex::new(r#"^HAS_TAG\([\s\n\t]{0,}(?P<column>\{[\w\s]+\})[\s\n\t]{0,},[\s\n\t]{0,}(?P<tag>"[^"]+")[\s\n\t]{0,}\)"#).unwrap();
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -58,6 +61,7 @@ impl CollectionStatsFunction for Stats {
         // MAX({Column})
         // AVG({Column})
         // SUM({Column})
+        // SUM({Amount}, "EUR") : Currency amounts converted into EUR
         let function_parse = &self.function.clone().unwrap();
         let data_map = self.data_map.clone();
         let expr_min = &RE_MIN;
@@ -98,10 +102,10 @@ impl CollectionStatsFunction for Stats {
                         matches = expr_max.captures(function_text.as_str()).unwrap();
                     },
                     StatOption::Avg => {
-                        matches = expr_max.captures(function_text.as_str()).unwrap();
+                        matches = expr_avg.captures(function_text.as_str()).unwrap();
                     },
                     StatOption::Sum => {
-                        matches = expr_max.captures(function_text.as_str()).unwrap();
+                        matches = expr_sum.captures(function_text.as_str()).unwrap();
                     },
                 }        
                 let attr_sequence = matches.name("sequence");
@@ -116,6 +120,11 @@ impl CollectionStatsFunction for Stats {
                     let sequence_ref: String = attr_sequence_ref.unwrap().as_str().to_string();
                     attributes_.push(sequence_ref);
                 }
+                let attr_currency = matches.name("currency");
+                if attr_currency.is_some() {
+                    let currency: String = attr_currency.unwrap().as_str().to_string();
+                    attributes_.push(currency);
+                }
                 function.attributes = Some(attributes_);
             }
         }
@@ -131,8 +140,17 @@ impl CollectionStatsFunction for Stats {
         let attributes = self.attributes.clone().unwrap();
         let data_map = &self.data_map.clone().unwrap();
         let column_config_map = self.column_config_map.clone();
+        // Target currency is not part of the collection
+        let mut currency: Option<String> = None;
+        let mut collection_attributes = attributes.clone();
+        if attributes.len() > 1 && attributes[0].is_reference {
+            let currency_item = attributes[1].clone();
+            let currency_value = currency_item.get_value(data_map, None, &column_config_map)?;
+            currency = Some(currency_value.replace("\"", "").trim().to_uppercase());
+            collection_attributes = vec![attributes[0].clone()];
+        }
         let result = validate_collection_stats(
-            &attributes,
+            &collection_attributes,
             &column_config_map
         );
         if result.is_err() {
@@ -142,10 +160,26 @@ impl CollectionStatsFunction for Stats {
         let tuple = result.unwrap();
         let column_type = tuple.0;
         let is_collection_attribute = tuple.2;
+        let config = tuple.3;
         let mut sequence_list: Vec<f64> = Vec::new();
         let replacement_string: String;
+        let mut value_column_type = column_type.clone();
+        if column_type == COLUMN_TYPE_REFERENCE && config.remote_column_type.is_some() {
+            value_column_type = config.remote_column_type.clone().unwrap();
+        }
+        if value_column_type == COLUMN_TYPE_CURRENCY || currency.is_some() {
+            let attribute = &attributes[0];
+            let value_list: Vec<String>;
+            if is_collection_attribute {
+                value_list = attribute.get_values(data_map, &column_config_map)?;
+            } else {
+                value_list = vec![attribute.get_value(data_map, None, &column_config_map)?];
+            }
+            let exchange_rates = self.function.clone().unwrap().get_exchange_rates();
+            return execute_currency_stats(&option, &value_list, &config, currency, &exchange_rates)
+        }
         if is_collection_attribute {
-            let column_type = column_type.as_str();
+            let column_type = value_column_type.as_str();
             if column_type == COLUMN_TYPE_NUMBER || column_type == COLUMN_TYPE_GENERATE_NUMBER ||
                 column_type == COLUMN_TYPE_RATING {
                 let attribute = &attributes[0];
@@ -156,6 +190,12 @@ impl CollectionStatsFunction for Stats {
                 }
                 let value_list = result.unwrap();
                 for item in value_list {
+                    if column_type == COLUMN_TYPE_GENERATE_NUMBER {
+                        // Formatted sequences, INV-2024-00001
+                        let item_number = GenerateNumberColumn::get_sort_number(&item) as f64;
+                        sequence_list.push(item_number);
+                        continue
+                    }
                     let has_dot = item.clone().find(".");
                     let mut item_string = item.trim().to_string();
                     if has_dot.is_none() {
@@ -181,6 +221,9 @@ impl CollectionStatsFunction for Stats {
                 sequence_list.push(item_number);
             }
         }
+        if sequence_list.len() == 0 {
+            return Ok(String::from("0"))
+        }
         let stat_result: f64;
         match option {
             StatOption::Min => {
@@ -223,3 +266,154 @@ impl CollectionStatsFunction for Stats {
         return Ok(replacement_string)
     }
 }
+
+// Currency amounts are summed as decimals, converting each amount into the target currency (or the 
+// currency of first amount) with space exchange rates.
+fn execute_currency_stats(
+    option: &StatOption,
+    value_list: &Vec<String>,
+    config: &ColumnConfig,
+    currency: Option<String>,
+    exchange_rates: &BTreeMap<String, Decimal>,
+) -> Result<String, PlanetError> {
+    let mut default_currency = String::from(CURRENCY_CODE_DEFAULT);
+    if config.currency.is_some() {
+        default_currency = config.currency.clone().unwrap();
+    }
+    let mut number_decimals: u32 = 2;
+    if config.number_decimals.is_some() {
+        number_decimals = config.number_decimals.unwrap().to_u32().unwrap_or(2);
+    }
+    let mut amounts: Vec<CurrencyAmount> = Vec::new();
+    let mut target_currency = currency.clone();
+    for value in value_list {
+        let value = value.replace("\"", "");
+        if value.trim() == "" {
+            continue
+        }
+        let amount = CurrencyAmount::parse(&value, &default_currency)?;
+        if target_currency.is_none() {
+            target_currency = Some(amount.currency.clone());
+        }
+        let target_currency = target_currency.clone().unwrap();
+        amounts.push(amount.convert(&target_currency, exchange_rates)?);
+    }
+    let target_currency = target_currency.unwrap_or(default_currency);
+    let mut result = Decimal::zero();
+    if amounts.len() > 0 {
+        match option {
+            StatOption::Min => {
+                result = amounts[0].amount;
+                for item in &amounts {
+                    if item.amount < result {
+                        result = item.amount;
+                    }
+                }
+            },
+            StatOption::Max => {
+                result = amounts[0].amount;
+                for item in &amounts {
+                    if item.amount > result {
+                        result = item.amount;
+                    }
+                }
+            },
+            StatOption::Avg => {
+                for item in &amounts {
+                    result += item.amount;
+                }
+                result = result / Decimal::from(amounts.len());
+            },
+            StatOption::Sum => {
+                for item in &amounts {
+                    result += item.amount;
+                }
+            },
+        }
+    }
+    let result = CurrencyAmount{
+        amount: result,
+        currency: target_currency,
+    };
+    return Ok(result.serialize(number_decimals))
+}
+
+pub trait CollectionFunction {
+    fn handle(&mut self) -> Result<FunctionParse, PlanetError>;
+    fn execute(&self) -> Result<bool, PlanetError>;
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HasTag {
+    function: Option<FunctionParse>,
+    data_map: Option<BTreeMap<String, Vec<BTreeMap<String, String>>>>,
+    attributes: Option<Vec<FunctionAttributeItem>>,
+    column_config_map: BTreeMap<String, ColumnConfig>
+}
+impl HasTag {
+    pub fn defaults(
+        function: Option<FunctionParse>, 
+        data_map: Option<BTreeMap<String, Vec<BTreeMap<String, String>>>>,
+        column_config_map: &BTreeMap<String, ColumnConfig>
+    ) -> Self {
+        let column_config_map = column_config_map.clone();
+        return Self{
+            function: function,
+            data_map: data_map,
+            attributes: None,
+            column_config_map: column_config_map
+        };
+    }
+}
+impl CollectionFunction for HasTag {
+    fn handle(&mut self) -> Result<FunctionParse, PlanetError> {
+        // HAS_TAG({Tags}, "project/alpha") : true for project/alpha and project/alpha/design
+        let function_parse = &self.function.clone().unwrap();
+        let data_map = self.data_map.clone();
+        let expr = &RE_HAS_TAG;
+        let mut function = function_parse.clone();
+        let data_map_wrap = data_map.clone();
+        let (
+            function_text_wrap,
+            function_text,
+            compiled_attributes,
+            mut function_result,
+            data_map,
+        ) = prepare_function_parse(function_parse, data_map.clone());
+        if function_text_wrap.is_some() {
+            function.validate = Some(expr.is_match(function_text.as_str()));
+            if function.validate.unwrap() {
+                let mut attributes_: Vec<String> = Vec::new();
+                let matches = &expr.captures(&function_text).unwrap();
+                let column = matches.name("column").unwrap().as_str().to_string();
+                let tag = matches.name("tag").unwrap().as_str().to_string();
+                attributes_.push(column);
+                attributes_.push(tag);
+                function.attributes = Some(attributes_);
+            }
+        }
+        if data_map_wrap.is_some() {
+            self.attributes = Some(compiled_attributes);
+            self.data_map = Some(data_map);
+            function_result.check = Some(self.execute()?);
+            function.result = Some(function_result.clone());
+        }
+        return Ok(function)
+    }
+    fn execute(&self) -> Result<bool, PlanetError> {
+        let attributes = self.attributes.clone().unwrap();
+        let data_map = &self.data_map.clone().unwrap();
+        let column_config_map = self.column_config_map.clone();
+        let column_item = attributes[0].clone();
+        let tag_item = attributes[1].clone();
+        let tag = tag_item.get_value(data_map, None, &column_config_map)?;
+        let tag = TagsColumn::normalize(&tag.replace("\"", ""))?;
+        // Items without tags have no entry in data map
+        let tags = column_item.get_values(data_map, &column_config_map);
+        if tags.is_err() {
+            return Ok(false)
+        }
+        let tags = tags.unwrap();
+        return Ok(TagsColumn::has_tag(&tags, &tag))
+    }
+}
@@ -35,6 +35,9 @@ pub const FUNCTION_INT: &str = "INT";
 pub const FUNCTION_LOG: &str = "LOG";
 pub const FUNCTION_MAX: &str = "MAX";
 pub const FUNCTION_MIN: &str = "MIN";
+pub const FUNCTION_AVG: &str = "AVG";
+pub const FUNCTION_SUM: &str = "SUM";
+pub const FUNCTION_CONVERT: &str = "CONVERT";
 pub const FUNCTION_MOD: &str = "MOD";
 pub const FUNCTION_POWER: &str = "POWER";
 pub const FUNCTION_ROUND: &str = "ROUND";
@@ -45,6 +48,21 @@ pub const FUNCTION_VALUE: &str = "VALUE";
 pub const FUNCTION_CREATED_TIME: &str = "CREATED_TIME";
 pub const FUNCTION_DATEADD: &str = "DATEADD";
 pub const FUNCTION_DATEDIF: &str = "DATEDIF";
+pub const FUNCTION_TOSECONDS: &str = "TOSECONDS";
+pub const FUNCTION_FROMSECONDS: &str = "FROMSECONDS";
+pub const FUNCTION_ADDDURATION: &str = "ADDDURATION";
+pub const FUNCTION_TIME_IN_STATE: &str = "TIME_IN_STATE";
+pub const FUNCTION_NEXT_OCCURRENCE: &str = "NEXT_OCCURRENCE";
+pub const FUNCTION_OCCURRENCES: &str = "OCCURRENCES";
+pub const FUNCTION_OCCURS_BETWEEN: &str = "OCCURS_BETWEEN";
+pub const FUNCTION_DISTANCE: &str = "DISTANCE";
+pub const FUNCTION_WITHIN_RADIUS: &str = "WITHIN_RADIUS";
+pub const FUNCTION_WITHIN_BOX: &str = "WITHIN_BOX";
+pub const FUNCTION_EMAIL_DOMAIN: &str = "EMAIL_DOMAIN";
+pub const FUNCTION_URL_HOST: &str = "URL_HOST";
+pub const FUNCTION_URL_PATH: &str = "URL_PATH";
+pub const FUNCTION_URL_QUERY_PARAM: &str = "URL_QUERY_PARAM";
+pub const FUNCTION_HAS_TAG: &str = "HAS_TAG";
 pub const FUNCTION_LAST_MODIFIED_TIME: &str = "LAST_MODIFIED_TIME";
 pub const FUNCTION_RECORD_ID: &str = "RECORD_ID";
 pub const FUNCTION_TRUE: &str = "TRUE";
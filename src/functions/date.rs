@@ -3,11 +3,14 @@ use regex::{Regex, Captures};
 use std::collections::BTreeMap;
 use serde::{Deserialize, Serialize};
 use lazy_static::lazy_static;
-use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, Utc, Duration, Timelike};
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, Utc, Duration, Timelike};
+use chrono_tz::Tz;
 use tr::tr;
 use ordinal::Ordinal;
 use crate::functions::*;
 use crate::statements::folder::schema::*;
+use crate::storage::columns::structure::WorkflowColumn;
+use crate::storage::columns::date::{get_time_zone, localize_date_time, parse_duration, get_duration_canonical, format_duration, Recurrence, RECURRENCE_MAX_OCCURRENCES};
 
 lazy_static! {
     pub static ref RE_DATE: Regex = Regex::new(r#"^DATE\([\s\n\t]{0,}(?P<year>(([\s\d+-/*]*)|(\{[\w\s]+\})|([A-Z]+\(.[^)]*\))))[\s\n\t]{0,},[\s\n\t]{0,}(?P<month>(([\s\d+-/*]+)|(\{[\w\s]+\})|([A-Z]+\(.[^)]*\))))[\s\n\t]{0,},[\s\n\t]{0,}(?P<day>(([\s\d+-/*]+)|(\{[\w\s]+\})|([A-Z]+\(.[^)]*\))))[\s\n\t]{0,}\)"#).unwrap();
@@ -19,11 +22,17 @@ lazy_static! {
     pub static ref RE_HOUR: Regex = Regex::new(r#"^HOUR\([\s\n\t]{0,}(?P<date>(("\d{4}-\d{2}-\d{2}T{0,1}[\s]{0,1}\d{2}:\d{2}:\d{2}(\+\d{2}:\d{2}){0,1}")|([A-Z]+\(.[^)]*\))))[\s\n\t]{0,}\)|HOUR\([\s\n\t]{0,}(?P<date_alt>"\d{2}-[a-zA-Z]{3}-\d{4}([\s]{0,1}\d{2}:\d{2}:\d{2}){0,1}(\+\d{2}:\d{2}){0,1}")[\s\n\t]{0,}\)|^HOUR\([\s\n\t]{0,}(?P<date_ref>\{[\w\s]+\})[\s\n\t]{0,}\)"#).unwrap();
     pub static ref RE_MINUTE: Regex = Regex::new(r#"MINUTE\([\s\n\t]{0,}(?P<date>(("\d{4}-\d{2}-\d{2}T{0,1}[\s]{0,1}\d{2}:\d{2}:\d{2}(\+\d{2}:\d{2}){0,1}")|([A-Z]+\(.[^)]*\))))[\s\n\t]{0,}\)|MINUTE\([\s\n\t]{0,}(?P<date_alt>"\d{2}-[a-zA-Z]{3}-\d{4}([\s]{0,1}\d{2}:\d{2}:\d{2}){0,1}(\+\d{2}:\d{2}){0,1}")[\s\n\t]{0,}\)|^MINUTE\([\s\n\t]{0,}(?P<date_ref>\{[\w\s]+\})[\s\n\t]{0,}\)"#).unwrap();
     pub static ref RE_SECOND: Regex = Regex::new(r#"SECOND\([\s\n\t]{0,}(?P<date>(("\d{4}-\d{2}-\d{2}T{0,1}[\s]{0,1}\d{2}:\d{2}:\d{2}(\+\d{2}:\d{2}){0,1}")|([A-Z]+\(.[^)]*\))))[\s\n\t]{0,}\)|SECOND\([\s\n\t]{0,}(?P<date_alt>"\d{2}-[a-zA-Z]{3}-\d{4}([\s]{0,1}\d{2}:\d{2}:\d{2}){0,1}(\+\d{2}:\d{2}){0,1}")[\s\n\t]{0,}\)|^SECOND\([\s\n\t]{0,}(?P<date_ref>\{[\w\s]+\})[\s\n\t]{0,}\)"#).unwrap();
-    pub static ref RE_NOW: Regex = Regex::new(r#"^NOW\(\)"#).unwrap();
-    pub static ref RE_TODAY: Regex = Regex::new(r#"^TODAY\(\)"#).unwrap();
+    pub static ref RE_NOW: Regex = Regex::new(r#"^NOW\([\s\n\t]{0,}(?P<zone>"[\w/+\-]+"){0,1}[\s\n\t]{0,}\)"#).unwrap();
+    pub static ref RE_TODAY: Regex = Regex::new(r#"^TODAY\([\s\n\t]{0,}(?P<zone>"[\w/+\-]+"){0,1}[\s\n\t]{0,}\)"#).unwrap();
     pub static ref RE_DAYS: Regex = Regex::new(r#"(^DAYS\([\s\n\t]{0,}(?P<start_date>(("\d{1,2}-[a-zA-Z]{3}-\d{4}")|([A-Z]+\(.[^)]*\))))[\s\n\t]{0,},[\s\n\t]{0,}(?P<end_date>(("\d{1,2}-[a-zA-Z]{3}-\d{4}")|([A-Z]+\(.[^)]*\)))))[\s\n\t]{0,}\)|^DAYS\([\s\n\t]{0,}((?P<start_date_ref>\{[\w\s]+\})[\s\n\t]{0,},[\s\n\t]{0,}(?P<end_date_ref>\{[\w\s]+\}))[\s\n\t]{0,}\)"#).unwrap();
     pub static ref RE_DATEADD: Regex = Regex::new(r#"^DATEADD\([\s\n\t]{0,}(?P<date>(("\d{1,2}-[a-zA-Z]{3}-\d{4}")|([A-Z]+\(.[^)]*\))))[\s\n\t]{0,},[\s\n\t]{0,}(?P<number>(([+\-*/\d]+)|([A-Z]+\(.[^)]*\))))[\s\n\t]{0,},[\s\n\t]{0,}(?P<units>("days")|("months")|("milliseconds")|("seconds")|("minutes")|("hours")|("weeks")|("quarters")|("years"))[\s\n\t]{0,}\)|^DATEADD\([\s\n\t]{0,}(?P<datetime>"\d{1,2}-[a-zA-Z]{3}-\d{4}[\s]{1}\d{1,2}:\d{1,2}:\d{1,2}")[\s\n\t]{0,},[\s\n\t]{0,}(?P<dt_number>\d+)[\s\n\t]{0,},[\s\n\t]{0,}(?P<dt_units>("days")|("months")|("milliseconds")|("seconds")|("minutes")|("hours")|("weeks")|("quarters")|("years"))[\s\n\t]{0,}\)|^DATEADD\([\s\n\t]{0,}(?P<date_ref>\{[\w\s]+\})[\s\n\t]{0,},[\s\n\t]{0,}(?P<ref_number>\d{1,2})[\s\n\t]{0,},[\s\n\t]{0,}(?P<ref_units>("days")|("months")|("milliseconds")|("seconds")|("minutes")|("hours")|("weeks")|("quarters")|("years"))[\s\n\t]{0,}\)"#).unwrap();
     pub static ref RE_DATEDIF: Regex = Regex::new(r#"^DATEDIF\([\s\n\t]{0,}(?P<date>(("\d{1,2}-[a-zA-Z]{3}-\d{4}")|([A-Z]+\(.[^)]*\))))[\s\n\t]{0,},[\s\n\t]{0,}(?P<number>(([+\-*/\d]+)|([A-Z]+\(.[^)]*\))))[\s\n\t]{0,},[\s\n\t]{0,}(?P<units>("days")|("months")|("milliseconds")|("seconds")|("minutes")|("hours")|("weeks")|("quarters")|("years"))[\s\n\t]{0,}\)|^DATEDIF\([\s\n\t]{0,}(?P<datetime>"\d{1,2}-[a-zA-Z]{3}-\d{4}[\s]{1}\d{1,2}:\d{1,2}:\d{1,2}")[\s\n\t]{0,},[\s\n\t]{0,}(?P<dt_number>\d+)[\s\n\t]{0,},[\s\n\t]{0,}(?P<dt_units>("days")|("months")|("milliseconds")|("seconds")|("minutes")|("hours")|("weeks")|("quarters")|("years"))[\s\n\t]{0,}\)|^DATEDIF\([\s\n\t]{0,}(?P<date_ref>\{[\w\s]+\})[\s\n\t]{0,},[\s\n\t]{0,}(?P<ref_number>\d{1,2})[\s\n\t]{0,},[\s\n\t]{0,}(?P<ref_units>("days")|("months")|("milliseconds")|("seconds")|("minutes")|("hours")|("weeks")|("quarters")|("years"))[\s\n\t]{0,}\)"#).unwrap();
+    pub static ref RE_TOSECONDS: Regex = Regex::new(r#"^TOSECONDS\([\s\n\t]{0,}(?P<duration>("[\w\s:.,]+")|(\{[\w\s]+\})|([A-Z]+\(.[^)]*\)))[\s\n\t]{0,}\)"#).unwrap();
+    pub static ref RE_FROMSECONDS: Regex = Regex::new(r#"^FROMSECONDS\([\s\n\t]{0,}(?P<seconds>([+\-]?\d+(\.\d+)?)|(\{[\w\s]+\})|([A-Z]+\(.[^)]*\)))[\s\n\t]{0,}(,[\s\n\t]{0,}(?P<format>"(Clock|Compact|Long|ISO)")){0,1}[\s\n\t]{0,}\)"#).unwrap();
+    pub static ref RE_ADDDURATION: Regex = Regex::new(r#"^ADDDURATION\([\s\n\t]{0,}(?P<date>("[\w\s:+\-]+")|(\{[\w\s]+\})|([A-Z]+\(.[^)]*\)))[\s\n\t]{0,},[\s\n\t]{0,}(?P<duration>("[\w\s:.,]+")|(\{[\w\s]+\}))[\s\n\t]{0,}\)"#).unwrap();
+    pub static ref RE_NEXT_OCCURRENCE: Regex = Regex::new(r#"^NEXT_OCCURRENCE\([\s\n\t]{0,}(?P<rule>\{[\w\s]+\})[\s\n\t]{0,},[\s\n\t]{0,}(?P<date>("[\w\s:+\-]+")|(\{[\w\s]+\})|([A-Z]+\([^)]*\)))[\s\n\t]{0,}\)"#).unwrap();
+    pub static ref RE_OCCURRENCES: Regex = Regex::new(r#"^(?P<name>OCCURRENCES|OCCURS_BETWEEN)\([\s\n\t]{0,}(?P<rule>\{[\w\s]+\})[\s\n\t]{0,},[\s\n\t]{0,}(?P<from>("[\w\s:+\-]+")|(\{[\w\s]+\})|([A-Z]+\([^)]*\)))[\s\n\t]{0,},[\s\n\t]{0,}(?P<to>("[\w\s:+\-]+")|(\{[\w\s]+\})|([A-Z]+\([^)]*\)))[\s\n\t]{0,}\)"#).unwrap();
+    pub static ref RE_TIME_IN_STATE: Regex = Regex::new(r#"^TIME_IN_STATE\([\s\n\t]{0,}(?P<column>\{[\w\s]+\})[\s\n\t]{0,},[\s\n\t]{0,}(?P<state>"[\w\s\-]+")[\s\n\t]{0,}\)"#).unwrap();
     pub static ref RE_DATEFMT: Regex = Regex::new(r#"^DATEFMT\([\s\n\t]{0,}(?P<date>(("\d{1,2}-[a-zA-Z]{3}-\d{4}")|([A-Z]+\(.[^)]*\))))[\s\n\t]{0,},[\s\n\t]{0,}(?P<format>"[\{a-zA-Z-/,:_\s\}].+")[\s\n\t]{0,}\)|^DATEFMT\([\s\n\t]{0,}(?P<datetime>"\d{1,2}-[a-zA-Z]{3}-\d{4}\s{1}\d{2}:\d{2}:\d{2}")[\s\n\t]{0,},[\s\n\t]{0,}(?P<dt_format>"[\{a-zA-Z-/,:_\s\}].+")[\s\n\t]{0,}\)|^DATEFMT\([\s\n\t]{0,}(?P<ref>\{[\w\s]+\})[\s\n\t]{0,},[\s\n\t]{0,}(?P<ref_format>"[\{a-zA-Z-/,:_\s\}].+")[\s\n\t]{0,}\)"#).unwrap();
 }
 
@@ -42,6 +51,11 @@ pub trait DateParseFunction {
     fn execute(&self, date_option: DateParseOption) -> Result<String, PlanetError>;
 }
 
+pub trait OccurrenceFunction {
+    fn handle(&mut self, option: OccurrenceOption) -> Result<FunctionParse, PlanetError>;
+    fn execute(&self, option: OccurrenceOption) -> Result<String, PlanetError>;
+}
+
 pub trait DateAddDiffFunction {
     fn handle(&mut self, operation: DateDeltaOperation) -> Result<FunctionParse, PlanetError>;
     fn execute(&self, operation: DateDeltaOperation) -> Result<String, PlanetError>;
@@ -76,6 +90,13 @@ pub enum DateUnits {
     Years,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub enum OccurrenceOption {
+    Next,
+    List,
+    Between,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum DateDeltaOperation {
     Add,
@@ -302,7 +323,8 @@ impl DateTimeFunction for DateTimeParse {
         if mode == DATE_MODE_ISO {
             let date_obj_wrap = get_date_object_iso(&date);
             if date_obj_wrap.is_ok() {
-                let date_obj = date_obj_wrap.unwrap();
+                let context_time_zone = self.function.clone().unwrap().get_time_zone();
+                let date_obj = date_obj_wrap.unwrap().with_timezone(&get_time_zone(&None, &context_time_zone));
                 match date_parse_option {
                     DateTimeParseOption::Hour => {
                         let hour = date_obj.hour();
@@ -348,7 +370,10 @@ impl DateTimeFunction for DateTimeParse {
                     &date, 
                     fmt
                 );
-                let date_obj = date_obj.unwrap();
+                // Stored in UTC, parts are taken in column time zone
+                let context_time_zone = self.function.clone().unwrap().get_time_zone();
+                let tz = get_column_time_zone(&field_name, &column_config_map, &context_time_zone);
+                let date_obj = date_obj.unwrap().with_timezone(&tz);
                 match date_parse_option {
                     DateTimeParseOption::Hour => {
                         let hour = date_obj.hour();
@@ -523,7 +548,8 @@ impl DateParseFunction for DateParse {
         if mode == DATE_MODE_ISO {
             let date_obj_wrap = get_date_object_iso(&date);
             if date_obj_wrap.is_ok() {
-                let date_obj = date_obj_wrap.unwrap();
+                let context_time_zone = self.function.clone().unwrap().get_time_zone();
+                let date_obj = date_obj_wrap.unwrap().with_timezone(&get_time_zone(&None, &context_time_zone));
                 match date_parse_option {
                     DateParseOption::Day => {
                         let day = date_obj.day();
@@ -614,7 +640,10 @@ impl DateParseFunction for DateParse {
                     &date, 
                     fmt
                 );
-                let date_obj = date_obj.unwrap();
+                // Stored in UTC, parts are taken in column time zone
+                let context_time_zone = self.function.clone().unwrap().get_time_zone();
+                let tz = get_column_time_zone(&field_name, &column_config_map, &context_time_zone);
+                let date_obj = date_obj.unwrap().with_timezone(&tz);
                 match date_parse_option {
                     DateParseOption::Day => {
                         let day = date_obj.day();
@@ -668,6 +697,19 @@ impl Now {
             column_config_map: column_config_map
         };
     }
+    fn get_zone(&self) -> Result<Tz, PlanetError> {
+        // Zone from attribute or context time zone
+        let attributes = self.attributes.clone().unwrap_or_default();
+        let mut time_zone: Option<String> = None;
+        if attributes.len() > 0 {
+            let data_map = &self.data_map.clone().unwrap_or_default();
+            let zone_item = attributes[0].clone();
+            let zone = zone_item.get_value(data_map, None, &self.column_config_map)?;
+            time_zone = Some(zone.replace("\"", ""));
+        }
+        let context_time_zone = self.function.clone().unwrap().get_time_zone();
+        return Ok(get_time_zone(&time_zone, &context_time_zone))
+    }
 }
 impl DateFunction for Now {
     fn handle(&mut self) -> Result<FunctionParse, PlanetError> {
@@ -686,7 +728,18 @@ impl DateFunction for Now {
         if function_text_wrap.is_some() {
             function.validate = Some(expr.is_match(function_text.as_str()));
             if function.validate.unwrap() {
-                let attributes_: Vec<String> = Vec::new();
+                // NOW("Europe/Madrid"), TODAY("America/New_York")
+                let matches = expr.captures(function_text.as_str()).unwrap();
+                let mut attributes_: Vec<String> = Vec::new();
+                let zone = matches.name("zone");
+                if zone.is_some() {
+                    let zone = zone.unwrap().as_str().to_string();
+                    let zone_str = zone.replace("\"", "");
+                    if Tz::from_str(zone_str.as_str()).is_err() {
+                        function.validate = Some(false);
+                    }
+                    attributes_.push(zone);
+                }
                 function.attributes = Some(attributes_);
             }
         }
@@ -699,7 +752,8 @@ impl DateFunction for Now {
         return Ok(function)
     }
     fn execute(&self) -> Result<String, PlanetError> {
-        let now_date_obj = Utc::now();
+        let tz = self.get_zone()?;
+        let now_date_obj = Utc::now().with_timezone(&tz);
         let replacement_string = now_date_obj.to_rfc3339();
         return Ok(replacement_string);
     }
@@ -726,6 +780,19 @@ impl Today {
             column_config_map: column_config_map
         };
     }
+    fn get_zone(&self) -> Result<Tz, PlanetError> {
+        // Zone from attribute or context time zone
+        let attributes = self.attributes.clone().unwrap_or_default();
+        let mut time_zone: Option<String> = None;
+        if attributes.len() > 0 {
+            let data_map = &self.data_map.clone().unwrap_or_default();
+            let zone_item = attributes[0].clone();
+            let zone = zone_item.get_value(data_map, None, &self.column_config_map)?;
+            time_zone = Some(zone.replace("\"", ""));
+        }
+        let context_time_zone = self.function.clone().unwrap().get_time_zone();
+        return Ok(get_time_zone(&time_zone, &context_time_zone))
+    }
 }
 impl DateFunction for Today {
     fn handle(&mut self) -> Result<FunctionParse, PlanetError> {
@@ -744,7 +811,18 @@ impl DateFunction for Today {
         if function_text_wrap.is_some() {
             function.validate = Some(expr.is_match(function_text.as_str()));
             if function.validate.unwrap() {
-                let attributes_: Vec<String> = Vec::new();
+                // NOW("Europe/Madrid"), TODAY("America/New_York")
+                let matches = expr.captures(function_text.as_str()).unwrap();
+                let mut attributes_: Vec<String> = Vec::new();
+                let zone = matches.name("zone");
+                if zone.is_some() {
+                    let zone = zone.unwrap().as_str().to_string();
+                    let zone_str = zone.replace("\"", "");
+                    if Tz::from_str(zone_str.as_str()).is_err() {
+                        function.validate = Some(false);
+                    }
+                    attributes_.push(zone);
+                }
                 function.attributes = Some(attributes_);
             }
         }
@@ -757,7 +835,8 @@ impl DateFunction for Today {
         return Ok(function)
     }
     fn execute(&self) -> Result<String, PlanetError> {
-        let today_date_obj = Utc::now();
+        let tz = self.get_zone()?;
+        let today_date_obj = Utc::now().with_timezone(&tz);
         let month_short = &today_date_obj.format("%b").to_string();
         let day = &today_date_obj.day();
         let year = &today_date_obj.year();
@@ -1011,8 +1090,8 @@ impl DateAddDiffFunction for DateAddDiff {
         let units_item = attributes[2].clone();
         let units_value = units_item.get_value(data_map, None, &column_config_map)?;
         let replacement_string: String;
-        let new_date: DateTime<FixedOffset>;
-        let date_obj: DateTime<FixedOffset>;
+        let new_date: DateTime<Tz>;
+        let date_obj: DateTime<Tz>;
         let mut has_time: bool;
         let number: i64 = FromStr::from_str(number_value.as_str()).unwrap();
         let units_str = units_value.as_str();
@@ -1028,6 +1107,7 @@ impl DateAddDiffFunction for DateAddDiff {
             "years" => DateUnits::Years,
             _ => DateUnits::Days,
         };
+        let context_time_zone = self.function.clone().unwrap().get_time_zone();
         if date_item.is_reference {
             let field_name = date_item.name.unwrap();
             let fmt = get_date_format(&field_name, &column_config_map);
@@ -1039,115 +1119,65 @@ impl DateAddDiffFunction for DateAddDiff {
                 let time_format = field_config.time_format;
                 has_time = time_format.is_some();
             }
+            // Stored in UTC, we operate in column time zone
+            let tz = get_column_time_zone(&field_name, &column_config_map, &context_time_zone);
             date_obj = DateTime::parse_from_str(
                 &date_value, 
                 fmt
-            ).unwrap();
+            ).unwrap().with_timezone(&tz);
         } else {
+            // Literal dates are wall clock time in context time zone
+            let tz = get_time_zone(&None, &context_time_zone);
+            let date_obj_literal: DateTime<FixedOffset>;
             has_time = *&date_value.find(" ").is_some();
             if has_time == true {
-                date_obj = get_date_object_human_time(&date_value).unwrap();
+                date_obj_literal = get_date_object_human_time(&date_value)?;
             } else {
-                date_obj = get_date_object_only_date(&date_value).unwrap();
+                date_obj_literal = get_date_object_only_date(&date_value)?;
             }
+            date_obj = localize_date_time(&date_obj_literal.naive_local(), &tz)?;
+        }
+        let tz = date_obj.timezone();
+        let mut sign: i64 = 1;
+        match operation {
+            DateDeltaOperation::Add => {},
+            DateDeltaOperation::Diff => {
+                sign = -1;
+            },
         }
+        // Time units are elapsed time, calendar units keep wall clock time across DST changes
         match units {
             DateUnits::Milliseconds => {
-                match operation {
-                    DateDeltaOperation::Add => {
-                        new_date = date_obj + Duration::microseconds(number);
-                    },
-                    DateDeltaOperation::Diff => {
-                        new_date = date_obj - Duration::microseconds(number);
-                    },
-                }
+                new_date = date_obj + Duration::milliseconds(sign*number);
             },
             DateUnits::Seconds => {
-                match operation {
-                    DateDeltaOperation::Add => {
-                        new_date = date_obj + Duration::seconds(number);
-                    },
-                    DateDeltaOperation::Diff => {
-                        new_date = date_obj - Duration::seconds(number);
-                    },
-                }
+                new_date = date_obj + Duration::seconds(sign*number);
             },
             DateUnits::Minutes => {
-                match operation {
-                    DateDeltaOperation::Add => {
-                        new_date = date_obj + Duration::minutes(number);
-                    },
-                    DateDeltaOperation::Diff => {
-                        new_date = date_obj - Duration::minutes(number);
-                    },
-                }
+                new_date = date_obj + Duration::minutes(sign*number);
             },
             DateUnits::Hours => {
-                match operation {
-                    DateDeltaOperation::Add => {
-                        new_date = date_obj + Duration::hours(number);
-                    },
-                    DateDeltaOperation::Diff => {
-                        new_date = date_obj - Duration::hours(number);
-                    },
-                }
+                new_date = date_obj + Duration::hours(sign*number);
             },
             DateUnits::Days => {
-                match operation {
-                    DateDeltaOperation::Add => {
-                        new_date = date_obj + Duration::days(number);
-                    },
-                    DateDeltaOperation::Diff => {
-                        new_date = date_obj - Duration::days(number);
-                    },
-                }
+                let naive = date_obj.naive_local() + Duration::days(sign*number);
+                new_date = localize_date_time(&naive, &tz)?;
             },
             DateUnits::Weeks => {
-                match operation {
-                    DateDeltaOperation::Add => {
-                        new_date = date_obj + Duration::weeks(number);
-                    },
-                    DateDeltaOperation::Diff => {
-                        new_date = date_obj - Duration::weeks(number);
-                    },
-                }
+                let naive = date_obj.naive_local() + Duration::weeks(sign*number);
+                new_date = localize_date_time(&naive, &tz)?;
             },
             DateUnits::Months => {
-                // Here same day in a month (+/-)
-                match operation {
-                    DateDeltaOperation::Add => {
-                        // I can have date or datetime
-                        let month = date_obj.month() + 1;
-                        new_date = date_obj.with_month(month).unwrap();
-                    },
-                    DateDeltaOperation::Diff => {
-                        let month = date_obj.month() - 1;
-                        new_date = date_obj.with_month(month).unwrap();
-                    },
-                }
+                let naive = add_months(&date_obj.naive_local(), sign*number);
+                new_date = localize_date_time(&naive, &tz)?;
             },
             DateUnits::Quarters => {
-                // Same day in three months (+/-)
-                match operation {
-                    DateDeltaOperation::Add => {
-                        let month = date_obj.month() + 3;
-                        new_date = date_obj.with_month(month).unwrap();
-                    },
-                    DateDeltaOperation::Diff => {
-                        let month = date_obj.month() - 3;
-                        new_date = date_obj.with_month(month).unwrap();
-                    },
-                }
+                let naive = add_months(&date_obj.naive_local(), sign*number*3);
+                new_date = localize_date_time(&naive, &tz)?;
             },
             DateUnits::Years => {
-                match operation {
-                    DateDeltaOperation::Add => {
-                        new_date = date_obj + Duration::days(number*365);
-                    },
-                    DateDeltaOperation::Diff => {
-                        new_date = date_obj - Duration::days(number*365);
-                    },
-                }
+                let naive = add_months(&date_obj.naive_local(), sign*number*12);
+                new_date = localize_date_time(&naive, &tz)?;
             },
         }
         if has_time == true {
@@ -1159,6 +1189,477 @@ impl DateAddDiffFunction for DateAddDiff {
     }
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ToSeconds {
+    function: Option<FunctionParse>,
+    data_map: Option<BTreeMap<String, Vec<BTreeMap<String, String>>>>,
+    attributes: Option<Vec<FunctionAttributeItem>>,
+    column_config_map: BTreeMap<String, ColumnConfig>,
+}
+impl ToSeconds {
+    pub fn defaults(
+        function: Option<FunctionParse>, 
+        data_map: Option<BTreeMap<String, Vec<BTreeMap<String, String>>>>,
+        column_config_map: &BTreeMap<String, ColumnConfig>
+    ) -> Self {
+        let column_config_map = column_config_map.clone();
+        return Self{
+            function: function, 
+            data_map: data_map, 
+            attributes: None,
+            column_config_map: column_config_map
+        };
+    }
+}
+impl DateFunction for ToSeconds {
+    fn handle(&mut self) -> Result<FunctionParse, PlanetError> {
+        // TOSECONDS("1h 30m")
+        // TOSECONDS({Column Duration})
+        let function_parse = &self.function.clone().unwrap();
+        let data_map = self.data_map.clone();
+        let expr = &RE_TOSECONDS;
+        let mut function = function_parse.clone();
+        let data_map_wrap = data_map.clone();
+        let (
+            function_text_wrap, 
+            function_text, 
+            compiled_attributes,
+            mut function_result,
+            data_map,
+        ) = prepare_function_parse(function_parse, data_map.clone());
+        if function_text_wrap.is_some() {
+            function.validate = Some(expr.is_match(function_text.as_str()));
+            if function.validate.unwrap() {
+                let mut attributes_: Vec<String> = Vec::new();
+                let matches = &expr.captures(&function_text).unwrap();
+                let duration = matches.name("duration").unwrap().as_str().to_string();
+                if duration.starts_with("\"") {
+                    let duration_check = parse_duration(&duration);
+                    if duration_check.is_err() {
+                        function.validate = Some(false);
+                    }
+                }
+                attributes_.push(duration);
+                function.attributes = Some(attributes_);
+            }
+        }
+        if data_map_wrap.is_some() {
+            self.attributes = Some(compiled_attributes);
+            self.data_map = Some(data_map);
+            function_result.text = Some(self.execute()?);
+            function.result = Some(function_result.clone());
+        }
+        return Ok(function)
+    }
+    fn execute(&self) -> Result<String, PlanetError> {
+        let attributes = self.attributes.clone().unwrap();
+        let data_map = &self.data_map.clone().unwrap();
+        let column_config_map = self.column_config_map.clone();
+        let duration_item = attributes[0].clone();
+        let duration_value = duration_item.get_value(data_map, None, &column_config_map)?;
+        let micros = parse_duration(&duration_value)?;
+        let seconds = micros as f64/1000000.0;
+        return Ok(seconds.to_string())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FromSeconds {
+    function: Option<FunctionParse>,
+    data_map: Option<BTreeMap<String, Vec<BTreeMap<String, String>>>>,
+    attributes: Option<Vec<FunctionAttributeItem>>,
+    column_config_map: BTreeMap<String, ColumnConfig>,
+}
+impl FromSeconds {
+    pub fn defaults(
+        function: Option<FunctionParse>, 
+        data_map: Option<BTreeMap<String, Vec<BTreeMap<String, String>>>>,
+        column_config_map: &BTreeMap<String, ColumnConfig>
+    ) -> Self {
+        let column_config_map = column_config_map.clone();
+        return Self{
+            function: function, 
+            data_map: data_map, 
+            attributes: None,
+            column_config_map: column_config_map
+        };
+    }
+}
+impl DateFunction for FromSeconds {
+    fn handle(&mut self) -> Result<FunctionParse, PlanetError> {
+        // FROMSECONDS(5400) => 1:30:00
+        // FROMSECONDS({Column}, "Compact") => 1h 30m
+        let function_parse = &self.function.clone().unwrap();
+        let data_map = self.data_map.clone();
+        let expr = &RE_FROMSECONDS;
+        let mut function = function_parse.clone();
+        let data_map_wrap = data_map.clone();
+        let (
+            function_text_wrap, 
+            function_text, 
+            compiled_attributes,
+            mut function_result,
+            data_map,
+        ) = prepare_function_parse(function_parse, data_map.clone());
+        if function_text_wrap.is_some() {
+            function.validate = Some(expr.is_match(function_text.as_str()));
+            if function.validate.unwrap() {
+                let mut attributes_: Vec<String> = Vec::new();
+                let matches = &expr.captures(&function_text).unwrap();
+                let seconds = matches.name("seconds").unwrap().as_str().to_string();
+                attributes_.push(seconds);
+                let format = matches.name("format");
+                if format.is_some() {
+                    attributes_.push(format.unwrap().as_str().to_string());
+                }
+                function.attributes = Some(attributes_);
+            }
+        }
+        if data_map_wrap.is_some() {
+            self.attributes = Some(compiled_attributes);
+            self.data_map = Some(data_map);
+            function_result.text = Some(self.execute()?);
+            function.result = Some(function_result.clone());
+        }
+        return Ok(function)
+    }
+    fn execute(&self) -> Result<String, PlanetError> {
+        let attributes = self.attributes.clone().unwrap();
+        let data_map = &self.data_map.clone().unwrap();
+        let column_config_map = self.column_config_map.clone();
+        let seconds_item = attributes[0].clone();
+        let seconds_value = seconds_item.get_value(data_map, None, &column_config_map)?;
+        let seconds: Result<f64, _> = FromStr::from_str(seconds_value.replace("\"", "").trim());
+        if seconds.is_err() {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Seconds value is not a number: \"{}\"", &seconds_value)),
+                )
+            );
+        }
+        let micros = (seconds.unwrap()*1000000.0).round() as i64;
+        let mut replacement_string = get_duration_canonical(micros);
+        if attributes.len() > 1 {
+            let format_item = attributes[1].clone();
+            let format_value = format_item.get_value(data_map, None, &column_config_map)?;
+            let format_value = format_value.replace("\"", "");
+            let duration_format = match format_value.as_str() {
+                DURATION_FORMAT_COMPACT => DurationFormat::Compact,
+                DURATION_FORMAT_LONG => DurationFormat::Long,
+                DURATION_FORMAT_ISO => DurationFormat::ISO,
+                _ => DurationFormat::Clock,
+            };
+            replacement_string = format_duration(micros, &duration_format);
+        }
+        return Ok(replacement_string)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TimeInState {
+    function: Option<FunctionParse>,
+    data_map: Option<BTreeMap<String, Vec<BTreeMap<String, String>>>>,
+    attributes: Option<Vec<FunctionAttributeItem>>,
+    column_config_map: BTreeMap<String, ColumnConfig>,
+}
+impl TimeInState {
+    pub fn defaults(
+        function: Option<FunctionParse>, 
+        data_map: Option<BTreeMap<String, Vec<BTreeMap<String, String>>>>,
+        column_config_map: &BTreeMap<String, ColumnConfig>
+    ) -> Self {
+        let column_config_map = column_config_map.clone();
+        return Self{
+            function: function, 
+            data_map: data_map, 
+            attributes: None,
+            column_config_map: column_config_map
+        };
+    }
+}
+impl DateFunction for TimeInState {
+    fn handle(&mut self) -> Result<FunctionParse, PlanetError> {
+        // TIME_IN_STATE({Status}, "Review") => 3600
+        let function_parse = &self.function.clone().unwrap();
+        let data_map = self.data_map.clone();
+        let expr = &RE_TIME_IN_STATE;
+        let mut function = function_parse.clone();
+        let data_map_wrap = data_map.clone();
+        let (
+            function_text_wrap, 
+            function_text, 
+            compiled_attributes,
+            mut function_result,
+            data_map,
+        ) = prepare_function_parse(function_parse, data_map.clone());
+        if function_text_wrap.is_some() {
+            function.validate = Some(expr.is_match(function_text.as_str()));
+            if function.validate.unwrap() {
+                let mut attributes_: Vec<String> = Vec::new();
+                let matches = &expr.captures(&function_text).unwrap();
+                let column = matches.name("column").unwrap().as_str().to_string();
+                let state = matches.name("state").unwrap().as_str().to_string();
+                attributes_.push(column);
+                attributes_.push(state);
+                function.attributes = Some(attributes_);
+            }
+        }
+        if data_map_wrap.is_some() {
+            self.attributes = Some(compiled_attributes);
+            self.data_map = Some(data_map);
+            function_result.text = Some(self.execute()?);
+            function.result = Some(function_result.clone());
+        }
+        return Ok(function)
+    }
+    fn execute(&self) -> Result<String, PlanetError> {
+        let attributes = self.attributes.clone().unwrap();
+        let data_map = &self.data_map.clone().unwrap();
+        let column_config_map = self.column_config_map.clone();
+        let column_item = attributes[0].clone();
+        let state_item = attributes[1].clone();
+        let column_id = column_item.id.clone().unwrap_or_default();
+        let state = state_item.get_value(data_map, None, &column_config_map)?;
+        let state = state.replace("\"", "");
+        let history_key = WorkflowColumn::get_history_key(&column_id);
+        let history = data_map.get(&history_key).cloned().unwrap_or_default();
+        let seconds = WorkflowColumn::get_time_in_state(&history, &state, &Utc::now());
+        return Ok(seconds.to_string())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AddDuration {
+    function: Option<FunctionParse>,
+    data_map: Option<BTreeMap<String, Vec<BTreeMap<String, String>>>>,
+    attributes: Option<Vec<FunctionAttributeItem>>,
+    column_config_map: BTreeMap<String, ColumnConfig>,
+}
+impl AddDuration {
+    pub fn defaults(
+        function: Option<FunctionParse>, 
+        data_map: Option<BTreeMap<String, Vec<BTreeMap<String, String>>>>,
+        column_config_map: &BTreeMap<String, ColumnConfig>
+    ) -> Self {
+        let column_config_map = column_config_map.clone();
+        return Self{
+            function: function, 
+            data_map: data_map, 
+            attributes: None,
+            column_config_map: column_config_map
+        };
+    }
+}
+impl DateFunction for AddDuration {
+    fn handle(&mut self) -> Result<FunctionParse, PlanetError> {
+        // ADDDURATION("25-Aug-2021 06:26:00", "1h 30m")
+        // ADDDURATION({Column Date}, {Column Duration})
+        let function_parse = &self.function.clone().unwrap();
+        let data_map = self.data_map.clone();
+        let expr = &RE_ADDDURATION;
+        let mut function = function_parse.clone();
+        let data_map_wrap = data_map.clone();
+        let (
+            function_text_wrap, 
+            function_text, 
+            compiled_attributes,
+            mut function_result,
+            data_map,
+        ) = prepare_function_parse(function_parse, data_map.clone());
+        if function_text_wrap.is_some() {
+            function.validate = Some(expr.is_match(function_text.as_str()));
+            if function.validate.unwrap() {
+                let mut attributes_: Vec<String> = Vec::new();
+                let matches = &expr.captures(&function_text).unwrap();
+                let date = matches.name("date").unwrap().as_str().to_string();
+                let duration = matches.name("duration").unwrap().as_str().to_string();
+                if duration.starts_with("\"") {
+                    let duration_check = parse_duration(&duration);
+                    if duration_check.is_err() {
+                        function.validate = Some(false);
+                    }
+                }
+                attributes_.push(date);
+                attributes_.push(duration);
+                function.attributes = Some(attributes_);
+            }
+        }
+        if data_map_wrap.is_some() {
+            self.attributes = Some(compiled_attributes);
+            self.data_map = Some(data_map);
+            function_result.text = Some(self.execute()?);
+            function.result = Some(function_result.clone());
+        }
+        return Ok(function)
+    }
+    fn execute(&self) -> Result<String, PlanetError> {
+        let attributes = self.attributes.clone().unwrap();
+        let data_map = &self.data_map.clone().unwrap();
+        let column_config_map = self.column_config_map.clone();
+        let date_item = attributes[0].clone();
+        let date_value = date_item.get_value(data_map, None, &column_config_map)?;
+        let date_value = date_value.replace("\"", "");
+        let duration_item = attributes[1].clone();
+        let duration_value = duration_item.get_value(data_map, None, &column_config_map)?;
+        let micros = parse_duration(&duration_value)?;
+        let context_time_zone = self.function.clone().unwrap().get_time_zone();
+        let (date_obj, _) = get_attribute_date(
+            &date_item, &date_value, &column_config_map, &context_time_zone
+        )?;
+        // Elapsed time
+        let new_date = date_obj + Duration::microseconds(micros);
+        return Ok(new_date.to_rfc3339())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Occurrences {
+    function: Option<FunctionParse>,
+    data_map: Option<BTreeMap<String, Vec<BTreeMap<String, String>>>>,
+    attributes: Option<Vec<FunctionAttributeItem>>,
+    column_config_map: BTreeMap<String, ColumnConfig>,
+}
+impl Occurrences {
+    pub fn defaults(
+        function: Option<FunctionParse>, 
+        data_map: Option<BTreeMap<String, Vec<BTreeMap<String, String>>>>,
+        column_config_map: &BTreeMap<String, ColumnConfig>
+    ) -> Self {
+        let column_config_map = column_config_map.clone();
+        return Self{
+            function: function, 
+            data_map: data_map, 
+            attributes: None,
+            column_config_map: column_config_map
+        };
+    }
+}
+impl OccurrenceFunction for Occurrences {
+    fn handle(&mut self, option: OccurrenceOption) -> Result<FunctionParse, PlanetError> {
+        // NEXT_OCCURRENCE({Meeting}, TODAY())
+        // OCCURRENCES({Meeting}, "01-Jan-2024", "31-Jan-2024")
+        // OCCURS_BETWEEN({Meeting}, "01-Jan-2024", "31-Jan-2024") : WHERE predicate
+        let function_parse = &self.function.clone().unwrap();
+        let data_map = self.data_map.clone();
+        let expr_next = &RE_NEXT_OCCURRENCE;
+        let expr = &RE_OCCURRENCES;
+        let mut function = function_parse.clone();
+        let data_map_wrap = data_map.clone();
+        let (
+            function_text_wrap, 
+            function_text, 
+            compiled_attributes,
+            mut function_result,
+            data_map,
+        ) = prepare_function_parse(function_parse, data_map.clone());
+        if function_text_wrap.is_some() {
+            let mut attributes_: Vec<String> = Vec::new();
+            match option {
+                OccurrenceOption::Next => {
+                    function.validate = Some(expr_next.is_match(function_text.as_str()));
+                    if function.validate.unwrap() {
+                        let matches = &expr_next.captures(&function_text).unwrap();
+                        attributes_.push(matches.name("rule").unwrap().as_str().to_string());
+                        attributes_.push(matches.name("date").unwrap().as_str().to_string());
+                    }
+                },
+                OccurrenceOption::List | OccurrenceOption::Between => {
+                    function.validate = Some(expr.is_match(function_text.as_str()));
+                    if function.validate.unwrap() {
+                        let matches = &expr.captures(&function_text).unwrap();
+                        attributes_.push(matches.name("rule").unwrap().as_str().to_string());
+                        attributes_.push(matches.name("from").unwrap().as_str().to_string());
+                        attributes_.push(matches.name("to").unwrap().as_str().to_string());
+                    }
+                },
+            }
+            if function.validate.unwrap() {
+                function.attributes = Some(attributes_);
+            }
+        }
+        if data_map_wrap.is_some() {
+            self.attributes = Some(compiled_attributes);
+            self.data_map = Some(data_map);
+            function_result.text = Some(self.execute(option)?);
+            function.result = Some(function_result.clone());
+        }
+        return Ok(function)
+    }
+    fn execute(&self, option: OccurrenceOption) -> Result<String, PlanetError> {
+        let attributes = self.attributes.clone().unwrap();
+        let data_map = &self.data_map.clone().unwrap();
+        let column_config_map = self.column_config_map.clone();
+        let rule_item = attributes[0].clone();
+        let rule_value = rule_item.get_value(data_map, None, &column_config_map)?;
+        let rule_value = rule_value.replace("\"", "");
+        if rule_value.trim() == "" {
+            // Items without rule have no occurrences
+            match option {
+                OccurrenceOption::Between => {
+                    return Ok(String::from("0"))
+                },
+                _ => {
+                    return Ok(String::from(""))
+                }
+            }
+        }
+        let context_time_zone = self.function.clone().unwrap().get_time_zone();
+        let recurrence = Recurrence::parse(&rule_value, &context_time_zone)?;
+        let tz = get_time_zone(&None, &context_time_zone);
+        let from_item = attributes[1].clone();
+        let from_value = from_item.get_value(data_map, None, &column_config_map)?;
+        let (from_date, _) = get_attribute_date(
+            &from_item, &from_value, &column_config_map, &context_time_zone
+        )?;
+        let from_date = from_date.with_timezone(&Utc);
+        let replacement_string: String;
+        match option {
+            OccurrenceOption::Next => {
+                let next = recurrence.get_next_occurrence(&from_date);
+                if next.is_some() {
+                    replacement_string = next.unwrap().with_timezone(&tz).to_rfc3339();
+                } else {
+                    replacement_string = String::from("");
+                }
+            },
+            OccurrenceOption::List | OccurrenceOption::Between => {
+                let to_item = attributes[2].clone();
+                let to_value = to_item.get_value(data_map, None, &column_config_map)?;
+                let (to_date, to_has_time) = get_attribute_date(
+                    &to_item, &to_value, &column_config_map, &context_time_zone
+                )?;
+                let mut to_date = to_date.with_timezone(&Utc);
+                if !to_has_time {
+                    // Date without time includes the whole day
+                    to_date = to_date + Duration::days(1) - Duration::seconds(1);
+                }
+                match option {
+                    OccurrenceOption::Between => {
+                        let check = recurrence.has_occurrence(&from_date, &to_date);
+                        if check {
+                            replacement_string = String::from("1");
+                        } else {
+                            replacement_string = String::from("0");
+                        }
+                    },
+                    _ => {
+                        let occurrences = recurrence.get_occurrences(
+                            &from_date, Some(&to_date), RECURRENCE_MAX_OCCURRENCES
+                        );
+                        let mut items: Vec<String> = Vec::new();
+                        for occurrence in occurrences {
+                            items.push(occurrence.with_timezone(&tz).to_rfc3339());
+                        }
+                        replacement_string = items.join(", ");
+                    }
+                }
+            },
+        }
+        return Ok(replacement_string)
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct DateFormatFunc {
     function: Option<FunctionParse>,
@@ -1556,3 +2057,70 @@ fn get_date_format(field_name: &String, column_config_map: &BTreeMap<String, Col
     }
     return fmt.to_string()
 }
+
+fn get_column_time_zone(
+    field_name: &String, 
+    column_config_map: &BTreeMap<String, ColumnConfig>,
+    context_time_zone: &Option<String>
+) -> Tz {
+    let field_config = column_config_map.get(field_name);
+    let mut time_zone: Option<String> = None;
+    if field_config.is_some() {
+        time_zone = field_config.unwrap().time_zone.clone();
+    }
+    return get_time_zone(&time_zone, context_time_zone)
+}
+
+fn add_months(naive: &NaiveDateTime, months: i64) -> NaiveDateTime {
+    // Same day in target month, last day of month when it does not exist (31-Jan + 1 month => 28-Feb)
+    let total_months = naive.year() as i64 * 12 + naive.month0() as i64 + months;
+    let year = total_months.div_euclid(12) as i32;
+    let month = total_months.rem_euclid(12) as u32 + 1;
+    let mut day = naive.day();
+    let mut date = NaiveDate::from_ymd_opt(year, month, day);
+    while date.is_none() && day > 28 {
+        day -= 1;
+        date = NaiveDate::from_ymd_opt(year, month, day);
+    }
+    return date.unwrap().and_time(naive.time())
+}
+
+fn get_attribute_date(
+    date_item: &FunctionAttributeItem, 
+    date_value: &String, 
+    column_config_map: &BTreeMap<String, ColumnConfig>,
+    context_time_zone: &Option<String>
+) -> Result<(DateTime<Tz>, bool), PlanetError> {
+    // Date from column reference, literal or function (NOW(), TODAY()). Returns whether date has time.
+    let date_value = date_value.replace("\"", "");
+    let date_obj: DateTime<Tz>;
+    let mut has_time = true;
+    if date_item.is_reference {
+        // Stored in UTC, result in column time zone
+        let field_name = date_item.name.clone().unwrap();
+        let fmt = get_date_format(&field_name, column_config_map);
+        let tz = get_column_time_zone(&field_name, column_config_map, context_time_zone);
+        let date_obj_wrap = DateTime::parse_from_str(&date_value, fmt.as_str());
+        if date_obj_wrap.is_err() {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Could not parse date")),
+                )
+            );
+        }
+        date_obj = date_obj_wrap.unwrap().with_timezone(&tz);
+    } else if date_value.find("T").is_some() {
+        date_obj = get_date_object_iso(&date_value)?.with_timezone(&get_time_zone(&None, context_time_zone));
+    } else {
+        let date_obj_literal: DateTime<FixedOffset>;
+        if date_value.find(" ").is_some() {
+            date_obj_literal = get_date_object_human_time(&date_value)?;
+        } else {
+            date_obj_literal = get_date_object_only_date(&date_value)?;
+            has_time = false;
+        }
+        date_obj = localize_date_time(&date_obj_literal.naive_local(), &get_time_zone(&None, context_time_zone))?;
+    }
+    return Ok((date_obj, has_time))
+}
@@ -0,0 +1,174 @@
+use std::str::FromStr;
+use regex::Regex;
+use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+use lazy_static::lazy_static;
+use tr::tr;
+use crate::functions::*;
+use crate::storage::columns::geo::{Location, LocationBox};
+
+lazy_static! {
+    pub static ref RE_DISTANCE: Regex = Regex::new(r#"^DISTANCE\([\s\n\t]{0,}(?P<from>\{[\w\s]+\})[\s\n\t]{0,},[\s\n\t]{0,}(?P<to>\{[\w\s]+\})[\s\n\t]{0,}\)"#).unwrap();
+    pub static ref RE_WITHIN_RADIUS: Regex = Regex::new(r#"^WITHIN_RADIUS\([\s\n\t]{0,}(?P<location>\{[\w\s]+\})[\s\n\t]{0,},[\s\n\t]{0,}(?P<lat>[+-]?\d+(\.\d+)?)[\s\n\t]{0,},[\s\n\t]{0,}(?P<lon>[+-]?\d+(\.\d+)?)[\s\n\t]{0,},[\s\n\t]{0,}(?P<km>\d+(\.\d+)?)[\s\n\t]{0,}\)"#).unwrap();
+    pub static ref RE_WITHIN_BOX: Regex = Regex::new(r#"^WITHIN_BOX\([\s\n\t]{0,}(?P<location>\{[\w\s]+\})[\s\n\t]{0,},[\s\n\t]{0,}(?P<min_lat>[+-]?\d+(\.\d+)?)[\s\n\t]{0,},[\s\n\t]{0,}(?P<min_lon>[+-]?\d+(\.\d+)?)[\s\n\t]{0,},[\s\n\t]{0,}(?P<max_lat>[+-]?\d+(\.\d+)?)[\s\n\t]{0,},[\s\n\t]{0,}(?P<max_lon>[+-]?\d+(\.\d+)?)[\s\n\t]{0,}\)"#).unwrap();
+}
+
+pub trait GeoFunction {
+    fn handle(&mut self, option: GeoOption) -> Result<FunctionParse, PlanetError>;
+    fn execute(&self, option: GeoOption) -> Result<String, PlanetError>;
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub enum GeoOption {
+    Distance,
+    Radius,
+    Box,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Geo {
+    function: Option<FunctionParse>,
+    data_map: Option<BTreeMap<String, Vec<BTreeMap<String, String>>>>,
+    attributes: Option<Vec<FunctionAttributeItem>>,
+    column_config_map: BTreeMap<String, ColumnConfig>,
+}
+impl Geo {
+    pub fn defaults(
+        function: Option<FunctionParse>,
+        data_map: Option<BTreeMap<String, Vec<BTreeMap<String, String>>>>,
+        column_config_map: &BTreeMap<String, ColumnConfig>
+    ) -> Self {
+        let column_config_map = column_config_map.clone();
+        return Self{
+            function: function,
+            data_map: data_map,
+            attributes: None,
+            column_config_map: column_config_map
+        };
+    }
+}
+impl GeoFunction for Geo {
+    fn handle(&mut self, option: GeoOption) -> Result<FunctionParse, PlanetError> {
+        // DISTANCE({Office}, {Customer Address}) : Kilometers
+        // WITHIN_RADIUS({Place}, 40.4168, -3.7038, 5) : WHERE predicate
+        // WITHIN_BOX({Place}, 40.3, -3.8, 40.5, -3.6) : WHERE predicate
+        let function_parse = &self.function.clone().unwrap();
+        let data_map = self.data_map.clone();
+        let mut function = function_parse.clone();
+        let data_map_wrap = data_map.clone();
+        let (
+            function_text_wrap,
+            function_text,
+            compiled_attributes,
+            mut function_result,
+            data_map,
+        ) = prepare_function_parse(function_parse, data_map.clone());
+        if function_text_wrap.is_some() {
+            let expr: &Regex;
+            let names: Vec<&str>;
+            match option {
+                GeoOption::Distance => {
+                    expr = &RE_DISTANCE;
+                    names = vec!["from", "to"];
+                },
+                GeoOption::Radius => {
+                    expr = &RE_WITHIN_RADIUS;
+                    names = vec!["location", "lat", "lon", "km"];
+                },
+                GeoOption::Box => {
+                    expr = &RE_WITHIN_BOX;
+                    names = vec!["location", "min_lat", "min_lon", "max_lat", "max_lon"];
+                },
+            }
+            function.validate = Some(expr.is_match(function_text.as_str()));
+            if function.validate.unwrap() {
+                let mut attributes_: Vec<String> = Vec::new();
+                let matches = &expr.captures(&function_text).unwrap();
+                for name in names {
+                    attributes_.push(matches.name(name).unwrap().as_str().to_string());
+                }
+                function.attributes = Some(attributes_);
+            }
+        }
+        if data_map_wrap.is_some() {
+            self.attributes = Some(compiled_attributes);
+            self.data_map = Some(data_map);
+            let result = self.execute(option.clone())?;
+            match option {
+                GeoOption::Distance => {
+                    function_result.text = Some(result);
+                },
+                _ => {
+                    function_result.check = Some(result == String::from("1"));
+                }
+            }
+            function.result = Some(function_result.clone());
+        }
+        return Ok(function)
+    }
+    fn execute(&self, option: GeoOption) -> Result<String, PlanetError> {
+        let attributes = self.attributes.clone().unwrap();
+        let data_map = &self.data_map.clone().unwrap();
+        let column_config_map = self.column_config_map.clone();
+        let mut values: Vec<String> = Vec::new();
+        for attribute in attributes {
+            let value = attribute.get_value(data_map, None, &column_config_map)?;
+            values.push(value.replace("\"", "").trim().to_string());
+        }
+        // Items without location don't match and have no distance
+        let location_value = values[0].clone();
+        if location_value == "" {
+            match option {
+                GeoOption::Distance => {
+                    return Ok(String::from(""))
+                },
+                _ => {
+                    return Ok(String::from("0"))
+                }
+            }
+        }
+        let location = Location::parse(&location_value)?;
+        let check: bool;
+        match option {
+            GeoOption::Distance => {
+                if values[1] == "" {
+                    return Ok(String::from(""))
+                }
+                let other = Location::parse(&values[1])?;
+                let distance = location.get_distance(&other);
+                return Ok(format!("{:.3}", distance))
+            },
+            GeoOption::Radius => {
+                let center = Location::parse(&format!("{},{}", &values[1], &values[2]))?;
+                let km = get_number(&values[3])?;
+                check = location.is_within_radius(&center, km);
+            },
+            GeoOption::Box => {
+                let bounding_box = LocationBox::defaults(
+                    get_number(&values[1])?,
+                    get_number(&values[2])?,
+                    get_number(&values[3])?,
+                    get_number(&values[4])?,
+                )?;
+                check = location.is_within_box(&bounding_box);
+            },
+        }
+        if check {
+            return Ok(String::from("1"))
+        }
+        return Ok(String::from("0"))
+    }
+}
+
+fn get_number(value: &String) -> Result<f64, PlanetError> {
+    let number: Result<f64, _> = FromStr::from_str(value.as_str());
+    if number.is_err() {
+        return Err(
+            PlanetError::new(
+                500,
+                Some(tr!("Value \"{}\" is not a number.", value)),
+            )
+        );
+    }
+    return Ok(number.unwrap())
+}
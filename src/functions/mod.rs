@@ -4,10 +4,13 @@ pub mod date;
 pub mod structure;
 pub mod number;
 pub mod collections;
+pub mod geo;
+pub mod web;
 
 use std::str::FromStr;
 use std::collections::{BTreeMap,HashMap};
 use lazy_static::lazy_static;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use regex::{Regex, CaptureMatches};
 use tr::tr;
@@ -17,6 +20,8 @@ use crate::planet::constants::*;
 use crate::storage::ConfigStorageColumn;
 use crate::storage::constants::*;
 use crate::storage::folder::{DbData, TreeFolder, get_value_list};
+use crate::storage::columns::structure::ObjectColumn;
+use crate::storage::columns::text::PhoneColumn;
 use crate::statements::folder::schema::*;
 use crate::functions::constants::*;
 use crate::functions::text::*;
@@ -24,6 +29,8 @@ use crate::functions::date::*;
 use crate::functions::number::*;
 use crate::functions::collections::*;
 use crate::functions::structure::*;
+use crate::functions::geo::*;
+use crate::functions::web::*;
 use crate::planet::PlanetError;
 
 lazy_static! {
@@ -33,7 +40,7 @@ lazy_static! {
     pub static ref RE_FORMULA_VALID: Regex = Regex::new(r#"(?im:\{[\w\s]+\})"#).unwrap();
     pub static ref RE_EMBED_FUNC: Regex = Regex::new(r#"\((?P<func_embed>[A-Z]+)"#).unwrap();
     pub static ref RE_STRING_MATCH: Regex = Regex::new(r#"(?P<string_match>"[\w\s]+"[\s\n\t]{0,}[=><][\s\n\t]{0,}"[\w\s]+")"#).unwrap();
-    pub static ref RE_FORMULA_QUERY: Regex = Regex::new(r#"(?P<assign>\{[\s\w]+\}[\s\t]{0,}(?P<log_op>=|>|<|>=|<=)[\s\t]{0,}.+)|(?P<op>AND|OR|NOT|XOR)\((?P<attrs>.+)\)"#).unwrap();
+    pub static ref RE_FORMULA_QUERY: Regex = Regex::new(r#"(?P<assign>\{[\s\w.]+\}[\s\t]{0,}(?P<log_op>=|>|<|>=|<=)[\s\t]{0,}.+)|(?P<op>AND|OR|NOT|XOR)\((?P<attrs>.+)\)"#).unwrap();
     pub static ref RE_FORMULA_FIELD_FUNCTIONS: Regex = Regex::new(r#"(?P<func>[A-Z]+[("\d,-.;_:+$€\s\w{})]+)"#).unwrap();
     pub static ref RE_FUNCTION_ATTRS_OLD: Regex = Regex::new(r#"("[\w\s-]+")|(\{[\w\s]+\})|([A-Z]+\(["\w\s]+\))|([+-]?[0-9]+\.?[0-9]*|\.[0-9]+)"#).unwrap();
     pub static ref RE_FUNCTION_ATTRS: Regex = Regex::new(r#"[A-Z]+\((?P<attrs>.+)\)"#).unwrap();
@@ -45,7 +52,7 @@ lazy_static! {
 }
 
 // achiever planet functions
-pub const FORMULA_FUNCTIONS: [&str; 50] = [
+pub const FORMULA_FUNCTIONS: [&str; 68] = [
     FUNCTION_CONCAT,
     FUNCTION_FORMAT,
     FUNCTION_JOINLIST,
@@ -82,6 +89,9 @@ pub const FORMULA_FUNCTIONS: [&str; 50] = [
     FUNCTION_LOG,
     FUNCTION_MAX,
     FUNCTION_MIN,
+    FUNCTION_AVG,
+    FUNCTION_SUM,
+    FUNCTION_CONVERT,
     FUNCTION_MOD,
     FUNCTION_POWER,
     FUNCTION_ROUND,
@@ -92,6 +102,21 @@ pub const FORMULA_FUNCTIONS: [&str; 50] = [
     FUNCTION_CREATED_TIME,
     FUNCTION_DATEADD,
     FUNCTION_DATEDIF,
+    FUNCTION_TOSECONDS,
+    FUNCTION_FROMSECONDS,
+    FUNCTION_ADDDURATION,
+    FUNCTION_TIME_IN_STATE,
+    FUNCTION_NEXT_OCCURRENCE,
+    FUNCTION_OCCURRENCES,
+    FUNCTION_OCCURS_BETWEEN,
+    FUNCTION_DISTANCE,
+    FUNCTION_WITHIN_RADIUS,
+    FUNCTION_WITHIN_BOX,
+    FUNCTION_EMAIL_DOMAIN,
+    FUNCTION_URL_HOST,
+    FUNCTION_URL_PATH,
+    FUNCTION_URL_QUERY_PARAM,
+    FUNCTION_HAS_TAG,
     FUNCTION_LAST_MODIFIED_TIME,
     FUNCTION_RECORD_ID,
     FUNCTION_TRUE,
@@ -248,12 +273,22 @@ impl FormulaQueryCompiled {
     }
 }
 
+// Statement data functions need when executed, like exchange rates of the space and context time zone. Not 
+// stored with compiled formulas, statements set it before executing them.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct FormulaEnv {
+    pub exchange_rates: BTreeMap<String, Decimal>,
+    pub time_zone: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Formula {
     pub functions: Option<BTreeMap<String, CompiledFunction>>,
     pub assignment: Option<AttributeAssign>,
     pub formula: String,
     pub data: Option<BTreeMap<String, String>>,
+    #[serde(skip)]
+    pub env: Option<FormulaEnv>,
 }
 impl Formula {
     pub fn defaults(
@@ -318,6 +353,7 @@ impl Formula {
             assignment: None,
             formula: String::from(""),
             data: None,
+            env: None,
         };
         let mut compiled_functions_map: BTreeMap<String, CompiledFunction> = BTreeMap::new();
         let mut compiled_functions: Vec<CompiledFunction> = Vec::new();
@@ -483,10 +519,11 @@ pub fn compile_assignment(
         )?;
         //eprintln!("compile_assignment: reference_name: {} items_new: {:?}", &reference_name, &items_new);
         // let column_config = properties_map.get(&reference_name).unwrap().clone();
-        let column_type = column_config.column_type;
+        let column_type = column_config.column_type.clone();
         // eprintln!("compile_assignment: colunn_type: {:?}", &column_type);
         let mut attribute_type: AttributeType = AttributeType::Text;
         let mut assign_name = items_new[0].clone();
+        let mut assign_value = items_new[1].clone();
         // eprintln!("compile_assignment: [1] assign_name: {}", &assign_name);
         if column_type.is_some() {
             //eprintln!("compile_assignment: I have field_type...");
@@ -501,6 +538,21 @@ pub fn compile_assignment(
                     assign_name = format!("{}.Name", &assign_name);
                 }
             }
+            if column_type_str == COLUMN_TYPE_PHONE {
+                // Phones are stored in E.164, {Phone} = "600 11 22 33" compares normalized number
+                let value = assign_value.trim().replace("\"", "");
+                let value = PhoneColumn::normalize(&value, &column_config.default_country);
+                if value.is_ok() {
+                    assign_value = format!("\"{}\"", value.unwrap());
+                }
+            }
+            if column_type_str == COLUMN_TYPE_OBJECT && assign_name.find(OBJECT_PATH_SEPARATOR).is_some() {
+                // Object paths compare as numbers when value is a number
+                let value: Result<f64, _> = items_new[1].trim().parse();
+                if value.is_ok() {
+                    attribute_type = AttributeType::Number;
+                }
+            }
         }
         // eprintln!("compile_assignment: [2] assign_name: {}", &assign_name);
         // {Counter} = 23
@@ -509,7 +561,7 @@ pub fn compile_assignment(
             AttributeAssign::defaults(
                 &assign_name, 
                 &attribute_operator, 
-                &items_new[1].clone(),
+                &assign_value,
                 &attribute_type
             )
         );
@@ -933,6 +985,7 @@ pub struct FunctionParse {
     column_config_map: BTreeMap<String, ColumnConfig>,
     score: Option<usize>,
     has_search_match: Option<bool>,
+    env: Option<FormulaEnv>,
 }
 impl FunctionParse {
     pub fn defaults(name: &String) -> Self {
@@ -947,9 +1000,24 @@ impl FunctionParse {
             column_config_map: column_config_map,
             score: None,
             has_search_match: None,
+            env: None,
         };
         return obj;
     }
+    pub fn get_exchange_rates(&self) -> BTreeMap<String, Decimal> {
+        let env = self.env.clone();
+        if env.is_some() {
+            return env.unwrap().exchange_rates
+        }
+        return BTreeMap::new()
+    }
+    pub fn get_time_zone(&self) -> Option<String> {
+        let env = self.env.clone();
+        if env.is_some() {
+            return env.unwrap().time_zone
+        }
+        return None
+    }
 }
 
 pub fn prepare_function_parse(
@@ -1149,6 +1217,54 @@ pub fn process_function(
             func = DateAddDiff::defaults(Some(func), data.clone(), &conf).handle(
                 DateDeltaOperation::Diff)?;
         },
+        FUNCTION_TOSECONDS => {
+            func = ToSeconds::defaults(Some(func), data.clone(), &conf).handle()?;
+        },
+        FUNCTION_FROMSECONDS => {
+            func = FromSeconds::defaults(Some(func), data.clone(), &conf).handle()?;
+        },
+        FUNCTION_ADDDURATION => {
+            func = AddDuration::defaults(Some(func), data.clone(), &conf).handle()?;
+        },
+        FUNCTION_TIME_IN_STATE => {
+            func = TimeInState::defaults(Some(func), data.clone(), &conf).handle()?;
+        },
+        FUNCTION_NEXT_OCCURRENCE => {
+            func = Occurrences::defaults(Some(func), data.clone(), &conf).handle(
+                OccurrenceOption::Next)?;
+        },
+        FUNCTION_OCCURRENCES => {
+            func = Occurrences::defaults(Some(func), data.clone(), &conf).handle(
+                OccurrenceOption::List)?;
+        },
+        FUNCTION_OCCURS_BETWEEN => {
+            func = Occurrences::defaults(Some(func), data.clone(), &conf).handle(
+                OccurrenceOption::Between)?;
+        },
+        FUNCTION_DISTANCE => {
+            func = Geo::defaults(Some(func), data.clone(), &conf).handle(GeoOption::Distance)?;
+        },
+        FUNCTION_WITHIN_RADIUS => {
+            func = Geo::defaults(Some(func), data.clone(), &conf).handle(GeoOption::Radius)?;
+        },
+        FUNCTION_WITHIN_BOX => {
+            func = Geo::defaults(Some(func), data.clone(), &conf).handle(GeoOption::Box)?;
+        },
+        FUNCTION_EMAIL_DOMAIN => {
+            func = Web::defaults(Some(func), data.clone(), &conf).handle(WebOption::EmailDomain)?;
+        },
+        FUNCTION_URL_HOST => {
+            func = Web::defaults(Some(func), data.clone(), &conf).handle(WebOption::UrlHost)?;
+        },
+        FUNCTION_URL_PATH => {
+            func = Web::defaults(Some(func), data.clone(), &conf).handle(WebOption::UrlPath)?;
+        },
+        FUNCTION_URL_QUERY_PARAM => {
+            func = Web::defaults(Some(func), data.clone(), &conf).handle(WebOption::UrlQueryParam)?;
+        },
+        FUNCTION_HAS_TAG => {
+            func = HasTag::defaults(Some(func), data.clone(), &conf).handle()?;
+        },
         FUNCTION_DATEFMT => {
             func = DateFormatFunc::defaults(Some(func), data.clone(), &conf).handle()?;
         },
@@ -1160,6 +1276,17 @@ pub fn process_function(
             func = Stats::defaults(Some(func), data.clone(), &conf).handle(
                 StatOption::Max)?;
         },
+        FUNCTION_AVG => {
+            func = Stats::defaults(Some(func), data.clone(), &conf).handle(
+                StatOption::Avg)?;
+        },
+        FUNCTION_SUM => {
+            func = Stats::defaults(Some(func), data.clone(), &conf).handle(
+                StatOption::Sum)?;
+        },
+        FUNCTION_CONVERT => {
+            func = Convert::defaults(Some(func), data.clone(), &conf).handle()?;
+        },
         FUNCTION_IF => {
             func = If::defaults(Some(func), data.clone(), &conf).handle()?;
         },
@@ -1422,6 +1549,25 @@ impl FormulaExecution {
     }
 }
 
+// Formulas in attributes are executed with the env of the formula they are part of
+fn set_attributes_env(
+    attributes: Option<Vec<FunctionAttributeItem>>, 
+    env: &Option<FormulaEnv>
+) -> Option<Vec<FunctionAttributeItem>> {
+    if attributes.is_none() || env.is_none() {
+        return attributes
+    }
+    let mut attributes = attributes.unwrap();
+    for attribute in attributes.iter_mut() {
+        if attribute.formula.is_some() {
+            let mut formula = attribute.formula.clone().unwrap();
+            formula.env = env.clone();
+            attribute.formula = Some(formula);
+        }
+    }
+    return Some(attributes)
+}
+
 pub fn execute_formula(
     formula: &Formula, 
     data_map: &BTreeMap<String, Vec<BTreeMap<String, String>>>,
@@ -1447,7 +1593,8 @@ pub fn execute_formula(
             let function_key = function_key.as_str();
             let mut function_parse = FunctionParse::defaults(&function.name);
             function_parse.text = function.text;
-            function_parse.compiled_attributes = function.attributes;
+            function_parse.compiled_attributes = set_attributes_env(function.attributes, &formula.env);
+            function_parse.env = formula.env.clone();
             let function_parse = process_function(
                 &function_parse, 
                 Some(data_map.clone()),
@@ -1617,7 +1764,14 @@ pub fn get_assignment_reference(
             let has_id = item.find(id_sep).is_some();
             // eprintln!("get_assignment_reference :: has_id: {}", &has_id);
             let mut column_id: String;
-            if has_id {
+            let object_path = get_object_path(&item, &properties_map);
+            if object_path.is_some() {
+                // {Metadata.customer.tier} => $column_id->customer.tier
+                let (column_name, path) = object_path.unwrap();
+                column_config = properties_map.get(&column_name).unwrap().clone();
+                column_id = column_config.id.clone().unwrap();
+                column_id = format!("{}{}{}", &column_id, OBJECT_PATH_SEPARATOR, &path);
+            } else if has_id {
                 let item_fields: Vec<&str> = item.split(id_sep).collect();
                 let item_new = item_fields[0];
                 column_config = properties_map.get(item_new).unwrap().clone();
@@ -1645,6 +1799,31 @@ pub fn get_assignment_reference(
     );
 }
 
+// Metadata.customer.tier => (Metadata, customer.tier) when Metadata is an Object column
+pub fn get_object_path(
+    item: &String, 
+    properties_map: &HashMap<String, ColumnConfig>
+) -> Option<(String, String)> {
+    if properties_map.get(item).is_some() {
+        return None
+    }
+    let fields: Vec<&str> = item.splitn(2, ".").collect();
+    if fields.len() != 2 {
+        return None
+    }
+    let column_name = fields[0].trim().to_string();
+    let path = fields[1].trim().to_string();
+    let column_config = properties_map.get(&column_name);
+    if column_config.is_none() || path.len() == 0 {
+        return None
+    }
+    let column_type = column_config.unwrap().column_type.clone().unwrap_or_default();
+    if column_type.as_str() != COLUMN_TYPE_OBJECT {
+        return None
+    }
+    return Some((column_name, path))
+}
+
 pub fn parse_assign_operator(
     attr_source: &str, 
     formula: &String
@@ -1714,8 +1893,27 @@ pub fn check_assignment(
     let has_obj_name = column_id.find(name_sep).is_some();
     let has_obj_id = column_id.find(id_sep).is_some();
     // eprintln!("check_assignment :: has_obj_name: {} has_obj_id: {}", &has_obj_name, &has_obj_id);
+    let has_obj_path = column_id.find(OBJECT_PATH_SEPARATOR).is_some();
     let mut db_values: Option<Vec<String>> = None;
-    if has_obj_name {        
+    if has_obj_path {
+        let column_path_fields: Vec<&str> = column_id.splitn(2, OBJECT_PATH_SEPARATOR).collect();
+        let column_id = column_path_fields[0];
+        let path = column_path_fields[1].to_string();
+        let obj_data = db_data_map.get(column_id);
+        let mut list: Vec<String> = Vec::new();
+        if obj_data.is_some() {
+            let source = get_value_list(obj_data.unwrap());
+            if source.is_some() {
+                list = ObjectColumn::get_path_values(&source.unwrap(), &path);
+            }
+        }
+        if is_null && list.len() == 0 {
+            return Ok(true)
+        }
+        if list.len() > 0 {
+            db_values = Some(list);
+        }
+    } else if has_obj_name {        
         let column_name_fields: Vec<&str> = column_id.split(name_sep).collect();
         let column_id = column_name_fields[0];
         let obj_data = db_data_map.get(column_id);
@@ -1796,7 +1994,11 @@ pub fn check_assignment(
                     let value = FromStr::from_str(value_str);
                     if value.is_ok() {
                         let value: f64 = value.unwrap();
-                        let db_value: f64 = FromStr::from_str(&db_value).unwrap();
+                        let db_value: Result<f64, _> = FromStr::from_str(&db_value);
+                        if db_value.is_err() {
+                            continue
+                        }
+                        let db_value: f64 = db_value.unwrap();
                         // eprintln!("check_assignment :: db_value: {}", &db_value);
                         // eprintln!("check_assignment :: op: {:?}", &op);
                         // eprintln!("check_assignment :: value: {:?}", &value);
@@ -1818,6 +2020,10 @@ pub fn check_assignment(
                     );
                 }
             }
+            // Any of the values at object path matching is enough, like lists inside objects
+            if has_obj_path && check {
+                break
+            }
         }
     }
     // eprintln!("check_assignment :: check: {}", &check);
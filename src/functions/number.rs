@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 use lazy_static::lazy_static;
 use math::round;
 use crate::functions::*;
+use crate::storage::columns::number::CurrencyAmount;
 
 lazy_static! {
     pub static ref RE_NUMBER_ATTRS: Regex = Regex::new(r#"("[\w\s-]*")|([+-]?[0-9]+\.?[0-9]*|\.[0-9]+)|(\{[\w\s]+\})|([A-Z]+\(["\w\s-]+\))|(null)"#).unwrap();
@@ -24,6 +25,7 @@ lazy_static! {
     pub static ref RE_ROUND_DOWN: Regex = Regex::new(r#"^ROUNDDOWN\([\s\n\t]{0,}(?P<number>(([+-]?[0-9]+\.?[0-9]*|\.[0-9]+)|(\{[\w\s]+\})|([A-Z]+\(.[^(]*\))))[\n\s\t]{0,},[\n\s\t]{0,}(?P<digits>((\d+)|(\{[\w\s]+\})|([A-Z]+\(.[^(]*\)))){0,}[\s\n\t]{0,}\)"#).unwrap();
     pub static ref RE_SQRT: Regex = Regex::new(r#"^SQRT\([\s\n\t]{0,}(?P<number>(([+-]?[0-9]+\.?[0-9]*|\.[0-9]+)|(\{[\w\s]+\})|([A-Z]+\(.[^)]*\))))[\s\n\t]{0,}\)"#).unwrap();
     pub static ref RE_VALUE: Regex = Regex::new(r#"^VALUE\([\s\n\t]{0,}(?P<text>(("[\w\d,.{0,}\$€{0,}]+")|(\{[\w\s]+\})|([A-Z]+\(.[^)]*\))))[\s\n\t]{0,}\)"#).unwrap();
+    pub static ref RE_CONVERT: Regex = Regex::new(r#"^CONVERT\([\s\n\t]{0,}(?P<amount>("[^"]+")|(\{[\w\s]+\}))[\s\n\t]{0,},[\s\n\t]{0,}(?P<currency>"[A-Za-z]{3}")[\s\n\t]{0,}\)"#).unwrap();
     pub static ref RE_BOOLEAN: Regex = Regex::new(r#"^TRUE\(\)|^FALSE\(\)|TRUE|FALSE"#).unwrap();
 }
 
@@ -1395,6 +1397,95 @@ impl NumberFunction for Value {
     }
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Convert {
+    function: Option<FunctionParse>,
+    data_map: Option<BTreeMap<String, Vec<BTreeMap<String, String>>>>,
+    attributes: Option<Vec<FunctionAttributeItem>>,
+    column_config_map: BTreeMap<String, ColumnConfig>,
+}
+impl Convert {
+    pub fn defaults(
+        function: Option<FunctionParse>, 
+        data_map: Option<BTreeMap<String, Vec<BTreeMap<String, String>>>>,
+        column_config_map: &BTreeMap<String, ColumnConfig>,
+    ) -> Self {
+        let column_config_map = column_config_map.clone();
+        return Self{
+            function: function, 
+            data_map: data_map, 
+            attributes: None,
+            column_config_map: column_config_map,
+        };
+    }
+}
+impl NumberFunction for Convert {
+    fn handle(&mut self) -> Result<FunctionParse, PlanetError> {
+        // CONVERT({Amount}, "EUR")
+        // CONVERT("12.50 USD", "EUR")
+        let function_parse = &self.function.clone().unwrap();
+        let data_map = self.data_map.clone();
+        let expr = &RE_CONVERT;
+        let mut function = function_parse.clone();
+        let data_map_wrap = data_map.clone();
+        let (
+            function_text_wrap, 
+            function_text, 
+            compiled_attributes,
+            mut function_result,
+            data_map,
+        ) = prepare_function_parse(function_parse, data_map.clone());
+        if function_text_wrap.is_some() {
+            function.validate = Some(expr.is_match(function_text.as_str()));
+            if function.validate.unwrap() {
+                let matches = &expr.captures(function_text.as_str()).unwrap();
+                let mut attributes_: Vec<String> = Vec::new();
+                attributes_.push(matches.name("amount").unwrap().as_str().to_string());
+                attributes_.push(matches.name("currency").unwrap().as_str().to_string());
+                function.attributes = Some(attributes_);
+            }
+        }
+        if data_map_wrap.is_some() {
+            self.attributes = Some(compiled_attributes);
+            self.data_map = Some(data_map);
+            function_result.text = Some(self.execute()?);
+            function.result = Some(function_result.clone());
+        }
+        return Ok(function)
+    }
+    fn execute(&self) -> Result<String, PlanetError> {
+        let attributes = self.attributes.clone().unwrap();
+        let data_map = &self.data_map.clone().unwrap();
+        let column_config_map = self.column_config_map.clone();
+        let amount_item = attributes[0].clone();
+        let amount = amount_item.get_value(data_map, None, &column_config_map)?;
+        let amount = amount.replace("\"", "");
+        let currency = attributes[1].get_value(data_map, None, &column_config_map)?;
+        let currency = currency.replace("\"", "").trim().to_uppercase();
+        if amount.trim() == "" {
+            return Ok(String::from(""))
+        }
+        let mut default_currency = String::from(CURRENCY_CODE_DEFAULT);
+        let mut number_decimals: u32 = 2;
+        if amount_item.is_reference {
+            let config = column_config_map.get(&amount_item.name.clone().unwrap());
+            if config.is_some() {
+                let config = config.unwrap();
+                if config.currency.is_some() {
+                    default_currency = config.currency.clone().unwrap();
+                }
+                if config.number_decimals.is_some() {
+                    number_decimals = config.number_decimals.unwrap() as u32;
+                }
+            }
+        }
+        let exchange_rates = self.function.clone().unwrap().get_exchange_rates();
+        let amount = CurrencyAmount::parse(&amount, &default_currency)?;
+        let amount = amount.convert(&currency, &exchange_rates)?;
+        return Ok(amount.serialize(number_decimals))
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Boolean {
     function: Option<FunctionParse>,
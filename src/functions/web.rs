@@ -0,0 +1,199 @@
+use regex::Regex;
+use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+use lazy_static::lazy_static;
+use crate::functions::*;
+use crate::storage::columns::text::{EmailColumn, UrlColumn};
+
+lazy_static! {
+    pub static ref RE_EMAIL_DOMAIN: Regex = Regex::new(r#"^EMAIL_DOMAIN\([\s\n\t]{0,}(?P<column>\{[\w\s]+\})([\s\n\t]{0,},[\s\n\t]{0,}(?P<match>"[^"]*"))?[\s\n\t]{0,}\)"#).unwrap();
+    pub static ref RE_URL_HOST: Regex = Regex::new(r#"^URL_HOST\([\s\n\t]{0,}(?P<column>\{[\w\s]+\})([\s\n\t]{0,},[\s\n\t]{0,}(?P<match>"[^"]*"))?[\s\n\t]{0,}\)"#).unwrap();
+    pub static ref RE_URL_PATH: Regex = Regex::new(r#"^URL_PATH\([\s\n\t]{0,}(?P<column>\{[\w\s]+\})([\s\n\t]{0,},[\s\n\t]{0,}(?P<match>"[^"]*"))?[\s\n\t]{0,}\)"#).unwrap();
+    pub static ref RE_URL_QUERY_PARAM: Regex = Regex::new(r#"^URL_QUERY_PARAM\([\s\n\t]{0,}(?P<column>\{[\w\s]+\})[\s\n\t]{0,},[\s\n\t]{0,}(?P<param>"[^"]+")([\s\n\t]{0,},[\s\n\t]{0,}(?P<match>"[^"]*"))?[\s\n\t]{0,}\)"#).unwrap();
+}
+
+pub trait WebFunction {
+    fn handle(&mut self, option: WebOption) -> Result<FunctionParse, PlanetError>;
+    fn execute(&self, option: WebOption) -> Result<String, PlanetError>;
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub enum WebOption {
+    EmailDomain,
+    UrlHost,
+    UrlPath,
+    UrlQueryParam,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Web {
+    function: Option<FunctionParse>,
+    data_map: Option<BTreeMap<String, Vec<BTreeMap<String, String>>>>,
+    attributes: Option<Vec<FunctionAttributeItem>>,
+    column_config_map: BTreeMap<String, ColumnConfig>,
+}
+impl Web {
+    pub fn defaults(
+        function: Option<FunctionParse>,
+        data_map: Option<BTreeMap<String, Vec<BTreeMap<String, String>>>>,
+        column_config_map: &BTreeMap<String, ColumnConfig>
+    ) -> Self {
+        let column_config_map = column_config_map.clone();
+        return Self{
+            function: function,
+            data_map: data_map,
+            attributes: None,
+            column_config_map: column_config_map
+        };
+    }
+    fn get_component(option: &WebOption, values: &Vec<String>) -> Result<String, PlanetError> {
+        let value = values[0].clone();
+        match option {
+            WebOption::EmailDomain => {
+                return Ok(EmailColumn::get_domain(&value))
+            },
+            _ => {}
+        }
+        let url = UrlColumn::parse(&value)?;
+        match option {
+            WebOption::UrlHost => {
+                return Ok(url.host_str().unwrap_or_default().to_string())
+            },
+            WebOption::UrlPath => {
+                return Ok(url.path().to_string())
+            },
+            _ => {
+                let param = values[1].clone();
+                for (name, param_value) in url.query_pairs() {
+                    if name == param {
+                        return Ok(param_value.into_owned())
+                    }
+                }
+                return Ok(String::from(""))
+            }
+        }
+    }
+}
+impl WebFunction for Web {
+    fn handle(&mut self, option: WebOption) -> Result<FunctionParse, PlanetError> {
+        // EMAIL_DOMAIN({Email}) : acme.com
+        // URL_HOST({Site}) : www.acme.com
+        // URL_PATH({Site}) : /pricing
+        // URL_QUERY_PARAM({Site}, "ref") : newsletter
+        // Last argument to match component makes them WHERE predicates: EMAIL_DOMAIN({Email}, "acme.com")
+        let function_parse = &self.function.clone().unwrap();
+        let data_map = self.data_map.clone();
+        let mut function = function_parse.clone();
+        let data_map_wrap = data_map.clone();
+        let (
+            function_text_wrap,
+            function_text,
+            compiled_attributes,
+            mut function_result,
+            data_map,
+        ) = prepare_function_parse(function_parse, data_map.clone());
+        if function_text_wrap.is_some() {
+            let expr: &Regex;
+            let names: Vec<&str>;
+            match option {
+                WebOption::EmailDomain => {
+                    expr = &RE_EMAIL_DOMAIN;
+                    names = vec!["column", "match"];
+                },
+                WebOption::UrlHost => {
+                    expr = &RE_URL_HOST;
+                    names = vec!["column", "match"];
+                },
+                WebOption::UrlPath => {
+                    expr = &RE_URL_PATH;
+                    names = vec!["column", "match"];
+                },
+                WebOption::UrlQueryParam => {
+                    expr = &RE_URL_QUERY_PARAM;
+                    names = vec!["column", "param", "match"];
+                },
+            }
+            function.validate = Some(expr.is_match(function_text.as_str()));
+            if function.validate.unwrap() {
+                let mut attributes_: Vec<String> = Vec::new();
+                let matches = &expr.captures(&function_text).unwrap();
+                for name in names {
+                    let attribute = matches.name(name);
+                    if attribute.is_some() {
+                        attributes_.push(attribute.unwrap().as_str().to_string());
+                    }
+                }
+                function.attributes = Some(attributes_);
+            }
+        }
+        if data_map_wrap.is_some() {
+            let number_attributes: usize;
+            match option {
+                WebOption::UrlQueryParam => {
+                    number_attributes = 2;
+                },
+                _ => {
+                    number_attributes = 1;
+                }
+            }
+            let is_predicate = compiled_attributes.len() > number_attributes;
+            self.attributes = Some(compiled_attributes);
+            self.data_map = Some(data_map);
+            let result = self.execute(option.clone())?;
+            if is_predicate {
+                function_result.check = Some(result == String::from("1"));
+            } else {
+                function_result.text = Some(result);
+            }
+            function.result = Some(function_result.clone());
+        }
+        return Ok(function)
+    }
+    fn execute(&self, option: WebOption) -> Result<String, PlanetError> {
+        let attributes = self.attributes.clone().unwrap();
+        let data_map = &self.data_map.clone().unwrap();
+        let column_config_map = self.column_config_map.clone();
+        let mut values: Vec<String> = Vec::new();
+        for attribute in attributes {
+            let value = attribute.get_value(data_map, None, &column_config_map)?;
+            values.push(value.replace("\"", "").trim().to_string());
+        }
+        let number_attributes: usize;
+        match option {
+            WebOption::UrlQueryParam => {
+                number_attributes = 2;
+            },
+            _ => {
+                number_attributes = 1;
+            }
+        }
+        let is_predicate = values.len() > number_attributes;
+        // Items without value have no components and don't match
+        let mut component = String::from("");
+        if values[0] != "" {
+            component = Web::get_component(&option, &values)?;
+        }
+        if !is_predicate {
+            return Ok(component)
+        }
+        let mut expected = values[number_attributes].clone();
+        let check: bool;
+        match option {
+            WebOption::EmailDomain | WebOption::UrlHost => {
+                // Domains compare in punycode, lower-cased
+                let expected_ascii = idna::domain_to_ascii(expected.as_str());
+                if expected_ascii.is_ok() {
+                    expected = expected_ascii.unwrap();
+                }
+                check = component != "" && component.to_lowercase() == expected.to_lowercase();
+            },
+            _ => {
+                check = component != "" && component == expected;
+            }
+        }
+        if check {
+            return Ok(String::from("1"))
+        }
+        return Ok(String::from("0"))
+    }
+}
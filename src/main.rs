@@ -6,6 +6,8 @@ extern crate lazy_static;
 extern crate tokio;
 use safefolder_data::storage::constants::PRIVATE;
 use colored::Colorize;
+use std::env;
+use std::io;
 use lingua::{LanguageDetector, LanguageDetectorBuilder};
 
 pub mod statements;
@@ -19,6 +21,7 @@ use tr::tr;
 use crate::planet::{PlanetContext, Context, ContextSource, Environment, PlanetContextSource};
 use planet::constants::*;
 use crate::statements::*;
+use crate::storage::columns::date::validate_time_zone;
 
 // #[tokio::main]
 fn main() {
@@ -27,6 +30,8 @@ fn main() {
     let mut space_id: String = String::from(PRIVATE);
     let mut site_id: String = String::from("");
     let mut statement = String::from("");
+    let mut time_zone = String::from("");
+    let mut secret_key_stdin = false;
     let mut op = String::from("run");
     let mut scope = String::from("");
     // println!("account_id: {}", hex::encode_upper(account_id));
@@ -51,6 +56,12 @@ fn main() {
         ap.refer(&mut statement).add_option(
             &["-b", "--statement"], Store,
             "Statement");
+        ap.refer(&mut time_zone).add_option(
+            &["-z", "--timezone"], Store,
+            "Time zone, like Europe/Madrid");
+        ap.refer(&mut secret_key_stdin).add_option(
+            &["-k", "--secretkey-stdin"], StoreTrue,
+            "Read key for Secret columns from stdin, otherwise SAFEFOLDER_SECRET_KEY is used");
         ap.refer(&mut op).add_argument(
             "op", 
             Store, 
@@ -66,8 +77,30 @@ fn main() {
 
     let planet_context_source = PlanetContextSource::import_context().unwrap();
     let planet_context = PlanetContext::import(&planet_context_source);
-    let context_source = ContextSource::defaults(space_id, site_id);
+    let mut context_source = ContextSource::defaults(space_id, site_id);
+    if time_zone != String::from("") {
+        context_source.time_zone = Some(time_zone);
+    }
+    // Key is not taken from arguments, so it does not end in shell history or process list
+    let mut secret_key = env::var(SECRET_KEY_ENV).unwrap_or_default();
+    if secret_key_stdin {
+        secret_key = String::from("");
+        let result = io::stdin().read_line(&mut secret_key);
+        if result.is_err() {
+            eprintln!("{}", tr!("Could not read secret key from stdin.").red());
+            return
+        }
+    }
+    let secret_key = secret_key.trim().to_string();
+    if secret_key != String::from("") {
+        context_source.secret_key = Some(secret_key);
+    }
     let context = Context::defaults(&context_source);
+    let time_zone_result = validate_time_zone(&context.time_zone);
+    if time_zone_result.is_err() {
+        eprintln!("{}", time_zone_result.unwrap_err().message.red());
+        return
+    }
     //eprintln!("main.rs :: context: {:#?}", &context);
 
     if op.to_lowercase() == "run" && &scope.to_lowercase() == "statement" {
@@ -13,6 +13,7 @@ pub const SERDE_ERROR_TYPE_MISSING_FIELD: &str = "missing field";
 pub const SERDE_ERROR_TYPE_DUPLICATE_FIELD: &str = "duplicate field";
 
 pub const NULL: &str = "null";
+pub const SECRET_KEY_ENV: &str = "SAFEFOLDER_SECRET_KEY";
 pub const ACCOUNT_ID: &str = "account_id";
 pub const SPACE_ID: &str = "space_id";
 pub const SITE_ID: &str = "site_id";
@@ -62,6 +63,17 @@ pub const LANGUAGE_ITEMS: [&str; 9] = [
     LANGUAGE_SWEDISH,
     LANGUAGE_DANISH
     ];
+pub const LANGUAGE_CODE_ITEMS: [&str; 9] = [
+    LANGUAGE_CODE_SPANISH,
+    LANGUAGE_CODE_ENGLISH,
+    LANGUAGE_CODE_FRENCH,
+    LANGUAGE_CODE_GERMAN,
+    LANGUAGE_CODE_ITALIAN,
+    LANGUAGE_CODE_PORTUGUESE,
+    LANGUAGE_CODE_NORWEGIAN,
+    LANGUAGE_CODE_SWEDISH,
+    LANGUAGE_CODE_DANISH
+    ];
 
 // Tika Local Server
 pub const TIKA_HOST: &str = "localhost";
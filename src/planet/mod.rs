@@ -27,6 +27,13 @@ pub struct StatementRegistryItem {
     pub category: Option<String>,
 }
 
+// Optional Apache Tika server for file formats we do not extract natively
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TikaConfig {
+    pub host: Option<String>,
+    pub port: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Validate, Clone)]
 pub struct PlanetContextSource {
     pub mission: String,
@@ -34,6 +41,7 @@ pub struct PlanetContextSource {
     pub space_id: String,
     pub home_path: Option<String>,
     pub statements: Vec<StatementRegistryItem>,
+    pub tika: Option<TikaConfig>,
 }
 impl PlanetContextSource {
     pub fn import_context() -> Result<PlanetContextSource, io::Error> {
@@ -58,7 +66,8 @@ impl PlanetContextSource {
             site_id: "c9p5rma79h979776ca6g".to_string(),
             space_id: "".to_string(),
             home_path: Some("base".to_string()),
-            statements: statements
+            statements: statements,
+            tika: None,
         };
         return Ok(ctx_source)
     }
@@ -71,6 +80,7 @@ pub struct PlanetContext<'gb> {
     pub space_id: &'gb str,
     pub home_path: Option<String>,
     pub statements: Vec<StatementRegistryItem>,
+    pub tika: Option<TikaConfig>,
 }
 impl<'gb> PlanetContext<'gb> {
     pub fn import(planet_context_source: &'gb PlanetContextSource) -> PlanetContext<'gb> {
@@ -79,7 +89,8 @@ impl<'gb> PlanetContext<'gb> {
             site_id: &planet_context_source.site_id,
             space_id: &planet_context_source.space_id,
             home_path: planet_context_source.home_path.clone(),
-            statements: planet_context_source.statements.clone()
+            statements: planet_context_source.statements.clone(),
+            tika: planet_context_source.tika.clone(),
         };
         return planet_context
     }
@@ -92,6 +103,8 @@ pub struct ContextSource {
     pub account_id: Option<String>,
     pub space_id: String,
     pub site_id: Option<String>,
+    pub time_zone: Option<String>,
+    pub secret_key: Option<String>,
 }
 impl ContextSource {
     pub fn defaults(space_id: String, site_id: String) -> Self {
@@ -109,6 +122,8 @@ impl ContextSource {
             space_id: space_id,
             account_id: None,
             site_id: site_id_wrap,
+            time_zone: None,
+            secret_key: None,
         };
         return context_source
     }
@@ -121,6 +136,8 @@ pub struct Context<'gb> {
     pub account_id: Option<String>,
     pub space_id: &'gb str,
     pub site_id: Option<String>,
+    pub time_zone: Option<String>,
+    pub secret_key: Option<String>,
 }
 impl<'gb> Context<'gb> {
     pub fn defaults(context_source: &'gb ContextSource) -> Self {
@@ -130,6 +147,8 @@ impl<'gb> Context<'gb> {
             account_id: context_source.account_id.clone(),
             space_id: &context_source.space_id,
             site_id: context_source.site_id.clone(),
+            time_zone: context_source.time_zone.clone(),
+            secret_key: context_source.secret_key.clone(),
         };
         return context
     }
@@ -7,7 +7,7 @@ use std::collections::{BTreeMap, HashMap, HashSet};
 use std::time::Instant;
 use std::cmp::Ordering;
 
-use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::prelude::{ToPrimitive, Decimal};
 use sled::IVec;
 use tr::tr;
 use regex::Regex;
@@ -21,8 +21,8 @@ use serde_encrypt::{
     AsSharedKey, EncryptedMessage,
 };
 
-use crate::functions::Formula;
-use crate::functions::constants::{FUNCTION_MATCH_ANY, FUNCTION_MATCH_ALL};
+use crate::functions::{Formula, FormulaEnv};
+use crate::functions::constants::{FUNCTION_MATCH_ANY, FUNCTION_MATCH_ALL, FUNCTION_WITHIN_RADIUS, FUNCTION_WITHIN_BOX};
 use crate::statements::folder::config::*;
 use crate::storage::constants::*;
 use crate::statements::folder::schema::*;
@@ -46,24 +46,34 @@ use crate::storage::columns::reference::*;
 use crate::storage::columns::structure::*;
 use crate::storage::columns::processing::*;
 use crate::storage::columns::media::*;
+use crate::storage::columns::geo::*;
 use crate::statements::constants::COLUMN_ID;
-use crate::functions::{RE_FORMULA_QUERY, execute_formula, RE_FORMULA_ASSIGN};
+use crate::functions::{RE_FORMULA_QUERY, RE_FORMULA_FUNCTIONS, execute_formula, RE_FORMULA_ASSIGN, get_object_path};
 
 lazy_static! {
     pub static ref RE_INSERT_INTO_FOLDER_MAIN: Regex = Regex::new(r#"INSERT INTO FOLDER (?P<FolderName>[\w\s]+)[\s\t\n]*(?P<Items>\([\s\S]+\));"#).unwrap();
     pub static ref RE_INSERT_INTO_FOLDER_ITEMS: Regex = Regex::new(r#"(?P<Item>\([\s\S][^)]+\)),*"#).unwrap();
-    pub static ref RE_INSERT_INTO_FOLDER_ITEM_KEYS: Regex = Regex::new(r#"([\s\t]*(?P<Key>[\w\s]+)=[\s\t]*(?P<Value>[\s\S][^,\n)]*),*)"#).unwrap();
+    pub static ref RE_INSERT_INTO_FOLDER_ITEM_KEYS: Regex = Regex::new(r#"([\s\t]*(?P<Key>[\w\s]+)(\[(?P<Language>[a-z]{2})\][\s\t]*)?=[\s\t]*(?P<Value>[\s\S][^,\n)]*),*)"#).unwrap();
+    pub static ref RE_DELETE_FROM_FOLDER: Regex = Regex::new(r#"DELETE[\s]+FROM[\s]+FOLDER[\s]+"(?P<FolderName>[\w\s]+)"[\s]+ID[\s]+(?P<Ids>[\w\s,]+);"#).unwrap();
+    pub static ref RE_EXPORT_FILE: Regex = Regex::new(r#"EXPORT[\s]+FILE[\s]+(?P<Id>\w+)[\s]+FROM[\s]+FOLDER[\s]+"(?P<FolderName>[\w\s]+)"([\s]+THUMBNAIL[\s]+(?P<Size>\d+))?[\s]*;"#).unwrap();
+    pub static ref RE_UPDATE_FOLDER: Regex = Regex::new(r#"UPDATE[\s]+FOLDER[\s]+"(?P<FolderName>[\w\s]+)"[\s]+ID[\s]+(?P<Id>\w+)[\s]+SET[\s]*(?P<Items>\([\s\S]+\));"#).unwrap();
     pub static ref RE_INSERT_INTO_FOLDER_SUBFOLDERS: Regex = Regex::new(r#"(SUB FOLDER (?P<SubFolderId>[\w]+)([\s]*WITH[\s]*(?P<SubFolderIsReference>IsReference[\s]*=[\s]*(true|false)))*,*)"#).unwrap();
     pub static ref RE_SELECT: Regex = Regex::new(r#"SELECT[\s]*[\s\S]*[\s]*FROM[\s]*[\s\S]*;"#).unwrap();
     pub static ref RE_SELECT_COUNT: Regex = Regex::new(r#"SELECT[\s]*((?P<CountAll>COUNT\(\*\))|(COUNT\(DISTINCT[\s]+(?P<CountColumnDis>[\w\s]+)\))|(COUNT\((?P<CountColumn>[\w\s]+)\)))[\s]*FROM[\s]*"(?P<FolderName>[\w\s]+)"([\s\S])*;"#).unwrap();
     pub static ref RE_SELECT_PAGING: Regex = Regex::new(r#"([\s]*PAGE[\s]*(?P<Page>[\d]+))*([\s]*NUMBER ITEMS[\s]*(?P<NumberItems>[\d]+))*"#).unwrap();
     pub static ref RE_SELECT_FROM: Regex = Regex::new(r#"FROM[\s]*"(?P<FolderName>[\w\s]*)"[\s]*(WHERE|SORT BY|GROUP BY|SEARCH)*"#).unwrap();
     pub static ref RE_SELECT_COLUMNS: Regex = Regex::new(r#"SELECT[\s]*((?P<AllColumns>\*)|(?P<Columns>[\w\s,]+))[\s]*FROM"#).unwrap();
-    pub static ref RE_SELECT_SORTING: Regex = Regex::new(r#"(SORT[\s]*BY[\s]*\{(?P<SortBy>[|\w\s]+)\})"#).unwrap();
-    pub static ref RE_SELECT_SORT_FIELDS: Regex = Regex::new(r#"(?P<Column>[\w\s]+)(?P<Mode>ASC|DESC)+"#).unwrap();
+    pub static ref RE_SELECT_SORTING: Regex = Regex::new(r#"(SORT[\s]*BY[\s]*\{(?P<SortBy>(\{[\w\s.]+\}|[|\w\s])+)\})"#).unwrap();
+    pub static ref RE_SELECT_SORT_FIELDS: Regex = Regex::new(r#"(?P<Column>(\{[\w\s.]+\}[\s]*)|[\w\s]+)(?P<Mode>ASC|DESC)+"#).unwrap();
     pub static ref RE_SELECT_GROUP_BY: Regex = Regex::new(r#"(GROUP[\s]*BY[\s]*(?P<GroupByColumns>[\w\s,]+))"#).unwrap();
     pub static ref RE_SELECT_GROUP_COLUMNS: Regex = Regex::new(r#"(?P<Column>[\w\s]+)"#).unwrap();
+    pub static ref RE_SELECT_LANGUAGE: Regex = Regex::new(r#"[\s]+LANGUAGE[\s]+"(?P<Language>[a-z]{2})""#).unwrap();
+    pub static ref RE_SELECT_GROUP_DEPTH: Regex = Regex::new(r#"^(?P<Column>[\w\s]+?)[\s]+DEPTH[\s]+(?P<Depth>\d+)$"#).unwrap();
     pub static ref RE_SELECT_WHERE: Regex = Regex::new(r#"WHERE[\s]*(?P<Where>[\s\S]+);+"#).unwrap();
+    pub static ref RE_SELECT_WHERE_OBJECT_PATH: Regex = Regex::new(r#"\{(?P<Column>[\w\s]+)\}(?P<Path>(\.[\w]+)+)"#).unwrap();
+    pub static ref RE_SELECT_WHERE_PATH_EQUAL: Regex = Regex::new(r#"^\{(?P<Column>[\w\s]+)\.(?P<Path>[\w.]+)\}[\s]*=[\s]*(?P<Value>("[^"]*")|([\w.\-]+))$"#).unwrap();
+    pub static ref RE_SELECT_WHERE_PREDICATE: Regex = Regex::new(r#"^[\s]*(WITHIN_RADIUS|WITHIN_BOX|EMAIL_DOMAIN|URL_HOST|URL_PATH|URL_QUERY_PARAM|HAS_TAG)\([^()]+\)[\s]*$"#).unwrap();
+    pub static ref RE_SELECT_WHERE_GEO: Regex = Regex::new(r#"(?P<Function>WITHIN_RADIUS|WITHIN_BOX)\([\s]*\{(?P<Column>[\w\s]+)\}(?P<Numbers>([\s]*,[\s]*[+-]?\d+(\.\d+)?)+)[\s]*\)"#).unwrap();
     pub static ref RE_SELECT_WHERE_BOOST_YES: Regex = Regex::new(r#"(?P<Column>\{[\w\d\s]*\})(?P<Op>(=)|(>=)|(<=)|(<)|(>))(?P<Value>"*[\w\d\s]*"*)"#).unwrap();
     pub static ref RE_SELECT_WHERE_BOOST_NO: Regex = Regex::new(r#"(?P<Column>\{[\w\d\s]*\})(?P<Op>(=)|(>=)|(<=)|(<)|(>))(?P<Value>[A-Z_0-9]*\([\s\S][^\)]*\))"#).unwrap();
 }
@@ -80,6 +90,7 @@ pub struct InsertIntoFolderCompiledStmt {
     pub name: Option<String>,
     pub sub_folders: Option<Vec<SubFolderDataConfig>>,
     pub data: Option<BTreeMap<String, Vec<BTreeMap<String, String>>>>,
+    pub translations: Option<BTreeMap<String, BTreeMap<String, String>>>,
 }
 
 impl InsertIntoFolderCompiledStmt {
@@ -90,6 +101,7 @@ impl InsertIntoFolderCompiledStmt {
             name: name,
             data: Some(BTreeMap::new()),
             sub_folders: None,
+            translations: None,
         };
         return config
     }
@@ -150,15 +162,18 @@ impl<'gb> StatementCompilerBulk<'gb, InsertIntoFolderCompiledStmt> for InsertInt
                             let expr = &RE_INSERT_INTO_FOLDER_ITEM_KEYS;
                             let list = expr.captures_iter(expr_item);
                             let mut map: BTreeMap<String, Vec<BTreeMap<String, String>>> = BTreeMap::new();
+                            // column name => language code => value
+                            let mut translations: BTreeMap<String, BTreeMap<String, String>> = BTreeMap::new();
                             for list_item in list {
                                 let key = list_item.name("Key");
                                 let value = list_item.name("Value");
+                                let language = list_item.name("Language");
                                 // eprintln!("InsertIntoFolderStatement.compile :: value: {:?}", &value);
                                 if key.is_some() && value.is_some() {
                                     let key = key.unwrap().as_str().trim();
                                     let mut value = value.unwrap().as_str().to_string();
                                     // eprintln!("InsertIntoFolderStatement.compile :: [str] value: {}", &value);
-                                    if key == NAME_CAMEL {
+                                    if key == NAME_CAMEL && language.is_none() {
                                         // eprintln!("InsertIntoFolderStatement.compile :: name: {}", &value);
                                         compiled_statement.name = Some(value);
                                         continue;
@@ -184,11 +199,24 @@ impl<'gb> StatementCompilerBulk<'gb, InsertIntoFolderCompiledStmt> for InsertInt
                                     if data_value.is_ok() {
                                         let data_value = data_value.unwrap();
                                         // eprintln!("InsertIntoFolderStatement.compile :: DataValue: {:?}", &data_value.value);
+                                        if language.is_some() {
+                                            let language = language.unwrap().as_str().to_string();
+                                            let translation = get_value_list(&data_value.value);
+                                            if translation.is_some() {
+                                                let mut column_translations = translations.get(key).cloned().unwrap_or_default();
+                                                column_translations.insert(language, translation.unwrap());
+                                                translations.insert(key.to_string(), column_translations);
+                                            }
+                                            continue;
+                                        }
                                         map.insert(key.to_string(), data_value.value);
                                     }
                                 }
                             }
                             compiled_statement.data = Some(map);
+                            if translations.len() > 0 {
+                                compiled_statement.translations = Some(translations);
+                            }
                             let expr = &RE_INSERT_INTO_FOLDER_SUBFOLDERS;
                             let sub_folders = expr.captures_iter(expr_item);
                             let mut sub_folder_list: Vec<SubFolderDataConfig> = Vec::new();
@@ -342,6 +370,15 @@ impl<'gb> Statement<'gb> for InsertIntoFolderStatement {
             );
             return Err(errors);
         }
+        let result = load_exchange_rates(&db_folder, &space_database);
+        if result.is_err() {
+            errors.push(result.unwrap_err());
+            return Err(errors);
+        }
+        let formula_env = FormulaEnv{
+            exchange_rates: result.unwrap(),
+            time_zone: env.context.time_zone.clone(),
+        };
 
         let folder = folder.unwrap();
         let folder_name = &folder.clone().name.unwrap();
@@ -392,6 +429,8 @@ impl<'gb> Statement<'gb> for InsertIntoFolderStatement {
                 // eprintln!("InsertIntoFolder.run :: config_columns: {:#?}", &config_columns);
 
                 let mut db_data_list: Vec<DbData> = Vec::new();
+                // Numbers taken from sequences, given back when insert fails
+                let mut sequence_numbers: Vec<SequenceNumber> = Vec::new();
                 let mut links_map_map: HashMap<String, HashMap<String, Vec<ColumnConfig>>> = HashMap::new();
                 let mut links_data_map_map: HashMap<String, HashMap<String, HashMap<String, Vec<String>>>> = HashMap::new();
                 for statement in statements {
@@ -596,6 +635,14 @@ impl<'gb> Statement<'gb> for InsertIntoFolderStatement {
                                 let obj = LongTextColumn::defaults(&column_config);
                                 column_data_wrap = obj.validate(&column_data);
                             },
+                            COLUMN_TYPE_RICH_TEXT => {
+                                let obj = RichTextColumn::defaults(&column_config);
+                                column_data_wrap = obj.validate(&column_data);
+                            },
+                            COLUMN_TYPE_SECRET => {
+                                let obj = SecretColumn::defaults(&column_config, &context.secret_key);
+                                column_data_wrap = obj.validate(&column_data);
+                            },
                             COLUMN_TYPE_CHECKBOX => {
                                 let obj = CheckBoxColumn::defaults(&column_config);
                                 column_data_wrap = obj.validate(&column_data);
@@ -611,18 +658,35 @@ impl<'gb> Statement<'gb> for InsertIntoFolderStatement {
                                 );
                                 column_data_wrap = obj.validate(&column_data);
                             },
+                            COLUMN_TYPE_TAGS => {
+                                let obj = TagsColumn::defaults(
+                                    &column_config, 
+                                    Some(db_folder.clone())
+                                );
+                                column_data_wrap = obj.validate(&column_data);
+                            },
                             COLUMN_TYPE_FORMULA => {
                                 let obj = FormulaColumn::defaults(&column_config);
-                                column_data_wrap = obj.validate(&data, &column_config_map);
+                                column_data_wrap = obj.validate(&data, &column_config_map, &formula_env);
                             },
                             COLUMN_TYPE_DATE => {
-                                let obj = DateColumn::defaults(&column_config);
+                                let mut obj = DateColumn::defaults(&column_config);
+                                obj.context_time_zone = context.time_zone.clone();
                                 column_data_wrap = obj.validate(&column_data);
                             },
                             COLUMN_TYPE_DURATION => {
                                 let obj = DurationColumn::defaults(&column_config);
                                 column_data_wrap = obj.validate(&column_data);
                             },
+                            COLUMN_TYPE_RECURRENCE => {
+                                let mut obj = RecurrenceColumn::defaults(&column_config);
+                                obj.context_time_zone = context.time_zone.clone();
+                                column_data_wrap = obj.validate(&column_data);
+                            },
+                            COLUMN_TYPE_LOCATION => {
+                                let obj = LocationColumn::defaults(&column_config);
+                                column_data_wrap = obj.validate(&column_data);
+                            },
                             COLUMN_TYPE_CREATED_TIME => {
                                 let obj = AuditDateColumn::defaults(&column_config);
                                 column_data_wrap = obj.validate(&column_data);
@@ -731,12 +795,20 @@ impl<'gb> Statement<'gb> for InsertIntoFolderStatement {
                                 column_data_wrap = obj.validate(&column_data);
                             },
                             COLUMN_TYPE_GENERATE_NUMBER => {
-                                let obj = GenerateNumberColumn::defaults(
+                                let mut obj = GenerateNumberColumn::defaults(
                                     &column_config,
                                     Some(folder.clone()),
                                     Some(db_folder.clone()),
                                 );
-                                column_data_wrap = obj.validate(&column_data);
+                                obj.context_time_zone = context.time_zone.clone();
+                                let number = obj.next_number();
+                                if number.is_ok() {
+                                    let number = number.unwrap();
+                                    column_data_wrap = Ok(vec![number.value.clone()]);
+                                    sequence_numbers.push(number);
+                                } else {
+                                    column_data_wrap = Err(number.unwrap_err());
+                                }
                             },
                             COLUMN_TYPE_PHONE => {
                                 let obj = PhoneColumn::defaults(&column_config);
@@ -750,6 +822,21 @@ impl<'gb> Statement<'gb> for InsertIntoFolderStatement {
                                 let obj = UrlColumn::defaults(&column_config);
                                 column_data_wrap = obj.validate(&column_data);
                             },
+                            COLUMN_TYPE_WORKFLOW => {
+                                let obj = WorkflowColumn::defaults(&column_config);
+                                column_data_wrap = obj.validate(&column_data);
+                                if column_data_wrap.is_ok() {
+                                    let states = column_data_wrap.clone().unwrap();
+                                    if states.len() > 0 && states[0] != String::from("") {
+                                        let result = obj.validate_initial_state(&states[0]);
+                                        if result.is_err() {
+                                            column_data_wrap = Err(vec![result.unwrap_err()]);
+                                        } else {
+                                            data = WorkflowColumn::record_state(&data, &column_id, &states[0]);
+                                        }
+                                    }
+                                }
+                            },
                             COLUMN_TYPE_RATING => {
                                 let obj = RatingColumn::defaults(&column_config);
                                 column_data_wrap = obj.validate(&column_data);
@@ -768,12 +855,24 @@ impl<'gb> Statement<'gb> for InsertIntoFolderStatement {
                                     &column_data,
                                     &data,
                                     routing_wrap.clone(),
-                                    &home_dir.clone().unwrap_or_default()
+                                    &home_dir.clone().unwrap_or_default(),
+                                    &column_config_map,
+                                    &planet_context.tika,
                                 );
                                 if fields.is_ok() {
                                     let fields = fields.unwrap();
                                     column_data_wrap = Ok(fields.0);
                                     data = fields.2;
+                                    // Extracted document text, indexed by the text column
+                                    let document_texts = fields.1;
+                                    if document_texts.len() > 0 {
+                                        let key = format!("{}__text", column_id);
+                                        data.insert(key, build_value_list(&document_texts.join(" ")));
+                                    }
+                                } else {
+                                    let mut errors_: Vec<PlanetError> = Vec::new();
+                                    errors_.push(fields.unwrap_err());
+                                    column_data_wrap = Err(errors_);
                                 }
                                 // skip_data_assign = true;
                             },
@@ -801,6 +900,23 @@ impl<'gb> Statement<'gb> for InsertIntoFolderStatement {
                             errors = tuple.1;
                         }
                     }
+                    // translations for translatable columns, {column_id, language, value}
+                    let insert_translations = statement.translations.clone();
+                    if insert_translations.is_some() {
+                        let insert_translations = insert_translations.unwrap();
+                        let translation_list = get_translation_list(
+                            &folder, 
+                            &column_config_map, 
+                            &insert_translations
+                        );
+                        if translation_list.is_err() {
+                            errors.extend(translation_list.clone().unwrap_err());
+                        }
+                        let translation_list = translation_list.unwrap_or_default();
+                        if translation_list.len() > 0 {
+                            data.insert(TRANSLATIONS.to_string(), translation_list);
+                        }
+                    }
                     // text and language
                     let mut text_map: BTreeMap<String, String> = BTreeMap::new();
                     let mut text_column_id: String = String::from("");
@@ -872,7 +988,31 @@ impl<'gb> Statement<'gb> for InsertIntoFolderStatement {
                             data.insert(column_id.clone(), list_value);
                         }
                     }
+                    // rollups and lookups
+                    let result_linked = compute_linked_columns(
+                        &env, 
+                        &space_database, 
+                        &db_folder, 
+                        &column_config_map, 
+                        &data,
+                        &formula_env
+                    );
+                    if result_linked.is_err() {
+                        let errors_ = result_linked.unwrap_err();
+                        errors.extend(errors_);
+                    } else {
+                        data = result_linked.unwrap();
+                    }
+                    // check rules
+                    let result_checks = validate_check_rules(
+                        &folder, &data, &column_config_map, &formula_env
+                    );
+                    if result_checks.is_err() {
+                        let errors_ = result_checks.unwrap_err();
+                        errors.extend(errors_);
+                    }
                     if errors.len() > 0 {
+                        GenerateNumberColumn::release_numbers(&db_folder, &sequence_numbers);
                         return Err(errors)
                     }
                     db_data.data = Some(data);
@@ -887,6 +1027,7 @@ impl<'gb> Statement<'gb> for InsertIntoFolderStatement {
                     &db_data_list
                 );
                 if response_list.is_err() {
+                    GenerateNumberColumn::release_numbers(&db_folder, &sequence_numbers);
                     let errors_response = response_list.unwrap_err();
                     for error in errors_response {
                         errors.push(error);
@@ -905,105 +1046,92 @@ impl<'gb> Statement<'gb> for InsertIntoFolderStatement {
                 // links_data_map_map
                 // I go through the response_list, and get links data from the id, process it.
 
-                let mut yaml_response: Vec<yaml_rust::Yaml> = Vec::new();
-                for response in response_list {
+                let mut reverse_links_map: HashMap<String, Vec<(String, String, String)>> = HashMap::new();
+                for response in &response_list {
                     let id_record = response.id.clone().unwrap_or_default();
                     let links_map = links_map_map.get(&id_record);
                     let links_data_map = links_data_map_map.get(&id_record);
-                    if links_map.is_some() && links_data_map.is_some() {
-                        let links_map = links_map.unwrap();
-                        let links_data_map = links_data_map.unwrap();
-                        for (column_id, config_column_list) in links_map {
-                            // Get db item for this link
-                            let column_id = column_id.clone();
-                            for config in config_column_list {
-                                let config = config.clone();
-                                let many = config.many.unwrap();
-                                let remote_folder_name = config.linked_folder;
-                                if remote_folder_name.is_none() {
-                                    continue
-                                }
-                                let remote_folder_name = remote_folder_name.unwrap();
-                                let folder = db_folder.get_by_name(&remote_folder_name).unwrap().unwrap();
-                                // let folder = db_folder.get(&remote_folder_name).unwrap();
-                                let remote_folder_id = folder.id.unwrap_or_default();
-                                let folder_name = folder.name.unwrap();
-                                let main_data_map = links_data_map.get(&column_id);
-                                if main_data_map.is_some() {
-                                    let main_data_map = main_data_map.unwrap();
-                                    for (_column_name, id_list) in main_data_map {
-                                        for item_id in id_list {
-                                            let result: Result<TreeFolderItem, PlanetError> = TreeFolderItem::defaults(
-                                                space_database.connection_pool.clone(),
-                                                home_dir.clone().unwrap_or_default().as_str(),
-                                                &account_id,
-                                                space_id,
-                                                site_id.clone(),
-                                                remote_folder_id.as_str(),
-                                                &db_folder,
-                                            );
-                                            if result.is_err() {
-                                                // Return error about database problem
-                                            }
-                                            let mut db_row = result.unwrap();
-                                            let linked_item = db_row.get(
-                                                &folder_name, 
-                                                GetItemOption::ById(item_id.clone()), 
-                                                None
-                                            );
-                                            if linked_item.is_ok() {
-                                                let mut linked_item = linked_item.unwrap();
-                                                eprintln!("InsertIntoFolder.run :: linked_item: {:#?}", &linked_item);
-                                                // I may need to update to data_objects or data_collections
-                                                if many {
-                                                    let data_wrap = linked_item.data;
-                                                    let mut data: BTreeMap<String, Vec<BTreeMap<String, String>>>;
-                                                    if data_wrap.is_some() {
-                                                        data = data_wrap.unwrap();
-                                                    } else {
-                                                        data = BTreeMap::new();
-                                                    }
-                                                    let list_wrap = data.get(&column_id);
-                                                    let mut list: Vec<BTreeMap<String, String>>;
-                                                    if list_wrap.is_some() {
-                                                        list = list_wrap.unwrap().clone();
-                                                        let mut item_object: BTreeMap<String, String> = BTreeMap::new();
-                                                        item_object.insert(ID.to_string(), id_record.clone());
-                                                        list.push(item_object);
-                                                    } else {
-                                                        list = Vec::new();
-                                                        let mut item_object: BTreeMap<String, String> = BTreeMap::new();
-                                                        item_object.insert(ID.to_string(), id_record.clone());
-                                                        list.push(item_object);
-                                                    }
-                                                    data.insert(column_id.clone(), list);
-                                                    linked_item.data = Some(data);
-                                                    let _linked_item = db_row.update(&linked_item);
-                                                } else {
-                                                    let data_wrap = linked_item.data;
-                                                    let mut data: BTreeMap<String, Vec<BTreeMap<String, String>>>;
-                                                    if data_wrap.is_some() {
-                                                        data = data_wrap.unwrap();
-                                                    } else {
-                                                        data = BTreeMap::new();
-                                                    }
-                                                    let mut item_object: BTreeMap<String, String> = BTreeMap::new();
-                                                    item_object.insert(ID.to_string(), id_record.clone());
-                                                    let mut my_list: Vec<BTreeMap<String, String>> = Vec::new();
-                                                    my_list.push(item_object);
-                                                    data.insert(column_id.clone(), my_list);
-                                                    linked_item.data = Some(data);
-                                                    let _linked_item = db_row.update(&linked_item);
-                                                }
-                                            } else {
-                                                let error = linked_item.unwrap_err();
-                                                eprintln!("InsertIntoFolder.run :: I have error on get linked_item: {}", &error.message);
-                                            }
-                                        }
-                                    }
+                    if links_map.is_none() || links_data_map.is_none() {
+                        continue
+                    }
+                    let links_map = links_map.unwrap();
+                    let links_data_map = links_data_map.unwrap();
+                    let mut reverse_links: Vec<(String, String, String)> = Vec::new();
+                    for (column_id, config_column_list) in links_map {
+                        for config in config_column_list {
+                            let remote_folder_name = config.linked_folder.clone();
+                            if remote_folder_name.is_none() {
+                                continue
+                            }
+                            let remote_folder_name = remote_folder_name.unwrap();
+                            let remote_folder = db_folder.get_by_name(&remote_folder_name);
+                            if remote_folder.is_err() || remote_folder.clone().unwrap().is_none() {
+                                errors.push(
+                                    PlanetError::new(
+                                        500, 
+                                        Some(tr!("Linked folder \"{}\" not found.", &remote_folder_name)),
+                                    )
+                                );
+                                continue
+                            }
+                            let remote_folder_id = remote_folder.unwrap().unwrap().id.unwrap_or_default();
+                            let main_data_map = links_data_map.get(column_id);
+                            if main_data_map.is_none() {
+                                continue
+                            }
+                            for (_column_name, id_list) in main_data_map.unwrap() {
+                                for item_id in id_list {
+                                    reverse_links.push(
+                                        (remote_folder_id.clone(), column_id.clone(), item_id.clone())
+                                    );
                                 }
                             }
-                        }        
+                        }
+                    }
+                    reverse_links_map.insert(id_record, reverse_links);
+                }
+                // Reverse link columns are always many, new records are undone if any link can't be written
+                let mut linked_records: Vec<String> = Vec::new();
+                if errors.len() == 0 {
+                    for (id_record, reverse_links) in &reverse_links_map {
+                        let result = db_row.add_reverse_links(id_record, reverse_links);
+                        if result.is_err() {
+                            errors.push(result.unwrap_err());
+                            break
+                        }
+                        linked_records.push(id_record.clone());
+                    }
+                }
+                if errors.len() > 0 {
+                    for id_record in &linked_records {
+                        let reverse_links = reverse_links_map.get(id_record).unwrap();
+                        let result = db_row.remove_reverse_links(id_record, reverse_links);
+                        if result.is_err() {
+                            errors.push(result.unwrap_err());
+                        }
+                    }
+                    let result = db_row.undo_insert(&response_list);
+                    if result.is_err() {
+                        errors.push(result.unwrap_err());
+                    }
+                    GenerateNumberColumn::release_numbers(&db_folder, &sequence_numbers);
+                    return Err(errors)
+                }
+                let mut yaml_response: Vec<yaml_rust::Yaml> = Vec::new();
+                for response in response_list {
+                    // Rollups and lookups at items linked to the new item
+                    let result = refresh_linked_columns(
+                        &env, 
+                        &space_database, 
+                        &db_folder, 
+                        &folder, 
+                        &response,
+                        &formula_env
+                    );
+                    if result.is_err() {
+                        let errors_ = result.unwrap_err();
+                        errors.extend(errors_);
+                        return Err(errors)
                     }
                     let response_coded = serde_yaml::to_string(&response);
                     if response_coded.is_err() {
@@ -1355,6 +1483,19 @@ pub struct SelectSortBy {
     pub mode: SelectSortMode,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SelectPathFilter {
+    pub column_id: String,
+    pub path: String,
+    pub value: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SelectGeoFilter {
+    pub column_id: String,
+    pub cells: Vec<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SortedtBy {
     pub sorted_item: String,
@@ -1379,13 +1520,19 @@ pub struct SelectFromFolderCompiledStmt {
     pub where_source: Option<String>,
     pub where_compiled: Option<Formula>,
     pub group_by: Option<Vec<String>>,
+    pub group_by_depth: Option<BTreeMap<String, usize>>,
     pub sort_by: Option<Vec<SelectSortBy>>,
+    pub path_filters: Option<Vec<SelectPathFilter>>,
+    pub geo_filters: Option<Vec<SelectGeoFilter>>,
     pub count: Option<SelectCount>,
     pub needs_filter_links: bool,
     pub needs_filter_aggs: bool,
     pub needs_output_links: bool,
     pub needs_output_aggs: bool,
     pub skip_group_items: bool,
+    pub language: Option<String>,
+    #[serde(skip)]
+    pub formula_env: FormulaEnv,
 }
 
 impl SelectFromFolderCompiledStmt {
@@ -1417,13 +1564,18 @@ impl SelectFromFolderCompiledStmt {
             where_source: None,
             where_compiled: None,
             group_by: None,
+            group_by_depth: None,
             sort_by: None,
+            path_filters: None,
+            geo_filters: None,
             count: None,
             needs_filter_links: false,
             needs_filter_aggs: false,
             needs_output_links: false,
             needs_output_aggs: false,
             skip_group_items: false,
+            language: None,
+            formula_env: FormulaEnv::default(),
         };
         return statement
     }
@@ -1538,6 +1690,24 @@ impl<'gb> SearchCompiler<'gb> {
             statement.skip_group_items = true;
             statement_text = statement_text.replace("SKIP_GROUP_ITEMS", "");
         }
+        let expr = &RE_SELECT_LANGUAGE;
+        let captures = expr.captures(&statement_text);
+        if captures.is_some() {
+            let captures = captures.unwrap();
+            let check_text = captures.get(0).unwrap().as_str().to_string();
+            let language = captures.name("Language").unwrap().as_str().to_string();
+            if !LANGUAGE_CODE_ITEMS.contains(&language.as_str()) {
+                errors.push(
+                    PlanetError::new(
+                        500, 
+                        Some(tr!("Language \"{}\" not supported.", &language)),
+                    )
+                );
+                return Err(errors)
+            }
+            statement.language = Some(language);
+            statement_text = statement_text.replace(&check_text, "");
+        }
         let statement_text = statement_text.replace("\n", "").clone();
         let expr = &RE_SELECT;
         let is_match = expr.is_match(&statement_text);
@@ -1651,7 +1821,9 @@ impl<'gb> SearchCompiler<'gb> {
                         };
                         if column.is_some() {
                             let column = column.unwrap().as_str();
-                            sort_obj.column = column.to_string().trim().to_string();
+                            // {Metadata.score} => Metadata.score
+                            let column = column.replace("{", "").replace("}", "");
+                            sort_obj.column = column.trim().to_string();
                         }
                         if mode.is_some() {
                             let mode = mode.unwrap().as_str();
@@ -1687,9 +1859,22 @@ impl<'gb> SearchCompiler<'gb> {
                     let expr = &RE_SELECT_GROUP_COLUMNS;
                     let columns = expr.captures_iter(group_by_columns);
                     let mut columns_string: Vec<String> = Vec::new();
+                    // Tags DEPTH 2 rolls up tags into their parents at depth 2
+                    let mut columns_depth: BTreeMap<String, usize> = BTreeMap::new();
                     for column in columns {
-                        let column_str = column.name("Column").unwrap().as_str();
-                        columns_string.push(column_str.to_string().trim().to_string());
+                        let column_str = column.name("Column").unwrap().as_str().trim().to_string();
+                        let expr_depth = &RE_SELECT_GROUP_DEPTH;
+                        let depth_captures = expr_depth.captures(&column_str);
+                        if depth_captures.is_some() {
+                            let depth_captures = depth_captures.unwrap();
+                            let column_name = depth_captures.name("Column").unwrap().as_str().trim().to_string();
+                            let depth = depth_captures.name("Depth").unwrap().as_str();
+                            let depth: usize = FromStr::from_str(depth).unwrap_or_default();
+                            columns_depth.insert(column_name.clone(), depth);
+                            columns_string.push(column_name);
+                        } else {
+                            columns_string.push(column_str);
+                        }
                     }
                     let length = columns_string.len();
                     if length > 10 {
@@ -1703,6 +1888,9 @@ impl<'gb> SearchCompiler<'gb> {
                     }
                     if length > 0 {
                         statement.group_by = Some(columns_string);
+                        if columns_depth.len() > 0 {
+                            statement.group_by_depth = Some(columns_depth);
+                        }
                         statement_text = expr_1.replace(&statement_text, "").to_string();
                     }
                 }
@@ -1717,8 +1905,17 @@ impl<'gb> SearchCompiler<'gb> {
                 let where_formula = captures.name("Where");
                 if where_formula.is_some() {
                     let where_formula_str = where_formula.unwrap().as_str();
+                    // {Metadata}.customer.tier => {Metadata.customer.tier}
+                    let expr_path = &RE_SELECT_WHERE_OBJECT_PATH;
+                    let has_object_paths = expr_path.is_match(where_formula_str);
+                    let where_formula_string = expr_path.replace_all(
+                        where_formula_str, "{$Column$Path}"
+                    ).to_string();
+                    let where_formula_str = where_formula_string.as_str();
                     let expr = &RE_FORMULA_QUERY;
-                    let is_valid = expr.is_match(where_formula_str);
+                    let expr_predicate = &RE_SELECT_WHERE_PREDICATE;
+                    let is_valid = expr.is_match(where_formula_str) || 
+                        expr_predicate.is_match(where_formula_str);
                     statement.where_source = Some(where_formula_str.to_string());
                     if !is_valid {
                         errors.push(
@@ -1730,7 +1927,7 @@ impl<'gb> SearchCompiler<'gb> {
                     }
                     // Where Search Index Boost words
                     let has_func_assigns = expr_boost_no.is_match(&where_formula_str);
-                    if !has_func_assigns {
+                    if !has_func_assigns && !has_object_paths {
                         let mut boost_word_set: HashSet<String> = HashSet::new();
                         let matches = expr_boost_yes.captures_iter(&where_formula_str);
                         for match_ in matches {
@@ -1799,7 +1996,11 @@ impl<'gb> SearchCompiler<'gb> {
         if compiled_statement.sort_by.is_some() {
             let sort_by = compiled_statement.sort_by.clone().unwrap();
             for sort_by_item in sort_by {
-                let item_column = sort_by_item.column;
+                let mut item_column = sort_by_item.column;
+                let object_path = SearchCompiler::get_object_path(&item_column, &column_config_map);
+                if object_path.is_some() {
+                    item_column = object_path.unwrap().0;
+                }
                 if !column_list.contains(&item_column) {
                     column_list.push(item_column);
                 }
@@ -1826,6 +2027,24 @@ impl<'gb> SearchCompiler<'gb> {
                 column_raised.insert(column.clone(), String::from(""));
             }
         }
+        if compiled_statement.group_by_depth.is_some() {
+            let group_by_depth = compiled_statement.group_by_depth.clone().unwrap();
+            for (column, depth) in group_by_depth {
+                let config = column_config_map.get(&column);
+                let column_type = config.and_then(|config| config.column_type.clone()).unwrap_or_default();
+                if column_type != COLUMN_TYPE_TAGS || depth == 0 {
+                    errors.push(
+                        PlanetError::new(
+                            500, 
+                            Some(tr!(
+                                "GROUP BY DEPTH is only allowed for tags columns with depth higher than 0, \"{}\".", 
+                                &column
+                            )),
+                        )
+                    );
+                }
+            }
+        }
         // - Compile Where formula
         let where_source = compiled_statement.where_source.clone();
         if where_source.is_some() {
@@ -1839,6 +2058,12 @@ impl<'gb> SearchCompiler<'gb> {
             for (k, v) in column_config_map.clone() {
                 properties_map.insert(k, v);
             }
+            compiled_statement.path_filters = SearchCompiler::get_path_filters(
+                &where_source, &column_config_map
+            );
+            compiled_statement.geo_filters = SearchCompiler::get_geo_filters(
+                &where_source, &column_config_map
+            );
             let formula_query = Formula::defaults(
                 &where_source, 
                 &String::from("bool"), 
@@ -1942,6 +2167,144 @@ impl<'gb> SearchCompiler<'gb> {
         return Ok(compiled_statement.clone())
     }
 
+    pub fn get_object_path(
+        column: &String, 
+        column_config_map: &BTreeMap<String, ColumnConfig>
+    ) -> Option<(String, String)> {
+        let mut properties_map: HashMap<String, ColumnConfig> = HashMap::new();
+        for (k, v) in column_config_map.clone() {
+            properties_map.insert(k, v);
+        }
+        return get_object_path(column, &properties_map)
+    }
+
+    // Equality filters on indexed object paths, like {Metadata.customer.tier} = "gold". Only used when WHERE 
+    // is one assignment or AND of assignments, so items found through the paths index are the only ones 
+    // that can match.
+    fn get_path_filters(
+        where_source: &String, 
+        column_config_map: &BTreeMap<String, ColumnConfig>
+    ) -> Option<Vec<SelectPathFilter>> {
+        let mut where_source = where_source.trim().to_string();
+        if where_source.starts_with("AND(") && where_source.ends_with(")") {
+            where_source = where_source[4..where_source.len()-1].to_string();
+        }
+        let expr_functions = &RE_FORMULA_FUNCTIONS;
+        if expr_functions.is_match(&where_source) {
+            return None
+        }
+        let expr = &RE_SELECT_WHERE_PATH_EQUAL;
+        let mut filters: Vec<SelectPathFilter> = Vec::new();
+        for assignment in where_source.split(",") {
+            let assignment = assignment.trim();
+            let captures = expr.captures(assignment);
+            if captures.is_none() {
+                continue
+            }
+            let captures = captures.unwrap();
+            let column = captures.name("Column").unwrap().as_str().trim().to_string();
+            let path = captures.name("Path").unwrap().as_str().to_string();
+            let value = captures.name("Value").unwrap().as_str();
+            let is_quoted = value.starts_with("\"");
+            let value = value.replace("\"", "");
+            // Quoted numbers compare as text, while paths index keeps numbers by value
+            let number: Result<f64, _> = value.parse();
+            if is_quoted && number.is_ok() {
+                continue
+            }
+            let value = ObjectColumn::get_path_index_value(&value);
+            let config = column_config_map.get(&column);
+            if config.is_none() {
+                continue
+            }
+            let config = config.unwrap();
+            let column_type = config.column_type.clone().unwrap_or_default();
+            let indexed_paths = config.indexed_paths.clone().unwrap_or_default();
+            if column_type.as_str() != COLUMN_TYPE_OBJECT || !indexed_paths.contains(&path) {
+                continue
+            }
+            filters.push(
+                SelectPathFilter{
+                    column_id: config.id.clone().unwrap_or_default(),
+                    path: path,
+                    value: value,
+                }
+            );
+        }
+        if filters.len() == 0 {
+            return None
+        }
+        return Some(filters)
+    }
+
+    // Location predicates, like WITHIN_RADIUS({Place}, 40.4168, -3.7038, 5), are turned into geohash cells 
+    // for the geo index. Only used when WHERE is the predicate or an AND without other logical operators, 
+    // so items outside the cells can't match.
+    fn get_geo_filters(
+        where_source: &String, 
+        column_config_map: &BTreeMap<String, ColumnConfig>
+    ) -> Option<Vec<SelectGeoFilter>> {
+        let where_source = where_source.trim().to_string();
+        let expr_predicate = &RE_SELECT_WHERE_PREDICATE;
+        let is_predicate = expr_predicate.is_match(&where_source);
+        let is_and = where_source.starts_with("AND(") && 
+            where_source.find("OR(").is_none() && 
+            where_source.find("NOT(").is_none();
+        if !is_predicate && !is_and {
+            return None
+        }
+        let expr = &RE_SELECT_WHERE_GEO;
+        let mut filters: Vec<SelectGeoFilter> = Vec::new();
+        for captures in expr.captures_iter(&where_source) {
+            let function = captures.name("Function").unwrap().as_str();
+            let column = captures.name("Column").unwrap().as_str().trim().to_string();
+            let numbers = captures.name("Numbers").unwrap().as_str();
+            let mut values: Vec<f64> = Vec::new();
+            for number in numbers.split(",") {
+                let number = number.trim();
+                if number.len() == 0 {
+                    continue
+                }
+                values.push(FromStr::from_str(number).unwrap());
+            }
+            let config = column_config_map.get(&column);
+            if config.is_none() {
+                continue
+            }
+            let config = config.unwrap();
+            let column_type = config.column_type.clone().unwrap_or_default();
+            if column_type.as_str() != COLUMN_TYPE_LOCATION {
+                continue
+            }
+            let bounding_box: LocationBox;
+            if function == FUNCTION_WITHIN_RADIUS && values.len() == 3 {
+                let center = Location::parse(&format!("{},{}", &values[0], &values[1]));
+                if center.is_err() {
+                    continue
+                }
+                bounding_box = LocationBox::from_radius(&center.unwrap(), values[2]);
+            } else if function == FUNCTION_WITHIN_BOX && values.len() == 4 {
+                let result = LocationBox::defaults(values[0], values[1], values[2], values[3]);
+                if result.is_err() {
+                    continue
+                }
+                bounding_box = result.unwrap();
+            } else {
+                continue
+            }
+            filters.push(
+                SelectGeoFilter{
+                    column_id: config.id.clone().unwrap_or_default(),
+                    cells: bounding_box.get_geohash_cells(),
+                }
+            );
+        }
+        if filters.len() == 0 {
+            return None
+        }
+        return Some(filters)
+    }
+
     pub fn do_compile(
         &self,
         db_folder: Option<TreeFolder>,
@@ -2748,6 +3111,8 @@ pub struct ColumnStat {
     pub max: Option<f64>,
     pub min: Option<f64>,
     pub avg: Option<f64>,
+    pub currency: Option<String>,
+    pub amount_sum: Option<Decimal>,
 }
 
 #[derive(Debug, Clone)]
@@ -3020,6 +3385,7 @@ impl<'gb> SearchGroupBy<'gb>{
     fn get_sort_value(
         &self, 
         group_by: &GroupByResult,
+        column_id: &String,
         column_item: &String,
         column_type: &String,
     ) -> Result<SortValueMode, PlanetError> {
@@ -3075,8 +3441,7 @@ impl<'gb> SearchGroupBy<'gb>{
         // eprintln!("get_sort_value :: value: {}", &value);
         match column_type {
             COLUMN_TYPE_DURATION => {
-                let number: i64 = FromStr::from_str(value.as_str()).unwrap();
-                let number = number*1000;
+                let number = parse_duration(&value).unwrap_or_default();
                 return Ok(SortValueMode{str: None, number: Some(number)})
             },
             COLUMN_TYPE_CHECKBOX => {
@@ -3090,17 +3455,30 @@ impl<'gb> SearchGroupBy<'gb>{
                 return Ok(SortValueMode{str: None, number: Some(number)})
             },
             COLUMN_TYPE_GENERATE_NUMBER => {
-                let number: i64 = FromStr::from_str(value.as_str()).unwrap();
+                let number = GenerateNumberColumn::get_sort_number(&value);
                 let number = number*1000;
                 return Ok(SortValueMode{str: None, number: Some(number)})
             },
             COLUMN_TYPE_CURRENCY => {
+                // Amounts in different currencies sort by value in base currency, amounts with no rate go after
+                let folder = self.folder.clone().unwrap();
+                let column = TreeFolder::get_column_by_id(column_id, &folder)?;
+                let currency = CurrencyColumn::get_column_currency_code(&column)?;
+                let amount = CurrencyAmount::parse(&value, &currency)?;
+                let exchange_rates = &self.query.formula_env.exchange_rates;
+                let value = amount.get_sort_key(exchange_rates);
+                return Ok(SortValueMode{str: Some(value), number: None})
+            },
+            COLUMN_TYPE_PERCENTAGE => {
                 let number: i64 = FromStr::from_str(value.as_str()).unwrap();
                 let number = number*1000;
                 return Ok(SortValueMode{str: None, number: Some(number)})
             },
-            COLUMN_TYPE_PERCENTAGE => {
-                let number: i64 = FromStr::from_str(value.as_str()).unwrap();
+            COLUMN_TYPE_SELECT => {
+                // Options sort by configured order, unknown options go last
+                let folder = self.folder.clone().unwrap();
+                let order = SelectColumn::get_sort_order(&folder, &value);
+                let number: i64 = order.and_then(|order| order.to_i64()).unwrap_or(i64::MAX/1000);
                 let number = number*1000;
                 return Ok(SortValueMode{str: None, number: Some(number)})
             },
@@ -3131,6 +3509,7 @@ impl<'gb> SearchGroupBy<'gb>{
             let column_type = column_type.unwrap();
             let result = self.get_sort_value(
                 &group_by, 
+                sorter_column_id, 
                 &sorter_column_item, 
                 column_type
             );
@@ -3256,18 +3635,34 @@ impl<'gb> SearchGroupBy<'gb>{
                 if grouped_data.len() == 0 {
                     continue
                 }
-                let key = grouped_data.join("|||");
-                let search_result_group_item = &search_result_grouping.get(&key);
-                if search_result_group_item.is_some() {
-                    // I add item to list of keyed items
-                    let mut search_result_group_item = search_result_group_item.unwrap().clone();
-                    search_result_group_item.push(item_result.clone());
-                    search_result_grouping.insert(key.clone(), search_result_group_item);
-                } else {
-                    // I init and add new item
-                    let mut search_result_group_item: Vec<SearchResultItem> = Vec::new();
-                    search_result_group_item.push(item_result.clone());
-                    search_result_grouping.insert(key.clone(), search_result_group_item);
+                // Tags columns have many group values, item goes into each of them
+                let mut keys: Vec<Vec<String>> = Vec::new();
+                keys.push(Vec::new());
+                for value in &grouped_data {
+                    let mut keys_new: Vec<Vec<String>> = Vec::new();
+                    for key in &keys {
+                        for value_item in value.split(TAGS_GROUP_SEPARATOR) {
+                            let mut key = key.clone();
+                            key.push(value_item.to_string());
+                            keys_new.push(key);
+                        }
+                    }
+                    keys = keys_new;
+                }
+                for key in keys {
+                    let key = key.join("|||");
+                    let search_result_group_item = &search_result_grouping.get(&key);
+                    if search_result_group_item.is_some() {
+                        // I add item to list of keyed items
+                        let mut search_result_group_item = search_result_group_item.unwrap().clone();
+                        search_result_group_item.push(item_result.clone());
+                        search_result_grouping.insert(key.clone(), search_result_group_item);
+                    } else {
+                        // I init and add new item
+                        let mut search_result_group_item: Vec<SearchResultItem> = Vec::new();
+                        search_result_group_item.push(item_result.clone());
+                        search_result_grouping.insert(key.clone(), search_result_group_item);
+                    }
                 }
             }
         }
@@ -3424,11 +3819,15 @@ impl SearchIterator{
                     sort_column_type = String::from(SORT_TYPE_NUMBER);
                 },
                 COLUMN_TYPE_CURRENCY => {
-                    sort_column_type = String::from(SORT_TYPE_NUMBER);
+                    // Sort key groups amounts with no exchange rate apart from converted ones
+                    sort_column_type = String::from(SORT_TYPE_STR);
                 },
                 COLUMN_TYPE_PERCENTAGE => {
                     sort_column_type = String::from(SORT_TYPE_NUMBER);
                 },
+                COLUMN_TYPE_SELECT => {
+                    sort_column_type = String::from(SORT_TYPE_NUMBER);
+                },
                 __ => {}
             }
         }
@@ -3446,9 +3845,27 @@ impl SearchIterator{
         let item_data = item.data.clone();
         let column_type = column_type.as_str();
         // eprintln!("get_sort_value :: column_type: {}", column_type);
+        if column_type == COLUMN_TYPE_OBJECT && column_id.find(OBJECT_PATH_SEPARATOR).is_some() {
+            let fields: Vec<&str> = column_id.splitn(2, OBJECT_PATH_SEPARATOR).collect();
+            let path = fields[1].to_string();
+            let mut source = String::from("");
+            if item_data.is_some() {
+                let values = item_data.unwrap().get(fields[0]).cloned();
+                if values.is_some() {
+                    source = get_value_list(&values.unwrap()).unwrap_or_default();
+                }
+            }
+            let value = ObjectColumn::get_path_sort_value(&source, &path);
+            return Ok(SortValueMode{str: Some(value), number: None})
+        }
         if item_data.is_some() {
             let item_data = item_data.unwrap();
             let values = item_data.get(&column_id);
+            if values.is_some() && column_type == COLUMN_TYPE_TAGS {
+                // Items with many tags sort by the first one
+                let value = values.unwrap().first().and_then(|value| value.get(VALUE)).cloned();
+                return Ok(SortValueMode{str: Some(value.unwrap_or_default()), number: None})
+            }
             if values.is_some() {
                 let values = values.unwrap();
                 let value = get_value_list(values);
@@ -3457,8 +3874,8 @@ impl SearchIterator{
                     // eprintln!("get_sort_value :: value: {}", &value);
                     match column_type {
                         COLUMN_TYPE_DURATION => {
-                            let number: i64 = FromStr::from_str(value.as_str()).unwrap();
-                            let number = number*1000;
+                            // Microseconds
+                            let number = parse_duration(&value).unwrap_or_default();
                             return Ok(SortValueMode{str: None, number: Some(number)})
                         },
                         COLUMN_TYPE_CHECKBOX => {
@@ -3472,17 +3889,30 @@ impl SearchIterator{
                             return Ok(SortValueMode{str: None, number: Some(number)})
                         },
                         COLUMN_TYPE_GENERATE_NUMBER => {
-                            let number: i64 = FromStr::from_str(value.as_str()).unwrap();
+                            let number = GenerateNumberColumn::get_sort_number(&value);
                             let number = number*1000;
                             return Ok(SortValueMode{str: None, number: Some(number)})
                         },
                         COLUMN_TYPE_CURRENCY => {
+                            // Amounts in different currencies sort by value in base currency, amounts with no rate go after
+                            let folder = self.folder.clone().unwrap();
+                            let column = TreeFolder::get_column_by_id(&column_id, &folder)?;
+                            let currency = CurrencyColumn::get_column_currency_code(&column)?;
+                            let amount = CurrencyAmount::parse(&value, &currency)?;
+                            let exchange_rates = &self.query.formula_env.exchange_rates;
+                            let value = amount.get_sort_key(exchange_rates);
+                            return Ok(SortValueMode{str: Some(value), number: None})
+                        },
+                        COLUMN_TYPE_PERCENTAGE => {
                             let number: i64 = FromStr::from_str(value.as_str()).unwrap();
                             let number = number*1000;
                             return Ok(SortValueMode{str: None, number: Some(number)})
                         },
-                        COLUMN_TYPE_PERCENTAGE => {
-                            let number: i64 = FromStr::from_str(value.as_str()).unwrap();
+                        COLUMN_TYPE_SELECT => {
+                            // Options sort by configured order, unknown options go last
+                            let folder = self.folder.clone().unwrap();
+                            let order = SelectColumn::get_sort_order(&folder, &value);
+                            let number: i64 = order.and_then(|order| order.to_i64()).unwrap_or(i64::MAX/1000);
                             let number = number*1000;
                             return Ok(SortValueMode{str: None, number: Some(number)})
                         },
@@ -3519,6 +3949,7 @@ impl SearchIterator{
         let group_by = self.query.group_by.clone();
         if group_by.is_some() {
             let group_by = group_by.unwrap();
+            let group_by_depth = self.query.group_by_depth.clone().unwrap_or_default();
             let mut grouped_data: Vec<String> = Vec::new();
             for column_name in &group_by {
                 // I need column_id for column_name
@@ -3526,8 +3957,25 @@ impl SearchIterator{
                 if config.is_some() {
                     let config = config.unwrap();
                     let column_id = config.id.clone().unwrap();
+                    let column_type = config.column_type.clone().unwrap_or_default();
                     let column_value = &data.get(&column_id);
-                    if column_value.is_some() {
+                    if column_value.is_some() && column_type == COLUMN_TYPE_TAGS {
+                        // Item is counted once on each tag, tags rolled up at depth
+                        let depth = group_by_depth.get(column_name).cloned().unwrap_or_default();
+                        let mut tags: Vec<String> = Vec::new();
+                        for value in column_value.unwrap().iter() {
+                            let tag = value.get(VALUE);
+                            if tag.is_some() {
+                                let tag = TagsColumn::get_tag_at_depth(tag.unwrap(), depth);
+                                if !tags.contains(&tag) {
+                                    tags.push(tag);
+                                }
+                            }
+                        }
+                        if tags.len() > 0 {
+                            grouped_data.push(tags.join(TAGS_GROUP_SEPARATOR));
+                        }
+                    } else if column_value.is_some() {
                         let column_value_list = column_value.unwrap();
                         let value = get_value_list(column_value_list);
                         if value.is_some() {
@@ -4097,13 +4545,36 @@ impl SearchIterator{
                 if column_name.to_lowercase() == String::from(ID) {
                     continue
                 }
-                let column = TreeFolder::get_column_by_name(
+                let mut column = TreeFolder::get_column_by_name(
                     &column_name, 
                     &folder
                 );
+                // Object paths, like Metadata.score
+                let mut object_path: Option<String> = None;
+                if column.is_err() && column_name.find(".").is_some() {
+                    let fields: Vec<&str> = column_name.splitn(2, ".").collect();
+                    let path_column = TreeFolder::get_column_by_name(
+                        &fields[0].to_string(), 
+                        &folder
+                    );
+                    if path_column.is_ok() {
+                        let path_column = path_column.unwrap();
+                        let column_type = path_column.get(COLUMN_TYPE).unwrap().clone();
+                        if column_type.as_str() == COLUMN_TYPE_OBJECT {
+                            column = Ok(path_column);
+                            object_path = Some(fields[1].to_string());
+                        }
+                    }
+                }
                 if column.is_ok() {
                     let column = column.unwrap();
-                    let column_id = column.get(ID).unwrap();
+                    let mut column_id = column.get(ID).unwrap().clone();
+                    if object_path.is_some() {
+                        column_id = format!(
+                            "{}{}{}", &column_id, OBJECT_PATH_SEPARATOR, object_path.unwrap()
+                        );
+                    }
+                    let column_id = &column_id;
                     let content_type = column.get(COLUMN_TYPE).unwrap().clone();
                     column_type_map.insert(column_id.clone(), content_type);
                     let column_sort_type = self.get_column_sort_type(&column);
@@ -4305,7 +4776,7 @@ impl SearchIterator{
             let column_type_str = column_type.as_str();
             let column_id = config.id.clone().unwrap();
             if column_type_str == COLUMN_TYPE_STATS {
-                let stats_column = StatsColumn::defaults(
+                let mut stats_column = StatsColumn::defaults(
                     &config.clone(), 
                     Some(column_config_map.clone()),
                     Some(folder_name.clone()), 
@@ -4313,6 +4784,7 @@ impl SearchIterator{
                     Some(properties_map.clone()), 
                     Some(data_map.clone())
                 );
+                stats_column.formula_env = Some(self.query.formula_env.clone());
                 let data: Vec<String> = Vec::new();
                 let result = stats_column.validate(&data);
                 if result.is_err() {
@@ -4609,8 +5081,14 @@ impl SearchIterator{
                                     &column_config_map, 
                                     &item_data
                                 );
-                                let stemmer = get_stemmer_by_language(&language_code);
-                                let stop_words = get_stop_words_by_language(&language_code);
+                                // translations are indexed with the stemmer of their language
+                                let mut language_codes: Vec<String> = Vec::new();
+                                language_codes.push(language_code);
+                                for translation_code in TreeFolderItem::get_translation_language_codes(&item_data) {
+                                    if !language_codes.contains(&translation_code) {
+                                        language_codes.push(translation_code);
+                                    }
+                                }
     
                                 let index_result = TreeFolderItem::get_index_item(
                                     index_tree.clone(), 
@@ -4625,13 +5103,21 @@ impl SearchIterator{
                                     let index_data = index_item.data.clone().unwrap();
                                     for word in &boost_words.clone() {
                                         let word = word.to_lowercase();
-                                        let is_stop = stop_words.contains(&word.to_string());
-                                        if is_stop {
-                                            continue
+                                        let mut has_stem = false;
+                                        for language_code in &language_codes {
+                                            let stop_words = get_stop_words_by_language(language_code);
+                                            let is_stop = stop_words.contains(&word.to_string());
+                                            if is_stop {
+                                                continue
+                                            }
+                                            let stemmer = get_stemmer_by_language(language_code);
+                                            let stem = stemmer.stem(&word);
+                                            let stem = stem.to_string();
+                                            if index_data.get(&stem).is_some() {
+                                                has_stem = true;
+                                                break
+                                            }
                                         }
-                                        let stem = stemmer.stem(&word);
-                                        let stem = stem.to_string();
-                                        let has_stem = *&index_data.get(&stem).is_some();
                                         if has_stem {
                                             let mut links_folder_by_column_id_wrap: Option<HashMap<String, String>> = None;
                                             let mut remote_folder_data_map_wrap: Option<HashMap<String, HashMap<String, DbData>>> = None;
@@ -4687,7 +5173,7 @@ impl SearchIterator{
         return Err(errors)
     }
 
-    fn do_search_sequential(
+    fn do_search_path_index(
         &self,
         db_folder_item: &TreeFolderItem,
         sorter_list: &Vec<SearchSorter>,
@@ -4697,54 +5183,221 @@ impl SearchIterator{
         remote_folder_obj_map: Option<HashMap<String, DbData>>,
         sorter_map: &HashMap<String, SortedtBy>,
         column_type_map: &HashMap<String, String>,
+        path_filters: &Vec<SelectPathFilter>,
     ) -> Result<Vec<SearchSorter>, Vec<PlanetError>> {
-        let needs_filter_links = self.query.needs_filter_links.clone();
         let mut db_folder_item = db_folder_item.clone();
-        let sorter_list = sorter_list.clone();
-        let partitions = db_folder_item.get_partitions();
-        let errors: Vec<PlanetError> = Vec::new();
-        if partitions.is_ok() {
-            let partitions = partitions.unwrap();
-            let mut handles= vec![];
-            let remote_folder_data_map: HashMap<String, HashMap<String, DbData>> = HashMap::new();
-            let sorter_list: Arc<Mutex<Vec<SearchSorter>>> = Arc::new(Mutex::new(sorter_list.clone()));
-            let column_config_map: Arc<Mutex<BTreeMap<String, ColumnConfig>>> = Arc::new(Mutex::new(column_config_map.clone()));
-            let links_folder_by_column_id: Arc<Mutex<Option<HashMap<String, String>>>> = Arc::new(Mutex::new(links_folder_by_column_id.clone()));
-            let remote_folder_map: Arc<Mutex<Option<HashMap<String, HashMap<u16, TreeFolderItem>>>>> = Arc::new(Mutex::new(remote_folder_map.clone()));
-            let remote_folder_obj_map: Arc<Mutex<Option<HashMap<String, DbData>>>> = Arc::new(Mutex::new(remote_folder_obj_map.clone()));
-            let sorter_map: Arc<Mutex<HashMap<String, SortedtBy>>> = Arc::new(Mutex::new(sorter_map.clone()));
-            let column_type_map: Arc<Mutex<HashMap<String, String>>> = Arc::new(Mutex::new(column_type_map.clone()));
-            let db_folder_item: Arc<Mutex<TreeFolderItem>> = Arc::new(Mutex::new(db_folder_item.clone()));
-            let this: Arc<Mutex<SearchIterator>> = Arc::new(Mutex::new(self.clone()));
-            let remote_folder_data_map: Arc<Mutex<HashMap<String, HashMap<String, DbData>>>> = Arc::new(Mutex::new(remote_folder_data_map.clone()));
-            let errors = Arc::new(Mutex::new(errors.clone()));
-            for partition in partitions {
-                let sorter_list = Arc::clone(&sorter_list);
-                let column_config_map = Arc::clone(&column_config_map);
-                let links_folder_by_column_id = Arc::clone(&links_folder_by_column_id);
-                let remote_folder_map = Arc::clone(&remote_folder_map);
-                let remote_folder_obj_map = Arc::clone(&remote_folder_obj_map);
-                let sorter_map = Arc::clone(&sorter_map);
-                let column_type_map = Arc::clone(&column_type_map);
-                let this = Arc::clone(&this);
-                let db_folder_item = Arc::clone(&db_folder_item);
-                let remote_folder_data_map = Arc::clone(&remote_folder_data_map);
-                let errors = Arc::clone(&errors);
-                let handle = thread::spawn(move || {
-                    let mut sorter_list = sorter_list.lock().unwrap();
-                    let column_config_map = column_config_map.lock().unwrap();
-                    let links_folder_by_column_id = links_folder_by_column_id.lock().unwrap();
-                    let remote_folder_map = remote_folder_map.lock().unwrap();
-                    let remote_folder_obj_map = remote_folder_obj_map.lock().unwrap();
-                    let sorter_map = sorter_map.lock().unwrap();
-                    let column_type_map = column_type_map.lock().unwrap();
-                    let this = this.lock().unwrap();
-                    let mut db_folder_item = db_folder_item.lock().unwrap();
-                    let remote_folder_data_map = remote_folder_data_map.lock().unwrap();
-                    let mut errors = errors.lock().unwrap();
-                    let (db_tree, index_tree) = db_folder_item.open_partition(&partition).unwrap();
-                    // I may need botth db and index to execute formula
-                    let iter = db_tree.iter();
+        let mut errors: Vec<PlanetError> = Vec::new();
+        // Items need to match all path filters
+        let mut item_ids: Option<Vec<String>> = None;
+        for path_filter in path_filters {
+            let result = db_folder_item.get_path_index_ids(
+                &path_filter.column_id, 
+                &path_filter.path, 
+                &path_filter.value
+            );
+            if result.is_err() {
+                errors.push(result.unwrap_err());
+                return Err(errors)
+            }
+            let ids = result.unwrap();
+            if item_ids.is_none() {
+                item_ids = Some(ids);
+            } else {
+                let previous_ids = item_ids.unwrap();
+                let mut filtered_ids: Vec<String> = Vec::new();
+                for id in previous_ids {
+                    if ids.contains(&id) {
+                        filtered_ids.push(id);
+                    }
+                }
+                item_ids = Some(filtered_ids);
+            }
+        }
+        let item_ids = item_ids.unwrap_or_default();
+        return self.do_search_item_ids(
+            &db_folder_item, 
+            sorter_list, 
+            column_config_map, 
+            links_folder_by_column_id, 
+            remote_folder_map, 
+            remote_folder_obj_map, 
+            sorter_map, 
+            column_type_map, 
+            &item_ids
+        )
+    }
+
+    fn do_search_geo_index(
+        &self,
+        db_folder_item: &TreeFolderItem,
+        sorter_list: &Vec<SearchSorter>,
+        column_config_map: &BTreeMap<String, ColumnConfig>,
+        links_folder_by_column_id: Option<HashMap<String, String>>,
+        remote_folder_map: Option<HashMap<String, HashMap<u16, TreeFolderItem>>>,
+        remote_folder_obj_map: Option<HashMap<String, DbData>>,
+        sorter_map: &HashMap<String, SortedtBy>,
+        column_type_map: &HashMap<String, String>,
+        geo_filters: &Vec<SelectGeoFilter>,
+    ) -> Result<Vec<SearchSorter>, Vec<PlanetError>> {
+        let mut db_folder_item = db_folder_item.clone();
+        let mut errors: Vec<PlanetError> = Vec::new();
+        // Items in geohash cells for all filters, WHERE formula then checks exact distance or box
+        let mut item_ids: Option<Vec<String>> = None;
+        for geo_filter in geo_filters {
+            let result = db_folder_item.get_geo_index_ids(
+                &geo_filter.column_id, 
+                &geo_filter.cells
+            );
+            if result.is_err() {
+                errors.push(result.unwrap_err());
+                return Err(errors)
+            }
+            let ids = result.unwrap();
+            if item_ids.is_none() {
+                item_ids = Some(ids);
+            } else {
+                let previous_ids = item_ids.unwrap();
+                let mut filtered_ids: Vec<String> = Vec::new();
+                for id in previous_ids {
+                    if ids.contains(&id) {
+                        filtered_ids.push(id);
+                    }
+                }
+                item_ids = Some(filtered_ids);
+            }
+        }
+        let item_ids = item_ids.unwrap_or_default();
+        return self.do_search_item_ids(
+            &db_folder_item, 
+            sorter_list, 
+            column_config_map, 
+            links_folder_by_column_id, 
+            remote_folder_map, 
+            remote_folder_obj_map, 
+            sorter_map, 
+            column_type_map, 
+            &item_ids
+        )
+    }
+
+    fn do_search_item_ids(
+        &self,
+        db_folder_item: &TreeFolderItem,
+        sorter_list: &Vec<SearchSorter>,
+        column_config_map: &BTreeMap<String, ColumnConfig>,
+        links_folder_by_column_id: Option<HashMap<String, String>>,
+        remote_folder_map: Option<HashMap<String, HashMap<u16, TreeFolderItem>>>,
+        remote_folder_obj_map: Option<HashMap<String, DbData>>,
+        sorter_map: &HashMap<String, SortedtBy>,
+        column_type_map: &HashMap<String, String>,
+        item_ids: &Vec<String>,
+    ) -> Result<Vec<SearchSorter>, Vec<PlanetError>> {
+        let needs_filter_links = self.query.needs_filter_links.clone();
+        let mut db_folder_item = db_folder_item.clone();
+        let mut sorter_list = sorter_list.clone();
+        let mut errors: Vec<PlanetError> = Vec::new();
+        let remote_folder_data_map: HashMap<String, HashMap<String, DbData>> = HashMap::new();
+        for item_id in item_ids {
+            let result = db_folder_item.get_item_tuple(&item_id);
+            if result.is_err() {
+                let error = result.unwrap_err();
+                if error.error_code == 404 {
+                    continue
+                }
+                errors.push(error);
+                return Err(errors)
+            }
+            let (partition, item_tuple, index_tree) = result.unwrap();
+            let mut links_folder_by_column_id_wrap: Option<HashMap<String, String>> = None;
+            let mut remote_folder_data_map_wrap: Option<HashMap<String, HashMap<String, DbData>>> = None;
+            let mut remote_folder_map_wrap: Option<HashMap<String, HashMap<u16, TreeFolderItem>>> = None;
+            let mut remote_folder_obj_map_wrap: Option<HashMap<String, DbData>> = None;
+            if needs_filter_links {
+                links_folder_by_column_id_wrap = links_folder_by_column_id.clone();
+                remote_folder_data_map_wrap = Some(remote_folder_data_map.clone());
+                remote_folder_map_wrap = remote_folder_map.clone();
+                remote_folder_obj_map_wrap = remote_folder_obj_map.clone();
+            }
+            let result = self.do_search_item(
+                item_tuple, 
+                index_tree, 
+                column_config_map, 
+                links_folder_by_column_id_wrap, 
+                remote_folder_data_map_wrap, 
+                remote_folder_map_wrap, 
+                remote_folder_obj_map_wrap, 
+                sorter_map, 
+                column_type_map, 
+                &sorter_list, 
+                &partition
+            );
+            if result.is_err() {
+                let errors = result.unwrap_err();
+                return Err(errors)
+            }
+            sorter_list = result.unwrap();
+        }
+        return Ok(sorter_list)
+    }
+
+    fn do_search_sequential(
+        &self,
+        db_folder_item: &TreeFolderItem,
+        sorter_list: &Vec<SearchSorter>,
+        column_config_map: &BTreeMap<String, ColumnConfig>,
+        links_folder_by_column_id: Option<HashMap<String, String>>,
+        remote_folder_map: Option<HashMap<String, HashMap<u16, TreeFolderItem>>>,
+        remote_folder_obj_map: Option<HashMap<String, DbData>>,
+        sorter_map: &HashMap<String, SortedtBy>,
+        column_type_map: &HashMap<String, String>,
+    ) -> Result<Vec<SearchSorter>, Vec<PlanetError>> {
+        let needs_filter_links = self.query.needs_filter_links.clone();
+        let mut db_folder_item = db_folder_item.clone();
+        let sorter_list = sorter_list.clone();
+        let partitions = db_folder_item.get_partitions();
+        let errors: Vec<PlanetError> = Vec::new();
+        if partitions.is_ok() {
+            let partitions = partitions.unwrap();
+            let mut handles= vec![];
+            let remote_folder_data_map: HashMap<String, HashMap<String, DbData>> = HashMap::new();
+            let sorter_list: Arc<Mutex<Vec<SearchSorter>>> = Arc::new(Mutex::new(sorter_list.clone()));
+            let column_config_map: Arc<Mutex<BTreeMap<String, ColumnConfig>>> = Arc::new(Mutex::new(column_config_map.clone()));
+            let links_folder_by_column_id: Arc<Mutex<Option<HashMap<String, String>>>> = Arc::new(Mutex::new(links_folder_by_column_id.clone()));
+            let remote_folder_map: Arc<Mutex<Option<HashMap<String, HashMap<u16, TreeFolderItem>>>>> = Arc::new(Mutex::new(remote_folder_map.clone()));
+            let remote_folder_obj_map: Arc<Mutex<Option<HashMap<String, DbData>>>> = Arc::new(Mutex::new(remote_folder_obj_map.clone()));
+            let sorter_map: Arc<Mutex<HashMap<String, SortedtBy>>> = Arc::new(Mutex::new(sorter_map.clone()));
+            let column_type_map: Arc<Mutex<HashMap<String, String>>> = Arc::new(Mutex::new(column_type_map.clone()));
+            let db_folder_item: Arc<Mutex<TreeFolderItem>> = Arc::new(Mutex::new(db_folder_item.clone()));
+            let this: Arc<Mutex<SearchIterator>> = Arc::new(Mutex::new(self.clone()));
+            let remote_folder_data_map: Arc<Mutex<HashMap<String, HashMap<String, DbData>>>> = Arc::new(Mutex::new(remote_folder_data_map.clone()));
+            let errors = Arc::new(Mutex::new(errors.clone()));
+            for partition in partitions {
+                let sorter_list = Arc::clone(&sorter_list);
+                let column_config_map = Arc::clone(&column_config_map);
+                let links_folder_by_column_id = Arc::clone(&links_folder_by_column_id);
+                let remote_folder_map = Arc::clone(&remote_folder_map);
+                let remote_folder_obj_map = Arc::clone(&remote_folder_obj_map);
+                let sorter_map = Arc::clone(&sorter_map);
+                let column_type_map = Arc::clone(&column_type_map);
+                let this = Arc::clone(&this);
+                let db_folder_item = Arc::clone(&db_folder_item);
+                let remote_folder_data_map = Arc::clone(&remote_folder_data_map);
+                let errors = Arc::clone(&errors);
+                let handle = thread::spawn(move || {
+                    let mut sorter_list = sorter_list.lock().unwrap();
+                    let column_config_map = column_config_map.lock().unwrap();
+                    let links_folder_by_column_id = links_folder_by_column_id.lock().unwrap();
+                    let remote_folder_map = remote_folder_map.lock().unwrap();
+                    let remote_folder_obj_map = remote_folder_obj_map.lock().unwrap();
+                    let sorter_map = sorter_map.lock().unwrap();
+                    let column_type_map = column_type_map.lock().unwrap();
+                    let this = this.lock().unwrap();
+                    let mut db_folder_item = db_folder_item.lock().unwrap();
+                    let remote_folder_data_map = remote_folder_data_map.lock().unwrap();
+                    let mut errors = errors.lock().unwrap();
+                    let (db_tree, index_tree) = db_folder_item.open_partition(&partition).unwrap();
+                    // I may need botth db and index to execute formula
+                    let iter = db_tree.iter();
                     for db_result in iter {
                         if db_result.is_err() {
                             errors.push(
@@ -4850,7 +5503,47 @@ impl SearchIterator{
         }
 
         let boost_words = self.query.boost_words.clone();
-        if boost_words.is_none() {
+        let path_filters = self.query.path_filters.clone();
+        let geo_filters = self.query.geo_filters.clone();
+        if path_filters.is_some() {
+            // WHERE on indexed object paths, we only go through items found in paths index
+            let path_filters = path_filters.unwrap();
+            let result = self.do_search_path_index(
+                &db_folder_item, 
+                &sorter_list, 
+                &column_config_map, 
+                links_folder_by_column_id_wrap, 
+                remote_folder_map_wrap, 
+                remote_folder_obj_map_wrap,
+                &sorter_map, 
+                &column_type_map, 
+                &path_filters
+            );
+            if result.is_err() {
+                let errors = result.unwrap_err();
+                return Err(errors)
+            }
+            sorter_list = result.unwrap();
+        } else if geo_filters.is_some() {
+            // WHERE on locations, we only go through items in geohash cells from geo index
+            let geo_filters = geo_filters.unwrap();
+            let result = self.do_search_geo_index(
+                &db_folder_item, 
+                &sorter_list, 
+                &column_config_map, 
+                links_folder_by_column_id_wrap, 
+                remote_folder_map_wrap, 
+                remote_folder_obj_map_wrap,
+                &sorter_map, 
+                &column_type_map, 
+                &geo_filters
+            );
+            if result.is_err() {
+                let errors = result.unwrap_err();
+                return Err(errors)
+            }
+            sorter_list = result.unwrap();
+        } else if boost_words.is_none() {
             // We get all items sorter by criterio, no WHERE in search
             // or having WHERE and index boosting does not apply, like functions inside assertions like
             // {My Column}=CONCAT("hello", "world")
@@ -5232,7 +5925,8 @@ impl<'gb> SearchOutputData {
         data_map: &BTreeMap<String, Vec<BTreeMap<String, String>>>,
         db_folder: &TreeFolder,
         folder: &DbData,
-        column_config_map: &BTreeMap<String, ColumnConfig>
+        column_config_map: &BTreeMap<String, ColumnConfig>,
+        formula_env: &FormulaEnv,
     ) -> Result<BTreeMap<String, Vec<BTreeMap<String, String>>>, Vec<PlanetError>> {
         let mut data_map = data_map.clone();
         let column_config_map = column_config_map.clone();
@@ -5248,7 +5942,7 @@ impl<'gb> SearchOutputData {
             let column_type_str = column_type.as_str();
             let column_id = config.id.clone().unwrap();
             if column_type_str == COLUMN_TYPE_STATS {
-                let stats_column = StatsColumn::defaults(
+                let mut stats_column = StatsColumn::defaults(
                     &config.clone(), 
                     Some(column_config_map.clone()),
                     Some(folder_name.clone()), 
@@ -5256,6 +5950,7 @@ impl<'gb> SearchOutputData {
                     Some(properties_map.clone()), 
                     Some(data_map.clone())
                 );
+                stats_column.formula_env = Some(formula_env.clone());
                 let data: Vec<String> = Vec::new();
                 let result = stats_column.validate(&data);
                 if result.is_err() {
@@ -5277,6 +5972,103 @@ impl<'gb> SearchOutputData {
     }
 
 
+    fn do_translations(
+        &self,
+        data: &BTreeMap<String, Vec<BTreeMap<String, String>>>,
+        column_config_map: &BTreeMap<String, ColumnConfig>,
+        language: &String,
+    ) -> BTreeMap<String, Vec<BTreeMap<String, String>>> {
+        let mut data = data.clone();
+        let translations = data.get(TRANSLATIONS);
+        if translations.is_none() {
+            return data
+        }
+        let translations = translations.unwrap().clone();
+        for (_, column_config) in column_config_map {
+            let translatable = column_config.translatable.unwrap_or_default();
+            if !translatable {
+                continue
+            }
+            let column_id = column_config.id.clone().unwrap_or_default();
+            for translation in &translations {
+                let translation_column_id = translation.get(COLUMN_ID);
+                let translation_language = translation.get(LANGUAGE);
+                let value = translation.get(VALUE);
+                if translation_column_id.is_none() || translation_language.is_none() || value.is_none() {
+                    continue
+                }
+                if *translation_column_id.unwrap() == column_id && translation_language.unwrap() == language {
+                    data.insert(column_id.clone(), build_value_list(value.unwrap()));
+                    break
+                }
+            }
+        }
+        return data
+    }
+
+    // Secret key is derived once per column for the statement, not for each value
+    fn get_secret_columns(
+        &self,
+        column_config_map: &BTreeMap<String, ColumnConfig>,
+        context: &Context,
+    ) -> BTreeMap<String, SecretColumn> {
+        let mut secret_columns: BTreeMap<String, SecretColumn> = BTreeMap::new();
+        for (_, column_config) in column_config_map {
+            let column_type = column_config.column_type.clone().unwrap_or_default();
+            if column_type.as_str() == COLUMN_TYPE_SECRET {
+                let column_id = column_config.id.clone().unwrap_or_default();
+                secret_columns.insert(column_id, SecretColumn::defaults(column_config, &context.secret_key));
+            }
+        }
+        return secret_columns
+    }
+
+    // Secret columns are shown only when secret key is in context, rich text as markdown or html
+    fn get_display_items(
+        &self,
+        items: &Vec<DbData>,
+        column_config_map: &BTreeMap<String, ColumnConfig>,
+        secret_columns: &BTreeMap<String, SecretColumn>,
+    ) -> Vec<DbData> {
+        let items = items.clone();
+        let mut items_output: Vec<DbData> = Vec::new();
+        for mut item in items {
+            let mut data = item.data.clone().unwrap_or_default();
+            for (_, column_config) in column_config_map {
+                let column_type = column_config.column_type.clone().unwrap_or_default();
+                let column_type = column_type.as_str();
+                if column_type != COLUMN_TYPE_SECRET && column_type != COLUMN_TYPE_RICH_TEXT {
+                    continue
+                }
+                let column_id = column_config.id.clone().unwrap_or_default();
+                let value_list = data.get(&column_id);
+                if value_list.is_none() {
+                    continue
+                }
+                let mut value_list = value_list.unwrap().clone();
+                for value_obj in value_list.iter_mut() {
+                    let value = value_obj.get(VALUE);
+                    if value.is_some() {
+                        let value = value.unwrap();
+                        let value_display: String;
+                        if column_type == COLUMN_TYPE_SECRET {
+                            let obj = secret_columns.get(&column_id).unwrap();
+                            value_display = obj.get_display_value(value);
+                        } else {
+                            let obj = RichTextColumn::defaults(column_config);
+                            value_display = obj.get_display_value(value);
+                        }
+                        value_obj.insert(VALUE.to_string(), value_display);
+                    }
+                }
+                data.insert(column_id, value_list);
+            }
+            item.data = Some(data);
+            items_output.push(item);
+        }
+        return items_output
+    }
+
     pub fn do_output(
         &self,
         env: &'gb Environment<'gb>,
@@ -5336,6 +6128,7 @@ impl<'gb> SearchOutputData {
         // Prepare for LINKS and REFERENCES
         let needs_output_links = statement.needs_output_links.clone();
         let needs_output_aggs = statement.needs_output_aggs.clone();
+        let formula_env = statement.formula_env.clone();
         let mut link_data: HashMap<String, HashMap<String, Vec<BTreeMap<String, String>>>> = HashMap::new();
         let mut link_tree_map: HashMap<String, TreeFolderItem> = HashMap::new();
         let mut links_map: HashMap<String, ColumnConfig> = HashMap::new();
@@ -5433,7 +6226,8 @@ impl<'gb> SearchOutputData {
                                             &data, 
                                             &db_folder,
                                             &folder,
-                                            &column_config_map.clone()
+                                            &column_config_map.clone(),
+                                            &formula_env
                                         );
                                         if results.is_err() {
                                             let errors = results.unwrap_err();
@@ -5442,6 +6236,16 @@ impl<'gb> SearchOutputData {
                                         data = results.unwrap();
                                     }
 
+                                    // Translatable columns in language requested, default language as fallback
+                                    if statement.language.is_some() {
+                                        let language = statement.language.clone().unwrap();
+                                        data = self.do_translations(
+                                            &data, 
+                                            &column_config_map, 
+                                            &language
+                                        );
+                                    }
+
                                     item.data = Some(data);
                                 }
                                 if columns.len() == 0 {
@@ -5471,6 +6275,8 @@ impl<'gb> SearchOutputData {
         if errors.len() > 0 {
             return Err(errors)
         }
+        let secret_columns = self.get_secret_columns(&column_config_map, &context);
+        let items = self.get_display_items(&items, &column_config_map, &secret_columns);
         let page = statement.page.to_usize().unwrap();
         let result = SelectResult::serialize_yaml(
             total, 
@@ -5478,7 +6284,8 @@ impl<'gb> SearchOutputData {
             page, 
             items, 
             folder,
-            column_config_map
+            column_config_map,
+            &context.time_zone
         );
         return Ok(result)
     }
@@ -5519,11 +6326,13 @@ impl<'gb> SearchOutputGroupByData {
         &self,
         group_results: &Vec<GroupByResult>,
         column_config_map: &BTreeMap<String, ColumnConfig>,
+        formula_env: &FormulaEnv,
     ) -> Result<Vec<GroupByResult>, Vec<PlanetError>> {
         let group_results = group_results.clone();
         let column_config_map = column_config_map.clone();
         let mut stats_column_ids: HashSet<String> = HashSet::new();
         let mut column_name_map: HashMap<String, String> = HashMap::new();
+        let mut currency_map: HashMap<String, String> = HashMap::new();
         for (column_name, config) in &column_config_map {
             let column_type = config.column_type.clone().unwrap();
             let column_type = column_type.as_str();
@@ -5545,6 +6354,15 @@ impl<'gb> SearchOutputGroupByData {
                 column_type == COLUMN_TYPE_PERCENTAGE ||
                 column_type == COLUMN_TYPE_RATING
             ) && !is_set {
+                if column_type == COLUMN_TYPE_CURRENCY {
+                    let currency = CurrencyColumn::get_currency_code(config);
+                    if currency.is_err() {
+                        let mut errors: Vec<PlanetError> = Vec::new();
+                        errors.push(currency.unwrap_err());
+                        return Err(errors)
+                    }
+                    currency_map.insert(column_id.clone(), currency.unwrap());
+                }
                 stats_column_ids.insert(column_id);
             }
         }
@@ -5578,7 +6396,25 @@ impl<'gb> SearchOutputGroupByData {
                                 let value = get_value_list(data_item);
                                 if value.is_some() {
                                     let value = value.unwrap();
-                                    let value_float: f64 = FromStr::from_str(value.as_str()).unwrap();
+                                    // Currency amounts are converted into column currency and summed as decimals
+                                    let currency = currency_map.get(&column_id);
+                                    let mut amount: Option<Decimal> = None;
+                                    let value_float: f64;
+                                    if currency.is_some() {
+                                        let currency = currency.unwrap();
+                                        let result = CurrencyAmount::parse(&value, currency);
+                                        let result = result.and_then(|item| item.convert(currency, &formula_env.exchange_rates));
+                                        if result.is_err() {
+                                            let mut errors: Vec<PlanetError> = Vec::new();
+                                            errors.push(result.unwrap_err());
+                                            return Err(errors)
+                                        }
+                                        let result = result.unwrap().amount;
+                                        value_float = result.to_f64().unwrap_or_default();
+                                        amount = Some(result);
+                                    } else {
+                                        value_float = FromStr::from_str(value.as_str()).unwrap();
+                                    }
                                     let stats = column_stats.get(&column_name);
                                     if stats.is_some() {
                                         let mut stats = stats.unwrap().clone();
@@ -5586,6 +6422,11 @@ impl<'gb> SearchOutputGroupByData {
                                         sum += value_float;
                                         stats.sum = Some(sum);
                                         stats.count += 1;
+                                        if amount.is_some() {
+                                            let amount_sum = stats.amount_sum.unwrap_or_default() + amount.unwrap();
+                                            stats.amount_sum = Some(amount_sum);
+                                            stats.sum = amount_sum.to_f64();
+                                        }
                                         let min = stats.min.unwrap();
                                         if value_float < min {
                                             stats.min = Some(value_float);
@@ -5604,6 +6445,8 @@ impl<'gb> SearchOutputGroupByData {
                                             avg: Some(value_float),
                                             min: Some(value_float),
                                             max: Some(value_float),
+                                            currency: currency.cloned(),
+                                            amount_sum: amount,
                                         };
                                         column_stats.insert(column_name.clone(), stats);
                                     }
@@ -5614,6 +6457,8 @@ impl<'gb> SearchOutputGroupByData {
                                         avg: None,
                                         min: None,
                                         max: None,
+                                        currency: None,
+                                        amount_sum: None,
                                     };
                                     column_stats.insert(column_name.clone(), stats);
                                 }
@@ -5631,6 +6476,8 @@ impl<'gb> SearchOutputGroupByData {
                                         avg: None,
                                         min: None,
                                         max: None,
+                                        currency: None,
+                                        amount_sum: None,
                                     };
                                     column_stats.insert(column_name.clone(), stats);
                                 }
@@ -5650,6 +6497,8 @@ impl<'gb> SearchOutputGroupByData {
                                 avg: None,
                                 min: None,
                                 max: None,
+                                currency: None,
+                                amount_sum: None,
                             };
                             column_stats.insert(column_name.clone(), stats);
                         }
@@ -5725,6 +6574,7 @@ impl<'gb> SearchOutputGroupByData {
         // Prepare for LINKS and REFERENCES
         let needs_output_links = statement.needs_output_links.clone();
         let needs_output_aggs = statement.needs_output_aggs.clone();
+        let formula_env = statement.formula_env.clone();
         let mut link_data: HashMap<String, HashMap<String, Vec<BTreeMap<String, String>>>> = HashMap::new();
         let mut link_tree_map: HashMap<String, TreeFolderItem> = HashMap::new();
         let mut links_map: HashMap<String, ColumnConfig> = HashMap::new();
@@ -5819,7 +6669,8 @@ impl<'gb> SearchOutputGroupByData {
                                                 &data, 
                                                 &db_folder,
                                                 &folder,
-                                                &column_config_map.clone()
+                                                &column_config_map.clone(),
+                                                &formula_env
                                             );
                                             if results.is_err() {
                                                 let errors = results.unwrap_err();
@@ -5855,6 +6706,7 @@ impl<'gb> SearchOutputGroupByData {
         }
         // Create new group list with DbData embedded
         let mut group_results_new: Vec<GroupByResult> = Vec::new();
+        let secret_columns = self.search_output_data.get_secret_columns(&column_config_map, &context);
         for mut group_result in group_results.clone() {
             let group_result_item = group_result.item.clone();
             if group_result_item.is_some() {
@@ -5870,6 +6722,11 @@ impl<'gb> SearchOutputGroupByData {
                     }
                 }
                 if item_data_list.len() > 0 {
+                    let item_data_list = self.search_output_data.get_display_items(
+                        &item_data_list, 
+                        &column_config_map, 
+                        &secret_columns
+                    );
                     group_result_item.items_data = Some(item_data_list);
                     group_result.item = Some(group_result_item);
                     group_results_new.push(group_result);    
@@ -5882,7 +6739,8 @@ impl<'gb> SearchOutputGroupByData {
         }
         let result = self.inject_column_stats(
             &group_results_new, 
-            &column_config_map
+            &column_config_map,
+            &formula_env
         );
         if result.is_err() {
             let errors = result.unwrap_err();
@@ -5927,7 +6785,8 @@ impl SelectResult {
         page: usize,
         items: Vec<DbData>,
         folder: &DbData,
-        column_config_map: BTreeMap<String, ColumnConfig>
+        column_config_map: BTreeMap<String, ColumnConfig>,
+        context_time_zone: &Option<String>,
     ) -> String {
         let data_count = items.len();
         let mut yaml_string = String::from("---\n");
@@ -5936,13 +6795,16 @@ impl SelectResult {
             COLUMN_TYPE_SMALL_TEXT, COLUMN_TYPE_CREATED_BY, COLUMN_TYPE_CREATED_TIME, COLUMN_TYPE_DATE, COLUMN_TYPE_EMAIL,
             COLUMN_TYPE_DURATION, COLUMN_TYPE_GENERATE_ID, COLUMN_TYPE_FILE, COLUMN_TYPE_LANGUAGE, COLUMN_TYPE_LAST_MODIFIED_BY,
             COLUMN_TYPE_LAST_MODIFIED_TIME, COLUMN_TYPE_PHONE, COLUMN_TYPE_CHECKBOX, COLUMN_TYPE_FORMULA,
-            COLUMN_TYPE_LINK, COLUMN_TYPE_REFERENCE
+            COLUMN_TYPE_LINK, COLUMN_TYPE_REFERENCE, COLUMN_TYPE_RECURRENCE, COLUMN_TYPE_LOCATION, COLUMN_TYPE_LOOKUP,
+            COLUMN_TYPE_SECRET
         ];
         let explicit_text_columns = [
-            COLUMN_TYPE_LONG_TEXT, COLUMN_TYPE_SELECT, COLUMN_TYPE_TEXT, COLUMN_TYPE_URL
+            COLUMN_TYPE_LONG_TEXT, COLUMN_TYPE_SELECT, COLUMN_TYPE_TEXT, COLUMN_TYPE_URL, COLUMN_TYPE_TAGS,
+            COLUMN_TYPE_WORKFLOW
         ];
         let number_columns = [
-            COLUMN_TYPE_NUMBER, COLUMN_TYPE_GENERATE_NUMBER, COLUMN_TYPE_PERCENTAGE, COLUMN_TYPE_RATING, COLUMN_TYPE_STATS
+            COLUMN_TYPE_NUMBER, COLUMN_TYPE_GENERATE_NUMBER, COLUMN_TYPE_PERCENTAGE, COLUMN_TYPE_RATING, COLUMN_TYPE_STATS,
+            COLUMN_TYPE_ROLLUP
         ];
         let object_columns = [
             COLUMN_TYPE_OBJECT, COLUMN_TYPE_STATEMENT
@@ -5998,6 +6860,7 @@ impl SelectResult {
                     continue
                 }
                 let column_config = column_config.unwrap().clone();
+                let column_config_ = column_config.clone();
                 let column_type = column_config.column_type;
                 if column_type.is_none() {
                     continue
@@ -6029,6 +6892,22 @@ impl SelectResult {
                             yaml_string.push_str(format!("      {field}:\n", field=&column_name).as_str());
                             yaml_string.push_str(format!("        {}: {value}\n", ID, value=value).as_str());
                             yaml_string.push_str(format!("        {}: {value}\n", NAME_CAMEL, value=link_name).as_str());
+                        } else if column_type == COLUMN_TYPE_DURATION {
+                            value = get_value_list(&v).unwrap();
+                            let obj = DurationColumn::defaults(&column_config_);
+                            let value = obj.get_display_value(&value);
+                            yaml_string.push_str(format!("      {field}: {value}\n", field=&column_name, value=value).as_str());
+                        } else if column_type == COLUMN_TYPE_DATE {
+                            value = get_value_list(&v).unwrap();
+                            let mut obj = DateColumn::defaults(&column_config_);
+                            obj.context_time_zone = context_time_zone.clone();
+                            let value = obj.get_display_value(&value);
+                            yaml_string.push_str(format!("      {field}: {value}\n", field=&column_name, value=value).as_str());
+                        } else if column_type == COLUMN_TYPE_PHONE {
+                            value = get_value_list(&v).unwrap();
+                            let obj = PhoneColumn::defaults(&column_config_);
+                            let value = obj.get_display_value(&value);
+                            yaml_string.push_str(format!("      {field}: {value}\n", field=&column_name, value=value).as_str());
                         } else {
                             value = get_value_list(&v).unwrap();
                             yaml_string.push_str(format!("      {field}: {value}\n", field=&column_name, value=value).as_str());
@@ -6070,6 +6949,12 @@ impl SelectResult {
                             }                            
                         }
                     }
+                } else if column_type == COLUMN_TYPE_RICH_TEXT {
+                    let value = get_value_list(&v).unwrap_or_default();
+                    yaml_string.push_str(format!("      {field}: |\n", field=&column_name).as_str());
+                    for line in value.lines() {
+                        yaml_string.push_str(format!("        {line}\n", line=line).as_str());
+                    }
                 } else if explicit_text_columns.contains(&column_type) {
                     if !is_set {
                         let value = get_value_list(&v).unwrap();
@@ -6159,13 +7044,16 @@ impl SelectGroupByResult {
             COLUMN_TYPE_SMALL_TEXT, COLUMN_TYPE_CREATED_BY, COLUMN_TYPE_CREATED_TIME, COLUMN_TYPE_DATE, COLUMN_TYPE_EMAIL,
             COLUMN_TYPE_DURATION, COLUMN_TYPE_GENERATE_ID, COLUMN_TYPE_FILE, COLUMN_TYPE_LANGUAGE, COLUMN_TYPE_LAST_MODIFIED_BY,
             COLUMN_TYPE_LAST_MODIFIED_TIME, COLUMN_TYPE_PHONE, COLUMN_TYPE_CHECKBOX, COLUMN_TYPE_FORMULA,
-            COLUMN_TYPE_LINK, COLUMN_TYPE_REFERENCE
+            COLUMN_TYPE_LINK, COLUMN_TYPE_REFERENCE, COLUMN_TYPE_RECURRENCE, COLUMN_TYPE_LOCATION, COLUMN_TYPE_LOOKUP,
+            COLUMN_TYPE_SECRET
         ];
         let explicit_text_columns = [
-            COLUMN_TYPE_LONG_TEXT, COLUMN_TYPE_SELECT, COLUMN_TYPE_TEXT, COLUMN_TYPE_URL
+            COLUMN_TYPE_LONG_TEXT, COLUMN_TYPE_SELECT, COLUMN_TYPE_TEXT, COLUMN_TYPE_URL, COLUMN_TYPE_TAGS,
+            COLUMN_TYPE_WORKFLOW
         ];
         let number_columns = [
-            COLUMN_TYPE_NUMBER, COLUMN_TYPE_GENERATE_NUMBER, COLUMN_TYPE_PERCENTAGE, COLUMN_TYPE_RATING, COLUMN_TYPE_STATS
+            COLUMN_TYPE_NUMBER, COLUMN_TYPE_GENERATE_NUMBER, COLUMN_TYPE_PERCENTAGE, COLUMN_TYPE_RATING, COLUMN_TYPE_STATS,
+            COLUMN_TYPE_ROLLUP
         ];
         let object_columns = [
             COLUMN_TYPE_OBJECT, COLUMN_TYPE_STATEMENT
@@ -6394,6 +7282,12 @@ impl SelectGroupByResult {
                                             }                            
                                         }
                                     }
+                                } else if column_type == COLUMN_TYPE_RICH_TEXT {
+                                    let value = get_value_list(&v).unwrap_or_default();
+                                    yaml_string.push_str(format!("{}        {field}: |\n", start_number_spaces, field=&column_name).as_str());
+                                    for line in value.lines() {
+                                        yaml_string.push_str(format!("{}          {line}\n", start_number_spaces, line=line).as_str());
+                                    }
                                 } else if explicit_text_columns.contains(&column_type) {
                                     if !is_set {
                                         let value = get_value_list(&v).unwrap();
@@ -6445,6 +7339,16 @@ impl SelectGroupByResult {
                         if !is_number_stats {
                             yaml_string.push_str(format!("{}      {}:\n", start_number_spaces, column_name).as_str());
                             yaml_string.push_str(format!("{}        count: {}\n", start_number_spaces, column_stats.count).as_str());
+                        } else if column_stats.currency.is_some() && column_stats.amount_sum.is_some() {
+                            let currency = column_stats.currency.clone().unwrap();
+                            let amount_sum = column_stats.amount_sum.unwrap();
+                            let amount_avg = amount_sum / Decimal::from(column_stats.count);
+                            yaml_string.push_str(format!("{}      {}:\n", start_number_spaces, column_name).as_str());
+                            yaml_string.push_str(format!("{}        count: {}\n", start_number_spaces, column_stats.count).as_str());
+                            yaml_string.push_str(format!("{}        sum: {} {}\n", start_number_spaces, amount_sum.round_dp(2), &currency).as_str());
+                            yaml_string.push_str(format!("{}        max: {:.2} {}\n", start_number_spaces, column_stats.max.unwrap(), &currency).as_str());
+                            yaml_string.push_str(format!("{}        min: {:.2} {}\n", start_number_spaces, column_stats.min.unwrap(), &currency).as_str());
+                            yaml_string.push_str(format!("{}        avg: {} {}\n", start_number_spaces, amount_avg.round_dp(2), &currency).as_str());
                         } else {
                             yaml_string.push_str(format!("{}      {}:\n", start_number_spaces, column_name).as_str());
                             yaml_string.push_str(format!("{}        count: {}\n", start_number_spaces, column_stats.count).as_str());
@@ -6751,6 +7655,16 @@ impl<'gb> Statement<'gb> for SelectFromFolderStatement {
         let init = init.unwrap();
         let db_folder = init.0;
         let folder = init.1;
+        let result = load_exchange_rates(&db_folder, &space_database);
+        if result.is_err() {
+            let mut errors: Vec<PlanetError> = Vec::new();
+            errors.push(result.unwrap_err());
+            return Err(errors)
+        }
+        let formula_env = FormulaEnv{
+            exchange_rates: result.unwrap(),
+            time_zone: env.context.time_zone.clone(),
+        };
         let column_config_map = ColumnConfig::get_column_config_map(
             env.planet_context,
             env.context,
@@ -6766,7 +7680,13 @@ impl<'gb> Statement<'gb> for SelectFromFolderStatement {
             let errors = result.unwrap_err();
             return Err(errors)
         }
-        let statement = result.unwrap();
+        let mut statement = result.unwrap();
+        if statement.where_compiled.is_some() {
+            let mut where_compiled = statement.where_compiled.clone().unwrap();
+            where_compiled.env = Some(formula_env.clone());
+            statement.where_compiled = Some(where_compiled);
+        }
+        statement.formula_env = formula_env;
         let columns = statement.columns.clone();
         let select_count = statement.count.clone();
         let context = env.context;
@@ -6998,26 +7918,1513 @@ pub struct SelectFromFolder<'gb> {
 //     // }
 // }
 
-fn handle_field_response(
-    column_data: &Result<Vec<String>, Vec<PlanetError>>, 
-    errors: &Vec<PlanetError>, 
-    column_id: &String,
-    data: &BTreeMap<String, Vec<BTreeMap<String, String>>>,
-    is_set: &String
-) -> (
-    BTreeMap<String, Vec<BTreeMap<String, String>>>,
-    Vec<PlanetError>
-) {
-    let column_data = column_data.clone();
-    eprintln!("handle_field_response :: column_id: {} column_data: {:?}", column_id, column_data);
-    let mut errors = errors.clone();
-    let mut data = data.clone();
-    let column_id = column_id.clone();
-    let is_set = is_set.clone();
-    if column_data.is_err() {
-        let err = column_data.unwrap_err();
-        for error in err {
-            errors.push(error);
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DeleteFromFolderCompiledStmt {
+    pub folder_name: String,
+    pub ids: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DeleteFromFolderStatement {
+}
+
+impl<'gb> StatementCompiler<'gb, DeleteFromFolderCompiledStmt> for DeleteFromFolderStatement {
+
+    fn compile(
+        &self, 
+        statement_text: &String
+    ) -> Result<DeleteFromFolderCompiledStmt, Vec<PlanetError>> {
+        let expr = &RE_DELETE_FROM_FOLDER;
+        let mut errors: Vec<PlanetError> = Vec::new();
+        let captures = expr.captures(&statement_text);
+        if captures.is_none() {
+            let error = PlanetError::new(
+                500, 
+                Some(
+                    tr!("Delete from folder syntax not valid.")
+                ),
+            );
+            errors.push(error);
+            return Err(errors)
+        }
+        let captures = captures.unwrap();
+        let folder_name = captures.name("FolderName").unwrap().as_str().to_string();
+        let ids_str = captures.name("Ids").unwrap().as_str();
+        let mut ids: Vec<String> = Vec::new();
+        for id in ids_str.split(",") {
+            let id = id.trim();
+            if id.len() == 0 {
+                continue
+            }
+            ids.push(id.to_string());
+        }
+        if ids.len() == 0 {
+            let error = PlanetError::new(
+                500, 
+                Some(
+                    tr!("No item ids found in delete from folder statement.")
+                ),
+            );
+            errors.push(error);
+            return Err(errors)
+        }
+        let statement = DeleteFromFolderCompiledStmt{
+            folder_name: folder_name,
+            ids: ids,
+        };
+        return Ok(statement)
+    }
+}
+
+impl<'gb> Statement<'gb> for DeleteFromFolderStatement {
+
+    fn run(
+        &self,
+        env: &'gb Environment<'gb>,
+        space_database: &SpaceDatabase,
+        statement_text: &String,
+    ) -> Result<Vec<yaml_rust::Yaml>, Vec<PlanetError>> {
+        let space_database = space_database.clone();
+        let context = env.context;
+        let planet_context = env.planet_context;
+        let t_1 = Instant::now();
+        let mut errors: Vec<PlanetError> = Vec::new();
+        let statement = self.compile(statement_text);
+        if statement.is_err() {
+            let errors = statement.unwrap_err();
+            return Err(errors)
+        }
+        let statement = statement.unwrap();
+        let folder_name = statement.folder_name.clone();
+        let home_dir = planet_context.home_path.clone();
+        let account_id = context.account_id.clone().unwrap_or_default();
+        let space_id = context.space_id;
+        let site_id = context.site_id.clone();
+        let db_folder= TreeFolder::defaults(
+            space_database.connection_pool.clone(),
+            Some(home_dir.clone().unwrap_or_default().as_str()),
+            Some(&account_id),
+            Some(space_id),
+            site_id.clone(),
+        ).unwrap();
+        let folder = db_folder.get_by_name(&folder_name);
+        if folder.is_err() {
+            let error = folder.unwrap_err();
+            errors.push(error);
+            return Err(errors)
+        }
+        let folder = folder.unwrap();
+        if folder.is_none() {
+            let error = PlanetError::new(
+                500, 
+                Some(tr!("Folder \"{}\" not found.", &folder_name)),
+            );
+            errors.push(error);
+            return Err(errors)
+        }
+        let folder = folder.unwrap();
+        let folder_id = folder.id.clone().unwrap_or_default();
+        let result: Result<TreeFolderItem, PlanetError> = TreeFolderItem::defaults(
+            space_database.connection_pool.clone(),
+            home_dir.clone().unwrap_or_default().as_str(),
+            &account_id,
+            space_id,
+            site_id.clone(),
+            folder_id.as_str(),
+            &db_folder,
+        );
+        if result.is_err() {
+            let error = result.unwrap_err();
+            errors.push(error);
+            return Err(errors)
+        }
+        let mut db_row = result.unwrap();
+        // All ids are checked before anything is deleted
+        for id in &statement.ids {
+            let item = db_row.get(&folder_name, GetItemOption::ById(id.clone()), None);
+            if item.is_err() {
+                errors.push(
+                    PlanetError::new(
+                        404, 
+                        Some(tr!("Item \"{}\" not found in folder \"{}\".", id, &folder_name)),
+                    )
+                );
+            }
+        }
+        if errors.len() > 0 {
+            return Err(errors)
+        }
+        let result = load_exchange_rates(&db_folder, &space_database);
+        if result.is_err() {
+            errors.push(result.unwrap_err());
+            return Err(errors)
+        }
+        let formula_env = FormulaEnv{
+            exchange_rates: result.unwrap(),
+            time_zone: env.context.time_zone.clone(),
+        };
+        let mut yaml_response: Vec<yaml_rust::Yaml> = Vec::new();
+        // Items already removed through cascade rules are skipped
+        let mut visited: Vec<String> = Vec::new();
+        let mut deleted_ids: Vec<String> = Vec::new();
+        for id in statement.ids {
+            let result = db_row.delete_item(&id, None, &mut visited);
+            if result.is_err() {
+                let error = result.unwrap_err();
+                errors.push(error);
+                if deleted_ids.len() > 0 {
+                    errors.push(
+                        PlanetError::new(
+                            500, 
+                            Some(tr!("Items deleted before the error: {}", deleted_ids.join(", "))),
+                        )
+                    );
+                }
+                return Err(errors)
+            }
+            deleted_ids.push(id.clone());
+            let response = result.unwrap();
+            // Rollups and lookups at items that were linked to the deleted item
+            let result = refresh_linked_columns(
+                env, 
+                &space_database, 
+                &db_folder, 
+                &folder, 
+                &response,
+                &formula_env
+            );
+            if result.is_err() {
+                let errors_ = result.unwrap_err();
+                errors.extend(errors_);
+                errors.push(
+                    PlanetError::new(
+                        500, 
+                        Some(tr!("Items deleted before the error: {}", deleted_ids.join(", "))),
+                    )
+                );
+                return Err(errors)
+            }
+            let response_coded = serde_yaml::to_string(&response);
+            if response_coded.is_err() {
+                let error = PlanetError::new(
+                    500, 
+                    Some(tr!("Error encoding statement response.")),
+                );
+                errors.push(error);
+                return Err(errors)
+            }
+            let response = response_coded.unwrap();
+            let yaml_item = yaml_rust::YamlLoader::load_from_str(
+                response.as_str()
+            ).unwrap();
+            yaml_response.push(yaml_item[0].clone());
+        }
+        eprintln!("DeleteFromFolder.run :: time: {} ms", &t_1.elapsed().as_millis());
+        return Ok(yaml_response)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UpdateFolderCompiledStmt {
+    pub folder_name: String,
+    pub id: String,
+    pub name: Option<String>,
+    pub data: BTreeMap<String, Vec<BTreeMap<String, String>>>,
+    pub translations: Option<BTreeMap<String, BTreeMap<String, String>>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct UpdateFolderStatement {
+}
+
+impl<'gb> StatementCompiler<'gb, UpdateFolderCompiledStmt> for UpdateFolderStatement {
+
+    fn compile(
+        &self, 
+        statement_text: &String
+    ) -> Result<UpdateFolderCompiledStmt, Vec<PlanetError>> {
+        let expr = &RE_UPDATE_FOLDER;
+        let mut errors: Vec<PlanetError> = Vec::new();
+        let captures = expr.captures(&statement_text);
+        if captures.is_none() {
+            let error = PlanetError::new(
+                500, 
+                Some(
+                    tr!("Update folder syntax not valid.")
+                ),
+            );
+            errors.push(error);
+            return Err(errors)
+        }
+        let captures = captures.unwrap();
+        let folder_name = captures.name("FolderName").unwrap().as_str().trim().to_string();
+        let id = captures.name("Id").unwrap().as_str().to_string();
+        let items = captures.name("Items").unwrap().as_str();
+        let long_text = DataValueLongText::defaults(
+            &items.to_string()
+        );
+        if long_text.is_err() {
+            let error = long_text.unwrap_err();
+            errors.push(error);
+            return Err(errors)
+        }
+        let long_text = long_text.unwrap();
+        let items = long_text.parsed_text.as_str();
+        let mut statement = UpdateFolderCompiledStmt{
+            folder_name: folder_name,
+            id: id,
+            name: None,
+            data: BTreeMap::new(),
+            translations: None,
+        };
+        // column name => language code => value
+        let mut translations: BTreeMap<String, BTreeMap<String, String>> = BTreeMap::new();
+        let expr = &RE_INSERT_INTO_FOLDER_ITEM_KEYS;
+        for list_item in expr.captures_iter(items) {
+            let key = list_item.name("Key");
+            let value = list_item.name("Value");
+            let language = list_item.name("Language");
+            if key.is_none() || value.is_none() {
+                continue
+            }
+            let key = key.unwrap().as_str().trim();
+            let mut value = value.unwrap().as_str().to_string();
+            if DataValueLongText::has_placeholder(&value) {
+                let value_ = value.clone().replace("\n", "");
+                let long_text_src = long_text.map.get(&value_);
+                if long_text_src.is_some() {
+                    value = long_text_src.unwrap().clone();
+                }
+            }
+            let data_value = DataValue::defaults(
+                &value
+            );
+            if data_value.is_err() {
+                continue
+            }
+            let data_value = data_value.unwrap();
+            if language.is_some() {
+                let language = language.unwrap().as_str().to_string();
+                let translation = get_value_list(&data_value.value);
+                if translation.is_some() {
+                    let mut column_translations = translations.get(key).cloned().unwrap_or_default();
+                    column_translations.insert(language, translation.unwrap());
+                    translations.insert(key.to_string(), column_translations);
+                }
+                continue
+            }
+            if key == NAME_CAMEL {
+                statement.name = get_value_list(&data_value.value);
+                continue
+            }
+            statement.data.insert(key.to_string(), data_value.value);
+        }
+        if translations.len() > 0 {
+            statement.translations = Some(translations);
+        }
+        if statement.data.len() == 0 && statement.name.is_none() && statement.translations.is_none() {
+            let error = PlanetError::new(
+                500, 
+                Some(
+                    tr!("No columns found to update in update folder statement.")
+                ),
+            );
+            errors.push(error);
+            return Err(errors)
+        }
+        return Ok(statement)
+    }
+}
+
+impl<'gb> Statement<'gb> for UpdateFolderStatement {
+
+    fn run(
+        &self,
+        env: &'gb Environment<'gb>,
+        space_database: &SpaceDatabase,
+        statement_text: &String,
+    ) -> Result<Vec<yaml_rust::Yaml>, Vec<PlanetError>> {
+        let space_database = space_database.clone();
+        let context = env.context;
+        let planet_context = env.planet_context;
+        let t_1 = Instant::now();
+        let mut errors: Vec<PlanetError> = Vec::new();
+        let statement = self.compile(statement_text);
+        if statement.is_err() {
+            let errors = statement.unwrap_err();
+            return Err(errors)
+        }
+        let statement = statement.unwrap();
+        let folder_name = statement.folder_name.clone();
+        let home_dir = planet_context.home_path.clone();
+        let account_id = context.account_id.clone().unwrap_or_default();
+        let space_id = context.space_id;
+        let site_id = context.site_id.clone();
+        let db_folder= TreeFolder::defaults(
+            space_database.connection_pool.clone(),
+            Some(home_dir.clone().unwrap_or_default().as_str()),
+            Some(&account_id),
+            Some(space_id),
+            site_id.clone(),
+        ).unwrap();
+        let folder = db_folder.get_by_name(&folder_name);
+        if folder.is_err() {
+            let error = folder.unwrap_err();
+            errors.push(error);
+            return Err(errors)
+        }
+        let folder = folder.unwrap();
+        if folder.is_none() {
+            let error = PlanetError::new(
+                500, 
+                Some(tr!("Folder \"{}\" not found.", &folder_name)),
+            );
+            errors.push(error);
+            return Err(errors)
+        }
+        let folder = folder.unwrap();
+        let folder_id = folder.id.clone().unwrap_or_default();
+        let result = load_exchange_rates(&db_folder, &space_database);
+        if result.is_err() {
+            errors.push(result.unwrap_err());
+            return Err(errors);
+        }
+        let formula_env = FormulaEnv{
+            exchange_rates: result.unwrap(),
+            time_zone: env.context.time_zone.clone(),
+        };
+        let result: Result<TreeFolderItem, PlanetError> = TreeFolderItem::defaults(
+            space_database.connection_pool.clone(),
+            home_dir.clone().unwrap_or_default().as_str(),
+            &account_id,
+            space_id,
+            site_id.clone(),
+            folder_id.as_str(),
+            &db_folder,
+        );
+        if result.is_err() {
+            let error = result.unwrap_err();
+            errors.push(error);
+            return Err(errors)
+        }
+        let mut db_row = result.unwrap();
+        let item = db_row.get(&folder_name, GetItemOption::ById(statement.id.clone()), None);
+        if item.is_err() {
+            let error = item.unwrap_err();
+            errors.push(error);
+            return Err(errors)
+        }
+        let mut item = item.unwrap();
+        let config_columns = ColumnConfig::get_config(
+            planet_context,
+            context,
+            &folder
+        );
+        if config_columns.is_err() {
+            let error = config_columns.unwrap_err();
+            errors.push(error);
+            return Err(errors);
+        }
+        let config_columns = config_columns.unwrap();
+        let mut column_config_map: BTreeMap<String, ColumnConfig> = BTreeMap::new();
+        for column in config_columns.clone() {
+            let column_name = column.name.clone().unwrap();
+            column_config_map.insert(column_name, column.clone());
+        }
+        let mut data = item.data.clone().unwrap_or_default();
+        if statement.name.is_some() {
+            let name = statement.name.clone().unwrap();
+            let item_name = item.name.clone().unwrap_or_default();
+            if name != item_name {
+                let name_item = db_row.get(&folder_name, GetItemOption::ByName(name.clone()), None);
+                if name_item.is_ok() {
+                    errors.push(
+                        PlanetError::new(
+                            500, 
+                            Some(tr!("A record with name \"{}\" already exists in database", &name)),
+                        )
+                    );
+                }
+                item.name = Some(name);
+            }
+        }
+        let item_previous = item.clone();
+        let routing_wrap = RoutingData::defaults(
+            Some(account_id.to_string()),
+            site_id.clone(), 
+            space_id, 
+            None
+        );
+        // (linked folder id, column id, linked item id) for links added and removed
+        let mut links_added: Vec<(String, String, String)> = Vec::new();
+        let mut links_removed: Vec<(String, String, String)> = Vec::new();
+        // Files written for new values, and files no longer at the item once updated
+        let mut files_added: Vec<String> = Vec::new();
+        let mut files_removed: Vec<String> = Vec::new();
+        // column config, previous state and new state
+        let mut workflow_changes: Vec<(ColumnConfig, Option<String>, String)> = Vec::new();
+        for (column_name, values) in statement.data.clone() {
+            let column_config = column_config_map.get(&column_name);
+            if column_config.is_none() {
+                errors.push(
+                    PlanetError::new(
+                        500, 
+                        Some(tr!("Column \"{}\" not found in folder.", &column_name)),
+                    )
+                );
+                continue
+            }
+            let column_config = column_config.unwrap().clone();
+            let column_id = column_config.id.clone().unwrap_or_default();
+            let column_type = column_config.column_type.clone().unwrap_or_default();
+            let column_type = column_type.as_str();
+            let is_set = column_config.is_set.clone().unwrap_or(FALSE.to_string());
+            let mut column_data: Vec<String> = Vec::new();
+            for value in values {
+                let value = value.get(VALUE);
+                if value.is_some() {
+                    column_data.push(value.unwrap().clone());
+                }
+            }
+            let column_data_wrap: Result<Vec<String>, Vec<PlanetError>>;
+            match column_type {
+                COLUMN_TYPE_SMALL_TEXT => {
+                    column_data_wrap = SmallTextColumn::defaults(&column_config).validate(&column_data);
+                },
+                COLUMN_TYPE_LONG_TEXT => {
+                    column_data_wrap = LongTextColumn::defaults(&column_config).validate(&column_data);
+                },
+                COLUMN_TYPE_RICH_TEXT => {
+                    column_data_wrap = RichTextColumn::defaults(&column_config).validate(&column_data);
+                },
+                COLUMN_TYPE_SECRET => {
+                    column_data_wrap = SecretColumn::defaults(
+                        &column_config,
+                        &context.secret_key
+                    ).validate(&column_data);
+                },
+                COLUMN_TYPE_CHECKBOX => {
+                    column_data_wrap = CheckBoxColumn::defaults(&column_config).validate(&column_data);
+                },
+                COLUMN_TYPE_NUMBER => {
+                    column_data_wrap = NumberColumn::defaults(&column_config).validate(&column_data);
+                },
+                COLUMN_TYPE_SELECT => {
+                    column_data_wrap = SelectColumn::defaults(&column_config, Some(&folder)).validate(&column_data);
+                },
+                COLUMN_TYPE_TAGS => {
+                    column_data_wrap = TagsColumn::defaults(
+                        &column_config, 
+                        Some(db_folder.clone())
+                    ).validate(&column_data);
+                },
+                COLUMN_TYPE_DATE => {
+                    let mut obj = DateColumn::defaults(&column_config);
+                    obj.context_time_zone = context.time_zone.clone();
+                    column_data_wrap = obj.validate(&column_data);
+                },
+                COLUMN_TYPE_DURATION => {
+                    column_data_wrap = DurationColumn::defaults(&column_config).validate(&column_data);
+                },
+                COLUMN_TYPE_RECURRENCE => {
+                    let mut obj = RecurrenceColumn::defaults(&column_config);
+                    obj.context_time_zone = context.time_zone.clone();
+                    column_data_wrap = obj.validate(&column_data);
+                },
+                COLUMN_TYPE_LOCATION => {
+                    column_data_wrap = LocationColumn::defaults(&column_config).validate(&column_data);
+                },
+                COLUMN_TYPE_CURRENCY => {
+                    column_data_wrap = CurrencyColumn::defaults(&column_config).validate(&column_data);
+                },
+                COLUMN_TYPE_PERCENTAGE => {
+                    column_data_wrap = PercentageColumn::defaults(&column_config).validate(&column_data);
+                },
+                COLUMN_TYPE_PHONE => {
+                    column_data_wrap = PhoneColumn::defaults(&column_config).validate(&column_data);
+                },
+                COLUMN_TYPE_EMAIL => {
+                    column_data_wrap = EmailColumn::defaults(&column_config).validate(&column_data);
+                },
+                COLUMN_TYPE_URL => {
+                    column_data_wrap = UrlColumn::defaults(&column_config).validate(&column_data);
+                },
+                COLUMN_TYPE_RATING => {
+                    column_data_wrap = RatingColumn::defaults(&column_config).validate(&column_data);
+                },
+                COLUMN_TYPE_OBJECT => {
+                    column_data_wrap = ObjectColumn::defaults(&column_config).validate(&column_data);
+                },
+                COLUMN_TYPE_WORKFLOW => {
+                    column_data_wrap = WorkflowColumn::defaults(&column_config).validate(&column_data);
+                    if column_data_wrap.is_ok() {
+                        let states = column_data_wrap.clone().unwrap();
+                        if states.len() > 0 && states[0] != String::from("") {
+                            let mut state_from: Option<String> = None;
+                            let previous = data.get(&column_id);
+                            if previous.is_some() {
+                                state_from = get_value_list(previous.unwrap());
+                            }
+                            workflow_changes.push((column_config.clone(), state_from, states[0].clone()));
+                        }
+                    }
+                },
+                COLUMN_TYPE_LINK => {
+                    let obj = LinkColumn::defaults(
+                        planet_context,
+                        context,
+                        &column_config,
+                        &folder_name,
+                        Some(db_folder.clone()),
+                        Some(space_database.clone())
+                    );
+                    let result = obj.validate(&column_data);
+                    if result.is_err() {
+                        errors.extend(result.unwrap_err());
+                        continue
+                    }
+                    let id_list = result.unwrap();
+                    let linked_folder_name = column_config.linked_folder.clone().unwrap_or_default();
+                    let linked_folder = db_folder.get_by_name(&linked_folder_name);
+                    if linked_folder.is_err() || linked_folder.clone().unwrap().is_none() {
+                        errors.push(
+                            PlanetError::new(
+                                500, 
+                                Some(tr!("Linked folder \"{}\" not found.", &linked_folder_name)),
+                            )
+                        );
+                        continue
+                    }
+                    let linked_folder_id = linked_folder.unwrap().unwrap().id.unwrap_or_default();
+                    let id_list_previous = TreeFolderItem::get_link_ids(&column_id, &item_previous);
+                    let mut list: Vec<BTreeMap<String, String>> = Vec::new();
+                    for linked_id in &id_list {
+                        let mut map: BTreeMap<String, String> = BTreeMap::new();
+                        map.insert(ID.to_string(), linked_id.clone());
+                        list.push(map);
+                        if !id_list_previous.contains(linked_id) {
+                            links_added.push((linked_folder_id.clone(), column_id.clone(), linked_id.clone()));
+                        }
+                    }
+                    for linked_id in id_list_previous {
+                        if !id_list.contains(&linked_id) {
+                            links_removed.push((linked_folder_id.clone(), column_id.clone(), linked_id));
+                        }
+                    }
+                    data.insert(column_id.clone(), list);
+                    continue
+                },
+                COLUMN_TYPE_FILE => {
+                    let obj = FileColumn::defaults(
+                        &column_config,
+                        Some(db_row.clone()),
+                        Some(space_database.clone())
+                    );
+                    let fields = obj.validate(
+                        &column_data,
+                        &data,
+                        routing_wrap.clone(),
+                        &home_dir.clone().unwrap_or_default(),
+                        &column_config_map,
+                        &planet_context.tika,
+                    );
+                    if fields.is_err() {
+                        errors.push(fields.unwrap_err());
+                        continue
+                    }
+                    let fields = fields.unwrap();
+                    let file_ids = fields.0;
+                    data = fields.2;
+                    // Extracted document text, indexed by the text column
+                    let key = format!("{}__text", column_id);
+                    data.remove(&key);
+                    let document_texts = fields.1;
+                    if document_texts.len() > 0 {
+                        data.insert(key, build_value_list(&document_texts.join(" ")));
+                    }
+                    files_added.extend(file_ids.clone());
+                    let previous = item_previous.data.clone().unwrap_or_default();
+                    let previous = previous.get(&column_id);
+                    if previous.is_some() {
+                        for value in previous.unwrap() {
+                            let mut file_id = value.get(ID);
+                            if file_id.is_none() {
+                                file_id = value.get(VALUE);
+                            }
+                            if file_id.is_some() && !file_ids.contains(file_id.unwrap()) {
+                                files_removed.push(file_id.unwrap().clone());
+                            }
+                        }
+                    }
+                    column_data_wrap = Ok(file_ids);
+                },
+                _ => {
+                    errors.push(
+                        PlanetError::new(
+                            500, 
+                            Some(tr!("Column \"{}\" of type \"{}\" can't be updated.", &column_name, &column_type)),
+                        )
+                    );
+                    continue
+                }
+            }
+            let tuple = handle_field_response(
+                &column_data_wrap, &errors, &column_id, &data, &is_set
+            );
+            data = tuple.0;
+            errors = tuple.1;
+        }
+        let update_translations = statement.translations.clone();
+        if update_translations.is_some() {
+            let update_translations = update_translations.unwrap();
+            let translation_list = get_translation_list(
+                &folder, 
+                &column_config_map, 
+                &update_translations
+            );
+            if translation_list.is_err() {
+                errors.extend(translation_list.clone().unwrap_err());
+            }
+            let translation_list = translation_list.unwrap_or_default();
+            // Same column and language replace the translation already on the item
+            let mut translations = data.get(TRANSLATIONS).cloned().unwrap_or_default();
+            for translation in translation_list {
+                translations.retain(|item| {
+                    item.get(COLUMN_ID) != translation.get(COLUMN_ID) || 
+                    item.get(LANGUAGE) != translation.get(LANGUAGE)
+                });
+                translations.push(translation);
+            }
+            if translations.len() > 0 {
+                data.insert(TRANSLATIONS.to_string(), translations);
+            }
+        }
+        if errors.len() > 0 {
+            let _ = db_row.delete_files(&files_added);
+            return Err(errors)
+        }
+        // Transition conditions are executed with item data already updated
+        for (column_config, state_from, state_to) in workflow_changes {
+            let column_id = column_config.id.clone().unwrap_or_default();
+            let obj = WorkflowColumn::defaults(&column_config);
+            let result = obj.validate_transition(
+                &state_from, 
+                &state_to, 
+                &data, 
+                &column_config_map,
+                &formula_env
+            );
+            if result.is_err() {
+                errors.push(result.unwrap_err());
+                continue
+            }
+            if state_from.is_none() || state_from.unwrap() != state_to {
+                data = WorkflowColumn::record_state(&data, &column_id, &state_to);
+            }
+        }
+        // formulas, modified time, text and language
+        let mut text_map: BTreeMap<String, String> = BTreeMap::new();
+        let mut text_column_id: String = String::from("");
+        for column_config in config_columns.clone() {
+            let column_type = column_config.column_type.clone().unwrap_or_default();
+            let column_type = column_type.as_str();
+            let column_id = column_config.id.clone().unwrap_or_default();
+            let is_set = column_config.is_set.clone().unwrap_or(FALSE.to_string());
+            match column_type {
+                COLUMN_TYPE_FORMULA => {
+                    let obj = FormulaColumn::defaults(&column_config);
+                    let column_data_wrap = obj.validate(&data, &column_config_map, &formula_env);
+                    let tuple = handle_field_response(
+                        &column_data_wrap, &errors, &column_id, &data, &is_set
+                    );
+                    data = tuple.0;
+                    errors = tuple.1;
+                },
+                COLUMN_TYPE_LAST_MODIFIED_TIME => {
+                    let obj = AuditDateColumn::defaults(&column_config);
+                    let column_data_wrap = obj.validate(&Vec::new());
+                    let tuple = handle_field_response(
+                        &column_data_wrap, &errors, &column_id, &data, &is_set
+                    );
+                    data = tuple.0;
+                    errors = tuple.1;
+                },
+                COLUMN_TYPE_TEXT => {
+                    let mut obj = TextColumn::defaults(
+                        &column_config,
+                        Some(column_config_map.clone()),
+                    );
+                    text_column_id = column_id.clone();
+                    let result_text = obj.validate(
+                        &data, 
+                        &folder,
+                        &text_column_id
+                    );
+                    if result_text.is_err() {
+                        let error_message = result_text.clone().unwrap_err().message;
+                        errors.push(
+                            PlanetError::new(
+                                500, 
+                                Some(tr!("Error capturing text for folder item: {}", &error_message)),
+                            )
+                        );
+                        continue
+                    }
+                    text_map = result_text.unwrap();
+                },
+                COLUMN_TYPE_LANGUAGE => {
+                    let text = text_map.get(&text_column_id);
+                    if text.is_none() {
+                        continue
+                    }
+                    let obj = LanguageColumn::defaults(&column_config);
+                    let result_lang = obj.validate(text.unwrap());
+                    if result_lang.is_err() {
+                        let error_message = result_lang.clone().unwrap_err().message;
+                        errors.push(
+                            PlanetError::new(
+                                500, 
+                                Some(tr!("Error capturing language for folder item: {}", &error_message)),
+                            )
+                        );
+                        continue
+                    }
+                    data.insert(column_id.clone(), build_value_list(&result_lang.unwrap()));
+                },
+                _ => {}
+            }
+        }
+        // rollups and lookups
+        let result_linked = compute_linked_columns(
+            &env, 
+            &space_database, 
+            &db_folder, 
+            &column_config_map, 
+            &data,
+            &formula_env
+        );
+        if result_linked.is_err() {
+            let errors_ = result_linked.unwrap_err();
+            errors.extend(errors_);
+        } else {
+            data = result_linked.unwrap();
+        }
+        // check rules
+        let result_checks = validate_check_rules(
+            &folder, &data, &column_config_map, &formula_env
+        );
+        if result_checks.is_err() {
+            let errors_ = result_checks.unwrap_err();
+            errors.extend(errors_);
+        }
+        if errors.len() > 0 {
+            let _ = db_row.delete_files(&files_added);
+            return Err(errors)
+        }
+        data.remove(TEXT);
+        item.data = Some(data);
+        let result = db_row.update(&item);
+        if result.is_err() {
+            let error = result.unwrap_err();
+            errors.push(error);
+            let _ = db_row.delete_files(&files_added);
+            return Err(errors)
+        }
+        let result = db_row.delete_files(&files_removed);
+        if result.is_err() {
+            let error = result.unwrap_err();
+            errors.push(error);
+            return Err(errors)
+        }
+        // Reverse link columns at linked folders follow the links changed on the item
+        let item_id = item.id.clone().unwrap_or_default();
+        let result = db_row.add_reverse_links(&item_id, &links_added);
+        if result.is_err() {
+            let error = result.unwrap_err();
+            errors.push(error);
+            return Err(errors)
+        }
+        let result = db_row.remove_reverse_links(&item_id, &links_removed);
+        if result.is_err() {
+            let error = result.unwrap_err();
+            errors.push(error);
+            return Err(errors)
+        }
+        if text_map.len() > 0 {
+            let result = db_row.reindex_item(&item, &text_map);
+            if result.is_err() {
+                let error = result.unwrap_err();
+                errors.push(error);
+                return Err(errors)
+            }
+        }
+        // Rollups and lookups at items linked to the updated item
+        let result = refresh_linked_columns(
+            env, 
+            &space_database, 
+            &db_folder, 
+            &folder, 
+            &item,
+            &formula_env
+        );
+        if result.is_err() {
+            let errors_ = result.unwrap_err();
+            errors.extend(errors_);
+            return Err(errors)
+        }
+        // Items no longer linked need their rollups and lookups without the updated item
+        if links_removed.len() > 0 {
+            let result = refresh_linked_columns(
+                env, 
+                &space_database, 
+                &db_folder, 
+                &folder, 
+                &item_previous,
+                &formula_env
+            );
+            if result.is_err() {
+                let errors_ = result.unwrap_err();
+                errors.extend(errors_);
+                return Err(errors)
+            }
+        }
+        let mut yaml_response: Vec<yaml_rust::Yaml> = Vec::new();
+        let response_coded = serde_yaml::to_string(&item);
+        if response_coded.is_err() {
+            let error = PlanetError::new(
+                500, 
+                Some(tr!("Error encoding statement response.")),
+            );
+            errors.push(error);
+            return Err(errors)
+        }
+        let response = response_coded.unwrap();
+        let yaml_item = yaml_rust::YamlLoader::load_from_str(
+            response.as_str()
+        ).unwrap();
+        yaml_response.push(yaml_item[0].clone());
+        eprintln!("UpdateFolder.run :: time: {} ms", &t_1.elapsed().as_millis());
+        return Ok(yaml_response)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExportFileCompiledStmt {
+    pub folder_name: String,
+    pub id: String,
+    pub thumbnail: Option<u32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ExportFileStatement {
+}
+
+impl<'gb> StatementCompiler<'gb, ExportFileCompiledStmt> for ExportFileStatement {
+
+    fn compile(
+        &self, 
+        statement_text: &String
+    ) -> Result<ExportFileCompiledStmt, Vec<PlanetError>> {
+        let expr = &RE_EXPORT_FILE;
+        let mut errors: Vec<PlanetError> = Vec::new();
+        let captures = expr.captures(&statement_text);
+        if captures.is_none() {
+            let error = PlanetError::new(
+                500, 
+                Some(
+                    tr!("Export file syntax not valid.")
+                ),
+            );
+            errors.push(error);
+            return Err(errors)
+        }
+        let captures = captures.unwrap();
+        let folder_name = captures.name("FolderName").unwrap().as_str().trim().to_string();
+        let id = captures.name("Id").unwrap().as_str().to_string();
+        let mut statement = ExportFileCompiledStmt{
+            folder_name: folder_name,
+            id: id,
+            thumbnail: None,
+        };
+        let size = captures.name("Size");
+        if size.is_some() {
+            let size = size.unwrap().as_str();
+            let size: Result<u32, _> = FromStr::from_str(size);
+            if size.is_err() {
+                let error = PlanetError::new(
+                    500, 
+                    Some(
+                        tr!("Thumbnail size not valid in export file statement.")
+                    ),
+                );
+                errors.push(error);
+                return Err(errors)
+            }
+            statement.thumbnail = Some(size.unwrap());
+        }
+        return Ok(statement)
+    }
+}
+
+impl<'gb> Statement<'gb> for ExportFileStatement {
+
+    fn run(
+        &self,
+        env: &'gb Environment<'gb>,
+        space_database: &SpaceDatabase,
+        statement_text: &String,
+    ) -> Result<Vec<yaml_rust::Yaml>, Vec<PlanetError>> {
+        let space_database = space_database.clone();
+        let context = env.context;
+        let planet_context = env.planet_context;
+        let t_1 = Instant::now();
+        let mut errors: Vec<PlanetError> = Vec::new();
+        let statement = self.compile(statement_text);
+        if statement.is_err() {
+            let errors = statement.unwrap_err();
+            return Err(errors)
+        }
+        let statement = statement.unwrap();
+        let folder_name = statement.folder_name.clone();
+        let home_dir = planet_context.home_path.clone();
+        let account_id = context.account_id.clone().unwrap_or_default();
+        let space_id = context.space_id;
+        let site_id = context.site_id.clone();
+        let db_folder= TreeFolder::defaults(
+            space_database.connection_pool.clone(),
+            Some(home_dir.clone().unwrap_or_default().as_str()),
+            Some(&account_id),
+            Some(space_id),
+            site_id.clone(),
+        ).unwrap();
+        let folder = db_folder.get_by_name(&folder_name);
+        if folder.is_err() {
+            let error = folder.unwrap_err();
+            errors.push(error);
+            return Err(errors)
+        }
+        let folder = folder.unwrap();
+        if folder.is_none() {
+            let error = PlanetError::new(
+                500, 
+                Some(tr!("Folder \"{}\" not found.", &folder_name)),
+            );
+            errors.push(error);
+            return Err(errors)
+        }
+        let folder = folder.unwrap();
+        let folder_id = folder.id.clone().unwrap_or_default();
+        let result: Result<TreeFolderItem, PlanetError> = TreeFolderItem::defaults(
+            space_database.connection_pool.clone(),
+            home_dir.clone().unwrap_or_default().as_str(),
+            &account_id,
+            space_id,
+            site_id.clone(),
+            folder_id.as_str(),
+            &db_folder,
+        );
+        if result.is_err() {
+            let error = result.unwrap_err();
+            errors.push(error);
+            return Err(errors)
+        }
+        let mut db_row = result.unwrap();
+        let db_file = db_row.get_file(&statement.id);
+        if db_file.is_err() {
+            let error = db_file.unwrap_err();
+            errors.push(error);
+            return Err(errors)
+        }
+        let mut db_file = db_file.unwrap();
+        let result: Result<(), PlanetError>;
+        if statement.thumbnail.is_some() {
+            let size = statement.thumbnail.unwrap();
+            let result_thumbnail = db_row.export_thumbnail(&statement.id, size);
+            if result_thumbnail.is_ok() {
+                let options = db_file.options.clone().unwrap_or_default();
+                let thumbnail_id = options.get(&format!("{} {}", FILE_PROP_THUMBNAIL, size)).unwrap().clone();
+                db_file = db_row.get_file(&thumbnail_id).unwrap();
+                result = Ok(());
+            } else {
+                result = Err(result_thumbnail.unwrap_err());
+            }
+        } else if db_file.path.is_some() {
+            // Large files live encrypted in the OS files dir
+            result = db_file.export_file();
+        } else {
+            let result_export = db_row.export_file(&statement.id);
+            if result_export.is_ok() {
+                result = Ok(());
+            } else {
+                result = Err(result_export.unwrap_err());
+            }
+        }
+        if result.is_err() {
+            let error = result.unwrap_err();
+            errors.push(error);
+            return Err(errors)
+        }
+        let path = db_file.get_home_path();
+        if path.is_err() {
+            let error = path.unwrap_err();
+            errors.push(error);
+            return Err(errors)
+        }
+        let mut response: BTreeMap<String, String> = BTreeMap::new();
+        response.insert(ID.to_string(), db_file.id.clone().unwrap_or_default());
+        response.insert(FILE_PROP_FILE_NAME.to_string(), db_file.name.clone().unwrap_or_default());
+        response.insert(FILE_PROP_SIZE.to_string(), db_file.size.unwrap_or_default().to_string());
+        response.insert(FILE_PROP_PATH.to_string(), path.unwrap());
+        if db_file.hash.is_some() {
+            response.insert(FILE_PROP_HASH.to_string(), db_file.hash.clone().unwrap());
+        }
+        let response_coded = serde_yaml::to_string(&response);
+        if response_coded.is_err() {
+            let error = PlanetError::new(
+                500, 
+                Some(tr!("Error encoding statement response.")),
+            );
+            errors.push(error);
+            return Err(errors)
+        }
+        let response = response_coded.unwrap();
+        let yaml_item = yaml_rust::YamlLoader::load_from_str(
+            response.as_str()
+        ).unwrap();
+        let mut yaml_response: Vec<yaml_rust::Yaml> = Vec::new();
+        yaml_response.push(yaml_item[0].clone());
+        eprintln!("ExportFile.run :: time: {} ms", &t_1.elapsed().as_millis());
+        return Ok(yaml_response)
+    }
+}
+
+// Exchange rates are kept in space folder "Exchange Rates": Currency (ISO 4217 code, or item name) and Rate 
+// (units of currency for one unit of base currency). They are loaded for each statement, since spaces 
+// have their own rates.
+pub fn load_exchange_rates(
+    db_folder: &TreeFolder,
+    space_database: &SpaceDatabase,
+) -> Result<BTreeMap<String, Decimal>, PlanetError> {
+    let mut rates: BTreeMap<String, Decimal> = BTreeMap::new();
+    let folder = db_folder.get_by_name(&EXCHANGE_RATES_FOLDER.to_string())?;
+    if folder.is_none() {
+        return Ok(rates)
+    }
+    let folder = folder.unwrap();
+    let folder_id = folder.id.clone().unwrap();
+    let column_currency = TreeFolder::get_column_by_name(
+        &EXCHANGE_RATES_COLUMN_CURRENCY.to_string(), &folder
+    );
+    let column_rate = TreeFolder::get_column_by_name(
+        &EXCHANGE_RATES_COLUMN_RATE.to_string(), &folder
+    )?;
+    let column_rate_id = column_rate.get(ID).unwrap().clone();
+    let mut column_currency_id: Option<String> = None;
+    if column_currency.is_ok() {
+        let column_currency = column_currency.unwrap();
+        column_currency_id = column_currency.get(ID).cloned();
+    }
+    let home_dir = db_folder.home_dir.clone().unwrap_or_default();
+    let account_id = db_folder.account_id.clone().unwrap_or_default();
+    let space_id = db_folder.space_id.clone().unwrap_or_default();
+    let mut db_row: TreeFolderItem = TreeFolderItem::defaults(
+        space_database.connection_pool.clone(),
+        home_dir.as_str(),
+        &account_id,
+        space_id.as_str(),
+        db_folder.site_id.clone(),
+        folder_id.as_str(),
+        db_folder,
+    )?;
+    let items = db_row.get_all_items()?;
+    for item in items {
+        let data = item.data.clone();
+        if data.is_none() {
+            continue
+        }
+        let data = data.unwrap();
+        let mut currency = item.name.clone();
+        if column_currency_id.is_some() {
+            let list = data.get(&column_currency_id.clone().unwrap());
+            if list.is_some() {
+                currency = get_value_list(list.unwrap());
+            }
+        }
+        let rate = data.get(&column_rate_id);
+        if currency.is_none() || rate.is_none() {
+            continue
+        }
+        let currency = currency.unwrap().trim().to_uppercase();
+        let rate = get_value_list(rate.unwrap());
+        if rate.is_none() {
+            continue
+        }
+        let rate = Decimal::from_str(rate.unwrap().trim());
+        if rate.is_err() {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Exchange rate for \"{}\" is not a number.", &currency)),
+                )
+            )
+        }
+        rates.insert(currency, rate.unwrap());
+    }
+    return Ok(rates)
+}
+
+// Rollup and lookup columns are stored on the item, computed from the items linked through their link column.
+pub fn compute_linked_columns<'gb>(
+    env: &Environment<'gb>,
+    space_database: &SpaceDatabase,
+    db_folder: &TreeFolder,
+    column_config_map: &BTreeMap<String, ColumnConfig>,
+    data: &BTreeMap<String, Vec<BTreeMap<String, String>>>,
+    formula_env: &FormulaEnv,
+) -> Result<BTreeMap<String, Vec<BTreeMap<String, String>>>, Vec<PlanetError>> {
+    let mut data = data.clone();
+    for (_, column_config) in column_config_map {
+        let column_type = column_config.column_type.clone().unwrap_or_default();
+        let column_type = column_type.as_str();
+        let column_id = column_config.id.clone().unwrap_or_default();
+        let values: Vec<String>;
+        if column_type == COLUMN_TYPE_ROLLUP {
+            let obj = RollupColumn::defaults(
+                env.planet_context,
+                env.context,
+                column_config,
+                Some(db_folder.clone()),
+                Some(space_database.clone()),
+            );
+            values = obj.compute(column_config_map, &data, formula_env)?;
+        } else if column_type == COLUMN_TYPE_LOOKUP {
+            let obj = LookupColumn::defaults(
+                env.planet_context,
+                env.context,
+                column_config,
+                Some(db_folder.clone()),
+                Some(space_database.clone()),
+            );
+            values = obj.compute(column_config_map, &data, formula_env)?;
+        } else {
+            continue
+        }
+        if values.len() == 0 {
+            data.remove(&column_id);
+            continue
+        }
+        let mut value_list: Vec<BTreeMap<String, String>> = Vec::new();
+        for value in values {
+            let mut value_obj: BTreeMap<String, String> = BTreeMap::new();
+            value_obj.insert(VALUE.to_string(), value);
+            value_list.push(value_obj);
+        }
+        data.insert(column_id, value_list);
+    }
+    return Ok(data)
+}
+
+// Items in other folders linked to this item get their rollup and lookup columns computed again. Link 
+// columns share id at both folders, so links to those items are found under the same column id.
+pub fn refresh_linked_columns<'gb>(
+    env: &Environment<'gb>,
+    space_database: &SpaceDatabase,
+    db_folder: &TreeFolder,
+    folder: &DbData,
+    item: &DbData,
+    formula_env: &FormulaEnv,
+) -> Result<(), Vec<PlanetError>> {
+    let folder_data = folder.data.clone().unwrap_or_default();
+    let columns = folder_data.get(COLUMNS);
+    if columns.is_none() {
+        return Ok(())
+    }
+    let columns = columns.unwrap();
+    for column in columns {
+        let column_type = column.get(COLUMN_TYPE);
+        if column_type.is_none() || column_type.unwrap().as_str() != COLUMN_TYPE_LINK {
+            continue
+        }
+        let column_id = column.get(ID).unwrap().clone();
+        let linked_folder_name = column.get(LINKED_FOLDER);
+        if linked_folder_name.is_none() {
+            continue
+        }
+        let linked_ids = TreeFolderItem::get_link_ids(&column_id, item);
+        if linked_ids.len() == 0 {
+            continue
+        }
+        let linked_folder = db_folder.get_by_name(linked_folder_name.unwrap());
+        if linked_folder.is_err() {
+            return Err(vec![linked_folder.unwrap_err()])
+        }
+        let linked_folder = linked_folder.unwrap();
+        if linked_folder.is_none() {
+            continue
+        }
+        let linked_folder = linked_folder.unwrap();
+        let linked_folder_id = linked_folder.id.clone().unwrap_or_default();
+        let linked_folder_name = linked_folder.name.clone().unwrap_or_default();
+        let column_config_map = ColumnConfig::get_column_config_map(
+            env.planet_context, 
+            env.context, 
+            &linked_folder
+        );
+        if column_config_map.is_err() {
+            return Err(vec![column_config_map.unwrap_err()])
+        }
+        let column_config_map = column_config_map.unwrap();
+        let mut link_column_name: Option<String> = None;
+        for (column_name, column_config) in &column_config_map {
+            if column_config.id.clone().unwrap_or_default() == column_id {
+                link_column_name = Some(column_name.clone());
+            }
+        }
+        if link_column_name.is_none() {
+            continue
+        }
+        let link_column_name = link_column_name.unwrap();
+        let mut has_linked_columns = false;
+        for (_, column_config) in &column_config_map {
+            let column_type = column_config.column_type.clone().unwrap_or_default();
+            let column_type = column_type.as_str();
+            if (column_type == COLUMN_TYPE_ROLLUP || column_type == COLUMN_TYPE_LOOKUP) && 
+                column_config.link_column.clone().unwrap_or_default() == link_column_name {
+                has_linked_columns = true;
+            }
+        }
+        if !has_linked_columns {
+            continue
+        }
+        let home_dir = db_folder.home_dir.clone().unwrap_or_default();
+        let account_id = db_folder.account_id.clone().unwrap_or_default();
+        let space_id = db_folder.space_id.clone().unwrap_or_default();
+        let db_row = TreeFolderItem::defaults(
+            space_database.connection_pool.clone(),
+            home_dir.as_str(),
+            &account_id,
+            space_id.as_str(),
+            db_folder.site_id.clone(),
+            linked_folder_id.as_str(),
+            db_folder,
+        );
+        if db_row.is_err() {
+            return Err(vec![db_row.unwrap_err()])
+        }
+        let mut db_row = db_row.unwrap();
+        for linked_id in linked_ids {
+            let linked_item = db_row.get(
+                &linked_folder_name, 
+                GetItemOption::ById(linked_id), 
+                None
+            );
+            if linked_item.is_err() {
+                // Removed through cascade rules
+                continue
+            }
+            let mut linked_item = linked_item.unwrap();
+            let data = linked_item.data.clone().unwrap_or_default();
+            let data = compute_linked_columns(
+                env, 
+                space_database, 
+                db_folder, 
+                &column_config_map, 
+                &data,
+                formula_env
+            )?;
+            linked_item.data = Some(data);
+            let result = db_row.update(&linked_item);
+            if result.is_err() {
+                return Err(vec![result.unwrap_err()])
+            }
+        }
+    }
+    return Ok(())
+}
+
+// Translations for translatable columns as {column_id, language, value}, translations come as column name => language => value
+pub fn get_translation_list(
+    folder: &DbData,
+    column_config_map: &BTreeMap<String, ColumnConfig>,
+    translations: &BTreeMap<String, BTreeMap<String, String>>,
+) -> Result<Vec<BTreeMap<String, String>>, Vec<PlanetError>> {
+    let mut errors: Vec<PlanetError> = Vec::new();
+    let default_language_code = get_default_language_code(folder);
+    if default_language_code.is_err() {
+        errors.push(default_language_code.clone().unwrap_err());
+    }
+    let default_language_code = default_language_code.unwrap_or_default();
+    let mut translation_list: Vec<BTreeMap<String, String>> = Vec::new();
+    for (column_name, column_translations) in translations.clone() {
+        let column_config = column_config_map.get(&column_name);
+        if column_config.is_none() {
+            errors.push(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Column \"{}\" not found in folder.", &column_name)),
+                )
+            );
+            continue
+        }
+        let column_config = column_config.unwrap();
+        let column_type = column_config.column_type.clone().unwrap_or_default();
+        let column_type = column_type.as_str();
+        let translatable = column_config.translatable.unwrap_or_default();
+        if !translatable {
+            errors.push(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Column \"{}\" is not translatable.", &column_name)),
+                )
+            );
+            continue
+        }
+        let column_id = column_config.id.clone().unwrap_or_default();
+        for (language_code, value) in column_translations {
+            if !LANGUAGE_CODE_ITEMS.contains(&language_code.as_str()) {
+                errors.push(
+                    PlanetError::new(
+                        500, 
+                        Some(tr!("Language \"{}\" not supported.", &language_code)),
+                    )
+                );
+                continue
+            }
+            if language_code == default_language_code {
+                errors.push(
+                    PlanetError::new(
+                        500, 
+                        Some(tr!(
+                            "Language \"{}\" is default for folder, use {} = ... for column \"{}\".", 
+                            &language_code, &column_name, &column_name
+                        )),
+                    )
+                );
+                continue
+            }
+            let mut values: Vec<String> = Vec::new();
+            values.push(value);
+            let result: Result<Vec<String>, Vec<PlanetError>>;
+            if column_type == COLUMN_TYPE_LONG_TEXT {
+                result = LongTextColumn::defaults(column_config).validate(&values);
+            } else {
+                result = SmallTextColumn::defaults(column_config).validate(&values);
+            }
+            if result.is_err() {
+                errors.extend(result.unwrap_err());
+                continue
+            }
+            let values = result.unwrap();
+            for value in values {
+                let mut translation: BTreeMap<String, String> = BTreeMap::new();
+                translation.insert(COLUMN_ID.to_string(), column_id.clone());
+                translation.insert(LANGUAGE.to_string(), language_code.clone());
+                translation.insert(VALUE.to_string(), value);
+                translation_list.push(translation);
+            }
+        }
+    }
+    if errors.len() > 0 {
+        return Err(errors)
+    }
+    return Ok(translation_list)
+}
+
+pub fn validate_check_rules(
+    folder: &DbData,
+    data: &BTreeMap<String, Vec<BTreeMap<String, String>>>,
+    column_config_map: &BTreeMap<String, ColumnConfig>,
+    formula_env: &FormulaEnv,
+) -> Result<(), Vec<PlanetError>> {
+    let mut errors: Vec<PlanetError> = Vec::new();
+    let folder_data = folder.data.clone();
+    if folder_data.is_none() {
+        return Ok(())
+    }
+    let folder_data = folder_data.unwrap();
+    let checks = folder_data.get(CHECKS);
+    if checks.is_none() {
+        return Ok(())
+    }
+    let checks = checks.unwrap();
+    for check in checks {
+        let check_name = check.get(NAME).unwrap().clone();
+        let formula_compiled = check.get(FORMULA_COMPILED);
+        if formula_compiled.is_none() {
+            continue
+        }
+        let formula_compiled = formula_compiled.unwrap();
+        let formula_compiled: Result<Formula, serde_yaml::Error> = serde_yaml::from_str(
+            formula_compiled.as_str()
+        );
+        if formula_compiled.is_err() {
+            errors.push(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Check rule \"{}\" is not correctly compiled.", &check_name)),
+                )
+            );
+            continue
+        }
+        let mut formula_compiled = formula_compiled.unwrap();
+        formula_compiled.env = Some(formula_env.clone());
+        let formula_result = execute_formula(
+            &formula_compiled, 
+            data, 
+            None,
+            column_config_map
+        );
+        if formula_result.is_err() {
+            let error = formula_result.unwrap_err();
+            errors.push(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Check rule \"{}\" could not be evaluated: {}", &check_name, &error.message)),
+                )
+            );
+            continue
+        }
+        let formula_result = formula_result.unwrap();
+        let result = formula_result.result.to_uppercase();
+        if !formula_result.matched && result != String::from("TRUE") {
+            let formula = check.get(FORMULA).unwrap().clone();
+            errors.push(
+                PlanetError::new(
+                    500, 
+                    Some(tr!(
+                        "Check rule {}{}{} failed: {}", 
+                        String::from("\"").blue(), &check_name.blue(), String::from("\"").blue(), &formula
+                    )),
+                )
+            );
+        }
+    }
+    if errors.len() > 0 {
+        return Err(errors)
+    }
+    return Ok(())
+}
+
+fn handle_field_response(
+    column_data: &Result<Vec<String>, Vec<PlanetError>>, 
+    errors: &Vec<PlanetError>, 
+    column_id: &String,
+    data: &BTreeMap<String, Vec<BTreeMap<String, String>>>,
+    is_set: &String
+) -> (
+    BTreeMap<String, Vec<BTreeMap<String, String>>>,
+    Vec<PlanetError>
+) {
+    let column_data = column_data.clone();
+    eprintln!("handle_field_response :: column_id: {} column_data: {:?}", column_id, column_data);
+    let mut errors = errors.clone();
+    let mut data = data.clone();
+    let column_id = column_id.clone();
+    let is_set = is_set.clone();
+    if column_data.is_err() {
+        let err = column_data.unwrap_err();
+        for error in err {
+            errors.push(error);
         }
     } else {
         let column_data = column_data.unwrap().clone();
@@ -7084,6 +9491,75 @@ pub fn resolve_data_statement(
             }
         }
     }
+    // DELETE FROM FOLDER
+    let expr = &RE_DELETE_FROM_FOLDER;
+    let check = expr.is_match(&statement_text);
+    if check {
+        let stmt = DeleteFromFolderStatement{};
+        match mode {
+            StatementCallMode::Run => {
+                let response = stmt.run(
+                    env, 
+                    &space_data, 
+                    &statement_text,
+                );
+                return Some(response);
+            }
+            StatementCallMode::Compile => {
+                let response = stmt.compile(&statement_text);
+                if response.is_err() {
+                    let errors = response.unwrap_err();
+                    return Some(Err(errors))
+                }
+            }
+        }
+    }
+    // EXPORT FILE
+    let expr = &RE_EXPORT_FILE;
+    let check = expr.is_match(&statement_text);
+    if check {
+        let stmt = ExportFileStatement{};
+        match mode {
+            StatementCallMode::Run => {
+                let response = stmt.run(
+                    env, 
+                    &space_data, 
+                    &statement_text,
+                );
+                return Some(response);
+            }
+            StatementCallMode::Compile => {
+                let response = stmt.compile(&statement_text);
+                if response.is_err() {
+                    let errors = response.unwrap_err();
+                    return Some(Err(errors))
+                }
+            }
+        }
+    }
+    // UPDATE FOLDER
+    let expr = &RE_UPDATE_FOLDER;
+    let check = expr.is_match(&statement_text);
+    if check {
+        let stmt = UpdateFolderStatement{};
+        match mode {
+            StatementCallMode::Run => {
+                let response = stmt.run(
+                    env, 
+                    &space_data, 
+                    &statement_text,
+                );
+                return Some(response);
+            }
+            StatementCallMode::Compile => {
+                let response = stmt.compile(&statement_text);
+                if response.is_err() {
+                    let errors = response.unwrap_err();
+                    return Some(Err(errors))
+                }
+            }
+        }
+    }
     // SELECT FROM FOLDER
     let expr = &RE_SELECT;
     let check = expr.is_match(&statement_text);
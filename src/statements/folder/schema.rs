@@ -14,6 +14,7 @@ use colored::*;
 use regex::{Regex, Captures};
 
 use crate::statements::folder::config::create_minimum_column_map;
+use crate::functions::Formula;
 use crate::statements::*;
 use crate::statements::{Statement, StatementCallMode};
 use crate::storage::folder::{
@@ -47,6 +48,7 @@ use crate::storage::columns::{
     media::*,
     structure::*,
     processing::*,
+    geo::*,
     StorageColumn,
     StorageColumnBasic,
     ObjectStorageColumn,
@@ -55,15 +57,20 @@ use crate::storage::columns::{
 
 lazy_static! {
     pub static ref RE_CREATE_FOLDER_MAIN: Regex = Regex::new(r#"CREATE[\s]+FOLDER[\s]+"*(?P<FolderName>[\w\s]+)"*\s+\([\n\t\s]*(?P<Config>[\s\S]+),*\);"#).unwrap();
-    pub static ref RE_CREATE_FOLDER_CONFIG: Regex = Regex::new(r#"([\s]*LANGUAGE (?P<Language>spanish|english|french|german|italian|portuguese|norwegian|swedish|danish),*)|([\s]*NAME COLUMN (?P<NameConfig>(SmallText|LongText|Number|Currency|Percentage|GenerateNumber|Phone|Email|Url|Rating)),*)|([\s]*("(?P<Column>[\w\s]+)")[\s]+(?P<ColumnType>SmallText|LongText|Checkbox|Number|Select|Currency|Percentage|GenerateNumber|Phone|Email|Url|Rating|Object|File|Date|Formula|Duration|CreatedTime|LastModifiedTime|CreatedBy|LastModifiedBy|Link|Reference|Language|GenerateId|Stats))([\s]*[WITH]*[\s]*(?P<Options>[\w\s"\$=\{\}\|\(\)]*)),|([\s]*SUB FOLDER (?P<SubFolderName>[\w\s]+)),|([\s]*SUB FOLDER (?P<SubFolderNameAlt>[\w\s]+) WITH (?P<SubFolderOptions>[\w\s"\$=\{\}\|]*)),|([\s]*SEARCH RELEVANCE WITH (?P<SearchRelevanceOptions>[\w\s"\$=\{\}\|]*)),"#).unwrap();
+    pub static ref RE_CREATE_FOLDER_CONFIG: Regex = Regex::new(r#"([\s]*LANGUAGE (?P<Language>spanish|english|french|german|italian|portuguese|norwegian|swedish|danish),*)|([\s]*NAME COLUMN (?P<NameConfig>(SmallText|LongText|Number|Currency|Percentage|GenerateNumber|Phone|Email|Url|Rating)),*)|([\s]*UNIQUE KEY "(?P<UniqueKeyName>[\w\s]+)"[\s]*\((?P<UniqueKeyColumns>[\w\s",]+)\),*)|([\s]*CHECK "(?P<CheckName>[\w\s]+)"[\s]+(?P<CheckFormula>\$AL__text_\d+|"[^"]+"),*)|([\s]*("(?P<Column>[\w\s]+)")[\s]+(?P<ColumnType>SmallText|LongText|Checkbox|Number|Select|Currency|Percentage|GenerateNumber|Phone|Email|Url|Rating|Object|File|Date|Formula|Duration|Recurrence|Location|CreatedTime|LastModifiedTime|CreatedBy|LastModifiedBy|Link|Reference|Language|GenerateId|Stats|Rollup|Lookup|Secret|RichText|Tags|Workflow))([\s]*[WITH]*[\s]*(?P<Options>[\w\s"\$=\{\}\|\(\)\./\+\-]*)),|([\s]*SUB FOLDER (?P<SubFolderName>[\w\s]+)),|([\s]*SUB FOLDER (?P<SubFolderNameAlt>[\w\s]+) WITH (?P<SubFolderOptions>[\w\s"\$=\{\}\|]*)),|([\s]*SEARCH RELEVANCE WITH (?P<SearchRelevanceOptions>[\w\s"\$=\{\}\|]*)),"#).unwrap();
     pub static ref RE_LIST_FOLDERS: Regex = Regex::new(r#"LIST[\s]+FOLDERS;"#).unwrap();
     pub static ref RE_DESCRIBE_FOLDER: Regex = Regex::new(r#"DESCRIBE[\s]+FOLDER[\s]+(?P<FolderName>[\w\s]+);"#).unwrap();
+    pub static ref RE_COPY_FOLDER: Regex = Regex::new(r#"COPY[\s]+FOLDER[\s]+"(?P<FolderName>[\w\s]+)"[\s]+TO[\s]+"(?P<TargetFolderName>[\w\s]+)"(?P<WithData>[\s]+WITH[\s]+DATA)*[\s]*;"#).unwrap();
+    pub static ref RE_MOVE_FOLDER: Regex = Regex::new(r#"MOVE[\s]+FOLDER[\s]+"(?P<FolderName>[\w\s]+)"[\s]+TO[\s]+SPACE[\s]+(?P<SpaceId>[\w]+)([\s]+SITE[\s]+(?P<SiteId>[\w]+))*(?P<AllowLinks>[\s]+ALLOW[\s]+CROSS[\s]+SPACE[\s]+LINKS)*[\s]*;"#).unwrap();
     pub static ref RE_DROP_FOLDER: Regex = Regex::new(r#"DROP[\s]+FOLDER[\s]+(?P<FolderName>[\w\s]+);"#).unwrap();
     pub static ref RE_ADD_COLUMN: Regex = Regex::new(r#"ADD[\s]+COLUMN[\s]+INTO[\s]+"*(?P<FolderName>[\w\s]+)"*\([\n\t\s]*(?P<Config>.[^)]+),*\);"#).unwrap();
-    pub static ref RE_ADD_COLUMN_CONFIG: Regex = Regex::new(r#"([\s]*("(?P<Column>[\w\s]+)")[\s]+(?P<ColumnType>SmallText|LongText|Checkbox|Number|Select|Currency|Percentage|GenerateNumber|Phone|Email|Url|Rating|Object|File|Date|Formula|Duration|CreatedTime|LastModifiedTime|CreatedBy|LastModifiedBy|Link|Reference|Language|GenerateId|Stats))([\s]*[WITH]*[\s]*(?P<Options>[\w\s"\$=\{\}\|]*))"#).unwrap();
+    pub static ref RE_ADD_COLUMN_CONFIG: Regex = Regex::new(r#"([\s]*("(?P<Column>[\w\s]+)")[\s]+(?P<ColumnType>SmallText|LongText|Checkbox|Number|Select|Currency|Percentage|GenerateNumber|Phone|Email|Url|Rating|Object|File|Date|Formula|Duration|Recurrence|Location|CreatedTime|LastModifiedTime|CreatedBy|LastModifiedBy|Link|Reference|Language|GenerateId|Stats|Rollup|Lookup|Secret|RichText|Tags|Workflow))([\s]*[WITH]*[\s]*(?P<Options>[\w\s"\$=\{\}\|/\-]*))"#).unwrap();
     pub static ref RE_MODIFY_COLUMN: Regex = Regex::new(r#"MODIFY[\s]+COLUMN[\s]+FROM[\s]+"*(?P<FolderName>[\w\s]+)"*\([\n\t\s]*(?P<Config>.[^)]+),*\);"#).unwrap();
-    pub static ref RE_MODIFY_COLUMN_CONFIG: Regex = Regex::new(r#"([\s]*NAME[\s]*COLUMN (?P<NameConfig>(SmallText|LongText|Number|Currency|Percentage|GenerateNumber|Phone|Email|Url|Rating)))|([\s]*("(?P<Column>[\w\s]+)")[\s]+(?P<ColumnType>SmallText|LongText|Checkbox|Number|Select|Currency|Percentage|GenerateNumber|Phone|Email|Url|Rating|Object|File|Date|Formula|Duration|CreatedTime|LastModifiedTime|CreatedBy|LastModifiedBy|Link|Reference|Language|GenerateId|Stats))([\s]*[WITH]*[\s]*(?P<Options>[\w\s"\$=\{\}\|]*))"#).unwrap();
+    pub static ref RE_MODIFY_COLUMN_CONFIG: Regex = Regex::new(r#"([\s]*NAME[\s]*COLUMN (?P<NameConfig>(SmallText|LongText|Number|Currency|Percentage|GenerateNumber|Phone|Email|Url|Rating)))|([\s]*("(?P<Column>[\w\s]+)")[\s]+(?P<ColumnType>SmallText|LongText|Checkbox|Number|Select|Currency|Percentage|GenerateNumber|Phone|Email|Url|Rating|Object|File|Date|Formula|Duration|Recurrence|Location|CreatedTime|LastModifiedTime|CreatedBy|LastModifiedBy|Link|Reference|Language|GenerateId|Stats|Rollup|Lookup|Secret|RichText|Tags|Workflow))([\s]*[WITH]*[\s]*(?P<Options>[\w\s"\$=\{\}\|/\-]*))"#).unwrap();
     pub static ref RE_DROP_COLUMN: Regex = Regex::new(r#"DROP[\s]+COLUMN[\s]+"*(?P<ColumnName>[\w\s]+)"*[\s]*FROM[\s]+"*(?P<FolderName>[\w\s]+)"*;"#).unwrap();
+    pub static ref RE_MODIFY_SELECT_OPTIONS: Regex = Regex::new(r#"MODIFY[\s]+COLUMN[\s]+FROM[\s]+"*(?P<FolderName>[\w\s]+)"*[\s]*\([\n\t\s]*"(?P<Column>[\w\s]+)"[\s]+((RENAME[\s]+OPTION[\s]+"(?P<RenameOption>[\w\s]+)"[\s]+TO[\s]+"(?P<RenameTo>[\w\s]+)")|(MERGE[\s]+OPTIONS[\s]+\{(?P<MergeOptions>[\w\s"|]+)\}[\s]+INTO[\s]+"(?P<MergeInto>[\w\s]+)")|(REORDER[\s]+OPTIONS[\s]+\{(?P<ReorderOptions>[\w\s"|]+)\}))[\n\t\s]*\);"#).unwrap();
+    pub static ref RE_LIST_TAGS: Regex = Regex::new(r#"LIST[\s]+TAGS[\s]*;"#).unwrap();
+    pub static ref RE_MODIFY_TAGS: Regex = Regex::new(r#"((RENAME[\s]+TAG[\s]+"(?P<RenameTag>[^"]+)"[\s]+TO[\s]+"(?P<RenameTo>[^"]+)")|(MERGE[\s]+TAGS[\s]+\{(?P<MergeTags>[^}]+)\}[\s]+INTO[\s]+"(?P<MergeInto>[^"]+)"))[\s]*;"#).unwrap();
     pub static ref RE_MODIFY_LANGUAGE: Regex = Regex::new(r#"MODIFY[\s]+LANGUAGE[\s]+FROM[\s]+"*(?P<FolderName>[\w\s]+)"*\([\n\t\s]*(?P<Config>.[^)]+),*\);"#).unwrap();
     pub static ref RE_ADD_SUBFOLDER: Regex = Regex::new(r#"ADD[\s]+SUBFOLDER[\s]+INTO[\s]+"*(?P<FolderName>[\w\s]+)"*[\s]*\([\n\t\s]*(?P<Config>[\s\S][^)]+)\);"#).unwrap();
     pub static ref RE_SUBFOLDER_CONFIG: Regex = Regex::new(r#"([\s]*(?P<SubFolderName>[\w\s]+))|([\s]*(?P<SubFolderNameAlt>[\w\s]+) WITH (?P<SubFolderOptions>[\w\s"\$=\{\}\|]*))"#).unwrap();
@@ -101,8 +108,30 @@ pub const WITH_STATS_FUNCTION: &str = "StatsFunction";
 pub const WITH_RELATED_COLUMN: &str = "RelatedColumn";
 pub const WITH_CONTENT_TYPES: &str = "ContentTypes";
 pub const WITH_MODE: &str = "Mode";
+pub const WITH_UNIQUE: &str = "Unique";
+pub const WITH_ON_DELETE: &str = "OnDelete";
+pub const WITH_OBJECT_SCHEMA: &str = "ObjectSchema";
+pub const WITH_INDEXED_PATHS: &str = "IndexedPaths";
+pub const WITH_TIME_ZONE: &str = "TimeZone";
+pub const WITH_DURATION_FORMAT: &str = "DurationFormat";
+pub const WITH_CURRENCY: &str = "Currency";
+pub const WITH_OPTION_COLORS: &str = "OptionColors";
+pub const WITH_ARCHIVED_OPTIONS: &str = "ArchivedOptions";
+pub const WITH_SEQUENCE_FORMAT: &str = "SequenceFormat";
+pub const WITH_SEQUENCE_START: &str = "SequenceStart";
+pub const WITH_SEQUENCE_STEP: &str = "SequenceStep";
+pub const WITH_SEQUENCE_RESET: &str = "SequenceReset";
+pub const WITH_DEFAULT_COUNTRY: &str = "DefaultCountry";
+pub const WITH_PHONE_FORMAT: &str = "PhoneFormat";
+pub const WITH_STRIP_TRACKING: &str = "StripTracking";
+pub const WITH_OUTPUT: &str = "Output";
+pub const WITH_TRANSLATABLE: &str = "Translatable";
+pub const WITH_STATES: &str = "States";
+pub const WITH_TRANSITIONS: &str = "Transitions";
+pub const WITH_THUMBNAILS: &str = "Thumbnails";
+pub const WITH_LOCATION_COLUMN: &str = "LocationColumn";
 
-pub const ALLOWED_WITH_OPTIONS: [&str; 26] = [
+pub const ALLOWED_WITH_OPTIONS: [&str; 48] = [
     WITH_PARENT, 
     WITH_REQUIRED, 
     WITH_OPTIONS, 
@@ -129,9 +158,40 @@ pub const ALLOWED_WITH_OPTIONS: [&str; 26] = [
     WITH_RELATED_COLUMN,
     WITH_CONTENT_TYPES, 
     WITH_MODE,
+    WITH_UNIQUE,
+    WITH_ON_DELETE,
+    WITH_OBJECT_SCHEMA,
+    WITH_INDEXED_PATHS,
+    WITH_TIME_ZONE,
+    WITH_DURATION_FORMAT,
+    WITH_CURRENCY,
+    WITH_OPTION_COLORS,
+    WITH_ARCHIVED_OPTIONS,
+    WITH_SEQUENCE_FORMAT,
+    WITH_SEQUENCE_START,
+    WITH_SEQUENCE_STEP,
+    WITH_SEQUENCE_RESET,
+    WITH_DEFAULT_COUNTRY,
+    WITH_PHONE_FORMAT,
+    WITH_STRIP_TRACKING,
+    WITH_OUTPUT,
+    WITH_TRANSLATABLE,
+    WITH_STATES,
+    WITH_TRANSITIONS,
+    WITH_THUMBNAILS,
+    WITH_LOCATION_COLUMN,
 ];
 
-pub const ALLOWED_COLUMN_TYPES: [&str; 29] = [
+pub const ALLOWED_UNIQUE_COLUMN_TYPES: [&str; 6] = [
+    COLUMN_TYPE_SMALL_TEXT, 
+    COLUMN_TYPE_EMAIL, 
+    COLUMN_TYPE_PHONE, 
+    COLUMN_TYPE_URL, 
+    COLUMN_TYPE_NUMBER, 
+    COLUMN_TYPE_GENERATE_ID
+];
+
+pub const ALLOWED_COLUMN_TYPES: [&str; 37] = [
     COLUMN_TYPE_CHECKBOX, 
     COLUMN_TYPE_CREATED_BY, 
     COLUMN_TYPE_CREATED_TIME, 
@@ -147,20 +207,28 @@ pub const ALLOWED_COLUMN_TYPES: [&str; 29] = [
     COLUMN_TYPE_LAST_MODIFIED_BY, 
     COLUMN_TYPE_LAST_MODIFIED_TIME, 
     COLUMN_TYPE_LINK, 
+    COLUMN_TYPE_LOCATION, 
     COLUMN_TYPE_LONG_TEXT, 
+    COLUMN_TYPE_LOOKUP, 
     COLUMN_TYPE_NUMBER, 
     COLUMN_TYPE_OBJECT, 
     COLUMN_TYPE_PERCENTAGE, 
     COLUMN_TYPE_PHONE, 
     COLUMN_TYPE_RATING, 
+    COLUMN_TYPE_RECURRENCE, 
     COLUMN_TYPE_REFERENCE, 
+    COLUMN_TYPE_RICH_TEXT, 
+    COLUMN_TYPE_ROLLUP, 
+    COLUMN_TYPE_SECRET, 
     COLUMN_TYPE_SELECT, 
     COLUMN_TYPE_SET, 
     COLUMN_TYPE_SMALL_TEXT, 
     COLUMN_TYPE_STATEMENT, 
     COLUMN_TYPE_STATS, 
+    COLUMN_TYPE_TAGS, 
     COLUMN_TYPE_TEXT, 
-    COLUMN_TYPE_URL
+    COLUMN_TYPE_URL, 
+    COLUMN_TYPE_WORKFLOW
 ];
 
 
@@ -195,6 +263,18 @@ impl SubFolderConfig {
     }
 }
 
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct UniqueKeyConfig {
+    pub name: String,
+    pub columns: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct CheckConfig {
+    pub name: String,
+    pub formula: String,
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub enum DateFormat {
     Friendly,
@@ -203,6 +283,14 @@ pub enum DateFormat {
     ISO,
 }
 
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub enum DurationFormat {
+    Clock,
+    Compact,
+    Long,
+    ISO,
+}
+
 #[derive(Debug, Deserialize, Serialize, Validate, Clone)]
 pub struct ColumnConfig {
     #[validate(length(equal=20))]
@@ -218,6 +306,7 @@ pub struct ColumnConfig {
     pub version: Option<String>,
     pub required: Option<bool>,
     pub indexed: Option<bool>,
+    pub unique: Option<bool>,
     pub many: Option<bool>,
     pub options: Option<Vec<String>>,
     pub formula: Option<String>,
@@ -229,10 +318,32 @@ pub struct ColumnConfig {
     pub number_decimals: Option<i8>,
     pub linked_folder: Option<String>,
     pub delete_on_link_drop: Option<bool>,
+    pub on_delete: Option<String>,
+    pub object_schema: Option<String>,
+    pub indexed_paths: Option<Vec<String>>,
+    pub time_zone: Option<String>,
+    pub duration_format: Option<DurationFormat>,
+    pub currency: Option<String>,
+    pub option_colors: Option<Vec<String>>,
+    pub archived_options: Option<Vec<String>>,
     pub link_column: Option<String>,
     pub remote_column: Option<String>,
     pub remote_column_type: Option<String>,
     pub sequence: Option<String>,
+    pub sequence_format: Option<String>,
+    pub sequence_start: Option<String>,
+    pub sequence_step: Option<String>,
+    pub sequence_reset: Option<String>,
+    pub default_country: Option<String>,
+    pub phone_format: Option<String>,
+    pub strip_tracking: Option<bool>,
+    pub output: Option<String>,
+    pub translatable: Option<bool>,
+    pub states: Option<Vec<String>>,
+    pub transitions: Option<Vec<String>>,
+    pub transitions_compiled: Option<String>,
+    pub thumbnails: Option<Vec<String>>,
+    pub location_column: Option<String>,
     pub maximum: Option<String>,
     pub minimum: Option<String>,
     pub set_maximum: Option<String>,
@@ -244,6 +355,8 @@ pub struct ColumnConfig {
     pub content_types: Option<Vec<String>>,
     pub mode: Option<String>,
     pub statements: Option<String>,
+    pub secret_salt: Option<String>,
+    pub secret_check: Option<String>,
 }
 
 impl ConfigStorageColumn for ColumnConfig {
@@ -258,6 +371,7 @@ impl ConfigStorageColumn for ColumnConfig {
             version: Some(String::from(FIELD_VERSION)),
             required: Some(false),
             indexed: Some(true),
+            unique: None,
             options: None,
             many: None,
             formula: None,
@@ -269,10 +383,32 @@ impl ConfigStorageColumn for ColumnConfig {
             number_decimals: None,
             linked_folder: None,
             delete_on_link_drop: None,
+            on_delete: None,
+            object_schema: None,
+            indexed_paths: None,
+            time_zone: None,
+            duration_format: None,
+            currency: None,
+            option_colors: None,
+            archived_options: None,
             link_column: None,
             remote_column: None,
             remote_column_type: None,
             sequence: None,
+            sequence_format: None,
+            sequence_start: None,
+            sequence_step: None,
+            sequence_reset: None,
+            default_country: None,
+            phone_format: None,
+            strip_tracking: None,
+            output: None,
+            translatable: None,
+            states: None,
+            transitions: None,
+            transitions_compiled: None,
+            thumbnails: None,
+            location_column: None,
             maximum: None,
             minimum: None,
             set_maximum: None,
@@ -284,6 +420,8 @@ impl ConfigStorageColumn for ColumnConfig {
             content_types: None,
             mode: None,
             statements: None,
+            secret_salt: None,
+            secret_check: None,
         };
         if options.is_some() {
             object.options = Some(options.unwrap());
@@ -314,6 +452,7 @@ impl ConfigStorageColumn for ColumnConfig {
                     version: Some(column_config_map.get(VERSION).unwrap().clone()),
                     required: Some(required),
                     indexed: Some(indexed),
+                    unique: None,
                     many: Some(many),
                     options: None,
                     formula: None,
@@ -325,10 +464,32 @@ impl ConfigStorageColumn for ColumnConfig {
                     number_decimals: None,
                     linked_folder: None,
                     delete_on_link_drop: None,
+                    on_delete: None,
+                    object_schema: None,
+                    indexed_paths: None,
+                    time_zone: None,
+                    duration_format: None,
+                    currency: None,
+                    option_colors: None,
+                    archived_options: None,
                     link_column: None,
                     remote_column: None,
                     remote_column_type: None,
                     sequence: None,
+                    sequence_format: None,
+                    sequence_start: None,
+                    sequence_step: None,
+                    sequence_reset: None,
+                    default_country: None,
+                    phone_format: None,
+                    strip_tracking: None,
+                    output: None,
+                    translatable: None,
+                    states: None,
+                    transitions: None,
+                    transitions_compiled: None,
+                    thumbnails: None,
+                    location_column: None,
                     maximum: None,
                     minimum: None,
                     set_maximum: None,
@@ -340,6 +501,8 @@ impl ConfigStorageColumn for ColumnConfig {
                     content_types: None,
                     mode: None,
                     statements: None,
+                    secret_salt: None,
+                    secret_check: None,
                 };
                 return Some(column_config);
             }
@@ -422,6 +585,11 @@ impl ConfigStorageColumn for ColumnConfig {
                     if many_wrap.is_some() {
                         many = make_bool_str(many_wrap.unwrap().clone());
                     }
+                    let mut unique = false;
+                    let unique_wrap = column_config_map.get(UNIQUE);
+                    if unique_wrap.is_some() {
+                        unique = make_bool_str(unique_wrap.unwrap().clone());
+                    }
                     let mut column_config = ColumnConfig::defaults(None);
                     column_config.default = None;
                     let default_wrap = column_config_map.get(DEFAULT);
@@ -443,6 +611,9 @@ impl ConfigStorageColumn for ColumnConfig {
                     column_config.required = Some(required);
                     column_config.indexed = Some(indexed);
                     column_config.many = Some(many);
+                    if unique {
+                        column_config.unique = Some(unique);
+                    }
                     // eprintln!("get_config :: column_type_str: {}", column_type_str);
     
                     let is_set = column_config_map.get(IS_SET);
@@ -460,10 +631,18 @@ impl ConfigStorageColumn for ColumnConfig {
                             let mut obj = SmallTextColumn::defaults(&column_config);
                             column_config = obj.get_config(column_config_map)?;
                         },
+                        COLUMN_TYPE_SECRET => {
+                            let mut obj = SecretColumn::defaults(&column_config, &None);
+                            column_config = obj.get_config(column_config_map)?;
+                        },
                         COLUMN_TYPE_LONG_TEXT => {
                             let mut obj = LongTextColumn::defaults(&column_config);
                             column_config = obj.get_config(column_config_map)?;
                         },
+                        COLUMN_TYPE_RICH_TEXT => {
+                            let mut obj = RichTextColumn::defaults(&column_config);
+                            column_config = obj.get_config(column_config_map)?;
+                        },
                         COLUMN_TYPE_NUMBER => {
                             let mut obj = NumberColumn::defaults(&column_config);
                             column_config = obj.get_config(column_config_map)?;
@@ -484,10 +663,26 @@ impl ConfigStorageColumn for ColumnConfig {
                             let mut obj = SelectColumn::defaults(&column_config, None);
                             column_config = obj.get_config(column_config_map)?;
                         },
+                        COLUMN_TYPE_TAGS => {
+                            let mut obj = TagsColumn::defaults(&column_config, None);
+                            column_config = obj.get_config(column_config_map)?;
+                        },
+                        COLUMN_TYPE_WORKFLOW => {
+                            let mut obj = WorkflowColumn::defaults(&column_config);
+                            column_config = obj.get_config(column_config_map)?;
+                        },
                         COLUMN_TYPE_DURATION => {
                             let mut obj = DurationColumn::defaults(&column_config);
                             column_config = obj.get_config(column_config_map)?;
                         },
+                        COLUMN_TYPE_RECURRENCE => {
+                            let mut obj = RecurrenceColumn::defaults(&column_config);
+                            column_config = obj.get_config(column_config_map)?;
+                        },
+                        COLUMN_TYPE_LOCATION => {
+                            let mut obj = LocationColumn::defaults(&column_config);
+                            column_config = obj.get_config(column_config_map)?;
+                        },
                         COLUMN_TYPE_CREATED_TIME => {
                             let mut obj = AuditDateColumn::defaults(&column_config);
                             column_config = obj.get_config(column_config_map)?;
@@ -600,6 +795,26 @@ impl ConfigStorageColumn for ColumnConfig {
                             );
                             column_config = obj.get_config(column_config_map)?;
                         },
+                        COLUMN_TYPE_ROLLUP => {
+                            let mut obj = RollupColumn::defaults(
+                                planet_context,
+                                context,
+                                &column_config,
+                                None,
+                                None,
+                            );
+                            column_config = obj.get_config(column_config_map)?;
+                        },
+                        COLUMN_TYPE_LOOKUP => {
+                            let mut obj = LookupColumn::defaults(
+                                planet_context,
+                                context,
+                                &column_config,
+                                None,
+                                None,
+                            );
+                            column_config = obj.get_config(column_config_map)?;
+                        },
                         _ => {}
                     }
                     let _ = &map_columns_by_id.insert(column_id, column_config.clone());
@@ -631,12 +846,21 @@ impl ConfigStorageColumn for ColumnConfig {
                         // let column_id = &propertty_config_.id.clone().unwrap();
                         // let column_id = column_id.clone();
                         let mut column_options: Vec<String> = Vec::new();
-                        for column_item in column_list {
+                        let mut option_colors: Vec<String> = Vec::new();
+                        let mut archived_options: Vec<String> = Vec::new();
+                        for column_item in SelectColumn::sort_options(&column_list) {
                             let column_value = column_item.get(VALUE).unwrap().clone();
+                            let color = column_item.get(SELECT_OPTION_COLOR).cloned();
+                            option_colors.push(color.unwrap_or_default());
+                            if SelectColumn::is_option_archived(&column_item) {
+                                archived_options.push(column_value.clone());
+                            }
                             column_options.push(column_value);
                         }
                         // eprintln!("get_config :: options: {:#?}", &field_options);
                         propertty_config_.options = Some(column_options);
+                        propertty_config_.option_colors = Some(option_colors);
+                        propertty_config_.archived_options = Some(archived_options);
                         // map_columns_by_id.insert(column_id, propertty_config_);
                         columns.push(propertty_config_);
                     }
@@ -680,6 +904,10 @@ impl ConfigStorageColumn for ColumnConfig {
         map.insert(String::from(REQUIRED), required.to_string());
         map.insert(String::from(INDEXED), indexed.to_string());
         map.insert(String::from(MANY), many.to_string());
+        let unique = column_config.unique.unwrap_or_default();
+        if unique {
+            map.insert(String::from(UNIQUE), unique.to_string());
+        }
 
         let is_set = column_config.is_set;
         if is_set.is_some() {
@@ -694,12 +922,29 @@ impl ConfigStorageColumn for ColumnConfig {
             COLUMN_TYPE_SMALL_TEXT => {
                 map = SmallTextColumn::defaults(&propertty_config_).create_config(&map)?;
             },
+            COLUMN_TYPE_SECRET => {
+                map = SecretColumn::defaults(&propertty_config_, &context.secret_key).create_config(&map)?;
+            },
             COLUMN_TYPE_LONG_TEXT => {
                 map = LongTextColumn::defaults(&propertty_config_).create_config(&map)?;
             },
+            COLUMN_TYPE_RICH_TEXT => {
+                map = RichTextColumn::defaults(&propertty_config_).create_config(&map)?;
+            },
             COLUMN_TYPE_SELECT => {
                 map = SelectColumn::defaults(&propertty_config_, None).create_config(&map)?;
             },
+            COLUMN_TYPE_TAGS => {
+                map = TagsColumn::defaults(&propertty_config_, None).create_config(&map)?;
+            },
+            COLUMN_TYPE_WORKFLOW => {
+                map = WorkflowColumn::defaults(&propertty_config_).create_config(
+                    &map,
+                    &properties_map,
+                    &db_folder,
+                    &folder_name
+                )?;
+            },
             COLUMN_TYPE_DATE => {
                 map = DateColumn::defaults(&propertty_config_).create_config(&map)?;
             },
@@ -714,6 +959,12 @@ impl ConfigStorageColumn for ColumnConfig {
             COLUMN_TYPE_DURATION => {
                 map = DurationColumn::defaults(&propertty_config_).create_config(&map)?;
             },
+            COLUMN_TYPE_RECURRENCE => {
+                map = RecurrenceColumn::defaults(&propertty_config_).create_config(&map)?;
+            },
+            COLUMN_TYPE_LOCATION => {
+                map = LocationColumn::defaults(&propertty_config_).create_config(&map)?;
+            },
             COLUMN_TYPE_CREATED_TIME => {
                 map = AuditDateColumn::defaults(&propertty_config_).create_config(&map)?;
             },
@@ -803,6 +1054,32 @@ impl ConfigStorageColumn for ColumnConfig {
                     &map
                 )?;
             },
+            COLUMN_TYPE_ROLLUP => {
+                map = RollupColumn::defaults(
+                    planet_context,
+                    context,
+                    &propertty_config_,
+                    Some(db_folder.clone()),
+                    Some(space_database.clone()),
+                ).create_config(
+                    &map,
+                    &properties_map,
+                    &folder_name
+                )?;
+            },
+            COLUMN_TYPE_LOOKUP => {
+                map = LookupColumn::defaults(
+                    planet_context,
+                    context,
+                    &propertty_config_,
+                    Some(db_folder.clone()),
+                    Some(space_database.clone()),
+                ).create_config(
+                    &map,
+                    &properties_map,
+                    &folder_name
+                )?;
+            },
             _ => {}
         }
         // eprintln!("map_object_db :: finished!!!");
@@ -821,14 +1098,30 @@ impl ConfigStorageColumn for ColumnConfig {
         // let map: BTreeMap<String, Vec<BTreeMap<String, String>>> = BTreeMap::new();
         // select_options and multi_select_options
         let options = column_config.options.unwrap_or_default();
+        let option_colors = column_config.option_colors.unwrap_or_default();
+        let archived_options = column_config.archived_options.unwrap_or_default();
         let mut map: BTreeMap<String, Vec<BTreeMap<String, String>>> = BTreeMap::new();
         let mut select_options: Vec<BTreeMap<String, String>> = Vec::new();
+        let mut order: usize = 0;
         for select_value in options {
             let select_id = generate_id().unwrap();
             let mut map: BTreeMap<String, String> = BTreeMap::new();
             map.insert(String::from("key"), select_id);
-            map.insert(String::from("value"), select_value);
+            map.insert(String::from("value"), select_value.clone());
+            map.insert(SELECT_OPTION_ORDER.to_string(), order.to_string());
+            // Colors follow the order of options
+            if order < option_colors.len() {
+                map.insert(SELECT_OPTION_COLOR.to_string(), option_colors[order].clone());
+            }
+            let mut archived = false;
+            for archived_option in &archived_options {
+                if archived_option.to_lowercase() == select_value.to_lowercase() {
+                    archived = true;
+                }
+            }
+            map.insert(SELECT_OPTION_ARCHIVED.to_string(), archived.to_string());
             select_options.push(map);
+            order += 1;
         }
         if select_options.len() != 0 {
             let column_name = column_config.name.unwrap_or_default();
@@ -864,6 +1157,8 @@ pub struct CreateFolderCompiledStmt {
     pub name: ColumnConfig,
     pub columns: Option<Vec<ColumnConfig>>,
     pub sub_folders: Option<Vec<SubFolderConfig>>,
+    pub unique_keys: Option<Vec<UniqueKeyConfig>>,
+    pub checks: Option<Vec<CheckConfig>>,
 }
 
 impl CreateFolderCompiledStmt {
@@ -879,6 +1174,8 @@ impl CreateFolderCompiledStmt {
             name: name_conf,
             columns: None,
             sub_folders: None,
+            unique_keys: None,
+            checks: None,
             folder_name: String::from(""),
         };
         return config
@@ -966,6 +1263,28 @@ pub fn process_column(
                         column.options = Some(options_string);
                     }
                 }
+                if *&with_options.contains_key(WITH_OPTION_COLORS) {
+                    let option_colors = with_options.get(WITH_OPTION_COLORS);
+                    if option_colors.is_some() {
+                        let option_colors = option_colors.unwrap().clone();
+                        let mut colors: Vec<String> = Vec::new();
+                        for option_color in option_colors {
+                            colors.push(option_color.value);
+                        }
+                        column.option_colors = Some(colors);
+                    }
+                }
+                if *&with_options.contains_key(WITH_ARCHIVED_OPTIONS) {
+                    let archived_options = with_options.get(WITH_ARCHIVED_OPTIONS);
+                    if archived_options.is_some() {
+                        let archived_options = archived_options.unwrap().clone();
+                        let mut archived: Vec<String> = Vec::new();
+                        for archived_option in archived_options {
+                            archived.push(archived_option.value);
+                        }
+                        column.archived_options = Some(archived);
+                    }
+                }
                 if *&with_options.contains_key(WITH_NUMBER_DECIMALS) {
                     let number_decimals = &with_options_obj.get_single_value(
                         WITH_NUMBER_DECIMALS
@@ -1031,7 +1350,7 @@ pub fn process_column(
                     );
                     // eprintln!("process_column :: formula: {}", formula);
                     if long_text.is_some() {
-                        let long_text = long_text.unwrap();
+                        let long_text = long_text.clone().unwrap();
                         let has_placeholder = DataValueLongText::has_placeholder(formula);
                         if has_placeholder {
                             let map = long_text.map;
@@ -1084,6 +1403,46 @@ pub fn process_column(
                     let time_format: i8 = FromStr::from_str(time_format.as_str()).unwrap();
                     column.time_format = Some(time_format);
                 }
+                if *&with_options.contains_key(WITH_DURATION_FORMAT) {
+                    let duration_format = &with_options_obj.get_single_value(
+                        WITH_DURATION_FORMAT
+                    );
+                    let duration_format = duration_format.as_str();
+                    match duration_format {
+                        DURATION_FORMAT_CLOCK => {
+                            column.duration_format = Some(DurationFormat::Clock);
+                        },
+                        DURATION_FORMAT_COMPACT => {
+                            column.duration_format = Some(DurationFormat::Compact);
+                        },
+                        DURATION_FORMAT_LONG => {
+                            column.duration_format = Some(DurationFormat::Long);
+                        },
+                        DURATION_FORMAT_ISO => {
+                            column.duration_format = Some(DurationFormat::ISO);
+                        },
+                        _ => {
+                            errors.push(
+                                PlanetError::new(
+                                    500, 
+                                    Some(tr!("Duration format not supported: {}", duration_format)),
+                                )
+                            );
+                        },
+                    }
+                }
+                if *&with_options.contains_key(WITH_CURRENCY) {
+                    let currency = &with_options_obj.get_single_value(
+                        WITH_CURRENCY
+                    );
+                    column.currency = Some(currency.to_uppercase());
+                }
+                if *&with_options.contains_key(WITH_TIME_ZONE) {
+                    let time_zone = &with_options_obj.get_single_value(
+                        WITH_TIME_ZONE
+                    );
+                    column.time_zone = Some(time_zone.clone());
+                }
                 if *&with_options.contains_key(WITH_LINKED_FOLDER) {
                     let linked_folder = &with_options_obj.get_single_value(
                         WITH_LINKED_FOLDER
@@ -1100,6 +1459,27 @@ pub fn process_column(
                         column.delete_on_link_drop = Some(false);
                     }
                 }
+                if *&with_options.contains_key(WITH_ON_DELETE) {
+                    let on_delete = &with_options_obj.get_single_value(
+                        WITH_ON_DELETE
+                    );
+                    let on_delete = on_delete.as_str();
+                    match on_delete {
+                        ON_DELETE_CASCADE | ON_DELETE_SET_NULL | ON_DELETE_RESTRICT => {
+                            column.on_delete = Some(on_delete.to_string());
+                        },
+                        _ => {
+                            errors.push(
+                                PlanetError::new(
+                                    500, 
+                                    Some(
+                                        tr!("Statement compile error: OnDelete must be one of Cascade, SetNull or Restrict, found \"{}\".", on_delete)
+                                    ),
+                                )
+                            );
+                        }
+                    }
+                }
                 if *&with_options.contains_key(WITH_LINK_COLUMN) {
                     let link_column = &with_options_obj.get_single_value(
                         WITH_LINK_COLUMN
@@ -1118,6 +1498,99 @@ pub fn process_column(
                     );
                     column.sequence = Some(sequence.clone());
                 }
+                if *&with_options.contains_key(WITH_SEQUENCE_FORMAT) {
+                    let sequence_format = &with_options_obj.get_single_value(
+                        WITH_SEQUENCE_FORMAT
+                    );
+                    column.sequence_format = Some(sequence_format.clone());
+                }
+                if *&with_options.contains_key(WITH_SEQUENCE_START) {
+                    let sequence_start = &with_options_obj.get_single_value(
+                        WITH_SEQUENCE_START
+                    );
+                    column.sequence_start = Some(sequence_start.clone());
+                }
+                if *&with_options.contains_key(WITH_SEQUENCE_STEP) {
+                    let sequence_step = &with_options_obj.get_single_value(
+                        WITH_SEQUENCE_STEP
+                    );
+                    column.sequence_step = Some(sequence_step.clone());
+                }
+                if *&with_options.contains_key(WITH_SEQUENCE_RESET) {
+                    let sequence_reset = &with_options_obj.get_single_value(
+                        WITH_SEQUENCE_RESET
+                    );
+                    column.sequence_reset = Some(sequence_reset.clone());
+                }
+                if *&with_options.contains_key(WITH_DEFAULT_COUNTRY) {
+                    let default_country = &with_options_obj.get_single_value(
+                        WITH_DEFAULT_COUNTRY
+                    );
+                    column.default_country = Some(default_country.clone());
+                }
+                if *&with_options.contains_key(WITH_PHONE_FORMAT) {
+                    let phone_format = &with_options_obj.get_single_value(
+                        WITH_PHONE_FORMAT
+                    );
+                    column.phone_format = Some(phone_format.clone());
+                }
+                if *&with_options.contains_key(WITH_STRIP_TRACKING) {
+                    let strip_tracking = &with_options_obj.get_single_value(
+                        WITH_STRIP_TRACKING
+                    );
+                    if *strip_tracking == String::from("True") || strip_tracking.to_lowercase() == TRUE {
+                        column.strip_tracking = Some(true);
+                    } else {
+                        column.strip_tracking = Some(false);
+                    }
+                }
+                if *&with_options.contains_key(WITH_OUTPUT) {
+                    let output = &with_options_obj.get_single_value(
+                        WITH_OUTPUT
+                    );
+                    column.output = Some(output.clone());
+                }
+                if *&with_options.contains_key(WITH_STATES) {
+                    let states = with_options.get(WITH_STATES);
+                    if states.is_some() {
+                        let states = states.unwrap().clone();
+                        let mut states_string: Vec<String> = Vec::new();
+                        for state in states {
+                            states_string.push(state.value);
+                        }
+                        column.states = Some(states_string);
+                    }
+                }
+                if *&with_options.contains_key(WITH_TRANSITIONS) {
+                    // Transitions={New/Review|"""Review/Approved WHEN {Score} >= 5"""}
+                    let transitions = with_options.get(WITH_TRANSITIONS);
+                    if transitions.is_some() {
+                        let transitions = transitions.unwrap().clone();
+                        let mut transitions_string: Vec<String> = Vec::new();
+                        for transition in transitions {
+                            let mut transition = transition.value;
+                            if long_text.is_some() && DataValueLongText::has_placeholder(&transition) {
+                                let long_text = long_text.clone().unwrap();
+                                let source = long_text.map.get(&transition);
+                                if source.is_some() {
+                                    transition = source.unwrap().clone();
+                                }
+                            }
+                            transitions_string.push(transition);
+                        }
+                        column.transitions = Some(transitions_string);
+                    }
+                }
+                if *&with_options.contains_key(WITH_TRANSLATABLE) {
+                    let translatable = &with_options_obj.get_single_value(
+                        WITH_TRANSLATABLE
+                    );
+                    if *translatable == String::from("True") || translatable.to_lowercase() == TRUE {
+                        column.translatable = Some(true);
+                    } else {
+                        column.translatable = Some(false);
+                    }
+                }
                 if *&with_options.contains_key(WITH_MAX_LENGTH) {
                     let max_length = &with_options_obj.get_single_value(
                         WITH_MAX_LENGTH
@@ -1150,253 +1623,1412 @@ pub fn process_column(
                         column.content_types = Some(content_types_str);
                     }
                 }
+                if *&with_options.contains_key(WITH_THUMBNAILS) {
+                    let thumbnails = with_options.get(
+                        WITH_THUMBNAILS
+                    );
+                    if thumbnails.is_some() {
+                        let thumbnails = thumbnails.unwrap().clone();
+                        let mut sizes: Vec<String> = Vec::new();
+                        for thumbnail in thumbnails {
+                            let size: u32 = FromStr::from_str(thumbnail.value.as_str()).unwrap_or(0);
+                            if size == 0 || size > THUMBNAIL_SIZE_MAX {
+                                errors.push(
+                                    PlanetError::new(
+                                        500, 
+                                        Some(tr!(
+                                            "Statement compile error: Thumbnail size \"{}\" not valid, use pixels from 1 to {}.", 
+                                            &thumbnail.value, THUMBNAIL_SIZE_MAX
+                                        )),
+                                    )
+                                );
+                                continue
+                            }
+                            sizes.push(thumbnail.value);
+                        }
+                        column.thumbnails = Some(sizes);
+                    }
+                }
+                if *&with_options.contains_key(WITH_LOCATION_COLUMN) {
+                    let location_column = &with_options_obj.get_single_value(
+                        WITH_LOCATION_COLUMN
+                    );
+                    column.location_column = Some(location_column.clone());
+                }
                 if *&with_options.contains_key(WITH_MODE) {
                     let mode = &with_options_obj.get_single_value(
                         WITH_MODE
                     );
                     column.mode = Some(mode.clone());
                 }
+                if *&with_options.contains_key(WITH_OBJECT_SCHEMA) {
+                    let object_schema = &with_options_obj.get_single_value(
+                        WITH_OBJECT_SCHEMA
+                    );
+                    let mut object_schema_source = object_schema.clone();
+                    if long_text.is_some() && DataValueLongText::has_placeholder(object_schema) {
+                        let long_text = long_text.clone().unwrap();
+                        let source = long_text.map.get(object_schema);
+                        if source.is_some() {
+                            object_schema_source = source.unwrap().clone();
+                        }
+                    }
+                    column.object_schema = Some(object_schema_source);
+                }
+                if *&with_options.contains_key(WITH_INDEXED_PATHS) {
+                    let indexed_paths = with_options.get(
+                        WITH_INDEXED_PATHS
+                    );
+                    if indexed_paths.is_some() {
+                        let indexed_paths = indexed_paths.unwrap().clone();
+                        let mut paths: Vec<String> = Vec::new();
+                        for indexed_path in indexed_paths {
+                            paths.push(indexed_path.value);
+                        }
+                        column.indexed_paths = Some(paths);
+                    }
+                }
+                if *&with_options.contains_key(WITH_UNIQUE) {
+                    let unique = &with_options_obj.get_single_value(
+                        WITH_UNIQUE
+                    );
+                    if *unique == String::from("True") || unique.to_lowercase() == TRUE {
+                        column.unique = Some(true);
+                    } else {
+                        column.unique = Some(false);
+                    }
+                }
             }
         }
     }
     // Validations for options for column types that are required
     let column_type = column_type.as_str();
-    if column_type == COLUMN_TYPE_FORMULA {
-        let has_formula = column.formula.is_some();
-        let has_formula_format = column.formula_format.is_some();
-        if !has_formula {
-            errors.push(
-                PlanetError::new(
-                    500, 
-                    Some(
-                        tr!("Statement compile error: Formula column requires formula string with \"Formula\" option.")
-                    ),
-                )
-            );
-        }
-        if !has_formula_format {
-            column.formula_format = Some(FORMULA_FORMAT_TEXT.to_string());
-        }
-    } else if column_type == COLUMN_TYPE_REFERENCE {
-        let has_link_column = column.link_column.is_some();
-        let has_remote_column = column.remote_column.is_some();
-        if !has_link_column {
-            errors.push(
-                PlanetError::new(
-                    500, 
-                    Some(
-                        tr!("Statement compile error: You need to define the Link column to use for the reference.")
-                    ),
-                )
-            );
-        }
-        if !has_remote_column {
-            errors.push(
-                PlanetError::new(
-                    500, 
-                    Some(
-                        tr!("Statement compile error: Remote column is required for references.")
-                    ),
-                )
-            );
-        }
-    }
-    if errors.len() > 0 {
-        return Err(errors)
-    }
-    return Ok(column)
-}
-
-#[derive(Debug, Clone)]
-pub struct CreateFolderStatement {
-}
-
-impl<'gb> StatementCompiler<'gb, CreateFolderCompiledStmt> for CreateFolderStatement {
-
-    fn compile(
-        &self, 
-        statement_text: &String
-    ) -> Result<CreateFolderCompiledStmt, Vec<PlanetError>> {
-        let mut compiled_statement = CreateFolderCompiledStmt::defaults(
-            None
-        );
-        let expr = &RE_CREATE_FOLDER_MAIN;
-        let check = expr.is_match(&statement_text);
-        let mut errors: Vec<PlanetError> = Vec::new();
-        if !check {
-            let error = PlanetError::new(
+    let unique = column.unique.unwrap_or_default();
+    if unique && !ALLOWED_UNIQUE_COLUMN_TYPES.contains(&column_type) {
+        errors.push(
+            PlanetError::new(
                 500, 
                 Some(
-                    tr!("Create folder syntax not valid.")
+                    tr!("Statement compile error: Option \"{}\" not allowed for column type \"{}\".", 
+                        WITH_UNIQUE, column_type
+                    )
                 ),
-            );
-            errors.push(error);
-            return Err(errors)
-        }
-        let captures = expr.captures(&statement_text);
-        let config: &str;
-        if captures.is_none() {
-            let error = PlanetError::new(
+            )
+        );
+    }
+    if column.indexed_paths.is_some() && column_type != COLUMN_TYPE_OBJECT {
+        errors.push(
+            PlanetError::new(
                 500, 
                 Some(
-                    tr!("Create folder syntax not valid.")
+                    tr!("Statement compile error: Option \"{}\" not allowed for column type \"{}\".", 
+                        WITH_INDEXED_PATHS, column_type
+                    )
                 ),
-            );
-            errors.push(error);
-            return Err(errors)
-        }
-        let captures = captures.unwrap();
-        let folder_name = captures.name("FolderName").unwrap().as_str();
-        compiled_statement.folder_name = folder_name.to_string();
-        config = captures.name("Config").unwrap().as_str();
-        // eprintln!("CreateFolderStatement.compile :: config: {}", config);
-        let long_text = DataValueLongText::defaults(
-            &config.to_string()
+            )
         );
-        if long_text.is_err() {
+    }
+    if column.duration_format.is_some() && column_type != COLUMN_TYPE_DURATION {
+        errors.push(
+            PlanetError::new(
+                500, 
+                Some(
+                    tr!("Statement compile error: Option \"{}\" not allowed for column type \"{}\".", 
+                        WITH_DURATION_FORMAT, column_type
+                    )
+                ),
+            )
+        );
+    }
+    if column.option_colors.is_some() && column_type != COLUMN_TYPE_SELECT {
+        errors.push(
+            PlanetError::new(
+                500, 
+                Some(
+                    tr!("Statement compile error: Option \"{}\" not allowed for column type \"{}\".", 
+                        WITH_OPTION_COLORS, column_type
+                    )
+                ),
+            )
+        );
+    }
+    if column.archived_options.is_some() && column_type != COLUMN_TYPE_SELECT {
+        errors.push(
+            PlanetError::new(
+                500, 
+                Some(
+                    tr!("Statement compile error: Option \"{}\" not allowed for column type \"{}\".", 
+                        WITH_ARCHIVED_OPTIONS, column_type
+                    )
+                ),
+            )
+        );
+    }
+    if column.sequence_format.is_some() && column_type != COLUMN_TYPE_GENERATE_NUMBER {
+        errors.push(
+            PlanetError::new(
+                500, 
+                Some(
+                    tr!("Statement compile error: Option \"{}\" not allowed for column type \"{}\".", 
+                        WITH_SEQUENCE_FORMAT, column_type
+                    )
+                ),
+            )
+        );
+    }
+    if column.sequence_start.is_some() && column_type != COLUMN_TYPE_GENERATE_NUMBER {
+        errors.push(
+            PlanetError::new(
+                500, 
+                Some(
+                    tr!("Statement compile error: Option \"{}\" not allowed for column type \"{}\".", 
+                        WITH_SEQUENCE_START, column_type
+                    )
+                ),
+            )
+        );
+    }
+    if column.sequence_step.is_some() && column_type != COLUMN_TYPE_GENERATE_NUMBER {
+        errors.push(
+            PlanetError::new(
+                500, 
+                Some(
+                    tr!("Statement compile error: Option \"{}\" not allowed for column type \"{}\".", 
+                        WITH_SEQUENCE_STEP, column_type
+                    )
+                ),
+            )
+        );
+    }
+    if column.sequence_reset.is_some() && column_type != COLUMN_TYPE_GENERATE_NUMBER {
+        errors.push(
+            PlanetError::new(
+                500, 
+                Some(
+                    tr!("Statement compile error: Option \"{}\" not allowed for column type \"{}\".", 
+                        WITH_SEQUENCE_RESET, column_type
+                    )
+                ),
+            )
+        );
+    }
+    if column.default_country.is_some() && column_type != COLUMN_TYPE_PHONE {
+        errors.push(
+            PlanetError::new(
+                500, 
+                Some(
+                    tr!("Statement compile error: Option \"{}\" not allowed for column type \"{}\".", 
+                        WITH_DEFAULT_COUNTRY, column_type
+                    )
+                ),
+            )
+        );
+    }
+    if column.phone_format.is_some() && column_type != COLUMN_TYPE_PHONE {
+        errors.push(
+            PlanetError::new(
+                500, 
+                Some(
+                    tr!("Statement compile error: Option \"{}\" not allowed for column type \"{}\".", 
+                        WITH_PHONE_FORMAT, column_type
+                    )
+                ),
+            )
+        );
+    }
+    if column.strip_tracking.is_some() && column_type != COLUMN_TYPE_URL {
+        errors.push(
+            PlanetError::new(
+                500, 
+                Some(
+                    tr!("Statement compile error: Option \"{}\" not allowed for column type \"{}\".", 
+                        WITH_STRIP_TRACKING, column_type
+                    )
+                ),
+            )
+        );
+    }
+    if column.output.is_some() && column_type != COLUMN_TYPE_RICH_TEXT {
+        errors.push(
+            PlanetError::new(
+                500, 
+                Some(
+                    tr!("Statement compile error: Option \"{}\" not allowed for column type \"{}\".", 
+                        WITH_OUTPUT, column_type
+                    )
+                ),
+            )
+        );
+    }
+    if (column.states.is_some() || column.transitions.is_some()) && column_type != COLUMN_TYPE_WORKFLOW {
+        errors.push(
+            PlanetError::new(
+                500, 
+                Some(
+                    tr!("Statement compile error: Options \"{}\" and \"{}\" not allowed for column type \"{}\".", 
+                        WITH_STATES, WITH_TRANSITIONS, column_type
+                    )
+                ),
+            )
+        );
+    }
+    if column_type == COLUMN_TYPE_WORKFLOW && column.states.is_none() {
+        errors.push(
+            PlanetError::new(
+                500, 
+                Some(
+                    tr!("Statement compile error: Option \"{}\" is required for column type \"{}\".", 
+                        WITH_STATES, column_type
+                    )
+                ),
+            )
+        );
+    }
+    if (column.thumbnails.is_some() || column.location_column.is_some()) && column_type != COLUMN_TYPE_FILE {
+        errors.push(
+            PlanetError::new(
+                500, 
+                Some(
+                    tr!("Statement compile error: Options \"{}\" and \"{}\" not allowed for column type \"{}\".", 
+                        WITH_THUMBNAILS, WITH_LOCATION_COLUMN, column_type
+                    )
+                ),
+            )
+        );
+    }
+    if column.translatable.is_some() && column_type != COLUMN_TYPE_SMALL_TEXT && column_type != COLUMN_TYPE_LONG_TEXT {
+        errors.push(
+            PlanetError::new(
+                500, 
+                Some(
+                    tr!("Statement compile error: Option \"{}\" not allowed for column type \"{}\".", 
+                        WITH_TRANSLATABLE, column_type
+                    )
+                ),
+            )
+        );
+    }
+    if column.currency.is_some() && column_type != COLUMN_TYPE_CURRENCY && column_type != COLUMN_TYPE_STATS && 
+        column_type != COLUMN_TYPE_ROLLUP {
+        errors.push(
+            PlanetError::new(
+                500, 
+                Some(
+                    tr!("Statement compile error: Option \"{}\" not allowed for column type \"{}\".", 
+                        WITH_CURRENCY, column_type
+                    )
+                ),
+            )
+        );
+    }
+    if column_type == COLUMN_TYPE_SECRET && 
+        (column.indexed.unwrap_or_default() || column.unique.unwrap_or_default()) {
+        errors.push(
+            PlanetError::new(
+                500, 
+                Some(
+                    tr!("Statement compile error: Secret columns cannot be indexed or unique.")
+                ),
+            )
+        );
+    }
+    if column.time_zone.is_some() && column_type != COLUMN_TYPE_DATE {
+        errors.push(
+            PlanetError::new(
+                500, 
+                Some(
+                    tr!("Statement compile error: Option \"{}\" not allowed for column type \"{}\".", 
+                        WITH_TIME_ZONE, column_type
+                    )
+                ),
+            )
+        );
+    }
+    if column.object_schema.is_some() && column_type != COLUMN_TYPE_OBJECT {
+        errors.push(
+            PlanetError::new(
+                500, 
+                Some(
+                    tr!("Statement compile error: Option \"{}\" not allowed for column type \"{}\".", 
+                        WITH_OBJECT_SCHEMA, column_type
+                    )
+                ),
+            )
+        );
+    }
+    if column_type == COLUMN_TYPE_FORMULA {
+        let has_formula = column.formula.is_some();
+        let has_formula_format = column.formula_format.is_some();
+        if !has_formula {
+            errors.push(
+                PlanetError::new(
+                    500, 
+                    Some(
+                        tr!("Statement compile error: Formula column requires formula string with \"Formula\" option.")
+                    ),
+                )
+            );
+        }
+        if !has_formula_format {
+            column.formula_format = Some(FORMULA_FORMAT_TEXT.to_string());
+        }
+    } else if column_type == COLUMN_TYPE_REFERENCE {
+        let has_link_column = column.link_column.is_some();
+        let has_remote_column = column.remote_column.is_some();
+        if !has_link_column {
+            errors.push(
+                PlanetError::new(
+                    500, 
+                    Some(
+                        tr!("Statement compile error: You need to define the Link column to use for the reference.")
+                    ),
+                )
+            );
+        }
+        if !has_remote_column {
+            errors.push(
+                PlanetError::new(
+                    500, 
+                    Some(
+                        tr!("Statement compile error: Remote column is required for references.")
+                    ),
+                )
+            );
+        }
+    } else if column_type == COLUMN_TYPE_ROLLUP || column_type == COLUMN_TYPE_LOOKUP {
+        let has_link_column = column.link_column.is_some();
+        let has_remote_column = column.remote_column.is_some();
+        let has_formula = column.formula.is_some();
+        if !has_link_column {
+            errors.push(
+                PlanetError::new(
+                    500, 
+                    Some(
+                        tr!("Statement compile error: You need to define the Link column for \"{}\" columns.", column_type)
+                    ),
+                )
+            );
+        }
+        if column_type == COLUMN_TYPE_ROLLUP {
+            if !has_remote_column && !has_formula {
+                errors.push(
+                    PlanetError::new(
+                        500, 
+                        Some(
+                            tr!("Statement compile error: Rollup needs a remote column or a formula over linked items.")
+                        ),
+                    )
+                );
+            }
+            if column.stats_function.is_none() {
+                errors.push(
+                    PlanetError::new(
+                        500, 
+                        Some(
+                            tr!("Statement compile error: Rollup needs the stats function with \"StatsFunction\" option.")
+                        ),
+                    )
+                );
+            }
+            if has_formula && column.formula_format.is_none() {
+                column.formula_format = Some(FORMULA_FORMAT_NUMBER.to_string());
+            }
+        } else if !has_remote_column {
+            errors.push(
+                PlanetError::new(
+                    500, 
+                    Some(
+                        tr!("Statement compile error: Remote column is required for lookups.")
+                    ),
+                )
+            );
+        }
+    }
+    if errors.len() > 0 {
+        return Err(errors)
+    }
+    return Ok(column)
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateFolderStatement {
+}
+
+impl<'gb> StatementCompiler<'gb, CreateFolderCompiledStmt> for CreateFolderStatement {
+
+    fn compile(
+        &self, 
+        statement_text: &String
+    ) -> Result<CreateFolderCompiledStmt, Vec<PlanetError>> {
+        let mut compiled_statement = CreateFolderCompiledStmt::defaults(
+            None
+        );
+        let expr = &RE_CREATE_FOLDER_MAIN;
+        let check = expr.is_match(&statement_text);
+        let mut errors: Vec<PlanetError> = Vec::new();
+        if !check {
+            let error = PlanetError::new(
+                500, 
+                Some(
+                    tr!("Create folder syntax not valid.")
+                ),
+            );
+            errors.push(error);
+            return Err(errors)
+        }
+        let captures = expr.captures(&statement_text);
+        let config: &str;
+        if captures.is_none() {
+            let error = PlanetError::new(
+                500, 
+                Some(
+                    tr!("Create folder syntax not valid.")
+                ),
+            );
+            errors.push(error);
+            return Err(errors)
+        }
+        let captures = captures.unwrap();
+        let folder_name = captures.name("FolderName").unwrap().as_str();
+        compiled_statement.folder_name = folder_name.to_string();
+        config = captures.name("Config").unwrap().as_str();
+        // eprintln!("CreateFolderStatement.compile :: config: {}", config);
+        let long_text = DataValueLongText::defaults(
+            &config.to_string()
+        );
+        if long_text.is_err() {
+            let error = PlanetError::new(
+                500, 
+                Some(
+                    tr!("Create folder syntax not valid.")
+                ),
+            );
+            errors.push(error);
+            return Err(errors)
+        }
+        let long_text = long_text.unwrap();
+        let statement_text_processed = long_text.clone().parsed_text;
+        // eprintln!("CreateFolderStatement.compile :: statement_text_processed: {}", &statement_text_processed);
+        let expr = &RE_CREATE_FOLDER_CONFIG;
+        let items = expr.captures_iter(&statement_text_processed.as_str());
+        let mut sub_folders: Vec<SubFolderConfig> = Vec::new();
+        let mut columns: Vec<ColumnConfig> = Vec::new();
+        let mut unique_keys: Vec<UniqueKeyConfig> = Vec::new();
+        let mut checks: Vec<CheckConfig> = Vec::new();
+        for item in items {
+            let name_config = item.name("NameConfig");
+            let unique_key_name = item.name("UniqueKeyName");
+            let check_name = item.name("CheckName");
+            let sub_folder_name = item.name("SubFolderName");
+            let sub_folder_name_alt = item.name("SubFolderNameAlt");
+            let column = item.name("Column");
+            let column_type = item.name("ColumnType");
+            let language = item.name("Language");
+            let search_options = item.name("SearchRelevanceOptions");
+            if language.is_some() {
+                let language_str = language.unwrap().as_str();
+                let language = LanguageConfig{
+                    default: language_str.to_string()
+                };
+                compiled_statement.language = Some(language);
+            }
+            if sub_folder_name.is_some() || sub_folder_name_alt.is_some() {
+                // Sub folders
+                let mut sub_folder_name_: &str = "";
+                if sub_folder_name.is_some() {
+                    sub_folder_name_ = sub_folder_name.unwrap().as_str();
+                }
+                if sub_folder_name_alt.is_some() {
+                    sub_folder_name_ = sub_folder_name_alt.unwrap().as_str();
+                }
+                let sub_folder_options = item.name("SubFolderOptions");
+                let mut sub_folder_obj: SubFolderConfig = SubFolderConfig{
+                    id: generate_id(),
+                    name: Some(sub_folder_name_.to_string()),
+                    parent: None,
+                    parent_id: None,
+                    version: SubFolderConfig::version(),
+                };
+                if sub_folder_options.is_some() {
+                    let sub_folder_options = sub_folder_options.unwrap().as_str();
+                    let result = WithOptions::defaults(
+                        &sub_folder_options.to_string()
+                    );
+                    if result.is_err() {
+                        let error = result.unwrap_err();
+                        errors.push(error);
+                    } else {
+                        let with_options_obj = result.unwrap();
+                        let with_options = &with_options_obj.options;
+                        if *&with_options.contains_key(WITH_PARENT) {
+                            let parent = &with_options_obj.get_single_value(
+                                WITH_PARENT
+                            );
+                            sub_folder_obj.parent = Some(parent.clone());
+                        }
+                    }
+                }
+                sub_folders.push(sub_folder_obj);
+            } else if check_name.is_some() {
+                // Check rule: CHECK "Rule Name" """{End Date} >= {Start Date}"""
+                let check_name = check_name.unwrap().as_str().trim();
+                let check_formula = item.name("CheckFormula").unwrap().as_str().to_string();
+                let mut formula = check_formula.replace("\"", "");
+                if DataValueLongText::has_placeholder(&check_formula) {
+                    let formula_source = long_text.map.get(&check_formula);
+                    if formula_source.is_some() {
+                        formula = formula_source.unwrap().clone();
+                    }
+                }
+                checks.push(
+                    CheckConfig{
+                        name: check_name.to_string(),
+                        formula: formula.trim().to_string(),
+                    }
+                );
+            } else if unique_key_name.is_some() {
+                // Composite unique key: UNIQUE KEY "Name" ("Column A", "Column B")
+                let unique_key_name = unique_key_name.unwrap().as_str().trim();
+                let unique_key_columns = item.name("UniqueKeyColumns").unwrap().as_str();
+                let mut key_columns: Vec<String> = Vec::new();
+                for key_column in unique_key_columns.split(",") {
+                    let key_column = key_column.replace("\"", "");
+                    let key_column = key_column.trim();
+                    if key_column != "" {
+                        key_columns.push(key_column.to_string());
+                    }
+                }
+                if key_columns.len() < 2 {
+                    errors.push(
+                        PlanetError::new(
+                            500, 
+                            Some(
+                                tr!("Unique key \"{}\" needs at least two columns.", unique_key_name)
+                            ),
+                        )
+                    );
+                    continue
+                }
+                unique_keys.push(
+                    UniqueKeyConfig{
+                        name: unique_key_name.to_string(),
+                        columns: key_columns,
+                    }
+                );
+            } else if name_config.is_some() {
+                // Name config, should be first item in columns
+                let column_type = name_config.unwrap().as_str();
+                let mut name = compiled_statement.name.clone();
+                name.name = Some(String::from("Name"));
+                name.id = generate_id();
+                name.column_type = Some(column_type.to_string());
+                compiled_statement.name = name;
+            } else if search_options.is_some() {
+                let search_options = search_options.unwrap().as_str();
+                let result = WithOptions::defaults(
+                    &search_options.to_string()
+                );
+                if result.is_err() {
+                    let error = result.unwrap_err();
+                    errors.push(error);
+                } else {
+                    let with_options_obj = result.unwrap();
+                    let with_options = &with_options_obj.options;
+                    let mut column_relevance: BTreeMap<String, u8> = BTreeMap::new();
+                    for (k, v) in with_options {
+                        let value = v.clone()[0].clone().value;
+                        let value_int: u8 = FromStr::from_str(value.as_str()).unwrap();
+                        let key = k.clone();
+                        column_relevance.insert(key, value_int);
+                    }
+                    // Validate column relevance
+                    let validate = validate_column_relevance(&column_relevance);
+                    if validate.is_err() {
+                        errors.push(
+                            PlanetError::new(
+                                500, 
+                                Some(
+                                    tr!("Validation error for column relevance: \"{}\".", &search_options)
+                                ),
+                            )
+                        );
+                    }
+                    let text_search_config = TextSearchConfig{
+                        column_relevance: column_relevance
+                    };
+                    compiled_statement.text_search = Some(text_search_config);
+                }
+            } else if column.is_some() && column_type.is_some() {
+                // Column
+                let column_str = column.unwrap().as_str();
+                let column_type = column_type.unwrap().as_str();
+                let result = process_column(
+                    column_str, 
+                    column_type, 
+                    &item,
+                    Some(long_text.clone())
+                );
+                if result.is_err() {
+                    let errors = result.unwrap_err();
+                    return Err(errors)
+                }
+                if result.is_ok() {
+                    let column = result.unwrap();
+                    columns.push(column);
+                }
+            }
+        }
+        compiled_statement.sub_folders = Some(sub_folders);
+        compiled_statement.columns = Some(columns);
+        if unique_keys.len() > 0 {
+            compiled_statement.unique_keys = Some(unique_keys);
+        }
+        if checks.len() > 0 {
+            compiled_statement.checks = Some(checks);
+        }
+        if errors.len() > 0 {
+            return Err(errors)
+        }
+        eprintln!("CreateFolder.compile :: compiled_statement: {:#?}", &compiled_statement);
+        return Ok(compiled_statement.clone())
+    }
+
+}
+
+impl<'gb> Statement<'gb> for CreateFolderStatement {
+
+    fn run(
+        &self,
+        env: &'gb Environment<'gb>,
+        space_database: &SpaceDatabase,
+        statement_text: &String,
+    ) -> Result<Vec<yaml_rust::Yaml>, Vec<PlanetError>> {
+        // run would return a generic Yaml object which has the statement response
+        // yaml_rust::Yaml
+        let space_database = space_database.clone();
+        let context = env.context;
+        let planet_context = env.planet_context;
+        let t_1 = Instant::now();
+        let mut errors: Vec<PlanetError> = Vec::new();
+        let statement = self.compile(statement_text);
+        if statement.is_err() {
+            let errors = statement.unwrap_err();
+            return Err(errors)
+        }
+        let statement = statement.unwrap();
+        let home_dir = planet_context.home_path.clone();
+        let account_id = context.account_id.clone().unwrap_or_default();
+        let space_id = context.space_id;
+        let site_id = context.site_id.clone();
+
+        let result: Result<TreeFolder, PlanetError> = TreeFolder::defaults(
+            space_database.connection_pool.clone(),
+            Some(home_dir.unwrap_or_default().as_str()),
+            Some(&account_id),
+            Some(space_id),
+            site_id,
+        );
+        match result {
+            Ok(_) => {
+                // let command = self.config.command.clone().unwrap_or_default();
+                // let expr = Regex::new(r#"(CREATE FOLDER) "(?P<folder_name>[a-zA-Z0-9_ ]+)"#).unwrap();
+                // let table_name_match = expr.captures(&command).unwrap();
+                // let folder_name = &table_name_match["folder_name"].to_string();
+                let folder_name = statement.folder_name;
+
+                // routing parameters
+                let account_id = Some(context.account_id.clone().unwrap_or_default().to_string());
+                let site_id = context.site_id.clone();
+                let space_id = context.space_id;
+
+                // db folder options with language data
+                let db_folder: TreeFolder = result.unwrap();
+                let mut data: BTreeMap<String, Vec<BTreeMap<String, String>>> = BTreeMap::new();
+                // config data
+                let mut columns = statement.columns.unwrap().clone();
+                let sub_folders = statement.sub_folders;
+                let mut column_ids: Vec<BTreeMap<String, String>> = Vec::new();
+                let mut columns_list: Vec<BTreeMap<String, String>> = Vec::new();
+
+                // validate and clean defaults for language and text_search
+                // TODO: Place this into a validation operation for the entity
+                let language = statement.language;
+                if language.is_some() {
+                    let language = language.unwrap();
+                    let language_default = language.default;
+                    data.insert(LANGUAGE_DEFAULT.to_string(), build_value_list(&language_default));
+                } else {
+                    data.insert(LANGUAGE_DEFAULT.to_string(), build_value_list(&LANGUAGE_ENGLISH.to_string()));
+                }
+                let text_search = statement.text_search;
+                if text_search.is_some() {
+                    let text_search = text_search.unwrap();
+                    let mut column_relevance = text_search.column_relevance;
+                    let mut my_map: BTreeMap<String, String> = BTreeMap::new();
+                    // Include Text rule if not found
+                    let mut has_text = false;
+                    for (column_relevance_item, _) in column_relevance.clone() {
+                        if column_relevance_item == String::from(TEXT_COLUMN) {
+                            has_text = true;
+                        }
+                    }
+                    if !has_text {
+                        let relevance: u8 = 1;
+                        column_relevance.insert(TEXT_COLUMN.to_string(), relevance);
+                    }
+                    for (column_relevance_item, relevance) in column_relevance {
+                        let mut has_column = false;
+                        for column in columns.clone().iter() {
+                            let column = column.clone();
+                            let column_name = column.name.unwrap_or_default();
+                            if column_name.to_lowercase() == column_relevance_item.to_lowercase() {
+                                has_column = true;
+                            }
+                        }
+                        if !has_column && column_relevance_item != TEXT_COLUMN.to_string() {
+                            errors.push(
+                                PlanetError::new(
+                                    500, 
+                                    Some(
+                                        tr!("Configuration does not have field \"{}\"", &column_relevance_item)
+                                    ),
+                                )
+                            );
+                        } else {
+                            let relevance_string= relevance.to_string();
+                            my_map.insert(column_relevance_item.clone(), relevance_string);
+                        }
+                    }
+                    let mut my_list: Vec<BTreeMap<String, String>> = Vec::new();
+                    my_list.push(my_map);
+                    data.insert(TEXT_SEARCH_COLUMN_RELEVANCE.to_string(), my_list);
+                } else {
+                    let mut my_map: BTreeMap<String, String> = BTreeMap::new();
+                    my_map.insert(TEXT_COLUMN.to_string(), String::from("1"));
+                    let mut my_list: Vec<BTreeMap<String, String>> = Vec::new();
+                    my_list.push(my_map);
+                    data.insert(TEXT_SEARCH_COLUMN_RELEVANCE.to_string(), my_list);
+                }
+
+                // name column
+                let name_field_config = statement.name;
+                columns.insert(0, name_field_config);
+                let mut column_name_map: BTreeMap<String, String> = BTreeMap::new();
+                // populate column_type_map and column_name_map
+                let mut column_type_map: BTreeMap<String, String> = BTreeMap::new();
+                let mut columns_map: HashMap<String, ColumnConfig> = HashMap::new();
+                for column in columns.iter() {
+                    let column_attrs = column.clone();
+                    let column_name = column.name.clone().unwrap();
+                    let column_type = column.column_type.clone();
+                    let mut column_id_map: BTreeMap<String, String> = BTreeMap::new();
+                    let column_id = column_attrs.id.unwrap_or_default();
+                    column_id_map.insert(String::from(ID), column_id.clone());
+                    columns_map.insert(column_name.clone(), column.clone());
+                    let _ = &column_ids.push(column_id_map);
+                    if column_type.is_some() {
+                        let column_type = column_type.unwrap();
+                        column_type_map.insert(column_name.clone(), column_type);
+                    }
+                    let column_name_str = column_name.as_str();
+                    if column_name_map.get(column_name_str).is_some() == false {
+                        // id => name
+                        column_name_map.insert(column_name.clone(), column_id.clone());
+                    } else {
+                        errors.push(
+                            PlanetError::new(
+                                500, 
+                                Some(tr!("There is already a column with name \"{}\"", &column_name)),
+                            )
+                        );
+                    }
+                }
+                
+                for column in columns.iter() {
+                    // column simple attributes
+                    let map = &column.create_config(
+                        planet_context,
+                        context,
+                        &columns_map,
+                        &db_folder,
+                        &folder_name,
+                        &space_database
+                    );
+                    if map.is_err() {
+                        let error = map.clone().unwrap_err();
+                        errors.push(error);
+                        return Err(errors)
+                    }
+                    let map = map.clone().unwrap();
+                    let map_list = &column.map_collections_db();
+                    if map_list.is_err() {
+                        let error = map_list.clone().unwrap_err();
+                        errors.push(error);
+                    }
+                    let map_list = map_list.clone().unwrap();
+                    let map_list = map_list.clone();
+                    data.extend(map_list);
+                    columns_list.push(map);
+                }
+                // Composite unique keys, I store column ids so keys survive column renames
+                let unique_keys = statement.unique_keys;
+                if unique_keys.is_some() {
+                    let unique_keys = unique_keys.unwrap();
+                    let mut list: Vec<BTreeMap<String, String>> = Vec::new();
+                    for unique_key in unique_keys {
+                        let mut key_column_ids: Vec<String> = Vec::new();
+                        for key_column in unique_key.columns.clone() {
+                            let key_column_id = column_name_map.get(&key_column);
+                            if key_column_id.is_some() {
+                                key_column_ids.push(key_column_id.unwrap().clone());
+                            } else {
+                                errors.push(
+                                    PlanetError::new(
+                                        500, 
+                                        Some(tr!(
+                                            "Unique key \"{}\" has column \"{}\" not found in folder.", 
+                                            &unique_key.name, &key_column
+                                        )),
+                                    )
+                                );
+                            }
+                        }
+                        let mut unique_key_map: BTreeMap<String, String> = BTreeMap::new();
+                        unique_key_map.insert(NAME.to_string(), unique_key.name.clone());
+                        unique_key_map.insert(COLUMN_IDS.to_string(), key_column_ids.join(","));
+                        list.push(unique_key_map);
+                    }
+                    if errors.len() > 0 {
+                        return Err(errors)
+                    }
+                    data.insert(UNIQUE_KEYS.to_string(), list);
+                }
+                // Check rules, compiled as Check formulas
+                let checks = statement.checks;
+                if checks.is_some() {
+                    let checks = checks.unwrap();
+                    let mut list: Vec<BTreeMap<String, String>> = Vec::new();
+                    for check in checks {
+                        let formula_compiled = Formula::defaults(
+                            &check.formula,
+                            &FORMULA_FORMAT_CHECK.to_string(),
+                            None,
+                            Some(columns_map.clone()),
+                            Some(db_folder.clone()),
+                            Some(folder_name.clone()),
+                            false,
+                            None
+                        );
+                        if formula_compiled.is_err() {
+                            let error = formula_compiled.unwrap_err();
+                            errors.push(
+                                PlanetError::new(
+                                    500, 
+                                    Some(tr!(
+                                        "Check rule \"{}\" could not be compiled: {}", 
+                                        &check.name, &error.message
+                                    )),
+                                )
+                            );
+                            continue
+                        }
+                        let formula_compiled = formula_compiled.unwrap();
+                        let formula_serialized = serde_yaml::to_string(&formula_compiled).unwrap();
+                        let mut check_map: BTreeMap<String, String> = BTreeMap::new();
+                        check_map.insert(NAME.to_string(), check.name.clone());
+                        check_map.insert(FORMULA.to_string(), check.formula.clone());
+                        check_map.insert(FORMULA_COMPILED.to_string(), formula_serialized);
+                        list.push(check_map);
+                    }
+                    if errors.len() > 0 {
+                        return Err(errors)
+                    }
+                    data.insert(CHECKS.to_string(), list);
+                }
+                // Sub folders
+                if sub_folders.is_some() {
+                    let sub_folders = sub_folders.unwrap();
+                    let mut my_map: HashMap<String, String> = HashMap::new();
+                    for sub_folder in sub_folders.clone() {
+                        let sub_folder_name = sub_folder.name.unwrap_or_default();
+                        let sub_folder_id = sub_folder.id.unwrap_or_default();
+                        my_map.insert(sub_folder_name, sub_folder_id);
+                    }
+                    // resolve parent id by parent name
+                    let mut list: Vec<BTreeMap<String, String>> = Vec::new();
+                    for sub_folder in sub_folders.clone() {
+                        let parent = sub_folder.parent.clone().unwrap_or_default();
+                        let parent_id = my_map.get(&parent);
+                        let mut sub_folder_db_map: BTreeMap<String, String> = BTreeMap::new();
+                        sub_folder_db_map.insert(NAME.to_string(), sub_folder.name.unwrap_or_default());
+                        sub_folder_db_map.insert(ID.to_string(), sub_folder.id.unwrap_or_default());
+                        sub_folder_db_map.insert(
+                            VERSION.to_string(), 
+                            sub_folder.version.unwrap_or_default()
+                        );
+                        if parent_id.is_some() {
+                            let parent_id = parent_id.unwrap();
+                            sub_folder_db_map.insert(
+                                PARENT_ID.to_string(), 
+                                parent_id.clone()
+                            );
+                        }
+                        list.push(sub_folder_db_map);
+                    }
+                    data.insert(
+                        SUB_FOLDERS.to_string(),
+                        list
+                    );
+                }
+                // eprintln!("CreateFolder.run :: data_objects_new: {:#?}", &data_objects_new);
+                // text column
+                let column_id = &generate_id().unwrap_or_default();
+                columns_list.push(
+                    create_minimum_column_map(
+                        column_id,
+                        &TEXT_COLUMN.to_string(),
+                        &COLUMN_TYPE_TEXT.to_string(),
+                    )
+                );
+                let mut column_id_map: BTreeMap<String, String> = BTreeMap::new();
+                column_id_map.insert(String::from(ID), column_id.clone());
+                column_ids.push(column_id_map);
+                // language column
+                let column_id = &generate_id().unwrap_or_default();
+                columns_list.push(
+                    create_minimum_column_map(
+                        column_id,
+                        &LANGUAGE_COLUMN.to_string(),
+                        &COLUMN_TYPE_LANGUAGE.to_string(),
+                    )
+                );
+                data.insert(COLUMNS.to_string(), columns_list);
+                // routing
+                let routing_wrap = RoutingData::defaults(
+                    account_id, 
+                    site_id, 
+                    space_id,
+                    None,
+                );
+                let mut data_wrap: Option<BTreeMap<String, Vec<BTreeMap<String, String>>>> = None;
+                if data.len() > 0 {
+                    data_wrap = Some(data);
+                }
+                let db_data = DbData::defaults(
+                    &folder_name, 
+                    data_wrap,
+                    None,
+                    routing_wrap,
+                    None,
+                    None,
+                );
+                if db_data.is_err() {
+                    let error = db_data.clone().unwrap_err();
+                    errors.push(error);
+                }
+                let db_data = db_data.unwrap();
+                // Only output TEMP the choices data to include in insert
+                eprintln!("CreateFolder.run :: db_data: {:#?}", db_data.clone());
+
+                let response = db_folder.create(&db_data);
+                if response.is_err() {
+                    let error = response.clone().unwrap_err();
+                    errors.push(error);
+                    // Since we have data error on creating folder, we return all errors
+                    return Err(errors);
+                }
+                let response = response.unwrap();
+                let response_src = response.clone();
+                // response.id
+                let folder_name = &response.name.unwrap_or_default();
+                let folder_id = &response.id.unwrap();
+
+                //
+                // Related folders, I need to update their config, like Links
+                //
+                let columns = response.data.unwrap();
+                let columns = columns.get(COLUMNS).unwrap();
+                let mut linked_folders: Vec<String> = Vec::new();
+                let mut map_column_ids: BTreeMap<String, String> = BTreeMap::new();
+                for v in columns {
+                    let v = v.clone();
+                    let column_type = v.get(COLUMN_TYPE);
+                    let column_id = v.get(ID);
+                    if column_type.is_some() {
+                        let column_type = column_type.unwrap();
+                        let column_id = column_id.unwrap();
+                        if column_type == COLUMN_TYPE_LINK {
+                            let linked_folder = v.get(LINKED_FOLDER);
+                            if linked_folder.is_some() {
+                                let linked_folder = linked_folder.unwrap();
+                                let has_id = linked_folders.contains(linked_folder);
+                                if !has_id {
+                                    linked_folders.push(linked_folder.clone());
+                                    map_column_ids.insert(
+                                        linked_folder.clone(), column_id.clone()
+                                    );
+                                }
+                            }
+                        }
+                    }
+                }
+                // Get each folder from db_folder instance and update with link to this created table
+                // eprintln!("CreateFolder.run :: LINKS map_column_names: {:?}", &map_column_names);
+                let local_column_map = db_data.data.unwrap();
+                let local_columns = local_column_map.get(COLUMNS).unwrap();
+                for link_folder in linked_folders {
+                    let linked_folder = db_folder.get_by_name(&link_folder);
+                    if linked_folder.is_ok() {
+                        let mut linked_folder = linked_folder.unwrap().unwrap();
+                        let mut map = linked_folder.clone().data.unwrap();
+                        let column_name = &link_folder;
+                        let mut remote_column_map: BTreeMap<String, String> = BTreeMap::new();
+                        for column_data in local_columns {
+                            let column_data_name = column_data.get(NAME);
+                            if column_data_name.is_some() {
+                                let column_data_name = column_data_name.unwrap().clone();
+                                if column_data_name.to_lowercase() == column_name.clone().to_lowercase() {
+                                    remote_column_map = column_data.clone();
+                                }
+                            }
+                        }
+                        remote_column_map.insert(String::from(LINKED_FOLDER), folder_name.clone());
+                        remote_column_map.insert(String::from(NAME), folder_name.clone());
+                        remote_column_map.insert(String::from(MANY), String::from(TRUE));
+                        // Reverse links only clean up references when items are deleted
+                        remote_column_map.remove(DELETE_ON_LINK_DROP);
+                        remote_column_map.insert(String::from(ON_DELETE), String::from(ON_DELETE_SET_NULL));
+                        let linked_folder_data = linked_folder.data.unwrap();
+                        let linked_folder_columns = linked_folder_data.get(
+                            COLUMNS
+                        ).unwrap();
+                        let mut column_found = false;
+                        let mut linked_folder_columns_new: Vec<BTreeMap<String, String>> = Vec::new();
+                        for remote_column in linked_folder_columns {
+                            let remote_column_name = remote_column.get(NAME).unwrap().clone();
+                            if remote_column_name.to_lowercase() == column_name.to_ascii_lowercase() {
+                                column_found = true;
+                                linked_folder_columns_new.push(remote_column_map.clone());
+                            } else {
+                                linked_folder_columns_new.push(remote_column.clone());
+                            }
+                        }
+                        if !column_found {
+                            linked_folder_columns_new.push(remote_column_map.clone());
+                        }
+                        map.insert(COLUMNS.to_string(), linked_folder_columns_new);
+                        linked_folder.data = Some(map);
+                        eprintln!("CreateFolder.run :: linked_folder: {:#?}", &linked_folder);
+                        let _ = db_folder.update(&linked_folder);
+                    }
+                }
+
+                println!();
+                let quote_color = format!("{}", String::from("\""));
+                if errors.len() > 0 {
+                    return Err(errors)
+                }
+                // TODO: I will have this into an object that I return
+                println!("Created folder {} :: {} => {}",
+                    format!("{}{}{}", &quote_color.blue(), &folder_name.blue(), &quote_color.blue()),
+                    &folder_id.magenta(),
+                    format!("{}{}{}", &quote_color.green(), &folder_name.green(), &quote_color.green()),
+                );
+                eprintln!("CreateFolder.run :: time: {} µs", &t_1.elapsed().as_micros());
+
+                let _mine = db_folder.get_by_name(folder_name);
+                let response_coded = serde_yaml::to_string(&response_src);
+                if response_coded.is_err() {
+                    let error = PlanetError::new(
+                        500, 
+                        Some(tr!("Error encoding statement response.")),
+                    );
+                    errors.push(error);
+                    return Err(errors)
+                }
+                let response = response_coded.unwrap();
+                let yaml_response = yaml_rust::YamlLoader::load_from_str(
+                    response.as_str()
+                ).unwrap();
+                let yaml_response = yaml_response.clone();
+                return Ok(yaml_response)
+                },
+            Err(error) => {
+                eprintln!("CreateFolder.run :: schema error...");
+                errors.push(error);
+                Err(errors)
+            }
+        }
+    }
+
+}
+
+#[derive(Debug, Clone)]
+pub struct ListFoldersStatement {
+}
+
+impl<'gb> StatementCompiler<'gb, ()> for ListFoldersStatement {
+
+    fn compile(
+        &self, 
+        statement_text: &String
+    ) -> Result<(), Vec<PlanetError>> {
+        let expr = &RE_LIST_FOLDERS;
+        let check = expr.is_match(&statement_text);
+        let mut errors: Vec<PlanetError> = Vec::new();
+        // This match is already executed on resolution operation. Here we include for consistency, would really
+        // apply if statement is more complex than LIST FOLDERS; with more parameters.
+        if !check {
             let error = PlanetError::new(
                 500, 
                 Some(
-                    tr!("Create folder syntax not valid.")
+                    tr!("List folder syntax not valid.")
                 ),
             );
             errors.push(error);
             return Err(errors)
         }
-        let long_text = long_text.unwrap();
-        let statement_text_processed = long_text.clone().parsed_text;
-        // eprintln!("CreateFolderStatement.compile :: statement_text_processed: {}", &statement_text_processed);
-        let expr = &RE_CREATE_FOLDER_CONFIG;
-        let items = expr.captures_iter(&statement_text_processed.as_str());
-        let mut sub_folders: Vec<SubFolderConfig> = Vec::new();
-        let mut columns: Vec<ColumnConfig> = Vec::new();
-        for item in items {
-            let name_config = item.name("NameConfig");
-            let sub_folder_name = item.name("SubFolderName");
-            let sub_folder_name_alt = item.name("SubFolderNameAlt");
-            let column = item.name("Column");
-            let column_type = item.name("ColumnType");
-            let language = item.name("Language");
-            let search_options = item.name("SearchRelevanceOptions");
-            if language.is_some() {
-                let language_str = language.unwrap().as_str();
-                let language = LanguageConfig{
-                    default: language_str.to_string()
-                };
-                compiled_statement.language = Some(language);
-            }
-            if sub_folder_name.is_some() || sub_folder_name_alt.is_some() {
-                // Sub folders
-                let mut sub_folder_name_: &str = "";
-                if sub_folder_name.is_some() {
-                    sub_folder_name_ = sub_folder_name.unwrap().as_str();
+        return Ok(())
+    }
+}
+
+impl<'gb> Statement<'gb> for ListFoldersStatement {
+
+    fn run(
+        &self,
+        env: &'gb Environment<'gb>,
+        space_database: &SpaceDatabase,
+        statement_text: &String,
+    ) -> Result<Vec<yaml_rust::Yaml>, Vec<PlanetError>> {
+        let space_database = space_database.clone();
+        let context = env.context;
+        let planet_context = env.planet_context;
+        let mut errors: Vec<PlanetError> = Vec::new();
+        let statement = self.compile(statement_text);
+        if statement.is_err() {
+            let errors = statement.unwrap_err();
+            return Err(errors)
+        }
+        let _statement = statement.unwrap();
+        let home_dir = planet_context.home_path.clone();
+        let account_id = context.account_id.clone().unwrap_or_default();
+        let space_id = context.space_id;
+        let site_id = context.site_id.clone();
+        let result: Result<TreeFolder, PlanetError> = TreeFolder::defaults(
+            space_database.connection_pool.clone(),
+            Some(home_dir.unwrap_or_default().as_str()),
+            Some(&account_id),
+            Some(space_id),
+            site_id,
+        );
+        match result {
+            Ok(_) => {
+                let result = result.unwrap();
+                let items = result.list();
+                if items.is_err() {
+                    let error = items.unwrap_err();
+                    errors.push(error);
+                    return Err(errors)
                 }
-                if sub_folder_name_alt.is_some() {
-                    sub_folder_name_ = sub_folder_name_alt.unwrap().as_str();
+                let items = items.unwrap();
+                let mut items_mini: Vec<DbDataMini> = Vec::new();
+                for item in items {
+                    let item_mini = DbDataMini{
+                        id: item.id,
+                        slug: item.slug,
+                        name: item.name,
+                        routing: item.routing
+                    };
+                    items_mini.push(item_mini);
                 }
-                let sub_folder_options = item.name("SubFolderOptions");
-                let mut sub_folder_obj: SubFolderConfig = SubFolderConfig{
-                    id: generate_id(),
-                    name: Some(sub_folder_name_.to_string()),
-                    parent: None,
-                    parent_id: None,
-                    version: SubFolderConfig::version(),
-                };
-                if sub_folder_options.is_some() {
-                    let sub_folder_options = sub_folder_options.unwrap().as_str();
-                    let result = WithOptions::defaults(
-                        &sub_folder_options.to_string()
+                let response_coded = serde_yaml::to_string(&items_mini);
+                if response_coded.is_err() {
+                    let error = PlanetError::new(
+                        500, 
+                        Some(tr!("Error encoding statement response.")),
                     );
-                    if result.is_err() {
-                        let error = result.unwrap_err();
-                        errors.push(error);
-                    } else {
-                        let with_options_obj = result.unwrap();
-                        let with_options = &with_options_obj.options;
-                        if *&with_options.contains_key(WITH_PARENT) {
-                            let parent = &with_options_obj.get_single_value(
-                                WITH_PARENT
-                            );
-                            sub_folder_obj.parent = Some(parent.clone());
-                        }
-                    }
-                }
-                sub_folders.push(sub_folder_obj);
-            } else if name_config.is_some() {
-                // Name config, should be first item in columns
-                let column_type = name_config.unwrap().as_str();
-                let mut name = compiled_statement.name.clone();
-                name.name = Some(String::from("Name"));
-                name.id = generate_id();
-                name.column_type = Some(column_type.to_string());
-                compiled_statement.name = name;
-            } else if search_options.is_some() {
-                let search_options = search_options.unwrap().as_str();
-                let result = WithOptions::defaults(
-                    &search_options.to_string()
-                );
-                if result.is_err() {
-                    let error = result.unwrap_err();
                     errors.push(error);
-                } else {
-                    let with_options_obj = result.unwrap();
-                    let with_options = &with_options_obj.options;
-                    let mut column_relevance: BTreeMap<String, u8> = BTreeMap::new();
-                    for (k, v) in with_options {
-                        let value = v.clone()[0].clone().value;
-                        let value_int: u8 = FromStr::from_str(value.as_str()).unwrap();
-                        let key = k.clone();
-                        column_relevance.insert(key, value_int);
-                    }
-                    // Validate column relevance
-                    let validate = validate_column_relevance(&column_relevance);
-                    if validate.is_err() {
-                        errors.push(
-                            PlanetError::new(
+                    return Err(errors)
+                }
+                let response = response_coded.unwrap();
+                let yaml_response = yaml_rust::YamlLoader::load_from_str(
+                    response.as_str()
+                ).unwrap();
+                let yaml_response = yaml_response.clone();
+                return Ok(yaml_response)
+            }
+            Err(error) => {
+                errors.push(error);
+                Err(errors)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DescribeFolderStatement {
+}
+
+impl<'gb> StatementCompiler<'gb, String> for DescribeFolderStatement {
+
+    fn compile(
+        &self, 
+        statement_text: &String
+    ) -> Result<String, Vec<PlanetError>> {
+        let expr = &RE_DESCRIBE_FOLDER;
+        let check = expr.is_match(&statement_text);
+        let mut errors: Vec<PlanetError> = Vec::new();
+        if !check {
+            let error = PlanetError::new(
+                500, 
+                Some(
+                    tr!("Describe folder syntax not valid.")
+                ),
+            );
+            errors.push(error);
+            return Err(errors)
+        }
+        let captures = expr.captures(&statement_text);
+        let folder_name: &str;
+        if captures.is_some() {
+            let captures = captures.unwrap();
+            folder_name = captures.name("FolderName").unwrap().as_str();
+            return Ok(folder_name.to_string().clone())
+        } else {
+            let error = PlanetError::new(
+                500, 
+                Some(
+                    tr!("Could not parse folder name from describe folder statement.")
+                ),
+            );
+            errors.push(error);
+            return Err(errors)
+        }
+    }
+}
+
+impl<'gb> Statement<'gb> for DescribeFolderStatement {
+
+    fn run(
+        &self,
+        env: &'gb Environment<'gb>,
+        space_database: &SpaceDatabase,
+        statement_text: &String,
+    ) -> Result<Vec<yaml_rust::Yaml>, Vec<PlanetError>> {
+        let space_database = space_database.clone();
+        let context = env.context;
+        let planet_context = env.planet_context;
+        let mut errors: Vec<PlanetError> = Vec::new();
+        let statement = self.compile(statement_text);
+        if statement.is_err() {
+            let errors = statement.unwrap_err();
+            return Err(errors)
+        }
+        let folder_name = statement.unwrap();
+        // get folder from data layer
+        let home_dir = planet_context.home_path.clone();
+        let account_id = context.account_id.clone().unwrap_or_default();
+        let space_id = context.space_id;
+        let site_id = context.site_id.clone();
+        let result: Result<TreeFolder, PlanetError> = TreeFolder::defaults(
+            space_database.connection_pool.clone(),
+            Some(home_dir.unwrap_or_default().as_str()),
+            Some(&account_id),
+            Some(space_id),
+            site_id,
+        );
+        match result {
+            Ok(_) => {
+                let result = result.unwrap();
+                let folder = result.get_by_name(folder_name.as_str());
+                if folder.is_ok() {
+                    let folder = folder.unwrap();
+                    if folder.is_some() {
+                        let folder = folder.unwrap();
+                        let response_coded = serde_yaml::to_string(&folder);
+                        if response_coded.is_err() {
+                            let error = PlanetError::new(
                                 500, 
-                                Some(
-                                    tr!("Validation error for column relevance: \"{}\".", &search_options)
-                                ),
-                            )
+                                Some(tr!("Error encoding statement response.")),
+                            );
+                            errors.push(error);
+                            return Err(errors)
+                        }
+                        let response = response_coded.unwrap();
+                        let yaml_response = yaml_rust::YamlLoader::load_from_str(
+                            response.as_str()
+                        ).unwrap();
+                        let yaml_response = yaml_response.clone();
+                        return Ok(yaml_response)
+                    } else {
+                        // Folder could not be found
+                        let error = PlanetError::new(
+                            500, 
+                            Some(
+                                tr!("Folder \"{}\" not found.", &folder_name)
+                            ),
                         );
+                        errors.push(error);
+                        return Err(errors)
                     }
-                    let text_search_config = TextSearchConfig{
-                        column_relevance: column_relevance
-                    };
-                    compiled_statement.text_search = Some(text_search_config);
-                }
-            } else if column.is_some() && column_type.is_some() {
-                // Column
-                let column_str = column.unwrap().as_str();
-                let column_type = column_type.unwrap().as_str();
-                let result = process_column(
-                    column_str, 
-                    column_type, 
-                    &item,
-                    Some(long_text.clone())
-                );
-                if result.is_err() {
-                    let errors = result.unwrap_err();
+                } else {
+                    let error = folder.unwrap_err();
+                    let mut errors: Vec<PlanetError> = Vec::new();
+                    errors.push(error);
                     return Err(errors)
                 }
-                if result.is_ok() {
-                    let column = result.unwrap();
-                    columns.push(column);
-                }
+            },
+            Err(error) => {
+                errors.push(error);
+                Err(errors)
             }
         }
-        compiled_statement.sub_folders = Some(sub_folders);
-        compiled_statement.columns = Some(columns);
-        if errors.len() > 0 {
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DropFolderStatement {
+}
+
+impl<'gb> StatementCompiler<'gb, String> for DropFolderStatement {
+
+    fn compile(
+        &self, 
+        statement_text: &String
+    ) -> Result<String, Vec<PlanetError>> {
+        let expr = &RE_DROP_FOLDER;
+        let check = expr.is_match(&statement_text);
+        let mut errors: Vec<PlanetError> = Vec::new();
+        if !check {
+            let error = PlanetError::new(
+                500, 
+                Some(
+                    tr!("Drop folder syntax not valid.")
+                ),
+            );
+            errors.push(error);
+            return Err(errors)
+        }
+        let captures = expr.captures(&statement_text);
+        let folder_name: &str;
+        if captures.is_some() {
+            let captures = captures.unwrap();
+            folder_name = captures.name("FolderName").unwrap().as_str();
+            return Ok(folder_name.to_string().clone())
+        } else {
+            let error = PlanetError::new(
+                500, 
+                Some(
+                    tr!("Could not parse folder name from drop folder statement.")
+                ),
+            );
+            errors.push(error);
             return Err(errors)
         }
-        eprintln!("CreateFolder.compile :: compiled_statement: {:#?}", &compiled_statement);
-        return Ok(compiled_statement.clone())
     }
-
 }
 
-impl<'gb> Statement<'gb> for CreateFolderStatement {
+impl<'gb> Statement<'gb> for DropFolderStatement {
 
     fn run(
         &self,
@@ -1404,418 +3036,554 @@ impl<'gb> Statement<'gb> for CreateFolderStatement {
         space_database: &SpaceDatabase,
         statement_text: &String,
     ) -> Result<Vec<yaml_rust::Yaml>, Vec<PlanetError>> {
-        // run would return a generic Yaml object which has the statement response
-        // yaml_rust::Yaml
         let space_database = space_database.clone();
         let context = env.context;
         let planet_context = env.planet_context;
-        let t_1 = Instant::now();
         let mut errors: Vec<PlanetError> = Vec::new();
         let statement = self.compile(statement_text);
         if statement.is_err() {
             let errors = statement.unwrap_err();
             return Err(errors)
         }
-        let statement = statement.unwrap();
+        let folder_name = statement.unwrap();
+        // get folder from data layer
         let home_dir = planet_context.home_path.clone();
         let account_id = context.account_id.clone().unwrap_or_default();
         let space_id = context.space_id;
         let site_id = context.site_id.clone();
-
+        let mut site_id_alt: Option<String> = None;
+        if site_id.is_some() {
+            let site_id = site_id.clone().unwrap();
+            site_id_alt = Some(site_id.to_string());
+        }
         let result: Result<TreeFolder, PlanetError> = TreeFolder::defaults(
             space_database.connection_pool.clone(),
-            Some(home_dir.unwrap_or_default().as_str()),
+            Some(home_dir.clone().unwrap_or_default().as_str()),
             Some(&account_id),
             Some(space_id),
             site_id,
         );
         match result {
             Ok(_) => {
-                // let command = self.config.command.clone().unwrap_or_default();
-                // let expr = Regex::new(r#"(CREATE FOLDER) "(?P<folder_name>[a-zA-Z0-9_ ]+)"#).unwrap();
-                // let table_name_match = expr.captures(&command).unwrap();
-                // let folder_name = &table_name_match["folder_name"].to_string();
-                let folder_name = statement.folder_name;
-
-                // routing parameters
-                let account_id = Some(context.account_id.clone().unwrap_or_default().to_string());
-                let site_id = context.site_id.clone();
-                let space_id = context.space_id;
-
-                // db folder options with language data
-                let db_folder: TreeFolder = result.unwrap();
-                let mut data: BTreeMap<String, Vec<BTreeMap<String, String>>> = BTreeMap::new();
-                // config data
-                let mut columns = statement.columns.unwrap().clone();
-                let sub_folders = statement.sub_folders;
-                let mut column_ids: Vec<BTreeMap<String, String>> = Vec::new();
-                let mut columns_list: Vec<BTreeMap<String, String>> = Vec::new();
-
-                // validate and clean defaults for language and text_search
-                // TODO: Place this into a validation operation for the entity
-                let language = statement.language;
-                if language.is_some() {
-                    let language = language.unwrap();
-                    let language_default = language.default;
-                    data.insert(LANGUAGE_DEFAULT.to_string(), build_value_list(&language_default));
-                } else {
-                    data.insert(LANGUAGE_DEFAULT.to_string(), build_value_list(&LANGUAGE_ENGLISH.to_string()));
-                }
-                let text_search = statement.text_search;
-                if text_search.is_some() {
-                    let text_search = text_search.unwrap();
-                    let mut column_relevance = text_search.column_relevance;
-                    let mut my_map: BTreeMap<String, String> = BTreeMap::new();
-                    // Include Text rule if not found
-                    let mut has_text = false;
-                    for (column_relevance_item, _) in column_relevance.clone() {
-                        if column_relevance_item == String::from(TEXT_COLUMN) {
-                            has_text = true;
-                        }
-                    }
-                    if !has_text {
-                        let relevance: u8 = 1;
-                        column_relevance.insert(TEXT_COLUMN.to_string(), relevance);
-                    }
-                    for (column_relevance_item, relevance) in column_relevance {
-                        let mut has_column = false;
-                        for column in columns.clone().iter() {
-                            let column = column.clone();
-                            let column_name = column.name.unwrap_or_default();
-                            if column_name.to_lowercase() == column_relevance_item.to_lowercase() {
-                                has_column = true;
-                            }
-                        }
-                        if !has_column && column_relevance_item != TEXT_COLUMN.to_string() {
-                            errors.push(
-                                PlanetError::new(
-                                    500, 
-                                    Some(
-                                        tr!("Configuration does not have field \"{}\"", &column_relevance_item)
-                                    ),
-                                )
-                            );
-                        } else {
-                            let relevance_string= relevance.to_string();
-                            my_map.insert(column_relevance_item.clone(), relevance_string);
+                let db_folder = result.unwrap();
+                let folder = db_folder.get_by_name(folder_name.as_str());
+                if folder.is_ok() {
+                    let folder = folder.unwrap();
+                    if folder.is_some() {
+                        let folder = folder.unwrap();
+                        // Delete folder items
+                        let folder_id = folder.id.clone().unwrap();
+                        let result: Result<TreeFolderItem, PlanetError> = TreeFolderItem::defaults(
+                            space_database.connection_pool.clone(),
+                            home_dir.unwrap_or_default().as_str(),
+                            &account_id,
+                            space_id,
+                            site_id_alt,
+                            folder_id.as_str(),
+                            &db_folder,
+                        );
+                        if result.is_err() {
+                            let error = result.unwrap_err();
+                            errors.push(error);
+                            return Err(errors)
                         }
-                    }
-                    let mut my_list: Vec<BTreeMap<String, String>> = Vec::new();
-                    my_list.push(my_map);
-                    data.insert(TEXT_SEARCH_COLUMN_RELEVANCE.to_string(), my_list);
-                } else {
-                    let mut my_map: BTreeMap<String, String> = BTreeMap::new();
-                    my_map.insert(TEXT_COLUMN.to_string(), String::from("1"));
-                    let mut my_list: Vec<BTreeMap<String, String>> = Vec::new();
-                    my_list.push(my_map);
-                    data.insert(TEXT_SEARCH_COLUMN_RELEVANCE.to_string(), my_list);
-                }
-
-                // name column
-                let name_field_config = statement.name;
-                columns.insert(0, name_field_config);
-                let mut column_name_map: BTreeMap<String, String> = BTreeMap::new();
-                // populate column_type_map and column_name_map
-                let mut column_type_map: BTreeMap<String, String> = BTreeMap::new();
-                let mut columns_map: HashMap<String, ColumnConfig> = HashMap::new();
-                for column in columns.iter() {
-                    let column_attrs = column.clone();
-                    let column_name = column.name.clone().unwrap();
-                    let column_type = column.column_type.clone();
-                    let mut column_id_map: BTreeMap<String, String> = BTreeMap::new();
-                    let column_id = column_attrs.id.unwrap_or_default();
-                    column_id_map.insert(String::from(ID), column_id.clone());
-                    columns_map.insert(column_name.clone(), column.clone());
-                    let _ = &column_ids.push(column_id_map);
-                    if column_type.is_some() {
-                        let column_type = column_type.unwrap();
-                        column_type_map.insert(column_name.clone(), column_type);
-                    }
-                    let column_name_str = column_name.as_str();
-                    if column_name_map.get(column_name_str).is_some() == false {
-                        // id => name
-                        column_name_map.insert(column_name.clone(), column_id.clone());
-                    } else {
-                        errors.push(
-                            PlanetError::new(
+                        let mut db_folder_item = result.unwrap();
+                        // Apply OnDelete rules for folders linking to this folder
+                        let result = db_folder_item.drop_links();
+                        if result.is_err() {
+                            let error = result.unwrap_err();
+                            errors.push(error);
+                            return Err(errors)
+                        }
+                        let result = db_folder_item.drop_trees();
+                        if result.is_err() {
+                            let error = result.unwrap_err();
+                            errors.push(error);
+                            return Err(errors)
+                        }
+
+                        // Delete from folders.db
+                        let result = db_folder.delete(&folder_id);
+                        if result.is_err() {
+                            let error = result.unwrap_err();
+                            errors.push(error);
+                            return Err(errors)
+                        }
+
+                        // Delete files from OS dir "files"
+                        let result = db_folder_item.drop_files();
+                        if result.is_err() {
+                            let error = result.unwrap_err();
+                            errors.push(error);
+                            return Err(errors)
+                        }
+
+                        // Build Output
+                        let response_coded = serde_yaml::to_string(&folder);
+                        if response_coded.is_err() {
+                            let error = PlanetError::new(
                                 500, 
-                                Some(tr!("There is already a column with name \"{}\"", &column_name)),
-                            )
+                                Some(tr!("Error encoding statement response.")),
+                            );
+                            errors.push(error);
+                            return Err(errors)
+                        }
+                        let response = response_coded.unwrap();
+                        let yaml_response = yaml_rust::YamlLoader::load_from_str(
+                            response.as_str()
+                        ).unwrap();
+                        let yaml_response = yaml_response.clone();
+                        return Ok(yaml_response)
+                    } else {
+                        let error = PlanetError::new(
+                            500, 
+                            Some(
+                                tr!("Folder \"{}\" not found.", &folder_name)
+                            ),
                         );
-                    }
-                }
-                
-                for column in columns.iter() {
-                    // column simple attributes
-                    let map = &column.create_config(
-                        planet_context,
-                        context,
-                        &columns_map,
-                        &db_folder,
-                        &folder_name,
-                        &space_database
-                    );
-                    if map.is_err() {
-                        let error = map.clone().unwrap_err();
                         errors.push(error);
                         return Err(errors)
                     }
-                    let map = map.clone().unwrap();
-                    let map_list = &column.map_collections_db();
-                    if map_list.is_err() {
-                        let error = map_list.clone().unwrap_err();
-                        errors.push(error);
-                    }
-                    let map_list = map_list.clone().unwrap();
-                    let map_list = map_list.clone();
-                    data.extend(map_list);
-                    columns_list.push(map);
+                } else {
+                    let error = folder.unwrap_err();
+                    let mut errors: Vec<PlanetError> = Vec::new();
+                    errors.push(error);
+                    return Err(errors)
                 }
-                // Sub folders
-                if sub_folders.is_some() {
-                    let sub_folders = sub_folders.unwrap();
-                    let mut my_map: HashMap<String, String> = HashMap::new();
-                    for sub_folder in sub_folders.clone() {
-                        let sub_folder_name = sub_folder.name.unwrap_or_default();
-                        let sub_folder_id = sub_folder.id.unwrap_or_default();
-                        my_map.insert(sub_folder_name, sub_folder_id);
-                    }
-                    // resolve parent id by parent name
-                    let mut list: Vec<BTreeMap<String, String>> = Vec::new();
-                    for sub_folder in sub_folders.clone() {
-                        let parent = sub_folder.parent.clone().unwrap_or_default();
-                        let parent_id = my_map.get(&parent);
-                        let mut sub_folder_db_map: BTreeMap<String, String> = BTreeMap::new();
-                        sub_folder_db_map.insert(NAME.to_string(), sub_folder.name.unwrap_or_default());
-                        sub_folder_db_map.insert(ID.to_string(), sub_folder.id.unwrap_or_default());
-                        sub_folder_db_map.insert(
-                            VERSION.to_string(), 
-                            sub_folder.version.unwrap_or_default()
-                        );
-                        if parent_id.is_some() {
-                            let parent_id = parent_id.unwrap();
-                            sub_folder_db_map.insert(
-                                PARENT_ID.to_string(), 
-                                parent_id.clone()
-                            );
-                        }
-                        list.push(sub_folder_db_map);
-                    }
-                    data.insert(
-                        SUB_FOLDERS.to_string(),
-                        list
-                    );
+            },
+            Err(error) => {
+                errors.push(error);
+                Err(errors)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CopyFolderCompiledStmt {
+    pub folder_name: String,
+    pub target_folder_name: String,
+    pub with_data: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct CopyFolderStatement {
+}
+
+impl CopyFolderStatement {
+    fn remap_id(value: &String, id_map: &BTreeMap<String, String>) -> String {
+        let id_new = id_map.get(value);
+        if id_new.is_some() {
+            return id_new.unwrap().clone()
+        }
+        return value.clone()
+    }
+    fn remap_formula(formula: &Formula, id_map: &BTreeMap<String, String>) -> Formula {
+        // Column references keep column id at attributes, also for formulas inside attributes
+        let mut formula = formula.clone();
+        if formula.functions.is_none() {
+            return formula
+        }
+        let mut functions = formula.functions.clone().unwrap();
+        for (_, function) in functions.iter_mut() {
+            if function.attributes.is_none() {
+                continue
+            }
+            let mut attributes = function.attributes.clone().unwrap();
+            for attribute in attributes.iter_mut() {
+                if attribute.id.is_some() {
+                    let id = attribute.id.clone().unwrap();
+                    attribute.id = Some(CopyFolderStatement::remap_id(&id, id_map));
                 }
-                // eprintln!("CreateFolder.run :: data_objects_new: {:#?}", &data_objects_new);
-                // text column
-                let column_id = &generate_id().unwrap_or_default();
-                columns_list.push(
-                    create_minimum_column_map(
-                        column_id,
-                        &TEXT_COLUMN.to_string(),
-                        &COLUMN_TYPE_TEXT.to_string(),
-                    )
-                );
-                let mut column_id_map: BTreeMap<String, String> = BTreeMap::new();
-                column_id_map.insert(String::from(ID), column_id.clone());
-                column_ids.push(column_id_map);
-                // language column
-                let column_id = &generate_id().unwrap_or_default();
-                columns_list.push(
-                    create_minimum_column_map(
-                        column_id,
-                        &LANGUAGE_COLUMN.to_string(),
-                        &COLUMN_TYPE_LANGUAGE.to_string(),
-                    )
-                );
-                data.insert(COLUMNS.to_string(), columns_list);
-                // routing
-                let routing_wrap = RoutingData::defaults(
-                    account_id, 
-                    site_id, 
-                    space_id,
-                    None,
-                );
-                let mut data_wrap: Option<BTreeMap<String, Vec<BTreeMap<String, String>>>> = None;
-                if data.len() > 0 {
-                    data_wrap = Some(data);
+                if attribute.formula.is_some() {
+                    let attribute_formula = attribute.formula.clone().unwrap();
+                    attribute.formula = Some(CopyFolderStatement::remap_formula(&attribute_formula, id_map));
                 }
-                let db_data = DbData::defaults(
-                    &folder_name, 
-                    data_wrap,
-                    None,
-                    routing_wrap,
-                    None,
-                    None,
+            }
+            function.attributes = Some(attributes);
+        }
+        formula.functions = Some(functions);
+        return formula
+    }
+    fn remap_formula_compiled(value: &String, id_map: &BTreeMap<String, String>) -> String {
+        let formula: Result<Formula, serde_yaml::Error> = serde_yaml::from_str(value.as_str());
+        if formula.is_err() {
+            return value.clone()
+        }
+        let formula = CopyFolderStatement::remap_formula(&formula.unwrap(), id_map);
+        return serde_yaml::to_string(&formula).unwrap()
+    }
+    fn remap_transitions(value: &String, id_map: &BTreeMap<String, String>) -> String {
+        let transitions: Result<Vec<WorkflowTransition>, serde_yaml::Error> = serde_yaml::from_str(
+            value.as_str()
+        );
+        if transitions.is_err() {
+            return value.clone()
+        }
+        let mut transitions = transitions.unwrap();
+        for transition in transitions.iter_mut() {
+            if transition.guard_compiled.is_some() {
+                let guard_compiled = transition.guard_compiled.clone().unwrap();
+                transition.guard_compiled = Some(
+                    CopyFolderStatement::remap_formula_compiled(&guard_compiled, id_map)
                 );
-                if db_data.is_err() {
-                    let error = db_data.clone().unwrap_err();
-                    errors.push(error);
-                }
-                let db_data = db_data.unwrap();
-                // Only output TEMP the choices data to include in insert
-                eprintln!("CreateFolder.run :: db_data: {:#?}", db_data.clone());
-
-                let response = db_folder.create(&db_data);
-                if response.is_err() {
-                    let error = response.clone().unwrap_err();
-                    errors.push(error);
-                    // Since we have data error on creating folder, we return all errors
-                    return Err(errors);
+            }
+        }
+        return serde_yaml::to_string(&transitions).unwrap()
+    }
+    fn remap_data(
+        data: &BTreeMap<String, Vec<BTreeMap<String, String>>>, 
+        id_map: &BTreeMap<String, String>,
+        is_schema: bool,
+    ) -> BTreeMap<String, Vec<BTreeMap<String, String>>> {
+        // Only keys and fields holding column ids are remapped, other values are kept as they are
+        let mut data_new: BTreeMap<String, Vec<BTreeMap<String, String>>> = BTreeMap::new();
+        for (key, list) in data {
+            let key = CopyFolderStatement::remap_id(key, id_map);
+            let mut list_new: Vec<BTreeMap<String, String>> = Vec::new();
+            for map in list {
+                let mut map_new: BTreeMap<String, String> = BTreeMap::new();
+                for (map_key, map_value) in map {
+                    let map_key = CopyFolderStatement::remap_id(map_key, id_map);
+                    let mut map_value = map_value.clone();
+                    match map_key.as_str() {
+                        COLUMN_ID => {
+                            map_value = CopyFolderStatement::remap_id(&map_value, id_map);
+                        },
+                        ID if is_schema => {
+                            map_value = CopyFolderStatement::remap_id(&map_value, id_map);
+                        },
+                        COLUMN_IDS if is_schema => {
+                            let column_ids: Vec<String> = map_value.split(",").map(
+                                |column_id| CopyFolderStatement::remap_id(&column_id.to_string(), id_map)
+                            ).collect();
+                            map_value = column_ids.join(",");
+                        },
+                        FORMULA_COMPILED if is_schema => {
+                            map_value = CopyFolderStatement::remap_formula_compiled(&map_value, id_map);
+                        },
+                        TRANSITIONS if is_schema => {
+                            map_value = CopyFolderStatement::remap_transitions(&map_value, id_map);
+                        },
+                        _ => {}
+                    }
+                    map_new.insert(map_key, map_value);
                 }
-                let response = response.unwrap();
-                let response_src = response.clone();
-                // response.id
-                let folder_name = &response.name.unwrap_or_default();
-                let folder_id = &response.id.unwrap();
-
-                //
-                // Related folders, I need to update their config, like Links
-                //
-                let columns = response.data.unwrap();
-                let columns = columns.get(COLUMNS).unwrap();
-                let mut linked_folders: Vec<String> = Vec::new();
-                let mut map_column_ids: BTreeMap<String, String> = BTreeMap::new();
-                for v in columns {
-                    let v = v.clone();
-                    let column_type = v.get(COLUMN_TYPE);
-                    let column_id = v.get(ID);
-                    if column_type.is_some() {
-                        let column_type = column_type.unwrap();
-                        let column_id = column_id.unwrap();
-                        if column_type == COLUMN_TYPE_LINK {
-                            let linked_folder = v.get(LINKED_FOLDER);
-                            if linked_folder.is_some() {
-                                let linked_folder = linked_folder.unwrap();
-                                let has_id = linked_folders.contains(linked_folder);
-                                if !has_id {
-                                    linked_folders.push(linked_folder.clone());
-                                    map_column_ids.insert(
-                                        linked_folder.clone(), column_id.clone()
-                                    );
-                                }
+                list_new.push(map_new);
+            }
+            data_new.insert(key, list_new);
+        }
+        return data_new
+    }
+    fn copy_items(
+        env: &Environment,
+        space_database: &SpaceDatabase,
+        db_folder: &TreeFolder,
+        folder_id: &String,
+        target_folder: &DbData,
+        column_id_map: &BTreeMap<String, String>,
+    ) -> Result<(), Vec<PlanetError>> {
+        let context = env.context;
+        let planet_context = env.planet_context;
+        let mut errors: Vec<PlanetError> = Vec::new();
+        let home_dir = planet_context.home_path.clone();
+        let account_id = context.account_id.clone().unwrap_or_default();
+        let space_id = context.space_id;
+        let site_id = context.site_id.clone();
+        let target_folder_id = target_folder.id.clone().unwrap_or_default();
+        let target_folder_name = target_folder.name.clone().unwrap_or_default();
+        let target_folder_data = target_folder.data.clone().unwrap_or_default();
+        let target_columns = target_folder_data.get(COLUMNS).unwrap().clone();
+        let result: Result<TreeFolderItem, PlanetError> = TreeFolderItem::defaults(
+            space_database.connection_pool.clone(),
+            home_dir.clone().unwrap_or_default().as_str(),
+            &account_id,
+            space_id,
+            site_id.clone(),
+            folder_id.as_str(),
+            &db_folder,
+        );
+        if result.is_err() {
+            let error = result.unwrap_err();
+            errors.push(error);
+            return Err(errors)
+        }
+        let mut db_folder_item = result.unwrap();
+        let result: Result<TreeFolderItem, PlanetError> = TreeFolderItem::defaults(
+            space_database.connection_pool.clone(),
+            home_dir.clone().unwrap_or_default().as_str(),
+            &account_id,
+            space_id,
+            site_id.clone(),
+            target_folder_id.as_str(),
+            &db_folder,
+        );
+        if result.is_err() {
+            let error = result.unwrap_err();
+            errors.push(error);
+            return Err(errors)
+        }
+        let mut target_folder_item = result.unwrap();
+        let column_config_map = ColumnConfig::get_column_config_map(
+            planet_context,
+            context,
+            &target_folder
+        );
+        if column_config_map.is_err() {
+            let error = column_config_map.unwrap_err();
+            errors.push(error);
+            return Err(errors)
+        }
+        let column_config_map = column_config_map.unwrap();
+        let items = db_folder_item.get_all_items();
+        if items.is_err() {
+            let error = items.unwrap_err();
+            errors.push(error);
+            return Err(errors)
+        }
+        let items = items.unwrap();
+        let mut item_id_map: BTreeMap<String, String> = BTreeMap::new();
+        for item in &items {
+            let item_id = item.id.clone().unwrap_or_default();
+            item_id_map.insert(item_id, generate_id().unwrap_or_default());
+        }
+        for item in items {
+            let item_id = item.id.clone().unwrap_or_default();
+            let item_id_new = item_id_map.get(&item_id).unwrap().clone();
+            let item_data = item.data.clone().unwrap_or_default();
+            let mut data = CopyFolderStatement::remap_data(&item_data, &column_id_map, false);
+            // (column id, linked folder id, linked item ids) for links to other folders
+            let mut remote_links: Vec<(String, String, Vec<String>)> = Vec::new();
+            for column in &target_columns {
+                let column_type = column.get(COLUMN_TYPE).unwrap().clone();
+                let column_type = column_type.as_str();
+                let column_id = column.get(ID).unwrap().clone();
+                let list = data.get(&column_id);
+                if list.is_none() {
+                    continue
+                }
+                let list = list.unwrap().clone();
+                match column_type {
+                    COLUMN_TYPE_LINK => {
+                        let linked_folder_name = column.get(LINKED_FOLDER).unwrap().clone();
+                        let mut linked_ids: Vec<String> = Vec::new();
+                        for map in &list {
+                            let linked_id = map.get(ID);
+                            if linked_id.is_some() {
+                                linked_ids.push(linked_id.unwrap().clone());
                             }
                         }
-                    }
-                }
-                // Get each folder from db_folder instance and update with link to this created table
-                // eprintln!("CreateFolder.run :: LINKS map_column_names: {:?}", &map_column_names);
-                let local_column_map = db_data.data.unwrap();
-                let local_columns = local_column_map.get(COLUMNS).unwrap();
-                for link_folder in linked_folders {
-                    let linked_folder = db_folder.get_by_name(&link_folder);
-                    if linked_folder.is_ok() {
-                        let mut linked_folder = linked_folder.unwrap().unwrap();
-                        let mut map = linked_folder.clone().data.unwrap();
-                        let column_name = &link_folder;
-                        let mut remote_column_map: BTreeMap<String, String> = BTreeMap::new();
-                        for column_data in local_columns {
-                            let column_data_name = column_data.get(NAME);
-                            if column_data_name.is_some() {
-                                let column_data_name = column_data_name.unwrap().clone();
-                                if column_data_name.to_lowercase() == column_name.clone().to_lowercase() {
-                                    remote_column_map = column_data.clone();
+                        if linked_folder_name.to_lowercase() == target_folder_name.to_lowercase() {
+                            let mut list_new: Vec<BTreeMap<String, String>> = Vec::new();
+                            for linked_id in linked_ids {
+                                let linked_id_new = item_id_map.get(&linked_id);
+                                if linked_id_new.is_some() {
+                                    let mut map: BTreeMap<String, String> = BTreeMap::new();
+                                    map.insert(ID.to_string(), linked_id_new.unwrap().clone());
+                                    list_new.push(map);
                                 }
                             }
-                        }
-                        remote_column_map.insert(String::from(LINKED_FOLDER), folder_name.clone());
-                        remote_column_map.insert(String::from(NAME), folder_name.clone());
-                        remote_column_map.insert(String::from(MANY), String::from(TRUE));
-                        let linked_folder_data = linked_folder.data.unwrap();
-                        let linked_folder_columns = linked_folder_data.get(
-                            COLUMNS
-                        ).unwrap();
-                        let mut column_found = false;
-                        let mut linked_folder_columns_new: Vec<BTreeMap<String, String>> = Vec::new();
-                        for remote_column in linked_folder_columns {
-                            let remote_column_name = remote_column.get(NAME).unwrap().clone();
-                            if remote_column_name.to_lowercase() == column_name.to_ascii_lowercase() {
-                                column_found = true;
-                                linked_folder_columns_new.push(remote_column_map.clone());
-                            } else {
-                                linked_folder_columns_new.push(remote_column.clone());
+                            data.insert(column_id.clone(), list_new);
+                        } else {
+                            let linked_folder = db_folder.get_by_name(&linked_folder_name);
+                            if linked_folder.is_ok() {
+                                let linked_folder = linked_folder.unwrap();
+                                if linked_folder.is_some() {
+                                    let linked_folder_id = linked_folder.unwrap().id.unwrap_or_default();
+                                    remote_links.push((column_id.clone(), linked_folder_id, linked_ids));
+                                }
                             }
                         }
-                        if !column_found {
-                            linked_folder_columns_new.push(remote_column_map.clone());
+                    },
+                    COLUMN_TYPE_FILE => {
+                        let mut list_new: Vec<BTreeMap<String, String>> = Vec::new();
+                        for map in list {
+                            let mut map = map.clone();
+                            let file_id = map.get(ID);
+                            if file_id.is_some() {
+                                let file_id = file_id.unwrap().clone();
+                                let result = db_folder_item.copy_file(
+                                    &file_id, 
+                                    &mut target_folder_item
+                                );
+                                if result.is_err() {
+                                    let error = result.unwrap_err();
+                                    errors.push(error);
+                                    return Err(errors)
+                                }
+                                map.insert(ID.to_string(), result.unwrap());
+                            }
+                            list_new.push(map);
                         }
-                        map.insert(COLUMNS.to_string(), linked_folder_columns_new);
-                        linked_folder.data = Some(map);
-                        eprintln!("CreateFolder.run :: linked_folder: {:#?}", &linked_folder);
-                        let _ = db_folder.update(&linked_folder);
-                    }
+                        data.insert(column_id.clone(), list_new);
+                    },
+                    _ => {}
                 }
-
-                println!();
-                let quote_color = format!("{}", String::from("\""));
-                if errors.len() > 0 {
-                    return Err(errors)
+            }
+            // text for search index
+            for column in &target_columns {
+                let column_type = column.get(COLUMN_TYPE).unwrap().clone();
+                if column_type.as_str() != COLUMN_TYPE_TEXT {
+                    continue
                 }
-                // TODO: I will have this into an object that I return
-                println!("Created folder {} :: {} => {}",
-                    format!("{}{}{}", &quote_color.blue(), &folder_name.blue(), &quote_color.blue()),
-                    &folder_id.magenta(),
-                    format!("{}{}{}", &quote_color.green(), &folder_name.green(), &quote_color.green()),
+                let column_id = column.get(ID).unwrap().clone();
+                let column_name = column.get(NAME).unwrap().clone();
+                let column_config = column_config_map.get(&column_name);
+                if column_config.is_none() {
+                    continue
+                }
+                let mut obj = TextColumn::defaults(
+                    column_config.unwrap(),
+                    Some(column_config_map.clone()),
                 );
-                eprintln!("CreateFolder.run :: time: {} µs", &t_1.elapsed().as_micros());
-
-                let _mine = db_folder.get_by_name(folder_name);
-                let response_coded = serde_yaml::to_string(&response_src);
-                if response_coded.is_err() {
-                    let error = PlanetError::new(
-                        500, 
-                        Some(tr!("Error encoding statement response.")),
-                    );
+                let text_map = obj.validate(
+                    &data, 
+                    &target_folder, 
+                    &column_id
+                );
+                if text_map.is_ok() {
+                    let mut my_list: Vec<BTreeMap<String, String>> = Vec::new();
+                    my_list.push(text_map.unwrap());
+                    data.insert(TEXT.to_string(), my_list);
+                }
+            }
+            let mut item_new = item.clone();
+            item_new.id = Some(item_id_new.clone());
+            item_new.data = Some(data);
+            let mut item_list: Vec<DbData> = Vec::new();
+            item_list.push(item_new);
+            let result = target_folder_item.insert(&target_folder_name, &item_list);
+            if result.is_err() {
+                let errors = result.unwrap_err();
+                return Err(errors)
+            }
+            for (column_id, linked_folder_id, linked_ids) in remote_links {
+                let result: Result<TreeFolderItem, PlanetError> = TreeFolderItem::defaults(
+                    space_database.connection_pool.clone(),
+                    home_dir.clone().unwrap_or_default().as_str(),
+                    &account_id,
+                    space_id,
+                    site_id.clone(),
+                    linked_folder_id.as_str(),
+                    &db_folder,
+                );
+                if result.is_err() {
+                    let error = result.unwrap_err();
                     errors.push(error);
                     return Err(errors)
                 }
-                let response = response_coded.unwrap();
-                let yaml_response = yaml_rust::YamlLoader::load_from_str(
-                    response.as_str()
-                ).unwrap();
-                let yaml_response = yaml_response.clone();
-                return Ok(yaml_response)
-                },
-            Err(error) => {
-                eprintln!("CreateFolder.run :: schema error...");
-                errors.push(error);
-                Err(errors)
+                let mut linked_folder_item = result.unwrap();
+                for linked_id in linked_ids {
+                    let result = linked_folder_item.add_link(&column_id, &linked_id, &item_id_new);
+                    if result.is_err() {
+                        let error = result.unwrap_err();
+                        errors.push(error);
+                        return Err(errors)
+                    }
+                }
             }
         }
+        return Ok(())
+    }
+    fn drop_target(
+        env: &Environment,
+        space_database: &SpaceDatabase,
+        db_folder: &TreeFolder,
+        target_folder: &DbData,
+    ) -> Result<(), PlanetError> {
+        let context = env.context;
+        let planet_context = env.planet_context;
+        let home_dir = planet_context.home_path.clone();
+        let account_id = context.account_id.clone().unwrap_or_default();
+        let target_folder_id = target_folder.id.clone().unwrap_or_default();
+        let target_folder_name = target_folder.name.clone().unwrap_or_default();
+        // Reverse columns created by the copy may restrict deletes, links to the copy are dropped. Dropping 
+        // links also removes reverse columns already added at linked folders.
+        let folders = db_folder.list()?;
+        for mut linked_folder in folders {
+            let linked_folder_id = linked_folder.id.clone().unwrap_or_default();
+            if linked_folder_id == target_folder_id {
+                continue
+            }
+            let mut linked_folder_data = linked_folder.data.clone().unwrap_or_default();
+            let columns = linked_folder_data.get(COLUMNS);
+            if columns.is_none() {
+                continue
+            }
+            let mut columns_new: Vec<BTreeMap<String, String>> = Vec::new();
+            for column in columns.unwrap() {
+                let mut column = column.clone();
+                let column_linked_folder = column.get(LINKED_FOLDER);
+                if column_linked_folder.is_some() && 
+                    column_linked_folder.unwrap().to_lowercase() == target_folder_name.to_lowercase() {
+                    column.insert(String::from(ON_DELETE), String::from(ON_DELETE_SET_NULL));
+                }
+                columns_new.push(column);
+            }
+            linked_folder_data.insert(COLUMNS.to_string(), columns_new);
+            linked_folder.data = Some(linked_folder_data);
+            db_folder.update(&linked_folder)?;
+        }
+        let mut db_folder_item = TreeFolderItem::defaults(
+            space_database.connection_pool.clone(),
+            home_dir.unwrap_or_default().as_str(),
+            &account_id,
+            context.space_id,
+            context.site_id.clone(),
+            target_folder_id.as_str(),
+            db_folder,
+        )?;
+        db_folder_item.drop_links()?;
+        db_folder_item.drop_trees()?;
+        db_folder.delete(&target_folder_id)?;
+        db_folder_item.drop_files()?;
+        return Ok(())
     }
-
-}
-
-#[derive(Debug, Clone)]
-pub struct ListFoldersStatement {
 }
 
-impl<'gb> StatementCompiler<'gb, ()> for ListFoldersStatement {
+impl<'gb> StatementCompiler<'gb, CopyFolderCompiledStmt> for CopyFolderStatement {
 
     fn compile(
         &self, 
         statement_text: &String
-    ) -> Result<(), Vec<PlanetError>> {
-        let expr = &RE_LIST_FOLDERS;
-        let check = expr.is_match(&statement_text);
+    ) -> Result<CopyFolderCompiledStmt, Vec<PlanetError>> {
+        let expr = &RE_COPY_FOLDER;
         let mut errors: Vec<PlanetError> = Vec::new();
-        // This match is already executed on resolution operation. Here we include for consistency, would really
-        // apply if statement is more complex than LIST FOLDERS; with more parameters.
-        if !check {
+        let captures = expr.captures(&statement_text);
+        if captures.is_none() {
             let error = PlanetError::new(
                 500, 
                 Some(
-                    tr!("List folder syntax not valid.")
+                    tr!("Copy folder syntax not valid.")
                 ),
             );
             errors.push(error);
             return Err(errors)
         }
-        return Ok(())
+        let captures = captures.unwrap();
+        let folder_name = captures.name("FolderName").unwrap().as_str().trim().to_string();
+        let target_folder_name = captures.name("TargetFolderName").unwrap().as_str().trim().to_string();
+        let with_data = captures.name("WithData").is_some();
+        if folder_name.to_lowercase() == target_folder_name.to_lowercase() {
+            let error = PlanetError::new(
+                500, 
+                Some(
+                    tr!("Target folder must be different from folder to copy.")
+                ),
+            );
+            errors.push(error);
+            return Err(errors)
+        }
+        let statement = CopyFolderCompiledStmt{
+            folder_name: folder_name,
+            target_folder_name: target_folder_name,
+            with_data: with_data,
+        };
+        return Ok(statement)
     }
 }
 
-impl<'gb> Statement<'gb> for ListFoldersStatement {
+impl<'gb> Statement<'gb> for CopyFolderStatement {
 
     fn run(
         &self,
@@ -1823,6 +3591,7 @@ impl<'gb> Statement<'gb> for ListFoldersStatement {
         space_database: &SpaceDatabase,
         statement_text: &String,
     ) -> Result<Vec<yaml_rust::Yaml>, Vec<PlanetError>> {
+        let t_1 = Instant::now();
         let space_database = space_database.clone();
         let context = env.context;
         let planet_context = env.planet_context;
@@ -1832,105 +3601,296 @@ impl<'gb> Statement<'gb> for ListFoldersStatement {
             let errors = statement.unwrap_err();
             return Err(errors)
         }
-        let _statement = statement.unwrap();
+        let statement = statement.unwrap();
+        let folder_name = statement.folder_name.clone();
+        let target_folder_name = statement.target_folder_name.clone();
         let home_dir = planet_context.home_path.clone();
         let account_id = context.account_id.clone().unwrap_or_default();
         let space_id = context.space_id;
         let site_id = context.site_id.clone();
         let result: Result<TreeFolder, PlanetError> = TreeFolder::defaults(
             space_database.connection_pool.clone(),
-            Some(home_dir.unwrap_or_default().as_str()),
+            Some(home_dir.clone().unwrap_or_default().as_str()),
             Some(&account_id),
             Some(space_id),
-            site_id,
+            site_id.clone(),
         );
-        match result {
-            Ok(_) => {
-                let result = result.unwrap();
-                let items = result.list();
-                if items.is_err() {
-                    let error = items.unwrap_err();
+        if result.is_err() {
+            let error = result.unwrap_err();
+            errors.push(error);
+            return Err(errors)
+        }
+        let db_folder = result.unwrap();
+        let folder = db_folder.get_by_name(folder_name.as_str());
+        if folder.is_err() {
+            let error = folder.unwrap_err();
+            errors.push(error);
+            return Err(errors)
+        }
+        let folder = folder.unwrap();
+        if folder.is_none() {
+            let error = PlanetError::new(
+                500, 
+                Some(tr!("Folder \"{}\" not found.", &folder_name)),
+            );
+            errors.push(error);
+            return Err(errors)
+        }
+        let folder = folder.unwrap();
+        let folder_id = folder.id.clone().unwrap_or_default();
+        let target_folder = db_folder.get_by_name(target_folder_name.as_str());
+        if target_folder.is_ok() && target_folder.unwrap().is_some() {
+            let error = PlanetError::new(
+                500, 
+                Some(tr!("Folder \"{}\" already exists.", &target_folder_name)),
+            );
+            errors.push(error);
+            return Err(errors)
+        }
+
+        // Schema, fresh column ids. Formulas and reference configs point to column ids, so these are
+        // remapped as well
+        let folder_data = folder.data.clone().unwrap_or_default();
+        let mut column_id_map: BTreeMap<String, String> = BTreeMap::new();
+        let columns = folder_data.get(COLUMNS);
+        if columns.is_some() {
+            let columns = columns.unwrap();
+            for column in columns {
+                let column_id = column.get(ID);
+                if column_id.is_some() {
+                    column_id_map.insert(column_id.unwrap().clone(), generate_id().unwrap_or_default());
+                }
+            }
+        }
+        let mut data = CopyFolderStatement::remap_data(&folder_data, &column_id_map, true);
+        let columns = data.get(COLUMNS);
+        if columns.is_some() {
+            let columns = columns.unwrap();
+            let mut columns_new: Vec<BTreeMap<String, String>> = Vec::new();
+            for column in columns {
+                let mut column = column.clone();
+                let column_type = column.get(COLUMN_TYPE).unwrap().clone();
+                let linked_folder = column.get(LINKED_FOLDER);
+                if column_type.as_str() == COLUMN_TYPE_LINK && linked_folder.is_some() {
+                    let linked_folder = linked_folder.unwrap().clone();
+                    if linked_folder.to_lowercase() == folder_name.to_lowercase() {
+                        column.insert(LINKED_FOLDER.to_string(), target_folder_name.clone());
+                    }
+                }
+                columns_new.push(column);
+            }
+            data.insert(COLUMNS.to_string(), columns_new);
+        }
+        let db_data = DbData::defaults(
+            &target_folder_name, 
+            Some(data),
+            folder.options.clone(),
+            None,
+            folder.context.clone(),
+            folder.sub_folders.clone(),
+        );
+        if db_data.is_err() {
+            let error = db_data.unwrap_err();
+            errors.push(error);
+            return Err(errors)
+        }
+        let mut db_data = db_data.unwrap();
+        db_data.routing = folder.routing.clone();
+        let response = db_folder.create(&db_data);
+        if response.is_err() {
+            let error = response.unwrap_err();
+            errors.push(error);
+            return Err(errors)
+        }
+        let target_folder = response.unwrap();
+
+        // Links to other folders need the reverse column for the new folder
+        let target_folder_data = target_folder.data.clone().unwrap_or_default();
+        let target_columns = target_folder_data.get(COLUMNS).unwrap().clone();
+        for column in target_columns.clone() {
+            let column_type = column.get(COLUMN_TYPE).unwrap().clone();
+            if column_type.as_str() != COLUMN_TYPE_LINK {
+                continue
+            }
+            let linked_folder_name = column.get(LINKED_FOLDER).unwrap().clone();
+            if linked_folder_name.to_lowercase() == target_folder_name.to_lowercase() {
+                continue
+            }
+            let linked_folder = db_folder.get_by_name(&linked_folder_name);
+            if linked_folder.is_err() {
+                continue
+            }
+            let linked_folder = linked_folder.unwrap();
+            if linked_folder.is_none() {
+                continue
+            }
+            let mut linked_folder = linked_folder.unwrap();
+            let column_id = column.get(ID).unwrap().clone();
+            let mut column_id_source = String::from("");
+            for (id, id_new) in &column_id_map {
+                if *id_new == column_id {
+                    column_id_source = id.clone();
+                }
+            }
+            let mut linked_folder_data = linked_folder.data.clone().unwrap_or_default();
+            let mut linked_columns = linked_folder_data.get(COLUMNS).unwrap().clone();
+            let mut remote_column_map = column.clone();
+            remote_column_map.insert(String::from(MANY), String::from(TRUE));
+            remote_column_map.remove(DELETE_ON_LINK_DROP);
+            remote_column_map.insert(String::from(ON_DELETE), String::from(ON_DELETE_SET_NULL));
+            let mut names: Vec<String> = Vec::new();
+            for linked_column in &linked_columns {
+                let linked_column_id = linked_column.get(ID).unwrap().clone();
+                if linked_column_id == column_id_source {
+                    remote_column_map = linked_column.clone();
+                }
+                names.push(linked_column.get(NAME).unwrap().to_lowercase());
+            }
+            let mut remote_column_name = target_folder_name.clone();
+            let mut count = 2;
+            while names.contains(&remote_column_name.to_lowercase()) {
+                remote_column_name = format!("{} {}", &target_folder_name, count);
+                count += 1;
+            }
+            remote_column_map.insert(String::from(ID), column_id.clone());
+            remote_column_map.insert(String::from(NAME), remote_column_name);
+            remote_column_map.insert(String::from(LINKED_FOLDER), target_folder_name.clone());
+            linked_columns.push(remote_column_map);
+            linked_folder_data.insert(COLUMNS.to_string(), linked_columns);
+            linked_folder.data = Some(linked_folder_data);
+            let result = db_folder.update(&linked_folder);
+            if result.is_err() {
+                let error = result.unwrap_err();
+                errors.push(error);
+                let result = CopyFolderStatement::drop_target(
+                    env, 
+                    &space_database, 
+                    &db_folder, 
+                    &target_folder
+                );
+                if result.is_err() {
+                    let error = result.unwrap_err();
                     errors.push(error);
-                    return Err(errors)
                 }
-                let items = items.unwrap();
-                let mut items_mini: Vec<DbDataMini> = Vec::new();
-                for item in items {
-                    let item_mini = DbDataMini{
-                        id: item.id,
-                        slug: item.slug,
-                        name: item.name,
-                        routing: item.routing
-                    };
-                    items_mini.push(item_mini);
+                return Err(errors)
+            }
+        }
+
+        if statement.with_data {
+            let mut result = CopyFolderStatement::copy_items(
+                env, 
+                &space_database, 
+                &db_folder, 
+                &folder_id, 
+                &target_folder, 
+                &column_id_map
+            );
+            if result.is_ok() {
+                // Generated numbers continue after the ones copied items already have
+                let target_folder_id = target_folder.id.clone().unwrap_or_default();
+                let result_sequences = db_folder.copy_sequences(
+                    &folder_id, 
+                    &db_folder, 
+                    &target_folder_id, 
+                    &column_id_map
+                );
+                if result_sequences.is_err() {
+                    result = Err(vec![result_sequences.unwrap_err()]);
                 }
-                let response_coded = serde_yaml::to_string(&items_mini);
-                if response_coded.is_err() {
-                    let error = PlanetError::new(
-                        500, 
-                        Some(tr!("Error encoding statement response.")),
-                    );
+            }
+            if result.is_err() {
+                let mut errors = result.unwrap_err();
+                let result = CopyFolderStatement::drop_target(
+                    env, 
+                    &space_database, 
+                    &db_folder, 
+                    &target_folder
+                );
+                if result.is_err() {
+                    let error = result.unwrap_err();
                     errors.push(error);
-                    return Err(errors)
                 }
-                let response = response_coded.unwrap();
-                let yaml_response = yaml_rust::YamlLoader::load_from_str(
-                    response.as_str()
-                ).unwrap();
-                let yaml_response = yaml_response.clone();
-                return Ok(yaml_response)
-            }
-            Err(error) => {
-                errors.push(error);
-                Err(errors)
+                return Err(errors)
             }
         }
+        eprintln!("CopyFolder.run :: time: {} ms", &t_1.elapsed().as_millis());
+        let response_coded = serde_yaml::to_string(&target_folder);
+        if response_coded.is_err() {
+            let error = PlanetError::new(
+                500, 
+                Some(tr!("Error encoding statement response.")),
+            );
+            errors.push(error);
+            return Err(errors)
+        }
+        let response = response_coded.unwrap();
+        let yaml_response = yaml_rust::YamlLoader::load_from_str(
+            response.as_str()
+        ).unwrap();
+        return Ok(yaml_response)
     }
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MoveFolderCompiledStmt {
+    pub folder_name: String,
+    pub space_id: String,
+    pub site_id: Option<String>,
+    pub allow_cross_space_links: bool,
+}
+
 #[derive(Debug, Clone)]
-pub struct DescribeFolderStatement {
+pub struct MoveFolderStatement {
 }
 
-impl<'gb> StatementCompiler<'gb, String> for DescribeFolderStatement {
+impl<'gb> StatementCompiler<'gb, MoveFolderCompiledStmt> for MoveFolderStatement {
 
     fn compile(
         &self, 
         statement_text: &String
-    ) -> Result<String, Vec<PlanetError>> {
-        let expr = &RE_DESCRIBE_FOLDER;
-        let check = expr.is_match(&statement_text);
+    ) -> Result<MoveFolderCompiledStmt, Vec<PlanetError>> {
+        let expr = &RE_MOVE_FOLDER;
         let mut errors: Vec<PlanetError> = Vec::new();
-        if !check {
+        let captures = expr.captures(&statement_text);
+        if captures.is_none() {
             let error = PlanetError::new(
                 500, 
                 Some(
-                    tr!("Describe folder syntax not valid.")
+                    tr!("Move folder syntax not valid.")
                 ),
             );
             errors.push(error);
             return Err(errors)
         }
-        let captures = expr.captures(&statement_text);
-        let folder_name: &str;
-        if captures.is_some() {
-            let captures = captures.unwrap();
-            folder_name = captures.name("FolderName").unwrap().as_str();
-            return Ok(folder_name.to_string().clone())
-        } else {
+        let captures = captures.unwrap();
+        let folder_name = captures.name("FolderName").unwrap().as_str().trim().to_string();
+        let space_id = captures.name("SpaceId").unwrap().as_str().to_string();
+        let mut site_id: Option<String> = None;
+        let site_id_wrap = captures.name("SiteId");
+        if site_id_wrap.is_some() {
+            site_id = Some(site_id_wrap.unwrap().as_str().to_string());
+        }
+        if space_id.as_str() == PRIVATE && site_id.is_some() {
             let error = PlanetError::new(
                 500, 
                 Some(
-                    tr!("Could not parse folder name from describe folder statement.")
+                    tr!("Private space does not belong to a site.")
                 ),
             );
             errors.push(error);
             return Err(errors)
         }
+        let statement = MoveFolderCompiledStmt{
+            folder_name: folder_name,
+            space_id: space_id,
+            site_id: site_id,
+            allow_cross_space_links: captures.name("AllowLinks").is_some(),
+        };
+        return Ok(statement)
     }
 }
 
-impl<'gb> Statement<'gb> for DescribeFolderStatement {
+impl<'gb> Statement<'gb> for MoveFolderStatement {
 
     fn run(
         &self,
@@ -1938,6 +3898,7 @@ impl<'gb> Statement<'gb> for DescribeFolderStatement {
         space_database: &SpaceDatabase,
         statement_text: &String,
     ) -> Result<Vec<yaml_rust::Yaml>, Vec<PlanetError>> {
+        let t_1 = Instant::now();
         let space_database = space_database.clone();
         let context = env.context;
         let planet_context = env.planet_context;
@@ -1947,102 +3908,347 @@ impl<'gb> Statement<'gb> for DescribeFolderStatement {
             let errors = statement.unwrap_err();
             return Err(errors)
         }
-        let folder_name = statement.unwrap();
-        // get folder from data layer
+        let statement = statement.unwrap();
+        let folder_name = statement.folder_name.clone();
         let home_dir = planet_context.home_path.clone();
         let account_id = context.account_id.clone().unwrap_or_default();
         let space_id = context.space_id;
         let site_id = context.site_id.clone();
         let result: Result<TreeFolder, PlanetError> = TreeFolder::defaults(
             space_database.connection_pool.clone(),
-            Some(home_dir.unwrap_or_default().as_str()),
+            Some(home_dir.clone().unwrap_or_default().as_str()),
             Some(&account_id),
             Some(space_id),
-            site_id,
+            site_id.clone(),
         );
-        match result {
-            Ok(_) => {
-                let result = result.unwrap();
-                let folder = result.get_by_name(folder_name.as_str());
-                if folder.is_ok() {
-                    let folder = folder.unwrap();
-                    if folder.is_some() {
-                        let folder = folder.unwrap();
-                        let response_coded = serde_yaml::to_string(&folder);
-                        if response_coded.is_err() {
-                            let error = PlanetError::new(
-                                500, 
-                                Some(tr!("Error encoding statement response.")),
-                            );
-                            errors.push(error);
-                            return Err(errors)
-                        }
-                        let response = response_coded.unwrap();
-                        let yaml_response = yaml_rust::YamlLoader::load_from_str(
-                            response.as_str()
-                        ).unwrap();
-                        let yaml_response = yaml_response.clone();
-                        return Ok(yaml_response)
-                    } else {
-                        // Folder could not be found
-                        let error = PlanetError::new(
-                            500, 
-                            Some(
-                                tr!("Folder \"{}\" not found.", &folder_name)
-                            ),
-                        );
-                        errors.push(error);
-                        return Err(errors)
+        if result.is_err() {
+            let error = result.unwrap_err();
+            errors.push(error);
+            return Err(errors)
+        }
+        let db_folder = result.unwrap();
+        let folder = db_folder.get_by_name(folder_name.as_str());
+        if folder.is_err() {
+            let error = folder.unwrap_err();
+            errors.push(error);
+            return Err(errors)
+        }
+        let folder = folder.unwrap();
+        if folder.is_none() {
+            let error = PlanetError::new(
+                500, 
+                Some(tr!("Folder \"{}\" not found.", &folder_name)),
+            );
+            errors.push(error);
+            return Err(errors)
+        }
+        let folder = folder.unwrap();
+        let folder_id = folder.id.clone().unwrap_or_default();
+
+        // Target space, site from context when not informed
+        let target_space_id = statement.space_id.clone();
+        let mut target_site_id: Option<String> = None;
+        if target_space_id.as_str() != PRIVATE {
+            target_site_id = statement.site_id.clone();
+            if target_site_id.is_none() {
+                target_site_id = site_id.clone();
+            }
+            if target_site_id.is_none() {
+                let error = PlanetError::new(
+                    500, 
+                    Some(tr!("Site is required to move folder into space \"{}\".", &target_space_id)),
+                );
+                errors.push(error);
+                return Err(errors)
+            }
+        }
+        if target_space_id.as_str() == space_id && target_site_id == site_id {
+            let error = PlanetError::new(
+                500, 
+                Some(tr!("Folder \"{}\" is already in space \"{}\".", &folder_name, &target_space_id)),
+            );
+            errors.push(error);
+            return Err(errors)
+        }
+
+        // Links would point to folders left in this space
+        if !statement.allow_cross_space_links {
+            let folder_data = folder.data.clone().unwrap_or_default();
+            let columns = folder_data.get(COLUMNS);
+            if columns.is_some() {
+                for column in columns.unwrap() {
+                    let column_type = column.get(COLUMN_TYPE).unwrap().clone();
+                    let linked_folder = column.get(LINKED_FOLDER);
+                    if column_type.as_str() != COLUMN_TYPE_LINK || linked_folder.is_none() {
+                        continue
                     }
-                } else {
-                    let error = folder.unwrap_err();
-                    let mut errors: Vec<PlanetError> = Vec::new();
+                    let linked_folder = linked_folder.unwrap();
+                    if linked_folder.to_lowercase() == folder_name.to_lowercase() {
+                        continue
+                    }
+                    let error = PlanetError::new(
+                        500, 
+                        Some(tr!(
+                            "Folder \"{}\" is linked with folder \"{}\", links cannot cross spaces. Use ALLOW CROSS SPACE LINKS to move folder.", 
+                            &folder_name, linked_folder
+                        )),
+                    );
                     errors.push(error);
-                    return Err(errors)
                 }
-            },
-            Err(error) => {
+            }
+            if errors.len() > 0 {
+                return Err(errors)
+            }
+        }
+
+        let target_space_database = space_database.with_space(
+            target_site_id.clone(), 
+            target_space_id.as_str(), 
+            home_dir.as_deref()
+        );
+        if target_space_database.is_err() {
+            let error = target_space_database.unwrap_err();
+            errors.push(error);
+            return Err(errors)
+        }
+        let target_space_database = target_space_database.unwrap();
+        let result: Result<TreeFolder, PlanetError> = TreeFolder::defaults(
+            target_space_database.connection_pool.clone(),
+            Some(home_dir.clone().unwrap_or_default().as_str()),
+            Some(&account_id),
+            Some(target_space_id.as_str()),
+            target_site_id.clone(),
+        );
+        if result.is_err() {
+            let error = result.unwrap_err();
+            errors.push(error);
+            return Err(errors)
+        }
+        let target_db_folder = result.unwrap();
+
+        // Routing for folder, items, index and files
+        let mut routing = folder.routing.clone().unwrap_or_default();
+        routing.insert(SPACE_ID.to_string(), target_space_id.clone());
+        if target_site_id.is_some() {
+            routing.insert(SITE_ID.to_string(), target_site_id.clone().unwrap());
+        } else {
+            routing.remove(SITE_ID);
+        }
+        let mut folder_moved = folder.clone();
+        folder_moved.routing = Some(routing.clone());
+        // Spaces in same site (or private workspace) keep folder schemas in same folders database under
+        // same folder id, schema routing is updated in place once items are moved.
+        let shared_folder_db = site_id == target_site_id;
+        if shared_folder_db {
+            let target_folder = target_db_folder.get_by_name(folder_name.as_str());
+            if target_folder.is_err() {
+                let error = target_folder.unwrap_err();
                 errors.push(error);
-                Err(errors)
+                return Err(errors)
+            }
+            if target_folder.unwrap().is_some() {
+                let error = PlanetError::new(
+                    500, 
+                    Some(tr!("Folder \"{}\" already exists in space \"{}\".", &folder_name, &target_space_id)),
+                );
+                errors.push(error);
+                return Err(errors)
+            }
+        } else {
+            let response = target_db_folder.create(&folder_moved);
+            if response.is_err() {
+                let error = response.unwrap_err();
+                errors.push(error);
+                return Err(errors)
+            }
+            folder_moved = response.unwrap();
+        }
+
+        let result: Result<TreeFolderItem, PlanetError> = TreeFolderItem::defaults(
+            space_database.connection_pool.clone(),
+            home_dir.clone().unwrap_or_default().as_str(),
+            &account_id,
+            space_id,
+            site_id.clone(),
+            folder_id.as_str(),
+            &db_folder,
+        );
+        if result.is_err() {
+            if !shared_folder_db {
+                let _ = target_db_folder.delete(&folder_id);
+            }
+            let error = result.unwrap_err();
+            errors.push(error);
+            return Err(errors)
+        }
+        let mut db_folder_item = result.unwrap();
+        let result: Result<TreeFolderItem, PlanetError> = TreeFolderItem::defaults(
+            target_space_database.connection_pool.clone(),
+            home_dir.clone().unwrap_or_default().as_str(),
+            &account_id,
+            target_space_id.as_str(),
+            target_site_id.clone(),
+            folder_id.as_str(),
+            &target_db_folder,
+        );
+        if result.is_err() {
+            if !shared_folder_db {
+                let _ = target_db_folder.delete(&folder_id);
+            }
+            let error = result.unwrap_err();
+            errors.push(error);
+            return Err(errors)
+        }
+        let mut target_folder_item = result.unwrap();
+        let result = db_folder_item.move_to(&mut target_folder_item, &routing);
+        if result.is_err() {
+            if !shared_folder_db {
+                let _ = target_db_folder.delete(&folder_id);
+            }
+            let error = result.unwrap_err();
+            errors.push(error);
+            return Err(errors)
+        }
+        if shared_folder_db {
+            let result = db_folder.update(&folder_moved);
+            if result.is_err() {
+                let error = result.unwrap_err();
+                errors.push(error);
+                return Err(errors)
+            }
+            folder_moved = result.unwrap();
+        } else {
+            let result = db_folder.delete(&folder_id);
+            if result.is_err() {
+                let error = result.unwrap_err();
+                errors.push(error);
+                return Err(errors)
             }
         }
+        eprintln!("MoveFolder.run :: time: {} ms", &t_1.elapsed().as_millis());
+        let response_coded = serde_yaml::to_string(&folder_moved);
+        if response_coded.is_err() {
+            let error = PlanetError::new(
+                500, 
+                Some(tr!("Error encoding statement response.")),
+            );
+            errors.push(error);
+            return Err(errors)
+        }
+        let response = response_coded.unwrap();
+        let yaml_response = yaml_rust::YamlLoader::load_from_str(
+            response.as_str()
+        ).unwrap();
+        return Ok(yaml_response)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ColumnCompiledStmt {
+    pub folder_name: String,
+    pub language: Option<LanguageConfig>,
+    pub text_search: Option<TextSearchConfig>,
+    pub name: Option<ColumnConfig>,
+    pub columns: Option<Vec<ColumnConfig>>,
+    pub sub_folders: Option<Vec<SubFolderConfig>>,
+}
+impl ColumnCompiledStmt {
+    pub fn defaults(folder_name: &String) -> Self {
+        let obj = Self{
+            folder_name: folder_name.clone(),
+            language: None,
+            text_search: None,
+            name: None,
+            columns: None,
+            sub_folders: None
+        };
+        return obj
     }
 }
 
 #[derive(Debug, Clone)]
-pub struct DropFolderStatement {
+pub struct AddColumnStatement {
 }
 
-impl<'gb> StatementCompiler<'gb, String> for DropFolderStatement {
+impl<'gb> StatementCompiler<'gb, ColumnCompiledStmt> for AddColumnStatement {
 
     fn compile(
         &self, 
         statement_text: &String
-    ) -> Result<String, Vec<PlanetError>> {
-        let expr = &RE_DROP_FOLDER;
+    ) -> Result<ColumnCompiledStmt, Vec<PlanetError>> {
+        let expr = &RE_ADD_COLUMN;
         let check = expr.is_match(&statement_text);
         let mut errors: Vec<PlanetError> = Vec::new();
         if !check {
             let error = PlanetError::new(
                 500, 
                 Some(
-                    tr!("Drop folder syntax not valid.")
+                    tr!("Add column syntax not valid.")
+                ),
+            );
+            errors.push(error);
+            return Err(errors)
+        }
+        let captures = expr.captures(&statement_text);
+        let config: &str;
+        let folder_name: &str;
+        if captures.is_none() {
+            let error = PlanetError::new(
+                500, 
+                Some(
+                    tr!("Could not parse add column statement.")
+                ),
+            );
+            errors.push(error);
+            return Err(errors)
+        }
+        let captures = captures.unwrap();
+        folder_name = captures.name("FolderName").unwrap().as_str();
+        config = captures.name("Config").unwrap().as_str();
+        let expr = &RE_ADD_COLUMN_CONFIG;
+        let columns = expr.captures_iter(&config);
+        if columns.count() > 1 {
+            let error = PlanetError::new(
+                500, 
+                Some(
+                    tr!("Only one column is supported for ADD COLUMN statement.")
                 ),
             );
             errors.push(error);
             return Err(errors)
         }
-        let captures = expr.captures(&statement_text);
-        let folder_name: &str;
-        if captures.is_some() {
-            let captures = captures.unwrap();
-            folder_name = captures.name("FolderName").unwrap().as_str();
-            return Ok(folder_name.to_string().clone())
+        let item = expr.captures(&config);
+        if item.is_some() {
+            let item = item.unwrap();
+            let column = item.name("Column");
+            let column_type = item.name("ColumnType");
+            let column_str = column.unwrap().as_str();
+            let column_type = column_type.unwrap().as_str();
+            let result = process_column(column_str, column_type, &item, None);
+            if result.is_ok() {
+                let column = result.unwrap();
+                let mut compiled = ColumnCompiledStmt::defaults(
+                    &folder_name.to_string()
+                );
+                let mut columns: Vec<ColumnConfig> = Vec::new();
+                columns.push(column);
+                compiled.columns = Some(columns);
+                return Ok(compiled)
+            } else {
+                let error = PlanetError::new(
+                    500, 
+                    Some(
+                        tr!("Could not parse config attributes for add column statement.")
+                    ),
+                );
+                errors.push(error);
+                return Err(errors)
+            }
         } else {
             let error = PlanetError::new(
                 500, 
                 Some(
-                    tr!("Could not parse folder name from drop folder statement.")
+                    tr!("Could not parse config detail for add column statement.")
                 ),
             );
             errors.push(error);
@@ -2051,7 +4257,7 @@ impl<'gb> StatementCompiler<'gb, String> for DropFolderStatement {
     }
 }
 
-impl<'gb> Statement<'gb> for DropFolderStatement {
+impl<'gb> Statement<'gb> for AddColumnStatement {
 
     fn run(
         &self,
@@ -2062,160 +4268,198 @@ impl<'gb> Statement<'gb> for DropFolderStatement {
         let space_database = space_database.clone();
         let context = env.context;
         let planet_context = env.planet_context;
-        let mut errors: Vec<PlanetError> = Vec::new();
         let statement = self.compile(statement_text);
         if statement.is_err() {
             let errors = statement.unwrap_err();
             return Err(errors)
         }
-        let folder_name = statement.unwrap();
-        // get folder from data layer
+        let mut errors: Vec<PlanetError> = Vec::new();
+        let column_compiled = statement.unwrap();
+        eprintln!("AddColumnStatement.run :: column_compiled: {:#?}", &column_compiled);
         let home_dir = planet_context.home_path.clone();
         let account_id = context.account_id.clone().unwrap_or_default();
         let space_id = context.space_id;
         let site_id = context.site_id.clone();
-        let mut site_id_alt: Option<String> = None;
-        if site_id.is_some() {
-            let site_id = site_id.clone().unwrap();
-            site_id_alt = Some(site_id.to_string());
-        }
         let result: Result<TreeFolder, PlanetError> = TreeFolder::defaults(
             space_database.connection_pool.clone(),
-            Some(home_dir.clone().unwrap_or_default().as_str()),
+            Some(home_dir.unwrap_or_default().as_str()),
             Some(&account_id),
             Some(space_id),
             site_id,
         );
-        match result {
-            Ok(_) => {
-                let db_folder = result.unwrap();
-                let folder = db_folder.get_by_name(folder_name.as_str());
-                if folder.is_ok() {
-                    let folder = folder.unwrap();
-                    if folder.is_some() {
-                        let folder = folder.unwrap();
-                        // Delete folder items
-                        let folder_id = folder.id.clone().unwrap();
-                        let result: Result<TreeFolderItem, PlanetError> = TreeFolderItem::defaults(
-                            space_database.connection_pool.clone(),
-                            home_dir.unwrap_or_default().as_str(),
-                            &account_id,
-                            space_id,
-                            site_id_alt,
-                            folder_id.as_str(),
-                            &db_folder,
-                        );
-                        if result.is_err() {
-                            let error = result.unwrap_err();
-                            errors.push(error);
-                            return Err(errors)
-                        }
-                        let mut db_folder_item = result.unwrap();
-                        let result = db_folder_item.drop_trees();
-                        if result.is_err() {
-                            let error = result.unwrap_err();
-                            errors.push(error);
-                            return Err(errors)
-                        }
-
-                        // Delete from folders.db
-                        let result = db_folder.delete(&folder_id);
-                        if result.is_err() {
-                            let error = result.unwrap_err();
-                            errors.push(error);
-                            return Err(errors)
-                        }
-
-                        // Delete files from OS dir "files"
-                        let result = db_folder_item.drop_files();
-                        if result.is_err() {
-                            let error = result.unwrap_err();
-                            errors.push(error);
-                            return Err(errors)
-                        }
-
-                        // Build Output
-                        let response_coded = serde_yaml::to_string(&folder);
-                        if response_coded.is_err() {
-                            let error = PlanetError::new(
-                                500, 
-                                Some(tr!("Error encoding statement response.")),
+        if result.is_ok() {
+            let db_folder = result.unwrap();
+            let folder_name = column_compiled.folder_name;
+            let folder = db_folder.get_by_name(folder_name.as_str());
+            if folder.is_ok() {
+                let folder = folder.unwrap();
+                if folder.is_some() {
+                    let mut folder = folder.unwrap();
+                    let data = folder.data;
+                    if data.is_some() {
+                        let mut data = data.unwrap();
+                        let column_list = data.get(COLUMNS);
+                        if column_list.is_some() {
+                            let mut column_list = column_list.unwrap().clone();
+                            let column = column_compiled.columns.unwrap()[0].clone();
+                            for column_item in column_list.clone() {
+                                let item_column_name = column_item.get(NAME);
+                                if item_column_name.is_some() {
+                                    let item_column_name = item_column_name.unwrap().clone();
+                                    let column_name = &column.name.clone().unwrap();
+                                    let column_name = column_name.clone();
+                                    if item_column_name.to_lowercase() == column_name.to_lowercase() {
+                                        let error = PlanetError::new(
+                                            500, 
+                                            Some(tr!("Column already exists with name \"{}\"", &column_name)),
+                                        );
+                                        errors.push(error);
+                                        return Err(errors)
+                                    }
+                                }
+                            }
+                            let mut columns_map: HashMap<String, ColumnConfig> = HashMap::new();
+                            let column_name = column.clone().name.unwrap_or_default();
+                            columns_map.insert(column_name, column.clone());
+                            let map = &column.create_config(
+                                planet_context,
+                                context,
+                                &columns_map,
+                                &db_folder,
+                                &folder_name,
+                                &space_database
                             );
-                            errors.push(error);
-                            return Err(errors)
+                            if map.is_err() {
+                                let error = map.clone().unwrap_err();
+                                errors.push(error);
+                            }
+                            let map = map.clone().unwrap();
+                            column_list.push(map);
+                            let map_list = &column.map_collections_db();
+                            if map_list.is_err() {
+                                let error = map_list.clone().unwrap_err();
+                                errors.push(error);
+                            }
+                            let map_list = map_list.clone().unwrap();
+                            let map_list = map_list.clone();
+                            data.extend(map_list);
+                            data.insert(COLUMNS.to_string(), column_list);
+                            // Update folder config
+                            folder.data = Some(data);
+                            let result = db_folder.update(&folder);
+                            // Build output
+                            if result.is_ok() {
+                                let folder = result.unwrap();
+                                let response_coded = serde_yaml::to_string(&folder);
+                                if response_coded.is_err() {
+                                    let error = PlanetError::new(
+                                        500, 
+                                        Some(tr!("Error encoding statement response.")),
+                                    );
+                                    errors.push(error);
+                                }
+                                let response = response_coded.unwrap();
+                                let yaml_response = yaml_rust::YamlLoader::load_from_str(
+                                    response.as_str()
+                                ).unwrap();
+                                let yaml_response = yaml_response.clone();
+                                return Ok(yaml_response)
+                            } else {
+                                let error = PlanetError::new(
+                                    500, 
+                                    Some(
+                                        tr!("Could not update folder on database.")
+                                    ),
+                                );
+                                errors.push(error);
+                            }    
                         }
-                        let response = response_coded.unwrap();
-                        let yaml_response = yaml_rust::YamlLoader::load_from_str(
-                            response.as_str()
-                        ).unwrap();
-                        let yaml_response = yaml_response.clone();
-                        return Ok(yaml_response)
                     } else {
                         let error = PlanetError::new(
                             500, 
                             Some(
-                                tr!("Folder \"{}\" not found.", &folder_name)
+                                tr!("Folder has no data.")
                             ),
                         );
                         errors.push(error);
-                        return Err(errors)
                     }
                 } else {
-                    let error = folder.unwrap_err();
-                    let mut errors: Vec<PlanetError> = Vec::new();
+                    let error = PlanetError::new(
+                        500, 
+                        Some(
+                            tr!("Folder not found.")
+                        ),
+                    );
                     errors.push(error);
                     return Err(errors)
                 }
-            },
-            Err(error) => {
-                errors.push(error);
-                Err(errors)
-            }
-        }
-    }
-}
-
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct ColumnCompiledStmt {
-    pub folder_name: String,
-    pub language: Option<LanguageConfig>,
-    pub text_search: Option<TextSearchConfig>,
-    pub name: Option<ColumnConfig>,
-    pub columns: Option<Vec<ColumnConfig>>,
-    pub sub_folders: Option<Vec<SubFolderConfig>>,
-}
-impl ColumnCompiledStmt {
-    pub fn defaults(folder_name: &String) -> Self {
-        let obj = Self{
-            folder_name: folder_name.clone(),
-            language: None,
-            text_search: None,
-            name: None,
-            columns: None,
-            sub_folders: None
-        };
-        return obj
+            } else {
+                let error = PlanetError::new(
+                    500, 
+                    Some(
+                        tr!("Error fetching folder by name.")
+                    ),
+                );
+                errors.push(error);
+            }
+        } else {
+            let error = PlanetError::new(
+                500, 
+                Some(
+                    tr!("Could not parse add column statement.")
+                ),
+            );
+            errors.push(error);
+        }
+        return Err(errors)
     }
 }
 
 #[derive(Debug, Clone)]
-pub struct AddColumnStatement {
+pub struct ModifyColumnStatement {
 }
 
-impl<'gb> StatementCompiler<'gb, ColumnCompiledStmt> for AddColumnStatement {
+impl ModifyColumnStatement {
+    fn rebuild_path_index(
+        &self,
+        env: &Environment,
+        space_database: &SpaceDatabase,
+        db_folder: &TreeFolder,
+        folder: &DbData,
+    ) -> Result<(), PlanetError> {
+        let home_dir = env.planet_context.home_path.clone().unwrap_or_default();
+        let account_id = env.context.account_id.clone().unwrap_or_default();
+        let space_id = env.context.space_id;
+        let site_id = env.context.site_id.clone();
+        let folder_id = folder.id.clone().unwrap();
+        let mut db_row: TreeFolderItem = TreeFolderItem::defaults(
+            space_database.connection_pool.clone(),
+            home_dir.as_str(),
+            &account_id,
+            space_id,
+            site_id,
+            folder_id.as_str(),
+            db_folder,
+        )?;
+        db_row.rebuild_path_index(folder)?;
+        return Ok(())
+    }
+}
+
+impl<'gb> StatementCompiler<'gb, ColumnCompiledStmt> for ModifyColumnStatement {
 
     fn compile(
         &self, 
         statement_text: &String
     ) -> Result<ColumnCompiledStmt, Vec<PlanetError>> {
-        let expr = &RE_ADD_COLUMN;
+        let expr = &RE_MODIFY_COLUMN;
         let check = expr.is_match(&statement_text);
         let mut errors: Vec<PlanetError> = Vec::new();
         if !check {
             let error = PlanetError::new(
                 500, 
                 Some(
-                    tr!("Add column syntax not valid.")
+                    tr!("Modify column syntax not valid.")
                 ),
             );
             errors.push(error);
@@ -2228,7 +4472,7 @@ impl<'gb> StatementCompiler<'gb, ColumnCompiledStmt> for AddColumnStatement {
             let error = PlanetError::new(
                 500, 
                 Some(
-                    tr!("Could not parse add column statement.")
+                    tr!("Could not parse modify column statement.")
                 ),
             );
             errors.push(error);
@@ -2237,34 +4481,46 @@ impl<'gb> StatementCompiler<'gb, ColumnCompiledStmt> for AddColumnStatement {
         let captures = captures.unwrap();
         folder_name = captures.name("FolderName").unwrap().as_str();
         config = captures.name("Config").unwrap().as_str();
-        let expr = &RE_ADD_COLUMN_CONFIG;
         let columns = expr.captures_iter(&config);
         if columns.count() > 1 {
             let error = PlanetError::new(
                 500, 
                 Some(
-                    tr!("Only one column is supported for ADD COLUMN statement.")
+                    tr!("Only one column is supported for MODIFY COLUMN statement.")
                 ),
             );
             errors.push(error);
             return Err(errors)
         }
+        let expr = &RE_MODIFY_COLUMN_CONFIG;
         let item = expr.captures(&config);
         if item.is_some() {
             let item = item.unwrap();
             let column = item.name("Column");
             let column_type = item.name("ColumnType");
-            let column_str = column.unwrap().as_str();
-            let column_type = column_type.unwrap().as_str();
-            let result = process_column(column_str, column_type, &item, None);
+            let name_config = item.name("NameConfig");
+            let column_str: &str;
+            let column_type_str: &str;
+            if name_config.is_some() {
+                column_str = NAME_CAMEL;
+                column_type_str = name_config.unwrap().as_str();
+            } else {
+                column_str = column.unwrap().as_str();
+                column_type_str = column_type.unwrap().as_str();
+            }
+            let result = process_column(column_str, column_type_str, &item, None);
             if result.is_ok() {
                 let column = result.unwrap();
                 let mut compiled = ColumnCompiledStmt::defaults(
                     &folder_name.to_string()
                 );
-                let mut columns: Vec<ColumnConfig> = Vec::new();
-                columns.push(column);
-                compiled.columns = Some(columns);
+                if name_config.is_some() {
+                    compiled.name = Some(column);
+                } else {
+                    let mut columns: Vec<ColumnConfig> = Vec::new();
+                    columns.push(column);
+                    compiled.columns = Some(columns);    
+                }
                 return Ok(compiled)
             } else {
                 let error = PlanetError::new(
@@ -2289,7 +4545,7 @@ impl<'gb> StatementCompiler<'gb, ColumnCompiledStmt> for AddColumnStatement {
     }
 }
 
-impl<'gb> Statement<'gb> for AddColumnStatement {
+impl<'gb> Statement<'gb> for ModifyColumnStatement {
 
     fn run(
         &self,
@@ -2307,7 +4563,6 @@ impl<'gb> Statement<'gb> for AddColumnStatement {
         }
         let mut errors: Vec<PlanetError> = Vec::new();
         let column_compiled = statement.unwrap();
-        eprintln!("AddColumnStatement.run :: column_compiled: {:#?}", &column_compiled);
         let home_dir = planet_context.home_path.clone();
         let account_id = context.account_id.clone().unwrap_or_default();
         let space_id = context.space_id;
@@ -2327,61 +4582,96 @@ impl<'gb> Statement<'gb> for AddColumnStatement {
                 let folder = folder.unwrap();
                 if folder.is_some() {
                     let mut folder = folder.unwrap();
+                    let column_config_map = ColumnConfig::get_column_config_map(
+                        &planet_context,
+                        &context,
+                        &folder
+                    ).unwrap();
+                    let mut columns_map: HashMap<String, ColumnConfig> = HashMap::new();
+                    for (k, v) in &column_config_map {
+                        columns_map.insert(k.clone(), v.clone());
+                    }
                     let data = folder.data;
                     if data.is_some() {
                         let mut data = data.unwrap();
                         let column_list = data.get(COLUMNS);
                         if column_list.is_some() {
-                            let mut column_list = column_list.unwrap().clone();
+                            let column_list = column_list.unwrap().clone();
                             let column = column_compiled.columns.unwrap()[0].clone();
-                            for column_item in column_list.clone() {
-                                let item_column_name = column_item.get(NAME);
-                                if item_column_name.is_some() {
-                                    let item_column_name = item_column_name.unwrap().clone();
-                                    let column_name = &column.name.clone().unwrap();
-                                    let column_name = column_name.clone();
-                                    if item_column_name.to_lowercase() == column_name.to_lowercase() {
-                                        let error = PlanetError::new(
-                                            500, 
-                                            Some(tr!("Column already exists with name \"{}\"", &column_name)),
-                                        );
+                            let column_name = column.clone().name.unwrap_or_default();
+                            let column_name_str = column_name.clone();
+                            let column_name_str = column_name_str.as_str();
+                            let mut column_list_new: Vec<BTreeMap<String, String>> = Vec::new();
+                            for column_item in column_list {
+                                let column_item_name = column_item.get(NAME).unwrap().clone();
+                                if column_item_name.to_lowercase().as_str() == column_name_str.to_lowercase() {
+                                    let mut column = column.clone();
+                                    column.secret_salt = column_item.get(SECRET_SALT).cloned();
+                                    column.secret_check = column_item.get(SECRET_CHECK).cloned();
+                                    columns_map.insert(column_name.clone(), column.clone());
+                                    let map = &column.create_config(
+                                        planet_context,
+                                        context,
+                                        &columns_map,
+                                        &db_folder,
+                                        &folder_name,
+                                        &space_database
+                                    );
+                                    if map.is_err() {
+                                        let error = map.clone().unwrap_err();
                                         errors.push(error);
                                         return Err(errors)
                                     }
+                                    let map = map.clone().unwrap();
+                                    column_list_new.push(map);
+                                } else {
+                                    column_list_new.push(column_item);
                                 }
                             }
-                            let mut columns_map: HashMap<String, ColumnConfig> = HashMap::new();
-                            let column_name = column.clone().name.unwrap_or_default();
-                            columns_map.insert(column_name, column.clone());
-                            let map = &column.create_config(
-                                planet_context,
-                                context,
-                                &columns_map,
-                                &db_folder,
-                                &folder_name,
-                                &space_database
-                            );
-                            if map.is_err() {
-                                let error = map.clone().unwrap_err();
-                                errors.push(error);
-                            }
-                            let map = map.clone().unwrap();
-                            column_list.push(map);
+                            data.insert(COLUMNS.to_string(), column_list_new);
                             let map_list = &column.map_collections_db();
                             if map_list.is_err() {
                                 let error = map_list.clone().unwrap_err();
                                 errors.push(error);
                             }
-                            let map_list = map_list.clone().unwrap();
-                            let map_list = map_list.clone();
+                            let mut map_list = map_list.clone().unwrap();
+                            // Select options keep their ids, so items are not orphaned
+                            let options_key = ColumnConfig::get_options_key(column_name_str);
+                            let options_new = map_list.get(&options_key).cloned();
+                            if options_new.is_some() {
+                                let mut options_existing: Option<Vec<BTreeMap<String, String>>> = None;
+                                for (key, options) in &data {
+                                    if key.to_lowercase() == options_key.to_lowercase() {
+                                        options_existing = Some(options.clone());
+                                    }
+                                }
+                                let options = SelectColumn::merge_options(
+                                    &options_new.unwrap(), 
+                                    options_existing.as_ref()
+                                );
+                                map_list.insert(options_key, options);
+                            }
                             data.extend(map_list);
-                            data.insert(COLUMNS.to_string(), column_list);
-                            // Update folder config
                             folder.data = Some(data);
                             let result = db_folder.update(&folder);
                             // Build output
                             if result.is_ok() {
                                 let folder = result.unwrap();
+                                // Items already in folder need their paths indexed
+                                let column_type = column.column_type.clone().unwrap_or_default();
+                                if column_type.as_str() == COLUMN_TYPE_OBJECT {
+                                    let result = self.rebuild_path_index(
+                                        env, 
+                                        &space_database, 
+                                        &db_folder, 
+                                        &folder
+                                    );
+                                    if result.is_err() {
+                                        let error = result.unwrap_err();
+                                        errors.push(error);
+                                        return Err(errors)
+                                    }
+                                }
                                 let response_coded = serde_yaml::to_string(&folder);
                                 if response_coded.is_err() {
                                     let error = PlanetError::new(
@@ -2438,119 +4728,457 @@ impl<'gb> Statement<'gb> for AddColumnStatement {
             let error = PlanetError::new(
                 500, 
                 Some(
-                    tr!("Could not parse add column statement.")
+                    tr!("Could not parse add column statement.")
+                ),
+            );
+            errors.push(error);
+        }
+        return Err(errors)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum SelectOptionsOperation {
+    Rename(String, String),
+    Merge(Vec<String>, String),
+    Reorder(Vec<String>),
+}
+
+#[derive(Debug, Clone)]
+pub struct ModifySelectOptionsStatement {
+}
+
+impl ModifySelectOptionsStatement {
+    fn parse_option_list(options: &str) -> Vec<String> {
+        let mut option_list: Vec<String> = Vec::new();
+        for option in options.split("|") {
+            let option = option.replace("\"", "").trim().to_string();
+            if option != "" {
+                option_list.push(option);
+            }
+        }
+        return option_list
+    }
+    fn find_option(options: &Vec<BTreeMap<String, String>>, name: &String) -> Option<usize> {
+        let mut index: usize = 0;
+        for option in options {
+            let value = option.get(VALUE).unwrap();
+            if value.to_lowercase() == name.to_lowercase() {
+                return Some(index)
+            }
+            index += 1;
+        }
+        return None
+    }
+    fn replace_item_options(
+        &self,
+        env: &Environment,
+        space_database: &SpaceDatabase,
+        db_folder: &TreeFolder,
+        folder: &DbData,
+        column_id: &String,
+        option_map: &BTreeMap<String, String>,
+    ) -> Result<usize, PlanetError> {
+        let home_dir = env.planet_context.home_path.clone().unwrap_or_default();
+        let account_id = env.context.account_id.clone().unwrap_or_default();
+        let space_id = env.context.space_id;
+        let site_id = env.context.site_id.clone();
+        let folder_id = folder.id.clone().unwrap();
+        let mut db_row: TreeFolderItem = TreeFolderItem::defaults(
+            space_database.connection_pool.clone(),
+            home_dir.as_str(),
+            &account_id,
+            space_id,
+            site_id,
+            folder_id.as_str(),
+            db_folder,
+        )?;
+        let items = db_row.get_all_items()?;
+        let mut number_items: usize = 0;
+        for mut item in items {
+            let data = item.data.clone();
+            if data.is_none() {
+                continue
+            }
+            let mut data = data.unwrap();
+            let values = data.get(column_id);
+            if values.is_none() {
+                continue
+            }
+            let values = values.unwrap().clone();
+            let mut values_new: Vec<BTreeMap<String, String>> = Vec::new();
+            let mut changed = false;
+            for value_map in values {
+                let mut value_map = value_map.clone();
+                let value = value_map.get(VALUE).cloned().unwrap_or_default();
+                let mut ids: Vec<String> = Vec::new();
+                for option_id in value.split(",") {
+                    let option_id = option_id.trim().to_string();
+                    let mut option_id_new = option_id.clone();
+                    let option_target = option_map.get(&option_id);
+                    if option_target.is_some() {
+                        option_id_new = option_target.unwrap().clone();
+                        changed = true;
+                    }
+                    if !ids.contains(&option_id_new) {
+                        ids.push(option_id_new);
+                    }
+                }
+                value_map.insert(VALUE.to_string(), ids.join(","));
+                values_new.push(value_map);
+            }
+            if changed {
+                data.insert(column_id.clone(), values_new);
+                item.data = Some(data);
+                db_row.update(&item)?;
+                number_items += 1;
+            }
+        }
+        return Ok(number_items)
+    }
+}
+
+impl<'gb> StatementCompiler<'gb, (String, String, SelectOptionsOperation)> for ModifySelectOptionsStatement {
+
+    fn compile(
+        &self, 
+        statement_text: &String
+    ) -> Result<(String, String, SelectOptionsOperation), Vec<PlanetError>> {
+        let expr = &RE_MODIFY_SELECT_OPTIONS;
+        let mut errors: Vec<PlanetError> = Vec::new();
+        let captures = expr.captures(&statement_text);
+        if captures.is_none() {
+            let error = PlanetError::new(
+                500, 
+                Some(
+                    tr!("Modify column options syntax not valid.")
+                ),
+            );
+            errors.push(error);
+            return Err(errors)
+        }
+        let captures = captures.unwrap();
+        let folder_name = captures.name("FolderName").unwrap().as_str().trim().to_string();
+        let column_name = captures.name("Column").unwrap().as_str().trim().to_string();
+        let rename_option = captures.name("RenameOption");
+        let merge_options = captures.name("MergeOptions");
+        let reorder_options = captures.name("ReorderOptions");
+        let operation: SelectOptionsOperation;
+        if rename_option.is_some() {
+            let rename_option = rename_option.unwrap().as_str().trim().to_string();
+            let rename_to = captures.name("RenameTo").unwrap().as_str().trim().to_string();
+            operation = SelectOptionsOperation::Rename(rename_option, rename_to);
+        } else if merge_options.is_some() {
+            let merge_options = ModifySelectOptionsStatement::parse_option_list(
+                merge_options.unwrap().as_str()
+            );
+            let merge_into = captures.name("MergeInto").unwrap().as_str().trim().to_string();
+            operation = SelectOptionsOperation::Merge(merge_options, merge_into);
+        } else if reorder_options.is_some() {
+            let reorder_options = ModifySelectOptionsStatement::parse_option_list(
+                reorder_options.unwrap().as_str()
+            );
+            operation = SelectOptionsOperation::Reorder(reorder_options);
+        } else {
+            let error = PlanetError::new(
+                500, 
+                Some(
+                    tr!("Could not parse modify column options statement.")
+                ),
+            );
+            errors.push(error);
+            return Err(errors)
+        }
+        return Ok(
+            (
+                folder_name,
+                column_name,
+                operation
+            )
+        )
+    }
+}
+
+impl<'gb> Statement<'gb> for ModifySelectOptionsStatement {
+
+    fn run(
+        &self,
+        env: &'gb Environment<'gb>,
+        space_database: &SpaceDatabase,
+        statement_text: &String,
+    ) -> Result<Vec<yaml_rust::Yaml>, Vec<PlanetError>> {
+        let space_database = space_database.clone();
+        let context = env.context;
+        let planet_context = env.planet_context;
+        let statement = self.compile(statement_text);
+        if statement.is_err() {
+            let errors = statement.unwrap_err();
+            return Err(errors)
+        }
+        let mut errors: Vec<PlanetError> = Vec::new();
+        let (folder_name, column_name, operation) = statement.unwrap();
+        let home_dir = planet_context.home_path.clone();
+        let account_id = context.account_id.clone().unwrap_or_default();
+        let space_id = context.space_id;
+        let site_id = context.site_id.clone();
+        let db_folder = TreeFolder::defaults(
+            space_database.connection_pool.clone(),
+            Some(home_dir.unwrap_or_default().as_str()),
+            Some(&account_id),
+            Some(space_id),
+            site_id,
+        );
+        if db_folder.is_err() {
+            errors.push(db_folder.unwrap_err());
+            return Err(errors)
+        }
+        let db_folder = db_folder.unwrap();
+        let folder = db_folder.get_by_name(folder_name.as_str());
+        if folder.is_err() {
+            errors.push(folder.unwrap_err());
+            return Err(errors)
+        }
+        let folder = folder.unwrap();
+        if folder.is_none() {
+            let error = PlanetError::new(
+                500, 
+                Some(
+                    tr!("Folder not found.")
+                ),
+            );
+            errors.push(error);
+            return Err(errors)
+        }
+        let mut folder = folder.unwrap();
+        let column = TreeFolder::get_column_by_name(&column_name, &folder);
+        if column.is_err() {
+            errors.push(column.unwrap_err());
+            return Err(errors)
+        }
+        let column = column.unwrap();
+        let column_id = column.get(ID).unwrap().clone();
+        let column_type = column.get(COLUMN_TYPE).cloned().unwrap_or_default();
+        if column_type != COLUMN_TYPE_SELECT {
+            let error = PlanetError::new(
+                500, 
+                Some(
+                    tr!("Column \"{}\" is not a select column.", &column_name)
                 ),
             );
             errors.push(error);
+            return Err(errors)
         }
-        return Err(errors)
-    }
-}
-
-#[derive(Debug, Clone)]
-pub struct ModifyColumnStatement {
-}
-
-impl<'gb> StatementCompiler<'gb, ColumnCompiledStmt> for ModifyColumnStatement {
-
-    fn compile(
-        &self, 
-        statement_text: &String
-    ) -> Result<ColumnCompiledStmt, Vec<PlanetError>> {
-        let expr = &RE_MODIFY_COLUMN;
-        let check = expr.is_match(&statement_text);
-        let mut errors: Vec<PlanetError> = Vec::new();
-        if !check {
+        let column_name = column.get(NAME).unwrap().clone();
+        let mut data = folder.data.clone().unwrap_or_default();
+        let options_key = ColumnConfig::get_options_key(column_name.as_str());
+        let mut options = SelectColumn::sort_options(
+            &data.get(&options_key).cloned().unwrap_or_default()
+        );
+        // Option id => Option id, for items with merged options
+        let mut option_map: BTreeMap<String, String> = BTreeMap::new();
+        match operation {
+            SelectOptionsOperation::Rename(option_name, option_name_new) => {
+                let index = ModifySelectOptionsStatement::find_option(&options, &option_name);
+                if index.is_none() {
+                    errors.push(
+                        PlanetError::new(
+                            500, 
+                            Some(tr!("Option \"{}\" not found in column \"{}\".", &option_name, &column_name)),
+                        )
+                    );
+                    return Err(errors)
+                }
+                let index = index.unwrap();
+                let index_new = ModifySelectOptionsStatement::find_option(&options, &option_name_new);
+                if index_new.is_some() && index_new.unwrap() != index {
+                    errors.push(
+                        PlanetError::new(
+                            500, 
+                            Some(tr!("Option \"{}\" already exists in column \"{}\".", &option_name_new, &column_name)),
+                        )
+                    );
+                    return Err(errors)
+                }
+                options[index].insert(VALUE.to_string(), option_name_new);
+            },
+            SelectOptionsOperation::Merge(option_names, option_name_into) => {
+                let index_into = ModifySelectOptionsStatement::find_option(&options, &option_name_into);
+                if index_into.is_none() {
+                    errors.push(
+                        PlanetError::new(
+                            500, 
+                            Some(tr!("Option \"{}\" not found in column \"{}\".", &option_name_into, &column_name)),
+                        )
+                    );
+                    return Err(errors)
+                }
+                let option_id_into = options[index_into.unwrap()].get(KEY).unwrap().clone();
+                for option_name in &option_names {
+                    let index = ModifySelectOptionsStatement::find_option(&options, option_name);
+                    if index.is_none() {
+                        errors.push(
+                            PlanetError::new(
+                                500, 
+                                Some(tr!("Option \"{}\" not found in column \"{}\".", option_name, &column_name)),
+                            )
+                        );
+                        return Err(errors)
+                    }
+                    let option_id = options[index.unwrap()].get(KEY).unwrap().clone();
+                    if option_id != option_id_into {
+                        option_map.insert(option_id, option_id_into.clone());
+                    }
+                }
+                let mut options_new: Vec<BTreeMap<String, String>> = Vec::new();
+                for option in options {
+                    let option_id = option.get(KEY).unwrap();
+                    if option_map.get(option_id).is_none() {
+                        options_new.push(option);
+                    }
+                }
+                options = options_new;
+            },
+            SelectOptionsOperation::Reorder(option_names) => {
+                let mut options_new: Vec<BTreeMap<String, String>> = Vec::new();
+                for option_name in &option_names {
+                    let index = ModifySelectOptionsStatement::find_option(&options, option_name);
+                    if index.is_none() {
+                        errors.push(
+                            PlanetError::new(
+                                500, 
+                                Some(tr!("Option \"{}\" not found in column \"{}\".", option_name, &column_name)),
+                            )
+                        );
+                        return Err(errors)
+                    }
+                    options_new.push(options.remove(index.unwrap()));
+                }
+                // Options not listed keep their order after listed ones
+                options_new.extend(options);
+                options = options_new;
+            },
+        }
+        let mut option_names: Vec<String> = Vec::new();
+        let mut order: usize = 0;
+        for option in options.iter_mut() {
+            option.insert(SELECT_OPTION_ORDER.to_string(), order.to_string());
+            if !SelectColumn::is_option_archived(option) {
+                option_names.push(option.get(VALUE).unwrap().clone());
+            }
+            order += 1;
+        }
+        data.insert(options_key, options);
+        // Column config keeps the option names
+        let column_list = data.get(COLUMNS).cloned().unwrap_or_default();
+        let mut column_list_new: Vec<BTreeMap<String, String>> = Vec::new();
+        for mut column_item in column_list {
+            let column_item_id = column_item.get(ID).cloned().unwrap_or_default();
+            if column_item_id == column_id {
+                let options_yaml = serde_yaml::to_string(&option_names);
+                if options_yaml.is_ok() {
+                    column_item.insert(OPTIONS.to_string(), options_yaml.unwrap());
+                }
+            }
+            column_list_new.push(column_item);
+        }
+        data.insert(COLUMNS.to_string(), column_list_new);
+        folder.data = Some(data);
+        if option_map.len() > 0 {
+            let result = self.replace_item_options(
+                env, 
+                &space_database, 
+                &db_folder, 
+                &folder, 
+                &column_id, 
+                &option_map
+            );
+            if result.is_err() {
+                errors.push(result.unwrap_err());
+                return Err(errors)
+            }
+        }
+        let result = db_folder.update(&folder);
+        if result.is_err() {
             let error = PlanetError::new(
                 500, 
                 Some(
-                    tr!("Modify column syntax not valid.")
+                    tr!("Could not update folder on database.")
                 ),
             );
             errors.push(error);
             return Err(errors)
         }
-        let captures = expr.captures(&statement_text);
-        let config: &str;
-        let folder_name: &str;
-        if captures.is_none() {
+        let folder = result.unwrap();
+        let response_coded = serde_yaml::to_string(&folder);
+        if response_coded.is_err() {
             let error = PlanetError::new(
                 500, 
-                Some(
-                    tr!("Could not parse modify column statement.")
-                ),
+                Some(tr!("Error encoding statement response.")),
             );
             errors.push(error);
             return Err(errors)
         }
-        let captures = captures.unwrap();
-        folder_name = captures.name("FolderName").unwrap().as_str();
-        config = captures.name("Config").unwrap().as_str();
-        let columns = expr.captures_iter(&config);
-        if columns.count() > 1 {
+        let response = response_coded.unwrap();
+        let yaml_response = yaml_rust::YamlLoader::load_from_str(
+            response.as_str()
+        ).unwrap();
+        return Ok(yaml_response)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TagsResult {
+    pub tags: Vec<String>,
+    pub items: Option<usize>,
+}
+impl TagsResult {
+    pub fn serialize_yaml(&self) -> Result<Vec<yaml_rust::Yaml>, Vec<PlanetError>> {
+        let mut errors: Vec<PlanetError> = Vec::new();
+        let response_coded = serde_yaml::to_string(&self);
+        if response_coded.is_err() {
             let error = PlanetError::new(
                 500, 
-                Some(
-                    tr!("Only one column is supported for MODIFY COLUMN statement.")
-                ),
+                Some(tr!("Error encoding statement response.")),
             );
             errors.push(error);
             return Err(errors)
         }
-        let expr = &RE_MODIFY_COLUMN_CONFIG;
-        let item = expr.captures(&config);
-        if item.is_some() {
-            let item = item.unwrap();
-            let column = item.name("Column");
-            let column_type = item.name("ColumnType");
-            let name_config = item.name("NameConfig");
-            let column_str: &str;
-            let column_type_str: &str;
-            if name_config.is_some() {
-                column_str = NAME_CAMEL;
-                column_type_str = name_config.unwrap().as_str();
-            } else {
-                column_str = column.unwrap().as_str();
-                column_type_str = column_type.unwrap().as_str();
-            }
-            let result = process_column(column_str, column_type_str, &item, None);
-            if result.is_ok() {
-                let column = result.unwrap();
-                let mut compiled = ColumnCompiledStmt::defaults(
-                    &folder_name.to_string()
-                );
-                if name_config.is_some() {
-                    compiled.name = Some(column);
-                } else {
-                    let mut columns: Vec<ColumnConfig> = Vec::new();
-                    columns.push(column);
-                    compiled.columns = Some(columns);    
-                }
-                return Ok(compiled)
-            } else {
-                let error = PlanetError::new(
-                    500, 
-                    Some(
-                        tr!("Could not parse config attributes for add column statement.")
-                    ),
-                );
-                errors.push(error);
-                return Err(errors)
-            }
-        } else {
+        let response = response_coded.unwrap();
+        let yaml_response = yaml_rust::YamlLoader::load_from_str(
+            response.as_str()
+        ).unwrap();
+        return Ok(yaml_response)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ListTagsStatement {
+}
+
+impl<'gb> StatementCompiler<'gb, ()> for ListTagsStatement {
+
+    fn compile(
+        &self, 
+        statement_text: &String
+    ) -> Result<(), Vec<PlanetError>> {
+        let expr = &RE_LIST_TAGS;
+        let check = expr.is_match(&statement_text);
+        let mut errors: Vec<PlanetError> = Vec::new();
+        if !check {
             let error = PlanetError::new(
                 500, 
                 Some(
-                    tr!("Could not parse config detail for add column statement.")
+                    tr!("List tags syntax not valid.")
                 ),
             );
             errors.push(error);
             return Err(errors)
         }
+        return Ok(())
     }
 }
 
-impl<'gb> Statement<'gb> for ModifyColumnStatement {
+impl<'gb> Statement<'gb> for ListTagsStatement {
 
     fn run(
         &self,
@@ -2567,145 +5195,311 @@ impl<'gb> Statement<'gb> for ModifyColumnStatement {
             return Err(errors)
         }
         let mut errors: Vec<PlanetError> = Vec::new();
-        let column_compiled = statement.unwrap();
         let home_dir = planet_context.home_path.clone();
         let account_id = context.account_id.clone().unwrap_or_default();
         let space_id = context.space_id;
         let site_id = context.site_id.clone();
-        let result: Result<TreeFolder, PlanetError> = TreeFolder::defaults(
+        let db_folder = TreeFolder::defaults(
             space_database.connection_pool.clone(),
             Some(home_dir.unwrap_or_default().as_str()),
             Some(&account_id),
             Some(space_id),
             site_id,
         );
-        if result.is_ok() {
-            let db_folder = result.unwrap();
-            let folder_name = column_compiled.folder_name;
-            let folder = db_folder.get_by_name(folder_name.as_str());
-            if folder.is_ok() {
-                let folder = folder.unwrap();
-                if folder.is_some() {
-                    let mut folder = folder.unwrap();
-                    let column_config_map = ColumnConfig::get_column_config_map(
-                        &planet_context,
-                        &context,
-                        &folder
-                    ).unwrap();
-                    let mut columns_map: HashMap<String, ColumnConfig> = HashMap::new();
-                    for (k, v) in &column_config_map {
-                        columns_map.insert(k.clone(), v.clone());
-                    }
-                    let data = folder.data;
-                    if data.is_some() {
-                        let mut data = data.unwrap();
-                        let column_list = data.get(COLUMNS);
-                        if column_list.is_some() {
-                            let column_list = column_list.unwrap().clone();
-                            let column = column_compiled.columns.unwrap()[0].clone();
-                            let column_name = column.clone().name.unwrap_or_default();
-                            let column_name_str = column_name.clone();
-                            let column_name_str = column_name_str.as_str();
-                            let mut column_list_new: Vec<BTreeMap<String, String>> = Vec::new();
-                            for column_item in column_list {
-                                let column_item_name = column_item.get(NAME).unwrap().clone();
-                                if column_item_name.to_lowercase().as_str() == column_name_str.to_lowercase() {
-                                    columns_map.insert(column_name.clone(), column.clone());
-                                    let map = &column.create_config(
-                                        planet_context,
-                                        context,
-                                        &columns_map,
-                                        &db_folder,
-                                        &folder_name,
-                                        &space_database
-                                    );
-                                    if map.is_err() {
-                                        let error = map.clone().unwrap_err();
-                                        errors.push(error);
-                                        return Err(errors)
-                                    }
-                                    let map = map.clone().unwrap();
-                                    column_list_new.push(map);
-                                } else {
-                                    column_list_new.push(column_item);
-                                }
-                            }
-                            data.insert(COLUMNS.to_string(), column_list_new);
-                            let map_list = &column.map_collections_db();
-                            if map_list.is_err() {
-                                let error = map_list.clone().unwrap_err();
-                                errors.push(error);
-                            }
-                            let map_list = map_list.clone().unwrap();
-                            let map_list = map_list.clone();
-                            data.extend(map_list);
-                            folder.data = Some(data);
-                            let result = db_folder.update(&folder);
-                            // Build output
-                            if result.is_ok() {
-                                let folder = result.unwrap();
-                                let response_coded = serde_yaml::to_string(&folder);
-                                if response_coded.is_err() {
-                                    let error = PlanetError::new(
-                                        500, 
-                                        Some(tr!("Error encoding statement response.")),
-                                    );
-                                    errors.push(error);
-                                }
-                                let response = response_coded.unwrap();
-                                let yaml_response = yaml_rust::YamlLoader::load_from_str(
-                                    response.as_str()
-                                ).unwrap();
-                                let yaml_response = yaml_response.clone();
-                                return Ok(yaml_response)
-                            } else {
-                                let error = PlanetError::new(
-                                    500, 
-                                    Some(
-                                        tr!("Could not update folder on database.")
-                                    ),
-                                );
-                                errors.push(error);
-                            }    
+        if db_folder.is_err() {
+            errors.push(db_folder.unwrap_err());
+            return Err(errors)
+        }
+        let tags = db_folder.unwrap().get_tags();
+        if tags.is_err() {
+            errors.push(tags.unwrap_err());
+            return Err(errors)
+        }
+        let result = TagsResult{
+            tags: tags.unwrap(),
+            items: None,
+        };
+        return result.serialize_yaml()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum TagsOperation {
+    Rename(String, String),
+    Merge(Vec<String>, String),
+}
+
+#[derive(Debug, Clone)]
+pub struct ModifyTagsStatement {
+}
+
+impl ModifyTagsStatement {
+    fn replace_item_tags(
+        &self,
+        env: &Environment,
+        space_database: &SpaceDatabase,
+        db_folder: &TreeFolder,
+        tag_map: &BTreeMap<String, String>,
+    ) -> Result<usize, PlanetError> {
+        // Tags columns on all folders of the space
+        let home_dir = env.planet_context.home_path.clone().unwrap_or_default();
+        let account_id = env.context.account_id.clone().unwrap_or_default();
+        let space_id = env.context.space_id;
+        let site_id = env.context.site_id.clone();
+        let folders = db_folder.list()?;
+        let mut number_items: usize = 0;
+        for folder in folders {
+            let folder_id = folder.id.clone().unwrap();
+            let columns = folder.data.clone().unwrap_or_default().get(COLUMNS).cloned().unwrap_or_default();
+            let mut column_ids: Vec<String> = Vec::new();
+            for column in columns {
+                let column_type = column.get(COLUMN_TYPE).cloned().unwrap_or_default();
+                if column_type == COLUMN_TYPE_TAGS {
+                    column_ids.push(column.get(ID).unwrap().clone());
+                }
+            }
+            if column_ids.len() == 0 {
+                continue
+            }
+            let mut db_row: TreeFolderItem = TreeFolderItem::defaults(
+                space_database.connection_pool.clone(),
+                home_dir.as_str(),
+                &account_id,
+                space_id,
+                site_id.clone(),
+                folder_id.as_str(),
+                db_folder,
+            )?;
+            let items = db_row.get_all_items()?;
+            for mut item in items {
+                let data = item.data.clone();
+                if data.is_none() {
+                    continue
+                }
+                let mut data = data.unwrap();
+                let mut changed = false;
+                for column_id in &column_ids {
+                    let values = data.get(column_id);
+                    if values.is_none() {
+                        continue
+                    }
+                    let values = values.unwrap().clone();
+                    let mut tags: Vec<String> = Vec::new();
+                    for value_map in values {
+                        let mut tag = value_map.get(VALUE).cloned().unwrap_or_default();
+                        for (tag_from, tag_to) in tag_map {
+                            let tag_new = TagsColumn::rename(&tag, tag_from, tag_to);
+                            if tag_new.is_some() {
+                                tag = tag_new.unwrap();
+                                changed = true;
+                                break
+                            }
+                        }
+                        if !tags.contains(&tag) {
+                            tags.push(tag);
                         }
-                    } else {
-                        let error = PlanetError::new(
-                            500, 
-                            Some(
-                                tr!("Folder has no data.")
-                            ),
-                        );
-                        errors.push(error);
                     }
-                } else {
-                    let error = PlanetError::new(
-                        500, 
-                        Some(
-                            tr!("Folder not found.")
-                        ),
-                    );
-                    errors.push(error);
+                    let mut values_new: Vec<BTreeMap<String, String>> = Vec::new();
+                    for tag in tags {
+                        let mut value_map: BTreeMap<String, String> = BTreeMap::new();
+                        value_map.insert(VALUE.to_string(), tag);
+                        values_new.push(value_map);
+                    }
+                    data.insert(column_id.clone(), values_new);
+                }
+                if changed {
+                    item.data = Some(data);
+                    db_row.update(&item)?;
+                    number_items += 1;
+                }
+            }
+        }
+        return Ok(number_items)
+    }
+}
+
+impl<'gb> StatementCompiler<'gb, TagsOperation> for ModifyTagsStatement {
+
+    fn compile(
+        &self, 
+        statement_text: &String
+    ) -> Result<TagsOperation, Vec<PlanetError>> {
+        let expr = &RE_MODIFY_TAGS;
+        let mut errors: Vec<PlanetError> = Vec::new();
+        let captures = expr.captures(&statement_text);
+        if captures.is_none() {
+            let error = PlanetError::new(
+                500, 
+                Some(
+                    tr!("Rename or merge tags syntax not valid.")
+                ),
+            );
+            errors.push(error);
+            return Err(errors)
+        }
+        let captures = captures.unwrap();
+        let rename_tag = captures.name("RenameTag");
+        let merge_tags = captures.name("MergeTags");
+        let operation: TagsOperation;
+        if rename_tag.is_some() {
+            let rename_tag = TagsColumn::normalize(&rename_tag.unwrap().as_str().to_string());
+            let rename_to = TagsColumn::normalize(&captures.name("RenameTo").unwrap().as_str().to_string());
+            if rename_tag.is_err() {
+                errors.push(rename_tag.unwrap_err());
+                return Err(errors)
+            }
+            if rename_to.is_err() {
+                errors.push(rename_to.unwrap_err());
+                return Err(errors)
+            }
+            operation = TagsOperation::Rename(rename_tag.unwrap(), rename_to.unwrap());
+        } else if merge_tags.is_some() {
+            let mut tags: Vec<String> = Vec::new();
+            for tag in merge_tags.unwrap().as_str().split("|") {
+                let tag = tag.replace("\"", "");
+                if tag.trim() == "" {
+                    continue
+                }
+                let tag = TagsColumn::normalize(&tag);
+                if tag.is_err() {
+                    errors.push(tag.unwrap_err());
                     return Err(errors)
                 }
-            } else {
-                let error = PlanetError::new(
-                    500, 
-                    Some(
-                        tr!("Error fetching folder by name.")
-                    ),
-                );
-                errors.push(error);
+                tags.push(tag.unwrap());
+            }
+            let merge_into = TagsColumn::normalize(&captures.name("MergeInto").unwrap().as_str().to_string());
+            if merge_into.is_err() {
+                errors.push(merge_into.unwrap_err());
+                return Err(errors)
             }
+            operation = TagsOperation::Merge(tags, merge_into.unwrap());
         } else {
             let error = PlanetError::new(
                 500, 
                 Some(
-                    tr!("Could not parse add column statement.")
+                    tr!("Could not parse rename or merge tags statement.")
                 ),
             );
             errors.push(error);
+            return Err(errors)
         }
-        return Err(errors)
+        return Ok(operation)
+    }
+}
+
+impl<'gb> Statement<'gb> for ModifyTagsStatement {
+
+    fn run(
+        &self,
+        env: &'gb Environment<'gb>,
+        space_database: &SpaceDatabase,
+        statement_text: &String,
+    ) -> Result<Vec<yaml_rust::Yaml>, Vec<PlanetError>> {
+        let space_database = space_database.clone();
+        let context = env.context;
+        let planet_context = env.planet_context;
+        let statement = self.compile(statement_text);
+        if statement.is_err() {
+            let errors = statement.unwrap_err();
+            return Err(errors)
+        }
+        let mut errors: Vec<PlanetError> = Vec::new();
+        let operation = statement.unwrap();
+        let home_dir = planet_context.home_path.clone();
+        let account_id = context.account_id.clone().unwrap_or_default();
+        let space_id = context.space_id;
+        let site_id = context.site_id.clone();
+        let db_folder = TreeFolder::defaults(
+            space_database.connection_pool.clone(),
+            Some(home_dir.unwrap_or_default().as_str()),
+            Some(&account_id),
+            Some(space_id),
+            site_id,
+        );
+        if db_folder.is_err() {
+            errors.push(db_folder.unwrap_err());
+            return Err(errors)
+        }
+        let db_folder = db_folder.unwrap();
+        let tags = db_folder.get_tags();
+        if tags.is_err() {
+            errors.push(tags.unwrap_err());
+            return Err(errors)
+        }
+        let tags = tags.unwrap();
+        // Tag => new tag, descendants are moved along
+        let mut tag_map: BTreeMap<String, String> = BTreeMap::new();
+        let tags_from: Vec<String>;
+        match operation {
+            TagsOperation::Rename(tag, tag_new) => {
+                if tags.contains(&tag_new) {
+                    errors.push(
+                        PlanetError::new(
+                            500, 
+                            Some(tr!("Tag \"{}\" already exists, use MERGE TAGS.", &tag_new)),
+                        )
+                    );
+                    return Err(errors)
+                }
+                tags_from = vec![tag.clone()];
+                tag_map.insert(tag, tag_new);
+            },
+            TagsOperation::Merge(tags_merge, tag_into) => {
+                for tag in &tags_merge {
+                    if *tag != tag_into {
+                        tag_map.insert(tag.clone(), tag_into.clone());
+                    }
+                }
+                tags_from = tags_merge;
+            },
+        }
+        for tag in &tags_from {
+            if !tags.contains(tag) {
+                errors.push(
+                    PlanetError::new(
+                        500, 
+                        Some(tr!("Tag \"{}\" not found.", tag)),
+                    )
+                );
+                return Err(errors)
+            }
+        }
+        for (tag_from, tag_to) in &tag_map {
+            if TagsColumn::is_descendant(tag_to, tag_from) {
+                errors.push(
+                    PlanetError::new(
+                        500, 
+                        Some(tr!("Tag \"{}\" can't be moved into its own descendant \"{}\".", tag_from, tag_to)),
+                    )
+                );
+                return Err(errors)
+            }
+        }
+        let number_items = self.replace_item_tags(
+            env, 
+            &space_database, 
+            &db_folder, 
+            &tag_map
+        );
+        if number_items.is_err() {
+            errors.push(number_items.unwrap_err());
+            return Err(errors)
+        }
+        let result = db_folder.replace_tags(&tag_map);
+        if result.is_err() {
+            errors.push(result.unwrap_err());
+            return Err(errors)
+        }
+        let tags = db_folder.get_tags();
+        if tags.is_err() {
+            errors.push(tags.unwrap_err());
+            return Err(errors)
+        }
+        let result = TagsResult{
+            tags: tags.unwrap(),
+            items: Some(number_items.unwrap()),
+        };
+        return result.serialize_yaml()
     }
 }
 
@@ -4464,6 +7258,56 @@ pub fn resolve_schema_statement(
             }
         }
     }
+    // COPY FOLDER
+    let expr = &RE_COPY_FOLDER;
+    let check = expr.is_match(&statement_text);
+    if check {
+        let stmt = CopyFolderStatement{};
+        match mode {
+            StatementCallMode::Run => {
+                let response = stmt.run(
+                    &env, 
+                    &space_data, 
+                    &statement_text,
+                );
+                return Some(response);
+            },
+            StatementCallMode::Compile => {
+                let response = stmt.compile(&statement_text);
+                if response.is_err() {
+                    let errors = response.unwrap_err();
+                    return Some(Err(errors))
+                }
+                let result = yaml_rust::YamlLoader::load_from_str("---\nstatus: ok");
+                return Some(Ok(result.unwrap()))
+            }
+        }
+    }
+    // MOVE FOLDER
+    let expr = &RE_MOVE_FOLDER;
+    let check = expr.is_match(&statement_text);
+    if check {
+        let stmt = MoveFolderStatement{};
+        match mode {
+            StatementCallMode::Run => {
+                let response = stmt.run(
+                    &env, 
+                    &space_data, 
+                    &statement_text,
+                );
+                return Some(response);
+            },
+            StatementCallMode::Compile => {
+                let response = stmt.compile(&statement_text);
+                if response.is_err() {
+                    let errors = response.unwrap_err();
+                    return Some(Err(errors))
+                }
+                let result = yaml_rust::YamlLoader::load_from_str("---\nstatus: ok");
+                return Some(Ok(result.unwrap()))
+            }
+        }
+    }
     // DROP FOLDER
     let expr = &RE_DROP_FOLDER;
     let check = expr.is_match(&statement_text);
@@ -4514,6 +7358,81 @@ pub fn resolve_schema_statement(
             }
         }
     }
+    // LIST TAGS
+    let expr = &RE_LIST_TAGS;
+    let check = expr.is_match(&statement_text);
+    if check {
+        let stmt = ListTagsStatement{};
+        match mode {
+            StatementCallMode::Run => {
+                let response = stmt.run(
+                    &env, 
+                    &space_data, 
+                    &statement_text,
+                );
+                return Some(response);
+            },
+            StatementCallMode::Compile => {
+                let response = stmt.compile(&statement_text);
+                if response.is_err() {
+                    let errors = response.unwrap_err();
+                    return Some(Err(errors))
+                }
+                let result = yaml_rust::YamlLoader::load_from_str("---\nstatus: ok");
+                return Some(Ok(result.unwrap()))
+            }
+        }
+    }
+    // RENAME TAG | MERGE TAGS
+    let expr = &RE_MODIFY_TAGS;
+    let check = expr.is_match(&statement_text);
+    if check {
+        let stmt = ModifyTagsStatement{};
+        match mode {
+            StatementCallMode::Run => {
+                let response = stmt.run(
+                    &env, 
+                    &space_data, 
+                    &statement_text,
+                );
+                return Some(response);
+            },
+            StatementCallMode::Compile => {
+                let response = stmt.compile(&statement_text);
+                if response.is_err() {
+                    let errors = response.unwrap_err();
+                    return Some(Err(errors))
+                }
+                let result = yaml_rust::YamlLoader::load_from_str("---\nstatus: ok");
+                return Some(Ok(result.unwrap()))
+            }
+        }
+    }
+    // MODIFY COLUMN ... RENAME OPTION | MERGE OPTIONS | REORDER OPTIONS
+    let expr = &RE_MODIFY_SELECT_OPTIONS;
+    let check = expr.is_match(&statement_text);
+    if check {
+        let stmt = ModifySelectOptionsStatement{};
+        match mode {
+            StatementCallMode::Run => {
+                let response = stmt.run(
+                    &env, 
+                    &space_data, 
+                    &statement_text,
+                );
+                return Some(response);
+            },
+            StatementCallMode::Compile => {
+                let response = stmt.compile(&statement_text);
+                if response.is_err() {
+                    let errors = response.unwrap_err();
+                    return Some(Err(errors))
+                }
+                let result = yaml_rust::YamlLoader::load_from_str("---\nstatus: ok");
+                return Some(Ok(result.unwrap()))
+            }
+        }
+    }
     // MODIFY COLUMN
     let expr = &RE_MODIFY_COLUMN;
     let check = expr.is_match(&statement_text);
@@ -2,7 +2,8 @@ use std::str::FromStr;
 use std::collections::BTreeMap;
 use serde::{Deserialize, Serialize};
 use colored::Colorize;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
 use lazy_static::lazy_static;
 use regex::{Regex};
 
@@ -13,22 +14,119 @@ use crate::storage::constants::*;
 
 lazy_static! {
     pub static ref RE_DURATION: Regex = Regex::new(r#"^(?P<hour>\d+):(?P<minute>\d+)(:(?P<second>\d+))*(.(?P<micro>\d+))*$"#).unwrap();
+    pub static ref RE_DURATION_ISO: Regex = Regex::new(r#"^P((?P<weeks>\d+(\.\d+)?)W)?((?P<days>\d+(\.\d+)?)D)?(T((?P<hours>\d+(\.\d+)?)H)?((?P<minutes>\d+(\.\d+)?)M)?((?P<seconds>\d+(\.\d+)?)S)?)?$"#).unwrap();
+    pub static ref RE_DURATION_HUMAN: Regex = Regex::new(r#"^([\s]*\d+(\.\d+)?[\s]*[a-z]+[\s]*)+$"#).unwrap();
+    pub static ref RE_DURATION_HUMAN_PART: Regex = Regex::new(r#"(?P<number>\d+(\.\d+)?)[\s]*(?P<unit>[a-z]+)"#).unwrap();
+    pub static ref RE_RRULE_BYDAY: Regex = Regex::new(r#"^(?P<nth>[+\-]?\d{1,2})?(?P<day>MO|TU|WE|TH|FR|SA|SU)$"#).unwrap();
+    pub static ref RE_RRULE_DATE: Regex = Regex::new(r#"^(?P<date>\d{8})(T(?P<time>\d{6})(?P<utc>Z)?)?$"#).unwrap();
+}
+
+pub fn validate_time_zone(time_zone: &Option<String>) -> Result<(), PlanetError> {
+    if time_zone.is_some() {
+        let time_zone_str = time_zone.clone().unwrap();
+        let tz = Tz::from_str(time_zone_str.as_str());
+        if tz.is_err() {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Time zone not supported: {}", &time_zone_str)),
+                )
+            );
+        }
+    }
+    return Ok(())
+}
+
+pub fn get_time_zone(time_zone: &Option<String>, context_time_zone: &Option<String>) -> Tz {
+    // Column time zone, then context time zone, then UTC
+    let mut time_zone = time_zone.clone();
+    if time_zone.is_none() {
+        time_zone = context_time_zone.clone();
+    }
+    if time_zone.is_some() {
+        let tz = Tz::from_str(time_zone.unwrap().as_str());
+        if tz.is_ok() {
+            return tz.unwrap()
+        }
+    }
+    return Tz::UTC
+}
+
+pub fn localize_date_time(naive: &NaiveDateTime, tz: &Tz) -> Result<DateTime<Tz>, PlanetError> {
+    // Wall clock time in zone. Ambiguous times (DST fall back) take the earliest one, 
+    // times inside a DST gap are not valid.
+    let local = tz.from_local_datetime(naive).earliest();
+    if local.is_none() {
+        return Err(
+            PlanetError::new(
+                500, 
+                Some(tr!("Date \"{}\" does not exist in time zone \"{}\"", naive, tz.name())),
+            )
+        );
+    }
+    return Ok(local.unwrap())
 }
 
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct DateColumn {
-    pub config: ColumnConfig
+    pub config: ColumnConfig,
+    pub context_time_zone: Option<String>,
 }
 impl DateColumn {
     pub fn defaults(config: &ColumnConfig) -> Self {
         let field_config = config.clone();
         let field_obj = Self{
-            config: field_config
+            config: field_config,
+            context_time_zone: None,
         };
         return field_obj
     }
 }
+impl DateColumn {
+    pub fn get_date_time_format(&self) -> Option<String> {
+        let date_format = self.config.date_format.clone();
+        let time_format = self.config.time_format.clone();
+        if time_format.is_none() {
+            return None
+        }
+        let mut sep = " ";
+        let fmt: &str;
+        match date_format.unwrap_or(DateFormat::Friendly) {
+            DateFormat::Friendly => {
+                fmt = "%d-%b-%Y";
+            },
+            DateFormat::US => {
+                fmt = "%m/%d/%Y";
+            },
+            DateFormat::European => {
+                fmt = "%d/%m/%Y";
+            },
+            DateFormat::ISO => {
+                fmt = "%Y-%m-%d";
+                sep = "T";
+            },
+        }
+        if time_format.unwrap() == 12 {
+            return Some(format!("{}{}%I:%M:%S%P%z", fmt, sep))
+        }
+        return Some(format!("{}{}%H:%M:%S%z", fmt, sep))
+    }
+    pub fn get_display_value(&self, value: &String) -> String {
+        // Stored in UTC, displayed in column time zone
+        let mut value = value.clone();
+        let fmt = self.get_date_time_format();
+        if fmt.is_some() {
+            let fmt = fmt.unwrap();
+            let date_obj = DateTime::parse_from_str(&value, fmt.as_str());
+            if date_obj.is_ok() {
+                let tz = get_time_zone(&self.config.time_zone, &self.context_time_zone);
+                value = date_obj.unwrap().with_timezone(&tz).format(fmt.as_str()).to_string();
+            }
+        }
+        return value
+    }
+}
 impl StorageColumn for DateColumn {
     fn create_config(
         &mut self, 
@@ -72,6 +170,20 @@ impl StorageColumn for DateColumn {
         } else {
             field_config_map.insert(TIME_FORMAT.to_string(), "24".to_string());
         }
+        let time_zone = self.config.time_zone.clone();
+        if time_zone.is_some() {
+            let time_zone = time_zone.unwrap();
+            let tz = Tz::from_str(time_zone.as_str());
+            if tz.is_err() {
+                return Err(
+                    PlanetError::new(
+                        500, 
+                        Some(tr!("Time zone not supported: {}", &time_zone)),
+                    )
+                );
+            }
+            field_config_map.insert(TIME_ZONE.to_string(), time_zone);
+        }
         return Ok(field_config_map)
     }
     fn get_config(
@@ -123,6 +235,10 @@ impl StorageColumn for DateColumn {
             let time_format: i8 = FromStr::from_str(time_format_str).unwrap();
             config.time_format = Some(time_format);
         }
+        let time_zone = field_config_map.get(TIME_ZONE);
+        if time_zone.is_some() {
+            config.time_zone = Some(time_zone.unwrap().clone());
+        }
         return Ok(config)
     }
     fn validate(&self, data: &Vec<String>) -> Result<Vec<String>, Vec<PlanetError>> {
@@ -184,6 +300,7 @@ impl StorageColumn for DateColumn {
                 fmt = fmt_string.as_str();
             }
         }
+        let tz = get_time_zone(&config.time_zone, &self.context_time_zone);
         let mut data_new: Vec<String> = Vec::new();
         for mut date_string in data {
             // Check date_string with fmt
@@ -203,15 +320,37 @@ impl StorageColumn for DateColumn {
                 &date_string, 
                 fmt
             );
-            if date_obj.is_err() {
-                // Raise validation error
-                let error = PlanetError::new(
-                    500, 
-                    Some(tr!("Validation error on date \"{}\"", date_string.clone())),
+            let date_utc: DateTime<Utc>;
+            if date_obj.is_ok() {
+                date_utc = date_obj.unwrap().with_timezone(&Utc);
+            } else {
+                // No offset in input, wall clock time in column time zone
+                let fmt_local = fmt.replace("%z", "");
+                let naive = NaiveDateTime::parse_from_str(
+                    &date_string, 
+                    fmt_local.as_str()
                 );
-                let mut errors: Vec<PlanetError> = Vec::new();
-                errors.push(error);
-                return Err(errors);
+                if naive.is_err() {
+                    // Raise validation error
+                    let error = PlanetError::new(
+                        500, 
+                        Some(tr!("Validation error on date \"{}\"", date_string.clone())),
+                    );
+                    let mut errors: Vec<PlanetError> = Vec::new();
+                    errors.push(error);
+                    return Err(errors);
+                }
+                let local = localize_date_time(&naive.unwrap(), &tz);
+                if local.is_err() {
+                    let mut errors: Vec<PlanetError> = Vec::new();
+                    errors.push(local.unwrap_err());
+                    return Err(errors);
+                }
+                date_utc = local.unwrap().with_timezone(&Utc);
+            }
+            // We store in UTC
+            if is_time {
+                date_string = date_utc.format(fmt).to_string();
             }
             data_new.push(date_string);
         }
@@ -219,11 +358,12 @@ impl StorageColumn for DateColumn {
     }
     fn get_yaml_out(&self, yaml_string: &String, value: &String) -> String {
         let field_config = self.config.clone();
-        let field_name = field_config.name.unwrap();
+        let field_name = field_config.name.clone().unwrap();
         let mut yaml_string = yaml_string.clone();
         let field = &field_name.truecolor(
             YAML_COLOR_BLUE[0], YAML_COLOR_BLUE[1], YAML_COLOR_BLUE[2]
         );
+        let value = self.get_display_value(value);
         let value = format!("{}",
             value.truecolor(YAML_COLOR_ORANGE[0], YAML_COLOR_ORANGE[1], YAML_COLOR_ORANGE[2]), 
         );
@@ -250,14 +390,58 @@ impl StorageColumn for DurationColumn {
         &mut self, 
         field_config_map: &BTreeMap<String, String>,
     ) -> Result<BTreeMap<String, String>, PlanetError> {
-        let field_config_map = field_config_map.clone();
+        let mut field_config_map = field_config_map.clone();
+        let duration_format = self.config.duration_format.clone();
+        if duration_format.is_some() {
+            let duration_format = duration_format.unwrap();
+            match duration_format {
+                DurationFormat::Clock => {
+                    field_config_map.insert(DURATION_FORMAT.to_string(), DURATION_FORMAT_CLOCK.to_string());
+                },
+                DurationFormat::Compact => {
+                    field_config_map.insert(DURATION_FORMAT.to_string(), DURATION_FORMAT_COMPACT.to_string());
+                },
+                DurationFormat::Long => {
+                    field_config_map.insert(DURATION_FORMAT.to_string(), DURATION_FORMAT_LONG.to_string());
+                },
+                DurationFormat::ISO => {
+                    field_config_map.insert(DURATION_FORMAT.to_string(), DURATION_FORMAT_ISO.to_string());
+                },
+            }
+        }
         return Ok(field_config_map)
     }
     fn get_config(
         &mut self, 
-        _: &BTreeMap<String, String>,
+        field_config_map: &BTreeMap<String, String>,
     ) -> Result<ColumnConfig, PlanetError> {
-        let config = self.config.clone();
+        let mut config = self.config.clone();
+        let duration_format = field_config_map.get(DURATION_FORMAT);
+        if duration_format.is_some() {
+            let duration_format_str = duration_format.unwrap().as_str();
+            match duration_format_str {
+                DURATION_FORMAT_CLOCK => {
+                    config.duration_format = Some(DurationFormat::Clock);
+                },
+                DURATION_FORMAT_COMPACT => {
+                    config.duration_format = Some(DurationFormat::Compact);
+                },
+                DURATION_FORMAT_LONG => {
+                    config.duration_format = Some(DurationFormat::Long);
+                },
+                DURATION_FORMAT_ISO => {
+                    config.duration_format = Some(DurationFormat::ISO);
+                },
+                _ => {
+                    return Err(
+                        PlanetError::new(
+                            500, 
+                            Some(tr!("Duration format not supported: {}", duration_format_str)),
+                        )
+                    );
+                }
+            }
+        }
         return Ok(config)
     }
     fn validate(&self, data: &Vec<String>) -> Result<Vec<String>, Vec<PlanetError>> {
@@ -270,72 +454,28 @@ impl StorageColumn for DurationColumn {
             errors.push(error);
             return Err(errors)
         }
-        // validate HH:MM[:SS.SSS]
-        let expr = &RE_DURATION;
+        // HH:MM[:SS.SSS], 1h 30m, 90 min, 2 days, PT1H30M. We store canonical HH:MM:SS[.SSSSSS]
         let mut data_new: Vec<String> = Vec::new();
         for data_item in data {
-            let is_valid = expr.is_match(&data_item);
-            if !is_valid {
-                let error = PlanetError::new(
-                    500, 
-                    Some(tr!("Duration format is not valid: \"{}\"", &data_item)),
-                );
+            let micros = parse_duration(&data_item);
+            if micros.is_err() {
                 let mut errors: Vec<PlanetError> = Vec::new();
-                errors.push(error);
+                errors.push(micros.unwrap_err());
                 return Err(errors);
             }
-            let matches = expr.captures(&data_item).unwrap();
-            let hour = matches.name("hour");
-            let minute = matches.name("minute");
-            let second = matches.name("second");
-            let micro = matches.name("micro");
-            let hour_str = hour.unwrap().as_str();
-            let minute_str = minute.unwrap().as_str();
-            let mut second_str: &str;
-            let micro_str: &str;
-            let minute: i8 = FromStr::from_str(minute_str).unwrap();
-            if minute > 60 {
-                let error = PlanetError::new(
-                    500, 
-                    Some(tr!("Minutes needs to be less than 60: \"{}\"", &data_item)),
-                );
-                let mut errors: Vec<PlanetError> = Vec::new();
-                errors.push(error);
-                return Err(errors);
-            }
-            if second.is_some() {
-                second_str = second.unwrap().as_str();
-                let second: i8 = FromStr::from_str(second_str).unwrap();
-                if second > 60 {
-                    let error = PlanetError::new(
-                        500, 
-                        Some(tr!("Seconds needs to be less than 60: \"{}\"", &data_item)),
-                    );
-                    let mut errors: Vec<PlanetError> = Vec::new();
-                    errors.push(error);
-                    return Err(errors);
-                }
-            }
-            let mut data_final = format!("{}:{}", hour_str, minute_str);
-            if second.is_some() {
-                second_str = second.unwrap().as_str();
-                data_final = format!("{}:{}", &data_final, second_str);
-            }
-            if micro.is_some() {
-                micro_str = micro.unwrap().as_str();
-                data_final = format!("{}.{}", &data_final, micro_str);
-            }
+            let data_final = get_duration_canonical(micros.unwrap());
             data_new.push(data_final);
         }
         return Ok(data_new)
     }
     fn get_yaml_out(&self, yaml_string: &String, value: &String) -> String {
         let field_config = self.config.clone();
-        let field_name = field_config.name.unwrap();
+        let field_name = field_config.name.clone().unwrap();
         let mut yaml_string = yaml_string.clone();
         let field = &field_name.truecolor(
             YAML_COLOR_BLUE[0], YAML_COLOR_BLUE[1], YAML_COLOR_BLUE[2]
         );
+        let value = self.get_display_value(value);
         let value = format!("{}",
             value.truecolor(YAML_COLOR_ORANGE[0], YAML_COLOR_ORANGE[1], YAML_COLOR_ORANGE[2]), 
         );
@@ -343,6 +483,226 @@ impl StorageColumn for DurationColumn {
         return yaml_string;
     }
 }
+impl DurationColumn {
+    pub fn get_display_value(&self, value: &String) -> String {
+        let duration_format = self.config.duration_format.clone();
+        let micros = parse_duration(value);
+        if duration_format.is_none() || micros.is_err() {
+            return value.clone()
+        }
+        return format_duration(micros.unwrap(), &duration_format.unwrap())
+    }
+}
+
+pub fn parse_duration(value: &String) -> Result<i64, PlanetError> {
+    // Returns duration in microseconds
+    let value = value.replace("\"", "");
+    let value = value.trim().to_string();
+    let expr = &RE_DURATION;
+    let expr_iso = &RE_DURATION_ISO;
+    let expr_human = &RE_DURATION_HUMAN;
+    if expr.is_match(&value) {
+        // HH:MM[:SS.SSS]
+        let matches = expr.captures(&value).unwrap();
+        let hour = matches.name("hour");
+        let minute = matches.name("minute");
+        let second = matches.name("second");
+        let micro = matches.name("micro");
+        let hour: i64 = FromStr::from_str(hour.unwrap().as_str()).unwrap();
+        let minute: i64 = FromStr::from_str(minute.unwrap().as_str()).unwrap();
+        if minute > 60 {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Minutes needs to be less than 60: \"{}\"", &value)),
+                )
+            );
+        }
+        let mut second_number: i64 = 0;
+        if second.is_some() {
+            second_number = FromStr::from_str(second.unwrap().as_str()).unwrap();
+            if second_number > 60 {
+                return Err(
+                    PlanetError::new(
+                        500, 
+                        Some(tr!("Seconds needs to be less than 60: \"{}\"", &value)),
+                    )
+                );
+            }
+        }
+        let mut micro_number: i64 = 0;
+        if micro.is_some() {
+            // Fraction of second
+            let mut micro_str = micro.unwrap().as_str().to_string();
+            micro_str.truncate(6);
+            while micro_str.len() < 6 {
+                micro_str.push('0');
+            }
+            micro_number = FromStr::from_str(micro_str.as_str()).unwrap();
+        }
+        let micros = ((hour*60 + minute)*60 + second_number)*1000000 + micro_number;
+        return Ok(micros)
+    }
+    let value_upper = value.to_uppercase();
+    if value_upper.starts_with("P") && expr_iso.is_match(&value_upper) && value_upper != "P" && !value_upper.ends_with("T") {
+        // ISO 8601: P2DT1H30M, PT90M, P1W. Years and months have no fixed length, not supported.
+        let matches = expr_iso.captures(&value_upper).unwrap();
+        let parts: [(&str, f64); 5] = [
+            ("weeks", 604800.0),
+            ("days", 86400.0),
+            ("hours", 3600.0),
+            ("minutes", 60.0),
+            ("seconds", 1.0),
+        ];
+        let mut seconds: f64 = 0.0;
+        for (name, factor) in parts {
+            let part = matches.name(name);
+            if part.is_some() {
+                let number: f64 = FromStr::from_str(part.unwrap().as_str()).unwrap();
+                seconds += number*factor;
+            }
+        }
+        return Ok((seconds*1000000.0).round() as i64)
+    }
+    // 1h 30m, 90 min, 2 days, 1 hour and 30 minutes
+    let value_human = value.to_lowercase().replace(",", " ").replace(" and ", " ");
+    if expr_human.is_match(&value_human) {
+        let mut seconds: f64 = 0.0;
+        for matches in RE_DURATION_HUMAN_PART.captures_iter(&value_human) {
+            let number: f64 = FromStr::from_str(matches.name("number").unwrap().as_str()).unwrap();
+            let unit = matches.name("unit").unwrap().as_str();
+            let factor: f64;
+            match unit {
+                "w" | "wk" | "wks" | "week" | "weeks" => {
+                    factor = 604800.0;
+                },
+                "d" | "day" | "days" => {
+                    factor = 86400.0;
+                },
+                "h" | "hr" | "hrs" | "hour" | "hours" => {
+                    factor = 3600.0;
+                },
+                "m" | "min" | "mins" | "minute" | "minutes" => {
+                    factor = 60.0;
+                },
+                "s" | "sec" | "secs" | "second" | "seconds" => {
+                    factor = 1.0;
+                },
+                "ms" | "msec" | "msecs" | "millisecond" | "milliseconds" => {
+                    factor = 0.001;
+                },
+                _ => {
+                    return Err(
+                        PlanetError::new(
+                            500, 
+                            Some(tr!("Duration unit not supported: \"{}\"", unit)),
+                        )
+                    );
+                }
+            }
+            seconds += number*factor;
+        }
+        return Ok((seconds*1000000.0).round() as i64)
+    }
+    return Err(
+        PlanetError::new(
+            500, 
+            Some(tr!("Duration format is not valid: \"{}\"", &value)),
+        )
+    );
+}
+
+pub fn get_duration_canonical(micros: i64) -> String {
+    // 1:30:00, 49:00:05.5
+    let mut sign = "";
+    let mut micros = micros;
+    if micros < 0 {
+        sign = "-";
+        micros = -micros;
+    }
+    let total_seconds = micros/1000000;
+    let fraction = micros%1000000;
+    let hours = total_seconds/3600;
+    let minutes = (total_seconds%3600)/60;
+    let seconds = total_seconds%60;
+    let mut canonical = format!("{}{}:{:02}:{:02}", sign, hours, minutes, seconds);
+    if fraction > 0 {
+        let fraction_str = format!("{:06}", fraction);
+        canonical = format!("{}.{}", canonical, fraction_str.trim_end_matches('0'));
+    }
+    return canonical
+}
+
+pub fn format_duration(micros: i64, duration_format: &DurationFormat) -> String {
+    let micros_total = micros;
+    let mut sign = "";
+    let mut micros = micros;
+    if micros < 0 {
+        sign = "-";
+        micros = -micros;
+    }
+    let total_seconds = micros/1000000;
+    let fraction = micros%1000000;
+    let mut seconds_str = (total_seconds%60).to_string();
+    if fraction > 0 {
+        let fraction_str = format!("{:06}", fraction);
+        seconds_str = format!("{}.{}", seconds_str, fraction_str.trim_end_matches('0'));
+    }
+    match duration_format {
+        DurationFormat::Clock => {
+            return get_duration_canonical(micros_total)
+        },
+        DurationFormat::ISO => {
+            // PT49H30M5S
+            let hours = total_seconds/3600;
+            let minutes = (total_seconds%3600)/60;
+            let mut iso = format!("{}PT", sign);
+            if hours > 0 {
+                iso = format!("{}{}H", iso, hours);
+            }
+            if minutes > 0 {
+                iso = format!("{}{}M", iso, minutes);
+            }
+            if seconds_str != "0" || (hours == 0 && minutes == 0) {
+                iso = format!("{}{}S", iso, seconds_str);
+            }
+            return iso
+        },
+        DurationFormat::Compact | DurationFormat::Long => {
+            // 2d 1h 30m 5s, 2 days 1 hour 30 minutes 5 seconds
+            let is_long = match duration_format {
+                DurationFormat::Long => true,
+                _ => false,
+            };
+            let parts: [(i64, &str, &str, &str); 3] = [
+                (total_seconds/86400, "d", "day", "days"),
+                ((total_seconds%86400)/3600, "h", "hour", "hours"),
+                ((total_seconds%3600)/60, "m", "minute", "minutes"),
+            ];
+            let mut items: Vec<String> = Vec::new();
+            for (number, short, singular, plural) in parts {
+                if number == 0 {
+                    continue
+                }
+                if is_long {
+                    let unit = if number == 1 { singular } else { plural };
+                    items.push(format!("{} {}", number, unit));
+                } else {
+                    items.push(format!("{}{}", number, short));
+                }
+            }
+            if seconds_str != "0" || items.len() == 0 {
+                if is_long {
+                    let unit = if seconds_str == "1" { "second" } else { "seconds" };
+                    items.push(format!("{} {}", seconds_str, unit));
+                } else {
+                    items.push(format!("{}s", seconds_str));
+                }
+            }
+            return format!("{}{}", sign, items.join(" "))
+        },
+    }
+}
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AuditDateColumn {
@@ -394,3 +754,633 @@ impl StorageColumn for AuditDateColumn {
         return yaml_string;
     }
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RecurrenceColumn {
+    pub config: ColumnConfig,
+    pub context_time_zone: Option<String>,
+}
+impl RecurrenceColumn {
+    pub fn defaults(config: &ColumnConfig) -> Self {
+        let field_config = config.clone();
+        let field_obj = Self{
+            config: field_config,
+            context_time_zone: None,
+        };
+        return field_obj
+    }
+}
+impl StorageColumn for RecurrenceColumn {
+    fn create_config(
+        &mut self, 
+        field_config_map: &BTreeMap<String, String>,
+    ) -> Result<BTreeMap<String, String>, PlanetError> {
+        let field_config_map = field_config_map.clone();
+        return Ok(field_config_map)
+    }
+    fn get_config(
+        &mut self, 
+        _: &BTreeMap<String, String>,
+    ) -> Result<ColumnConfig, PlanetError> {
+        let config = self.config.clone();
+        return Ok(config)
+    }
+    fn validate(&self, data: &Vec<String>) -> Result<Vec<String>, Vec<PlanetError>> {
+        let data = data.clone();
+        let config = self.config.clone();
+        let set_validate = validate_set(&config, &data);
+        if set_validate.is_err() {
+            let error = set_validate.unwrap_err();
+            let mut errors: Vec<PlanetError> = Vec::new();
+            errors.push(error);
+            return Err(errors)
+        }
+        // FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE;COUNT=10, stored in canonical form
+        let mut data_new: Vec<String> = Vec::new();
+        for data_item in data {
+            let recurrence = Recurrence::parse(&data_item, &self.context_time_zone);
+            if recurrence.is_err() {
+                let mut errors: Vec<PlanetError> = Vec::new();
+                errors.push(recurrence.unwrap_err());
+                return Err(errors);
+            }
+            data_new.push(recurrence.unwrap().get_rule());
+        }
+        return Ok(data_new)
+    }
+    fn get_yaml_out(&self, yaml_string: &String, value: &String) -> String {
+        let field_config = self.config.clone();
+        let field_name = field_config.name.unwrap();
+        let mut yaml_string = yaml_string.clone();
+        let field = &field_name.truecolor(
+            YAML_COLOR_BLUE[0], YAML_COLOR_BLUE[1], YAML_COLOR_BLUE[2]
+        );
+        let value = format!("{}",
+            value.truecolor(YAML_COLOR_ORANGE[0], YAML_COLOR_ORANGE[1], YAML_COLOR_ORANGE[2]), 
+        );
+        yaml_string.push_str(format!("  {field}: {value}\n", field=field, value=value).as_str());
+        return yaml_string;
+    }
+}
+
+// Maximum periods (days, weeks, months or years) we expand for a rule
+pub const RECURRENCE_MAX_PERIODS: i64 = 50000;
+pub const RECURRENCE_MAX_OCCURRENCES: usize = 1000;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum RecurrenceFrequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+#[derive(Debug, Clone)]
+pub struct Recurrence {
+    pub frequency: RecurrenceFrequency,
+    pub interval: i64,
+    pub by_day: Vec<(Option<i32>, Weekday)>,
+    pub count: Option<u32>,
+    pub until: Option<DateTime<Utc>>,
+    pub start: Option<DateTime<Utc>>,
+    pub time_zone: Option<String>,
+}
+impl Recurrence {
+    pub fn parse(rule: &String, time_zone: &Option<String>) -> Result<Self, PlanetError> {
+        // RRULE:FREQ=MONTHLY;BYDAY=-1FR;UNTIL=20241231T000000Z
+        // DTSTART:20240105T090000Z RRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE;COUNT=10
+        let rule_source = rule.replace("\"", "");
+        let rule = rule_source.replace("\\n", ";").replace("\n", ";").replace(" ", ";");
+        let rule = rule.trim().to_uppercase();
+        let rule = rule.replace("RRULE:", ";").replace("DTSTART:", "DTSTART=");
+        let mut frequency: Option<RecurrenceFrequency> = None;
+        let mut interval: i64 = 1;
+        let mut by_day: Vec<(Option<i32>, Weekday)> = Vec::new();
+        let mut count: Option<u32> = None;
+        let mut until: Option<DateTime<Utc>> = None;
+        let mut start: Option<DateTime<Utc>> = None;
+        for part in rule.split(";") {
+            let part = part.trim();
+            if part == "" {
+                continue
+            }
+            let fields: Vec<&str> = part.splitn(2, "=").collect();
+            if fields.len() != 2 || fields[1] == "" {
+                return Err(
+                    PlanetError::new(
+                        500, 
+                        Some(tr!("Recurrence rule part is not valid: \"{}\"", part)),
+                    )
+                );
+            }
+            let value = fields[1];
+            match fields[0] {
+                "FREQ" => {
+                    match value {
+                        "DAILY" => {
+                            frequency = Some(RecurrenceFrequency::Daily);
+                        },
+                        "WEEKLY" => {
+                            frequency = Some(RecurrenceFrequency::Weekly);
+                        },
+                        "MONTHLY" => {
+                            frequency = Some(RecurrenceFrequency::Monthly);
+                        },
+                        "YEARLY" => {
+                            frequency = Some(RecurrenceFrequency::Yearly);
+                        },
+                        _ => {
+                            return Err(
+                                PlanetError::new(
+                                    500, 
+                                    Some(tr!("Recurrence frequency not supported: \"{}\"", value)),
+                                )
+                            );
+                        }
+                    }
+                },
+                "INTERVAL" => {
+                    let number: Result<i64, _> = FromStr::from_str(value);
+                    if number.is_err() || *number.as_ref().unwrap() < 1 {
+                        return Err(
+                            PlanetError::new(
+                                500, 
+                                Some(tr!("Recurrence interval must be a positive number: \"{}\"", value)),
+                            )
+                        );
+                    }
+                    interval = number.unwrap();
+                },
+                "COUNT" => {
+                    let number: Result<u32, _> = FromStr::from_str(value);
+                    if number.is_err() || *number.as_ref().unwrap() < 1 {
+                        return Err(
+                            PlanetError::new(
+                                500, 
+                                Some(tr!("Recurrence count must be a positive number: \"{}\"", value)),
+                            )
+                        );
+                    }
+                    count = Some(number.unwrap());
+                },
+                "UNTIL" => {
+                    until = Some(get_rrule_date(value, time_zone)?);
+                },
+                "DTSTART" => {
+                    start = Some(get_rrule_date(value, time_zone)?);
+                },
+                "BYDAY" => {
+                    for day in value.split(",") {
+                        let matches = RE_RRULE_BYDAY.captures(day.trim());
+                        if matches.is_none() {
+                            return Err(
+                                PlanetError::new(
+                                    500, 
+                                    Some(tr!("Recurrence day not valid: \"{}\"", day)),
+                                )
+                            );
+                        }
+                        let matches = matches.unwrap();
+                        let weekday = get_rrule_weekday(matches.name("day").unwrap().as_str());
+                        let mut nth: Option<i32> = None;
+                        if matches.name("nth").is_some() {
+                            let nth_number: i32 = FromStr::from_str(
+                                matches.name("nth").unwrap().as_str().trim_start_matches("+")
+                            ).unwrap();
+                            if nth_number == 0 || nth_number.abs() > 5 {
+                                return Err(
+                                    PlanetError::new(
+                                        500, 
+                                        Some(tr!("Recurrence day not valid: \"{}\"", day)),
+                                    )
+                                );
+                            }
+                            nth = Some(nth_number);
+                        }
+                        by_day.push((nth, weekday));
+                    }
+                },
+                _ => {
+                    return Err(
+                        PlanetError::new(
+                            500, 
+                            Some(tr!("Recurrence rule part not supported: \"{}\"", fields[0])),
+                        )
+                    );
+                }
+            }
+        }
+        if frequency.is_none() {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Recurrence rule needs FREQ: \"{}\"", &rule_source)),
+                )
+            );
+        }
+        let frequency = frequency.unwrap();
+        if count.is_some() && until.is_some() {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Recurrence rule cannot have both COUNT and UNTIL: \"{}\"", &rule_source)),
+                )
+            );
+        }
+        if by_day.len() > 0 && frequency == RecurrenceFrequency::Yearly {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("BYDAY is not supported with FREQ=YEARLY: \"{}\"", &rule_source)),
+                )
+            );
+        }
+        for (nth, _) in &by_day {
+            if nth.is_some() && frequency != RecurrenceFrequency::Monthly {
+                return Err(
+                    PlanetError::new(
+                        500, 
+                        Some(tr!("BYDAY with position is only supported with FREQ=MONTHLY: \"{}\"", &rule_source)),
+                    )
+                );
+            }
+        }
+        let recurrence = Self{
+            frequency: frequency,
+            interval: interval,
+            by_day: by_day,
+            count: count,
+            until: until,
+            start: start,
+            time_zone: time_zone.clone(),
+        };
+        return Ok(recurrence)
+    }
+    pub fn get_rule(&self) -> String {
+        let frequency = match self.frequency {
+            RecurrenceFrequency::Daily => "DAILY",
+            RecurrenceFrequency::Weekly => "WEEKLY",
+            RecurrenceFrequency::Monthly => "MONTHLY",
+            RecurrenceFrequency::Yearly => "YEARLY",
+        };
+        let mut parts: Vec<String> = Vec::new();
+        parts.push(format!("FREQ={}", frequency));
+        if self.interval != 1 {
+            parts.push(format!("INTERVAL={}", self.interval));
+        }
+        if self.by_day.len() > 0 {
+            let mut days: Vec<String> = Vec::new();
+            for (nth, weekday) in &self.by_day {
+                let day = get_rrule_day(weekday);
+                if nth.is_some() {
+                    days.push(format!("{}{}", nth.unwrap(), day));
+                } else {
+                    days.push(day);
+                }
+            }
+            parts.push(format!("BYDAY={}", days.join(",")));
+        }
+        if self.count.is_some() {
+            parts.push(format!("COUNT={}", self.count.unwrap()));
+        }
+        if self.until.is_some() {
+            parts.push(format!("UNTIL={}", self.until.unwrap().format("%Y%m%dT%H%M%SZ")));
+        }
+        if self.start.is_some() {
+            parts.push(format!("DTSTART={}", self.start.unwrap().format("%Y%m%dT%H%M%SZ")));
+        }
+        return parts.join(";")
+    }
+    pub fn get_occurrences(
+        &self, 
+        from: &DateTime<Utc>, 
+        to: Option<&DateTime<Utc>>, 
+        limit: usize,
+    ) -> Vec<DateTime<Utc>> {
+        // Rules without DTSTART start at from date. We expand in context time zone, so occurrences keep
+        // wall clock time across DST changes.
+        let tz = get_time_zone(&None, &self.time_zone);
+        let start = self.start.unwrap_or(from.clone());
+        let start_local = start.with_timezone(&tz).naive_local();
+        let mut occurrences: Vec<DateTime<Utc>> = Vec::new();
+        let mut count: u32 = 0;
+        for period in 0..RECURRENCE_MAX_PERIODS {
+            let candidates = self.get_period_candidates(&start_local, period);
+            for candidate in candidates {
+                if candidate < start_local {
+                    continue
+                }
+                let candidate = tz.from_local_datetime(&candidate).earliest();
+                if candidate.is_none() {
+                    continue
+                }
+                let candidate = candidate.unwrap().with_timezone(&Utc);
+                if self.until.is_some() && candidate > self.until.unwrap() {
+                    return occurrences
+                }
+                count += 1;
+                if self.count.is_some() && count > self.count.unwrap() {
+                    return occurrences
+                }
+                if to.is_some() && candidate > *to.unwrap() {
+                    return occurrences
+                }
+                if candidate >= *from {
+                    occurrences.push(candidate);
+                    if occurrences.len() >= limit {
+                        return occurrences
+                    }
+                }
+            }
+        }
+        return occurrences
+    }
+    pub fn get_next_occurrence(&self, after: &DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let from = after.clone() + Duration::seconds(1);
+        let occurrences = self.get_occurrences(&from, None, 1);
+        if occurrences.len() == 0 {
+            return None
+        }
+        return Some(occurrences[0])
+    }
+    pub fn has_occurrence(&self, from: &DateTime<Utc>, to: &DateTime<Utc>) -> bool {
+        let occurrences = self.get_occurrences(from, Some(to), 1);
+        return occurrences.len() > 0
+    }
+    fn get_period_candidates(&self, start: &NaiveDateTime, period: i64) -> Vec<NaiveDateTime> {
+        let mut candidates: Vec<NaiveDateTime> = Vec::new();
+        let start_date = start.date();
+        let start_time = start.time();
+        let step = period*self.interval;
+        match self.frequency {
+            RecurrenceFrequency::Daily => {
+                let date = start_date + Duration::days(step);
+                if self.by_day.len() == 0 || self.has_weekday(&date.weekday()) {
+                    candidates.push(date.and_time(start_time));
+                }
+            },
+            RecurrenceFrequency::Weekly => {
+                let monday = start_date - Duration::days(start_date.weekday().num_days_from_monday() as i64);
+                let monday = monday + Duration::weeks(step);
+                let mut days: Vec<u32> = Vec::new();
+                if self.by_day.len() == 0 {
+                    days.push(start_date.weekday().num_days_from_monday());
+                }
+                for (_, weekday) in &self.by_day {
+                    days.push(weekday.num_days_from_monday());
+                }
+                days.sort();
+                days.dedup();
+                for day in days {
+                    let date = monday + Duration::days(day as i64);
+                    candidates.push(date.and_time(start_time));
+                }
+            },
+            RecurrenceFrequency::Monthly => {
+                let total_months = start_date.year() as i64 * 12 + start_date.month0() as i64 + step;
+                let year = total_months.div_euclid(12) as i32;
+                let month = total_months.rem_euclid(12) as u32 + 1;
+                let mut dates: Vec<NaiveDate> = Vec::new();
+                if self.by_day.len() == 0 {
+                    // Months without this day are skipped
+                    let date = NaiveDate::from_ymd_opt(year, month, start_date.day());
+                    if date.is_some() {
+                        dates.push(date.unwrap());
+                    }
+                }
+                for (nth, weekday) in &self.by_day {
+                    let mut month_days: Vec<NaiveDate> = Vec::new();
+                    let mut date = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
+                    while date.month() == month {
+                        if date.weekday() == *weekday {
+                            month_days.push(date);
+                        }
+                        date = date + Duration::days(1);
+                    }
+                    if nth.is_some() {
+                        let nth = nth.unwrap();
+                        let index: i32;
+                        if nth > 0 {
+                            index = nth - 1;
+                        } else {
+                            index = month_days.len() as i32 + nth;
+                        }
+                        if index >= 0 && (index as usize) < month_days.len() {
+                            dates.push(month_days[index as usize]);
+                        }
+                    } else {
+                        dates.extend(month_days);
+                    }
+                }
+                dates.sort();
+                dates.dedup();
+                for date in dates {
+                    candidates.push(date.and_time(start_time));
+                }
+            },
+            RecurrenceFrequency::Yearly => {
+                let year = start_date.year() + step as i32;
+                let date = NaiveDate::from_ymd_opt(year, start_date.month(), start_date.day());
+                if date.is_some() {
+                    candidates.push(date.unwrap().and_time(start_time));
+                }
+            },
+        }
+        return candidates
+    }
+    fn has_weekday(&self, weekday: &Weekday) -> bool {
+        for (_, item) in &self.by_day {
+            if item == weekday {
+                return true
+            }
+        }
+        return false
+    }
+}
+
+fn get_rrule_weekday(day: &str) -> Weekday {
+    match day {
+        "MO" => Weekday::Mon,
+        "TU" => Weekday::Tue,
+        "WE" => Weekday::Wed,
+        "TH" => Weekday::Thu,
+        "FR" => Weekday::Fri,
+        "SA" => Weekday::Sat,
+        _ => Weekday::Sun,
+    }
+}
+
+fn get_rrule_day(weekday: &Weekday) -> &str {
+    match weekday {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+fn get_rrule_date(value: &str, time_zone: &Option<String>) -> Result<DateTime<Utc>, PlanetError> {
+    // 20241231, 20241231T090000Z (UTC), 20241231T090000 (context time zone)
+    let matches = RE_RRULE_DATE.captures(value);
+    if matches.is_some() {
+        let matches = matches.unwrap();
+        let date = matches.name("date").unwrap().as_str();
+        let mut time = "000000";
+        if matches.name("time").is_some() {
+            time = matches.name("time").unwrap().as_str();
+        }
+        let naive = NaiveDateTime::parse_from_str(
+            format!("{}{}", date, time).as_str(), 
+            "%Y%m%d%H%M%S"
+        );
+        if naive.is_ok() {
+            let naive = naive.unwrap();
+            if matches.name("utc").is_some() {
+                return Ok(Utc.from_utc_datetime(&naive))
+            }
+            let local = localize_date_time(&naive, &get_time_zone(&None, time_zone))?;
+            return Ok(local.with_timezone(&Utc))
+        }
+    }
+    return Err(
+        PlanetError::new(
+            500, 
+            Some(tr!("Recurrence date not valid: \"{}\"", value)),
+        )
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECOND: i64 = 1000000;
+
+    #[test]
+    fn parse_duration_clock() {
+        assert_eq!(parse_duration(&String::from("1:30")).unwrap(), 5400*SECOND);
+        assert_eq!(parse_duration(&String::from("1:30:15.5")).unwrap(), 5415*SECOND + 500000);
+        assert_eq!(parse_duration(&String::from("\"49:00:05\"")).unwrap(), (49*3600 + 5)*SECOND);
+        assert!(parse_duration(&String::from("1:75")).is_err());
+    }
+
+    #[test]
+    fn parse_duration_iso() {
+        assert_eq!(parse_duration(&String::from("PT90M")).unwrap(), 5400*SECOND);
+        assert_eq!(parse_duration(&String::from("P1DT2H")).unwrap(), 93600*SECOND);
+        assert_eq!(parse_duration(&String::from("P1W")).unwrap(), 604800*SECOND);
+        assert!(parse_duration(&String::from("P1Y")).is_err());
+        assert!(parse_duration(&String::from("PT")).is_err());
+    }
+
+    #[test]
+    fn parse_duration_human() {
+        assert_eq!(parse_duration(&String::from("1h 30m")).unwrap(), 5400*SECOND);
+        assert_eq!(parse_duration(&String::from("90 min")).unwrap(), 5400*SECOND);
+        assert_eq!(parse_duration(&String::from("1 hour and 30 minutes")).unwrap(), 5400*SECOND);
+        assert_eq!(parse_duration(&String::from("2 days, 1.5 hours")).unwrap(), (2*86400 + 5400)*SECOND);
+        assert_eq!(parse_duration(&String::from("250ms")).unwrap(), 250000);
+        assert!(parse_duration(&String::from("3 fortnights")).is_err());
+        assert!(parse_duration(&String::from("soon")).is_err());
+    }
+
+    #[test]
+    fn format_duration_formats() {
+        let micros = 5415*SECOND + 500000;
+        assert_eq!(format_duration(micros, &DurationFormat::Clock), "1:30:15.5");
+        assert_eq!(format_duration(5400*SECOND, &DurationFormat::ISO), "PT1H30M");
+        assert_eq!(format_duration(0, &DurationFormat::ISO), "PT0S");
+        assert_eq!(format_duration(93605*SECOND, &DurationFormat::Compact), "1d 2h 5s");
+        assert_eq!(format_duration(3660*SECOND, &DurationFormat::Long), "1 hour 1 minute");
+        assert_eq!(format_duration(-5400*SECOND, &DurationFormat::Compact), "-1h 30m");
+        assert_eq!(format_duration(0, &DurationFormat::Compact), "0s");
+    }
+
+    #[test]
+    fn format_duration_parses_back() {
+        let micros = (2*86400 + 3*3600 + 4*60 + 5)*SECOND;
+        for duration_format in [DurationFormat::Clock, DurationFormat::ISO, DurationFormat::Compact, DurationFormat::Long] {
+            let formatted = format_duration(micros, &duration_format);
+            assert_eq!(parse_duration(&formatted).unwrap(), micros, "{}", &formatted);
+        }
+    }
+
+    fn get_date(year: i32, month: u32, day: u32, hour: u32) -> DateTime<Utc> {
+        return Utc.with_ymd_and_hms(year, month, day, hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn recurrence_weekly_by_day() {
+        let rule = String::from("DTSTART:20240101T090000Z RRULE:FREQ=WEEKLY;BYDAY=MO,WE;COUNT=4");
+        let recurrence = Recurrence::parse(&rule, &None).unwrap();
+        let occurrences = recurrence.get_occurrences(&get_date(2024, 1, 1, 0), None, 10);
+        assert_eq!(occurrences, vec![
+            get_date(2024, 1, 1, 9), get_date(2024, 1, 3, 9), get_date(2024, 1, 8, 9), get_date(2024, 1, 10, 9),
+        ]);
+    }
+
+    #[test]
+    fn recurrence_monthly_last_weekday_until() {
+        let rule = String::from("DTSTART:20240101T090000Z RRULE:FREQ=MONTHLY;BYDAY=-1FR;UNTIL=20240430T000000Z");
+        let recurrence = Recurrence::parse(&rule, &None).unwrap();
+        let occurrences = recurrence.get_occurrences(&get_date(2024, 1, 1, 0), None, 10);
+        assert_eq!(occurrences, vec![
+            get_date(2024, 1, 26, 9), get_date(2024, 2, 23, 9), get_date(2024, 3, 29, 9), get_date(2024, 4, 26, 9),
+        ]);
+    }
+
+    #[test]
+    fn recurrence_monthly_skips_short_months() {
+        let rule = String::from("DTSTART:20240131T090000Z RRULE:FREQ=MONTHLY;COUNT=3");
+        let recurrence = Recurrence::parse(&rule, &None).unwrap();
+        let occurrences = recurrence.get_occurrences(&get_date(2024, 1, 1, 0), None, 10);
+        assert_eq!(occurrences, vec![
+            get_date(2024, 1, 31, 9), get_date(2024, 3, 31, 9), get_date(2024, 5, 31, 9),
+        ]);
+    }
+
+    #[test]
+    fn recurrence_keeps_wall_clock_across_dst() {
+        let rule = String::from("DTSTART:20240330T090000 RRULE:FREQ=DAILY;COUNT=3");
+        let recurrence = Recurrence::parse(&rule, &Some(String::from("Europe/Madrid"))).unwrap();
+        let occurrences = recurrence.get_occurrences(&get_date(2024, 3, 1, 0), None, 10);
+        assert_eq!(occurrences, vec![
+            get_date(2024, 3, 30, 8), get_date(2024, 3, 31, 7), get_date(2024, 4, 1, 7),
+        ]);
+    }
+
+    #[test]
+    fn recurrence_next_occurrence_and_range() {
+        let rule = String::from("DTSTART:20240101T090000Z RRULE:FREQ=DAILY;INTERVAL=2");
+        let recurrence = Recurrence::parse(&rule, &None).unwrap();
+        let next = recurrence.get_next_occurrence(&get_date(2024, 1, 1, 9));
+        assert_eq!(next, Some(get_date(2024, 1, 3, 9)));
+        assert!(recurrence.has_occurrence(&get_date(2024, 1, 5, 0), &get_date(2024, 1, 5, 23)));
+        assert!(!recurrence.has_occurrence(&get_date(2024, 1, 6, 0), &get_date(2024, 1, 6, 23)));
+    }
+
+    #[test]
+    fn recurrence_rule_round_trip() {
+        let rule = String::from("DTSTART:20240105T090000Z RRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE;COUNT=10");
+        let recurrence = Recurrence::parse(&rule, &None).unwrap();
+        assert_eq!(recurrence.get_rule(), "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE;COUNT=10;DTSTART=20240105T090000Z");
+    }
+
+    #[test]
+    fn recurrence_rules_not_valid() {
+        let rules = [
+            "RRULE:FREQ=HOURLY",
+            "RRULE:BYDAY=MO",
+            "RRULE:FREQ=DAILY;COUNT=2;UNTIL=20240101",
+            "RRULE:FREQ=WEEKLY;BYDAY=1MO",
+            "RRULE:FREQ=YEARLY;BYDAY=MO",
+            "RRULE:FREQ=DAILY;INTERVAL=0",
+        ];
+        for rule in rules {
+            assert!(Recurrence::parse(&rule.to_string(), &None).is_err(), "{}", rule);
+        }
+    }
+}
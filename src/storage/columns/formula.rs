@@ -7,7 +7,7 @@ use serde_yaml;
 use crate::planet::{PlanetError};
 use crate::statements::folder::schema::*;
 use crate::storage::constants::*;
-use crate::functions::{execute_formula, Formula};
+use crate::functions::{execute_formula, Formula, FormulaEnv};
 use crate::storage::columns::*;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -87,6 +87,7 @@ impl FormulaColumn {
     pub fn validate(&self, 
         data_map: &BTreeMap<String, Vec<BTreeMap<String, String>>>,
         field_config_map: &BTreeMap<String, ColumnConfig>,
+        formula_env: &FormulaEnv,
     ) -> Result<Vec<String>, Vec<PlanetError>> {
         let config = self.config.clone();
         let field_config_map = field_config_map.clone();
@@ -95,9 +96,10 @@ impl FormulaColumn {
         // execute formula and return result string
         if formula_compiled_str.is_some() {
             let formula_compiled_str = formula_compiled_str.unwrap();
-            let formula_compiled: Formula = serde_yaml::from_str(
+            let mut formula_compiled: Formula = serde_yaml::from_str(
                 formula_compiled_str.as_str()
             ).unwrap();
+            formula_compiled.env = Some(formula_env.clone());
             let formula_result = execute_formula(
                 &formula_compiled, 
                 &data_map, 
@@ -0,0 +1,400 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use regex::Regex;
+use lazy_static::lazy_static;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+
+use crate::planet::PlanetError;
+use crate::statements::folder::schema::*;
+use crate::storage::constants::*;
+use crate::storage::columns::*;
+
+lazy_static! {
+    pub static ref RE_LOCATION: Regex = Regex::new(r#"^[\s]*(?P<lat>[+-]?\d{1,3}(\.\d+)?)[\s]*[,;\s][\s]*(?P<lon>[+-]?\d{1,3}(\.\d+)?)([\s]*[,;|][\s]*(?P<label>.*))?[\s]*$"#).unwrap();
+}
+
+pub const EARTH_RADIUS_KM: f64 = 6371.0088;
+// Geohash precision we write into the geo index, cells of about 4.8 x 4.8 meters.
+pub const GEOHASH_PRECISION: usize = 9;
+// Maximum number of geohash cells we scan for a radius or bounding box query
+pub const GEOHASH_MAX_CELLS: usize = 32;
+const GEOHASH_BASE32: &str = "0123456789bcdefghjkmnpqrstuvwxyz";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LocationColumn {
+    pub config: ColumnConfig,
+}
+impl LocationColumn {
+    pub fn defaults(config: &ColumnConfig) -> Self {
+        let field_config = config.clone();
+        let field_obj = Self{
+            config: field_config,
+        };
+        return field_obj
+    }
+}
+impl StorageColumn for LocationColumn {
+    fn create_config(
+        &mut self,
+        field_config_map: &BTreeMap<String, String>,
+    ) -> Result<BTreeMap<String, String>, PlanetError> {
+        let field_config_map = field_config_map.clone();
+        return Ok(field_config_map)
+    }
+    fn get_config(
+        &mut self,
+        _: &BTreeMap<String, String>,
+    ) -> Result<ColumnConfig, PlanetError> {
+        let config = self.config.clone();
+        return Ok(config)
+    }
+    fn validate(&self, data: &Vec<String>) -> Result<Vec<String>, Vec<PlanetError>> {
+        let data = data.clone();
+        let config = self.config.clone();
+        let set_validate = validate_set(&config, &data);
+        if set_validate.is_err() {
+            let error = set_validate.unwrap_err();
+            let mut errors: Vec<PlanetError> = Vec::new();
+            errors.push(error);
+            return Err(errors)
+        }
+        // 40.4168, -3.7038, Madrid => 40.416800,-3.703800|Madrid
+        let mut data_new: Vec<String> = Vec::new();
+        for data_item in data {
+            let location = Location::parse(&data_item);
+            if location.is_err() {
+                let mut errors: Vec<PlanetError> = Vec::new();
+                errors.push(location.unwrap_err());
+                return Err(errors);
+            }
+            data_new.push(location.unwrap().serialize());
+        }
+        return Ok(data_new)
+    }
+    fn get_yaml_out(&self, yaml_string: &String, value: &String) -> String {
+        let field_config = self.config.clone();
+        let field_name = field_config.name.unwrap();
+        let mut yaml_string = yaml_string.clone();
+        let field = &field_name.truecolor(
+            YAML_COLOR_BLUE[0], YAML_COLOR_BLUE[1], YAML_COLOR_BLUE[2]
+        );
+        let value = format!("{}",
+            value.truecolor(YAML_COLOR_ORANGE[0], YAML_COLOR_ORANGE[1], YAML_COLOR_ORANGE[2]),
+        );
+        yaml_string.push_str(format!("  {field}: {value}\n", field=field, value=value).as_str());
+        return yaml_string;
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Location {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub label: Option<String>,
+}
+impl Location {
+    pub fn parse(value: &String) -> Result<Self, PlanetError> {
+        let value = value.replace("\"", "");
+        let expr = &RE_LOCATION;
+        let captures = expr.captures(value.as_str());
+        if captures.is_none() {
+            return Err(
+                PlanetError::new(
+                    500,
+                    Some(tr!("Location \"{}\" is not valid. Use \"latitude, longitude\" with optional label.", &value)),
+                )
+            );
+        }
+        let captures = captures.unwrap();
+        let latitude: f64 = FromStr::from_str(captures.name("lat").unwrap().as_str()).unwrap();
+        let longitude: f64 = FromStr::from_str(captures.name("lon").unwrap().as_str()).unwrap();
+        if latitude < -90.0 || latitude > 90.0 {
+            return Err(
+                PlanetError::new(
+                    500,
+                    Some(tr!("Latitude \"{}\" needs to be between -90 and 90.", &latitude)),
+                )
+            );
+        }
+        if longitude < -180.0 || longitude > 180.0 {
+            return Err(
+                PlanetError::new(
+                    500,
+                    Some(tr!("Longitude \"{}\" needs to be between -180 and 180.", &longitude)),
+                )
+            );
+        }
+        let mut label: Option<String> = None;
+        let label_match = captures.name("label");
+        if label_match.is_some() {
+            let label_str = label_match.unwrap().as_str().trim().to_string();
+            if label_str.len() > 0 {
+                label = Some(label_str);
+            }
+        }
+        let location = Self{
+            latitude: latitude,
+            longitude: longitude,
+            label: label,
+        };
+        return Ok(location)
+    }
+    pub fn serialize(&self) -> String {
+        let mut value = format!("{:.6},{:.6}", &self.latitude, &self.longitude);
+        if self.label.is_some() {
+            value = format!("{}|{}", &value, self.label.clone().unwrap());
+        }
+        return value
+    }
+    // Great circle distance in kilometers (haversine)
+    pub fn get_distance(&self, other: &Location) -> f64 {
+        let lat_a = self.latitude.to_radians();
+        let lat_b = other.latitude.to_radians();
+        let delta_lat = (other.latitude - self.latitude).to_radians();
+        let delta_lon = (other.longitude - self.longitude).to_radians();
+        let a = (delta_lat/2.0).sin().powi(2) + lat_a.cos()*lat_b.cos()*(delta_lon/2.0).sin().powi(2);
+        let c = 2.0*a.sqrt().atan2((1.0-a).sqrt());
+        return EARTH_RADIUS_KM*c
+    }
+    pub fn is_within_radius(&self, center: &Location, km: f64) -> bool {
+        return self.get_distance(center) <= km
+    }
+    pub fn is_within_box(&self, bounding_box: &LocationBox) -> bool {
+        let check_lat = self.latitude >= bounding_box.min_latitude &&
+            self.latitude <= bounding_box.max_latitude;
+        let check_lon: bool;
+        if bounding_box.min_longitude <= bounding_box.max_longitude {
+            check_lon = self.longitude >= bounding_box.min_longitude &&
+                self.longitude <= bounding_box.max_longitude;
+        } else {
+            // Box crossing the antimeridian
+            check_lon = self.longitude >= bounding_box.min_longitude ||
+                self.longitude <= bounding_box.max_longitude;
+        }
+        return check_lat && check_lon
+    }
+    pub fn get_geohash(&self, precision: usize) -> String {
+        return get_geohash(self.latitude, self.longitude, precision)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LocationBox {
+    pub min_latitude: f64,
+    pub min_longitude: f64,
+    pub max_latitude: f64,
+    pub max_longitude: f64,
+}
+impl LocationBox {
+    pub fn defaults(
+        min_latitude: f64,
+        min_longitude: f64,
+        max_latitude: f64,
+        max_longitude: f64
+    ) -> Result<Self, PlanetError> {
+        let corner_min = Location::parse(&format!("{},{}", &min_latitude, &min_longitude))?;
+        let corner_max = Location::parse(&format!("{},{}", &max_latitude, &max_longitude))?;
+        if corner_min.latitude > corner_max.latitude {
+            return Err(
+                PlanetError::new(
+                    500,
+                    Some(tr!("Bounding box minimum latitude is higher than maximum latitude.")),
+                )
+            );
+        }
+        let obj = Self{
+            min_latitude: corner_min.latitude,
+            min_longitude: corner_min.longitude,
+            max_latitude: corner_max.latitude,
+            max_longitude: corner_max.longitude,
+        };
+        return Ok(obj)
+    }
+    // Box around center enclosing a radius in kilometers
+    pub fn from_radius(center: &Location, km: f64) -> Self {
+        let delta_lat = (km/EARTH_RADIUS_KM).to_degrees();
+        let mut min_latitude = center.latitude - delta_lat;
+        let mut max_latitude = center.latitude + delta_lat;
+        let mut min_longitude = -180.0;
+        let mut max_longitude = 180.0;
+        if min_latitude > -90.0 && max_latitude < 90.0 {
+            let delta_lon = (km/(EARTH_RADIUS_KM*center.latitude.to_radians().cos())).to_degrees();
+            if delta_lon < 180.0 {
+                min_longitude = center.longitude - delta_lon;
+                max_longitude = center.longitude + delta_lon;
+                if min_longitude < -180.0 {
+                    min_longitude += 360.0;
+                }
+                if max_longitude > 180.0 {
+                    max_longitude -= 360.0;
+                }
+            }
+        }
+        if min_latitude < -90.0 {
+            min_latitude = -90.0;
+        }
+        if max_latitude > 90.0 {
+            max_latitude = 90.0;
+        }
+        let obj = Self{
+            min_latitude: min_latitude,
+            min_longitude: min_longitude,
+            max_latitude: max_latitude,
+            max_longitude: max_longitude,
+        };
+        return obj
+    }
+    // Geohash cells covering the box, as prefixes for the geo index. Precision goes down until we have
+    // less than GEOHASH_MAX_CELLS cells.
+    pub fn get_geohash_cells(&self) -> Vec<String> {
+        let mut ranges: Vec<(f64, f64)> = Vec::new();
+        if self.min_longitude <= self.max_longitude {
+            ranges.push((self.min_longitude, self.max_longitude));
+        } else {
+            ranges.push((self.min_longitude, 180.0));
+            ranges.push((-180.0, self.max_longitude));
+        }
+        let mut precision = GEOHASH_PRECISION;
+        while precision > 1 {
+            let (cell_height, cell_width) = get_geohash_cell_size(precision);
+            let mut number_cells: f64 = 0.0;
+            for (min_longitude, max_longitude) in ranges.clone() {
+                let rows = ((self.max_latitude - self.min_latitude)/cell_height).floor() + 2.0;
+                let columns = ((max_longitude - min_longitude)/cell_width).floor() + 2.0;
+                number_cells += rows*columns;
+            }
+            if number_cells <= GEOHASH_MAX_CELLS as f64 {
+                break
+            }
+            precision -= 1;
+        }
+        let (cell_height, cell_width) = get_geohash_cell_size(precision);
+        let mut cells: Vec<String> = Vec::new();
+        for (min_longitude, max_longitude) in ranges {
+            let mut latitude = self.min_latitude;
+            loop {
+                let mut longitude = min_longitude;
+                loop {
+                    let cell = get_geohash(latitude, longitude, precision);
+                    if !cells.contains(&cell) {
+                        cells.push(cell);
+                    }
+                    if longitude >= max_longitude {
+                        break
+                    }
+                    longitude = (longitude + cell_width).min(max_longitude);
+                }
+                if latitude >= self.max_latitude {
+                    break
+                }
+                latitude = (latitude + cell_height).min(self.max_latitude);
+            }
+        }
+        return cells
+    }
+}
+
+pub fn get_geohash(latitude: f64, longitude: f64, precision: usize) -> String {
+    let alphabet: Vec<char> = GEOHASH_BASE32.chars().collect();
+    let mut lat_range = (-90.0, 90.0);
+    let mut lon_range = (-180.0, 180.0);
+    let mut geohash = String::from("");
+    let mut is_longitude = true;
+    let mut bit = 0;
+    let mut index: usize = 0;
+    while geohash.len() < precision {
+        if is_longitude {
+            let middle = (lon_range.0 + lon_range.1)/2.0;
+            if longitude >= middle {
+                index = index*2 + 1;
+                lon_range.0 = middle;
+            } else {
+                index = index*2;
+                lon_range.1 = middle;
+            }
+        } else {
+            let middle = (lat_range.0 + lat_range.1)/2.0;
+            if latitude >= middle {
+                index = index*2 + 1;
+                lat_range.0 = middle;
+            } else {
+                index = index*2;
+                lat_range.1 = middle;
+            }
+        }
+        is_longitude = !is_longitude;
+        bit += 1;
+        if bit == 5 {
+            geohash.push(alphabet[index]);
+            bit = 0;
+            index = 0;
+        }
+    }
+    return geohash
+}
+
+// Cell (height, width) in degrees for a geohash precision
+pub fn get_geohash_cell_size(precision: usize) -> (f64, f64) {
+    let bits = 5*precision as i32;
+    let lon_bits = (bits + 1)/2;
+    let lat_bits = bits/2;
+    let height = 180.0/2.0_f64.powi(lat_bits);
+    let width = 360.0/2.0_f64.powi(lon_bits);
+    return (height, width)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_covered(cells: &Vec<String>, latitude: f64, longitude: f64) -> bool {
+        let precision = cells[0].len();
+        let geohash = get_geohash(latitude, longitude, precision);
+        return cells.contains(&geohash)
+    }
+
+    #[test]
+    fn geohash_known_values() {
+        assert_eq!(get_geohash(57.64911, 10.40744, 11), "u4pruydqqvj");
+        assert_eq!(get_geohash(42.6, -5.6, 5), "ezs42");
+        assert_eq!(get_geohash(-90.0, -180.0, 3), "000");
+    }
+
+    #[test]
+    fn geohash_cell_size() {
+        assert_eq!(get_geohash_cell_size(1), (45.0, 45.0));
+        assert_eq!(get_geohash_cell_size(2), (5.625, 11.25));
+        let (height, width) = get_geohash_cell_size(GEOHASH_PRECISION);
+        assert!(height < 0.0001 && width < 0.0001);
+    }
+
+    #[test]
+    fn geohash_cells_cover_box() {
+        let center = Location::parse(&String::from("40.4168,-3.7038")).unwrap();
+        let bounding_box = LocationBox::from_radius(&center, 5.0);
+        let cells = bounding_box.get_geohash_cells();
+        assert!(cells.len() > 0 && cells.len() <= GEOHASH_MAX_CELLS);
+        for i in 0..=10 {
+            for j in 0..=10 {
+                let latitude = bounding_box.min_latitude + 
+                    (bounding_box.max_latitude - bounding_box.min_latitude)*(i as f64)/10.0;
+                let longitude = bounding_box.min_longitude + 
+                    (bounding_box.max_longitude - bounding_box.min_longitude)*(j as f64)/10.0;
+                assert!(is_covered(&cells, latitude, longitude), "{},{}", latitude, longitude);
+            }
+        }
+        assert!(!is_covered(&cells, 41.3874, 2.1686));
+    }
+
+    #[test]
+    fn geohash_cells_cross_antimeridian() {
+        let center = Location::parse(&String::from("0.0,179.99")).unwrap();
+        let bounding_box = LocationBox::from_radius(&center, 10.0);
+        assert!(bounding_box.min_longitude > bounding_box.max_longitude);
+        let cells = bounding_box.get_geohash_cells();
+        assert!(is_covered(&cells, 0.0, 179.95));
+        assert!(is_covered(&cells, 0.0, -179.95));
+        assert!(!is_covered(&cells, 0.0, 0.0));
+    }
+}
@@ -2,20 +2,24 @@ extern crate rust_stemmers;
 extern crate dirs;
 
 use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
+use std::io::{BufReader, Cursor};
 use colored::Colorize;
 use std::fs::File;
 use mime_guess;
-use json;
-use reqwest::blocking::Client;
+use image::{DynamicImage, ImageFormat};
+use exif;
 use serde_yaml;
 
-use crate::planet::PlanetError;
+use crate::planet::{PlanetError, TikaConfig};
 use crate::statements::folder::schema::*;
 use crate::storage::constants::*;
 use crate::storage::columns::*;
-use crate::storage::folder::{DbFile, RoutingData, TreeFolderItem};
+use crate::storage::folder::{DbFile, RoutingData, TreeFolderItem, build_value_list};
 use crate::storage::generate_id;
 use crate::storage::space::SpaceDatabase;
+use crate::storage::columns::geo::Location;
+use crate::storage::extractors::{extract_document, ExtractedDocument};
 
 #[derive(Debug, Clone)]
 pub struct FileColumn {
@@ -43,6 +47,8 @@ impl FileColumn {
         data: &BTreeMap<String, Vec<BTreeMap<String, String>>>,
         routing: Option<RoutingData>,
         home_dir: &String,
+        column_config_map: &BTreeMap<String, ColumnConfig>,
+        tika: &Option<TikaConfig>,
     ) -> Result<(
             Vec<String>,
             Vec<String>,
@@ -58,9 +64,13 @@ impl FileColumn {
         if content_types_wrap.is_some() {
             content_types = content_types_wrap.unwrap();
         }
+        let thumbnail_sizes = self.get_thumbnail_sizes();
+        let location_column_id = self.get_location_column_id(column_config_map)?;
         let mut document_texts: Vec<String> = Vec::new();
         let mut file_ids: Vec<String> = Vec::new();
         for path in paths.clone() {
+            // Image properties and thumbnail file ids, kept also at file database options
+            let mut file_options: BTreeMap<String, String> = BTreeMap::new();
             let path_fields: Vec<&str> = path.split("/").collect();
             let file_name = path_fields.last().unwrap();
             // 1. Check path exists, raise error if does not exist
@@ -99,6 +109,7 @@ impl FileColumn {
                     );
                 }
             }
+            let is_image = content_type.starts_with("image/");
             let file_type = get_file_type(&content_type.as_str());
             if file_type.is_err() {
                 let error = file_type.unwrap_err();
@@ -117,185 +128,89 @@ impl FileColumn {
                     )
                 );
             }
-            let client = Client::new();
-            let url = format!("http://{host}:{port}/rmeta/text", host=TIKA_HOST, port=TIKA_PORT);
-            let res = client.put(&url)
-            .body(file)
-            .send();
+            // File is stored even when extractor fails, without text for the index
+            let document: ExtractedDocument;
+            let result = extract_document(&path, &content_type, tika);
+            if result.is_ok() {
+                document = result.unwrap();
+            } else {
+                let error = result.unwrap_err();
+                eprintln!("{}", tr!(
+                    "Warning: no text extracted from file \"{}\": {}", &file_name, &error.message
+                ).yellow());
+                document = ExtractedDocument::defaults();
+            }
             let mut file_id: Option<String> = None;
-            if res.is_ok() {
-                let response = res.unwrap();
-                let response = response.text().unwrap();
-                let json_document = json::parse(&response);
-                let mut my_map: BTreeMap<String, String> = BTreeMap::new();
-                if json_document.is_ok() {
-                    let json_document = json_document.unwrap();
-                    let main_document = &json_document[0];
-                    let title = &main_document["dc:title"];
-                    let created_time = &main_document["dcterms:created"];
-                    let last_modified_time = &main_document["dcterms:modified"];
-                    let content_type = &main_document["Content-Type"];
-                    let creator = &main_document["dc:creator"];
-                    let subject = &main_document["dc:subject"];
-                    let description = &main_document["dc:description"];
-                    let category = &main_document["cp:category"];
-                    let image_width = &main_document["Image Width"];
-                    let image_height = &main_document["Image Height"];
-                    let text = &main_document["X-TIKA:content"];
-                    let id = generate_id();
-                    if id.is_some() {
-                        let id = id.unwrap();
-                        file_id = Some(id.clone());
-                        my_map.insert(
-                            ID.to_string(), 
-                            id
-                        );
-                    }
-                    if !title.is_null() {
-                        my_map.insert(
-                            FILE_PROP_TITLE.to_string(), 
-                            title.to_string()
-                        );
-                    }
-                    my_map.insert(
-                        FILE_PROP_FILE_NAME.to_string(), 
-                        file_name.to_string()
-                    );
-                    my_map.insert(
-                        FILE_PROP_SIZE.to_string(), 
-                        file_size.to_string()
-                    );
-                    if !created_time.is_null() {
-                        my_map.insert(
-                            FILE_PROP_CREATED_TIME.to_string(), 
-                            created_time.to_string()
-                        );
-                    }
-                    if !last_modified_time.is_null() {
-                        my_map.insert(
-                            FILE_PROP_LAST_MODIFIED_TIME.to_string(), 
-                            last_modified_time.to_string()
-                        );
-                    }
-                    if !content_type.is_null() {
-                        my_map.insert(
-                            FILE_PROP_CONTENT_TYPE.to_string(), 
-                            content_type.to_string()
-                        );
-                        my_map.insert(
-                            FILE_PROP_FILE_TYPE.to_string(), 
-                            file_type.clone()
-                        );
-                    }
-                    if !creator.is_null() {
-                        my_map.insert(
-                            FILE_PROP_CREATOR.to_string(), 
-                            creator.to_string()
-                        );
-                    }
-                    if !category.is_null() {
-                        my_map.insert(
-                            FILE_PROP_CATEGORY.to_string(), 
-                            category.to_string()
-                        );
-                    }
-                    if !subject.is_null() {
-                        if subject.is_string() {
-                            my_map.insert(
-                                FILE_PROP_SUBJECT.to_string(), 
-                                subject.to_string()
-                            );
-                        } else {
-                            my_map.insert(
-                                FILE_PROP_SUBJECT.to_string(), 
-                                subject[0].to_string()
-                            );
-                            let keywords = subject[1].to_string();
-                            let check = keywords.contains(",");
-                            if check {
-                                let keyword_list: Vec<&str> = keywords.split(",").collect();
-                                let mut list: Vec<BTreeMap<String, String>> = Vec::new();
-                                for keyword in keyword_list {
-                                    let keyword = keyword.trim();
-                                    let mut my_map: BTreeMap<String, String> = BTreeMap::new();
-                                    my_map.insert(VALUE.to_string(), keyword.to_string());
-                                    list.push(my_map);
-                                }
-                                let key = format!("{}__tags", column_id);
-                                data.insert(key, list);
-                            } else {
-                                let keyword = keywords;
-                                let mut list: Vec<BTreeMap<String, String>> = Vec::new();
-                                let mut my_map: BTreeMap<String, String> = BTreeMap::new();
-                                my_map.insert(VALUE.to_string(), keyword.to_string());
-                                list.push(my_map);
-                                let key = format!("{}__tags", column_id);
-                                data.insert(key, list);
-                            }
-                            my_map.insert(
-                                FILE_PROP_SUBJECT.to_string(), 
-                                subject[0].to_string()
-                            );
+            let mut my_map: BTreeMap<String, String> = BTreeMap::new();
+            let id = generate_id();
+            if id.is_some() {
+                let id = id.unwrap();
+                file_id = Some(id.clone());
+                my_map.insert(
+                    ID.to_string(), 
+                    id
+                );
+            }
+            my_map.insert(
+                FILE_PROP_FILE_NAME.to_string(), 
+                file_name.to_string()
+            );
+            my_map.insert(
+                FILE_PROP_SIZE.to_string(), 
+                file_size.to_string()
+            );
+            if content_type.len() > 0 {
+                my_map.insert(
+                    FILE_PROP_CONTENT_TYPE.to_string(), 
+                    content_type.clone()
+                );
+                my_map.insert(
+                    FILE_PROP_FILE_TYPE.to_string(), 
+                    file_type.clone()
+                );
+            }
+            for (key, value) in document.properties {
+                my_map.insert(key, value);
+            }
+            if document.keywords.len() > 0 {
+                let mut list: Vec<BTreeMap<String, String>> = Vec::new();
+                for keyword in document.keywords {
+                    let mut my_map: BTreeMap<String, String> = BTreeMap::new();
+                    my_map.insert(VALUE.to_string(), keyword);
+                    list.push(my_map);
+                }
+                let key = format!("{}__tags", column_id);
+                data.insert(key, list);
+            }
+            if is_image {
+                let image_properties = get_image_properties(&path);
+                for (key, value) in image_properties {
+                    if key == FILE_PROP_LOCATION && location_column_id.is_some() {
+                        let location_column_id = location_column_id.clone().unwrap();
+                        if data.get(&location_column_id).is_none() {
+                            data.insert(location_column_id, build_value_list(&value));
                         }
                     }
-                    if !image_width.is_null() {
-                        let width_str = image_width.to_string();
-                        let fields: Vec<&str> = width_str.split(" pixels").collect();
-                        my_map.insert(
-                            FILE_PROP_IMAGE_WIDTH.to_string(), 
-                            fields[0].to_string()
-                        );
-                    }
-                    if !image_height.is_null() {
-                        let height_str = image_height.to_string();
-                        let fields: Vec<&str> = height_str.split(" pixels").collect();
-                        my_map.insert(
-                            FILE_PROP_IMAGE_HEIGHT.to_string(), 
-                            fields[0].to_string()
-                        );
-                    }
-                    if !description.is_null() {
-                        my_map.insert(
-                            FILE_PROP_DESCRIPTION.to_string(), 
-                            description.to_string()
-                        );
-                    }
-                    if !text.is_null() {
-                        let mut text = text.to_string();
-                        text = text.replace("\n", "");
-                        text = text.replace("\t", "");
-                        text = text.replace("\r", "");
-                        document_texts.push(text);
-                    }
-                    // let text = &main_document["X-TIKA:content"];
-                    // main_document.remove("X-TIKA:content");
-                    my_map.insert(
-                        FILE_PROP_METADATA.to_string(), 
-                        response
-                    );
-                    let mut my_list: Vec<BTreeMap<String, String>> = Vec::new();
-                    my_list.push(my_map);
-                    data.insert(column_id.clone(), my_list);
-                } else {
-                    return Err(
-                        PlanetError::new(
-                            500, 
-                            Some(tr!(
-                                "Error processing metadata response."
-                            )),
-                        )
-                    );
+                    my_map.insert(key.clone(), value.clone());
+                    file_options.insert(key, value);
                 }
-            } else {
-                return Err(
-                    PlanetError::new(
-                        500, 
-                        Some(tr!(
-                            "Error processing file metadata."
-                        )),
-                    )
+            }
+            if document.text.is_some() {
+                let text = document.text.unwrap();
+                let words: Vec<&str> = text.split_whitespace().collect();
+                if words.len() > 0 {
+                    document_texts.push(words.join(" "));
+                }
+            }
+            if document.metadata.is_some() {
+                my_map.insert(
+                    FILE_PROP_METADATA.to_string(), 
+                    document.metadata.unwrap()
                 );
             }
+            let mut my_list: Vec<BTreeMap<String, String>> = Vec::new();
+            my_list.push(my_map);
+            data.insert(column_id.clone(), my_list);
             // Write file into database or home OS dir
             if file_id.is_some() {
                 let file_id = file_id.unwrap();
@@ -321,6 +236,34 @@ impl FileColumn {
                     );                    
                 }
                 let mut db_file = db_file.unwrap();
+                if db_file.hash.is_some() {
+                    file_options.insert(FILE_PROP_HASH.to_string(), db_file.hash.clone().unwrap());
+                }
+                if is_image && thumbnail_sizes.len() > 0 {
+                    // Image is stored without thumbnails when they cannot be generated
+                    let thumbnail_ids = self.write_thumbnails(
+                        &path, 
+                        &file_id, 
+                        &file_name.to_string(), 
+                        &thumbnail_sizes, 
+                        &db_file, 
+                        &mut db_folder_item
+                    );
+                    if thumbnail_ids.is_ok() {
+                        for (size, thumbnail_id) in thumbnail_ids.unwrap() {
+                            let key = format!("{} {}", FILE_PROP_THUMBNAIL, size);
+                            file_options.insert(key, thumbnail_id);
+                        }
+                    } else {
+                        let error = thumbnail_ids.unwrap_err();
+                        eprintln!("{}", tr!(
+                            "Warning: no thumbnails for file \"{}\": {}", &file_name, &error.message
+                        ).yellow());
+                    }
+                }
+                if file_options.len() > 0 {
+                    db_file.options = Some(file_options);
+                }
                 if size < MAX_FILE_DB {
                     let file_id = db_folder_item.write_file(&db_file);
                     if file_id.is_err() {
@@ -372,6 +315,112 @@ impl FileColumn {
         )
     }
 }
+impl FileColumn {
+    pub fn get_thumbnail_sizes(&self) -> Vec<u32> {
+        let thumbnails = self.config.thumbnails.clone();
+        let mut sizes: Vec<u32> = Vec::new();
+        if thumbnails.is_none() {
+            return sizes
+        }
+        for size in thumbnails.unwrap() {
+            let size: Result<u32, _> = FromStr::from_str(size.as_str());
+            if size.is_ok() {
+                sizes.push(size.unwrap());
+            }
+        }
+        return sizes
+    }
+    fn get_location_column_id(
+        &self, 
+        column_config_map: &BTreeMap<String, ColumnConfig>
+    ) -> Result<Option<String>, PlanetError> {
+        let location_column = self.config.location_column.clone();
+        if location_column.is_none() {
+            return Ok(None)
+        }
+        let location_column = location_column.unwrap();
+        let location_config = column_config_map.get(&location_column);
+        if location_config.is_none() {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Location column \"{}\" not found in folder.", &location_column)),
+                )
+            );
+        }
+        let location_config = location_config.unwrap();
+        let column_type = location_config.column_type.clone().unwrap_or_default();
+        if column_type != COLUMN_TYPE_LOCATION.to_string() {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Column \"{}\" is not a location column.", &location_column)),
+                )
+            );
+        }
+        return Ok(location_config.id.clone())
+    }
+    // Thumbnails are small, always written encrypted into the file database
+    fn write_thumbnails(
+        &self,
+        path: &String,
+        file_id: &String,
+        file_name: &String,
+        sizes: &Vec<u32>,
+        db_file: &DbFile,
+        db_folder_item: &mut TreeFolderItem,
+    ) -> Result<Vec<(u32, String)>, PlanetError> {
+        let thumbnails = get_thumbnails(path, sizes)?;
+        let file_type = get_file_type(THUMBNAIL_CONTENT_TYPE)?;
+        let mut file_stem = file_name.clone();
+        let dot = file_name.rfind(".");
+        if dot.is_some() {
+            file_stem = file_name[..dot.unwrap()].to_string();
+        }
+        let mut thumbnail_ids: Vec<(u32, String)> = Vec::new();
+        for (size, content) in thumbnails {
+            let thumbnail_id = generate_id();
+            if thumbnail_id.is_none() {
+                return Err(
+                    PlanetError::new(
+                        500, 
+                        Some(tr!("Error generating file id.")),
+                    )
+                );
+            }
+            let thumbnail_id = thumbnail_id.unwrap();
+            let mut context: BTreeMap<String, String> = BTreeMap::new();
+            context.insert(THUMBNAIL_OF.to_string(), file_id.clone());
+            let thumbnail_file = DbFile{
+                id: Some(thumbnail_id.clone()),
+                name: Some(format!("{}_{}.jpg", &file_stem, size)),
+                size: Some(content.len() as u64),
+                content_type: Some(THUMBNAIL_CONTENT_TYPE.to_string()),
+                file_type: Some(file_type.clone()),
+                routing: db_file.routing.clone(),
+                options: None,
+                context: Some(context),
+                hash: Some(DbFile::get_content_hash(&content)),
+                content: Some(content),
+                path: None,
+            };
+            let result = db_folder_item.write_file(&thumbnail_file);
+            if result.is_err() {
+                for (_, thumbnail_id) in &thumbnail_ids {
+                    let _ = db_folder_item.delete_file(thumbnail_id);
+                }
+                return Err(
+                    PlanetError::new(
+                        500, 
+                        Some(tr!("Error writing thumbnail into file database.")),
+                    )
+                );
+            }
+            thumbnail_ids.push((size, thumbnail_id));
+        }
+        return Ok(thumbnail_ids)
+    }
+}
 impl StorageColumnBasic for FileColumn {
     fn create_config(
         &mut self, 
@@ -390,6 +439,15 @@ impl StorageColumnBasic for FileColumn {
             let content_types_serialized = serde_yaml::to_string(&content_types).unwrap();
             column_config_map.insert(String::from(CONTENT_TYPES), content_types_serialized);
         }
+        let thumbnails = config.thumbnails;
+        if thumbnails.is_some() {
+            let thumbnails = thumbnails.unwrap();
+            column_config_map.insert(String::from(THUMBNAILS), thumbnails.join(","));
+        }
+        let location_column = config.location_column;
+        if location_column.is_some() {
+            column_config_map.insert(String::from(LOCATION_COLUMN), location_column.unwrap());
+        }
         return Ok(column_config_map)
     }
     fn get_config(
@@ -410,6 +468,18 @@ impl StorageColumnBasic for FileColumn {
             let content_types: Vec<String> = serde_yaml::from_str(content_types).unwrap();
             config.content_types = Some(content_types);
         }
+        let thumbnails = column_config_map.get(THUMBNAILS);
+        if thumbnails.is_some() {
+            let thumbnails = thumbnails.unwrap();
+            let thumbnails: Vec<String> = thumbnails.split(",").filter(|size| size.len() > 0).map(
+                |size| size.to_string()
+            ).collect();
+            config.thumbnails = Some(thumbnails);
+        }
+        let location_column = column_config_map.get(LOCATION_COLUMN);
+        if location_column.is_some() {
+            config.location_column = Some(location_column.unwrap().clone());
+        }
         return Ok(config)
     }
     fn get_yaml_out(&self, yaml_string: &String, value: &String) -> String {
@@ -427,6 +497,134 @@ impl StorageColumnBasic for FileColumn {
     }
 }
 
+// Decodes image once, thumbnails keep aspect ratio within size x size pixels, JPEG encoded
+pub fn get_thumbnails(path: &String, sizes: &Vec<u32>) -> Result<Vec<(u32, Vec<u8>)>, PlanetError> {
+    let image = image::open(path);
+    if image.is_err() {
+        let error = image.unwrap_err();
+        return Err(
+            PlanetError::new(
+                500, 
+                Some(tr!("Could not decode image at \"{}\". Error: \"{}\".", path, &error)),
+            )
+        );
+    }
+    let image = image.unwrap();
+    let mut thumbnails: Vec<(u32, Vec<u8>)> = Vec::new();
+    for size in sizes {
+        let thumbnail = image.thumbnail(*size, *size);
+        let thumbnail = DynamicImage::ImageRgb8(thumbnail.to_rgb8());
+        let mut content: Vec<u8> = Vec::new();
+        let result = thumbnail.write_to(&mut Cursor::new(&mut content), ImageFormat::Jpeg);
+        if result.is_err() {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Could not encode thumbnail of size {} for \"{}\".", size, path)),
+                )
+            );
+        }
+        thumbnails.push((*size, content));
+    }
+    return Ok(thumbnails)
+}
+
+// EXIF camera, taken time and GPS location. Images with no EXIF data return empty map.
+pub fn get_image_properties(path: &String) -> BTreeMap<String, String> {
+    let mut properties: BTreeMap<String, String> = BTreeMap::new();
+    let file = File::open(path);
+    if file.is_err() {
+        return properties
+    }
+    let file = file.unwrap();
+    let mut reader = BufReader::new(&file);
+    let exif_data = exif::Reader::new().read_from_container(&mut reader);
+    if exif_data.is_err() {
+        return properties
+    }
+    let exif_data = exif_data.unwrap();
+    let make = get_exif_ascii(&exif_data, exif::Tag::Make);
+    let model = get_exif_ascii(&exif_data, exif::Tag::Model);
+    if model.is_some() {
+        let model = model.unwrap();
+        let mut camera = model.clone();
+        if make.is_some() {
+            let make = make.unwrap();
+            if !model.starts_with(&make) {
+                camera = format!("{} {}", &make, &model);
+            }
+        }
+        properties.insert(FILE_PROP_CAMERA.to_string(), camera);
+    }
+    let taken_time = get_exif_ascii(&exif_data, exif::Tag::DateTimeOriginal);
+    if taken_time.is_some() {
+        // 2021:12:18 12:14:15 => 2021-12-18 12:14:15
+        let taken_time = taken_time.unwrap();
+        let date_time = exif::DateTime::from_ascii(taken_time.as_bytes());
+        if date_time.is_ok() {
+            properties.insert(FILE_PROP_TAKEN_TIME.to_string(), date_time.unwrap().to_string());
+        }
+    }
+    let latitude = get_exif_coordinate(&exif_data, exif::Tag::GPSLatitude, exif::Tag::GPSLatitudeRef);
+    let longitude = get_exif_coordinate(&exif_data, exif::Tag::GPSLongitude, exif::Tag::GPSLongitudeRef);
+    if latitude.is_some() && longitude.is_some() {
+        let location = Location{
+            latitude: latitude.unwrap(),
+            longitude: longitude.unwrap(),
+            label: None,
+        };
+        properties.insert(FILE_PROP_LOCATION.to_string(), location.serialize());
+    }
+    return properties
+}
+
+fn get_exif_ascii(exif_data: &exif::Exif, tag: exif::Tag) -> Option<String> {
+    let field = exif_data.get_field(tag, exif::In::PRIMARY);
+    if field.is_none() {
+        return None
+    }
+    match field.unwrap().value {
+        exif::Value::Ascii(ref items) => {
+            if items.len() == 0 {
+                return None
+            }
+            let value = String::from_utf8_lossy(&items[0]).trim().to_string();
+            if value.len() == 0 {
+                return None
+            }
+            return Some(value)
+        },
+        _ => {
+            return None
+        }
+    }
+}
+
+// Degrees, minutes and seconds into decimal degrees, south and west are negative
+fn get_exif_coordinate(exif_data: &exif::Exif, tag: exif::Tag, tag_ref: exif::Tag) -> Option<f64> {
+    let field = exif_data.get_field(tag, exif::In::PRIMARY);
+    if field.is_none() {
+        return None
+    }
+    let mut coordinate: f64;
+    match field.unwrap().value {
+        exif::Value::Rational(ref items) => {
+            if items.len() < 3 {
+                return None
+            }
+            coordinate = items[0].to_f64() + items[1].to_f64()/60.0 + items[2].to_f64()/3600.0;
+        },
+        _ => {
+            return None
+        }
+    }
+    let coordinate_ref = get_exif_ascii(exif_data, tag_ref).unwrap_or_default();
+    if coordinate_ref == String::from("S") || coordinate_ref == String::from("W") {
+        coordinate = -coordinate;
+    }
+    return Some(coordinate)
+}
+
 pub fn get_file_types() -> HashMap<String, String> {
     let mut map: HashMap<String, String> = HashMap::new();
     map.insert(String::from("application/vnd.hzn-3d-crossword"), String::from("3D Crossword Plugin"));
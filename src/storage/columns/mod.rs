@@ -6,6 +6,7 @@ pub mod reference;
 pub mod structure;
 pub mod media;
 pub mod processing;
+pub mod geo;
 
 use std::collections::{BTreeMap, HashMap};
 use std::str::FromStr;
@@ -27,6 +28,7 @@ Table fields
 
 * SmallTextColumn                 [done] - text
 * LongTextColumn                  [done] - text : This is the text field, needs to be updated based on full text search.
+* RichTextColumn                  [done] - text : Markdown, sanitized, plain text to index and html output.
 * CheckBoxColumn                  [done] - number
 * SelectColumn                    [done] - text
 * DateColumn                      [done] - date
@@ -43,12 +45,19 @@ Table fields
 * LanguageColumn                  [done] - text
 * TextColumn                      [done] - text
 * GenerateIdColumn                [done] - text : Random ids
-* GenerateNumberColumn            [done] - number: Sequential number - number : Sequence number.
-* PhoneColumn                     [done]
-* EmailColumn                     [done]
-* UrlColumn                       [done]
+* GenerateNumberColumn            [done] - number: Sequential number - number : Sequence number from folder sequences tree, with format, start, step and reset.
+    Numbers of failed inserts are given back, gaps are still possible when a concurrent insert took a later 
+    number or a write fails partway.
+* PhoneColumn                     [done] - text : E.164 numbers, national numbers with default country, digits
+    otherwise.
+* EmailColumn                     [done] - text : Domain lower-cased, IDN domains in punycode.
+* UrlColumn                       [done] - text : Canonical url, optional tracking params stripped.
 * RatingColumn                    [done]
 * SetColumn                       [done] - For example, tags
+* TagsColumn                      [done] - structure : Hierarchical tag paths like project/alpha/design, registered
+    in the space tag registry.
+* WorkflowColumn                  [done] - structure : States with allowed transitions, optionally guarded by a
+    formula. State changes are recorded with time on the item.
 * ObjectColumn                    [done] - I may need this so commands can store unstructured data. I 
     serialize as YAML format.
 * SubFolder                       [done]: This links to another db file with some media data: photo, etc...
@@ -58,7 +67,22 @@ Table fields
 COUNT, COUNTA, COUNTALL, SUM, MAX, AND, OR, XOR, CONCATENATE. I execute these formulas once I post 
 processed the links and references. I would need to parse in a way to use those number functions.
 * FileColumn                      [done] - Custom file and image management with IPFS. I add many.
+    Images get encrypted thumbnails when Thumbnails option is set, and EXIF camera, taken time and GPS, 
+    which can fill a Location column (LocationColumn option). EXPORT FILE ... THUMBNAIL <size> exports a 
+    thumbnail.
+    Text and properties come from native extractors (text, Markdown, CSV, JSON, HTML, DOCX, ODT, PDF), Tika 
+    server as optional backend from planet context for other formats.
+    Content is addressed by SHA-256 hash (Hash file property), stored once per space and reference counted, 
+    removed when last file referencing it is deleted.
 * StatementColumn                 [done]: This does processing for complex cases, like image manipulation
+* RecurrenceColumn                [done] - date: iCalendar RRULE (FREQ, INTERVAL, BYDAY, COUNT, UNTIL, DTSTART).
+* LocationColumn                  [done] - geo: latitude, longitude and optional label. Geohash index.
+* RollupColumn                    [done] - reference: Stats function over a remote column (or formula) of all linked
+    items. Stored on the item and refreshed when linked items are inserted, updated or deleted.
+* LookupColumn                    [done] - reference: List of remote column values of all linked items. Refreshed
+    like rollups.
+* SecretColumn                    [done] - text: Encrypted with a user held key on top of folder encryption. Masked 
+    unless key is in context, never indexed. Key derived with PBKDF2 and a salt stored per column.
 
 Above fields gives us what we need as EXCEL functions into the formula field. Formula can provide a 
 combination of these function fields, which are not needed.
@@ -6,18 +6,43 @@ use tr::tr;
 use lazy_static::lazy_static;
 use regex::{Regex};
 use rust_decimal::prelude::*;
+use chrono::Utc;
 
 use crate::planet::{PlanetError};
 use crate::statements::folder::schema::*;
 use crate::storage::constants::*;
 use crate::storage::columns::*;
-use crate::storage::folder::FolderSchema;
-use crate::functions::{execute_formula, Formula};
+use crate::storage::columns::date::get_time_zone;
+use crate::functions::{execute_formula, Formula, FormulaEnv};
 
 lazy_static! {
     pub static ref RE_CURRENCY: Regex = Regex::new(r#"^(?P<symbol_pre>[^\d\.]*)*(?P<amount>\d+[\.\d+]*)(?P<symbol_post>[^\d\.]+)*$"#).unwrap();
+    pub static ref RE_CURRENCY_AMOUNT: Regex = Regex::new(r#"^[\s]*(?P<code_pre>[A-Za-z]{3})?[\s]*(?P<symbol_pre>[^\d\s.+\-A-Za-z]+)?[\s]*(?P<amount>[+\-]?\d+(\.\d+)?)[\s]*(?P<symbol_post>[^\d\s.+\-A-Za-z]+)?[\s]*(?P<code_post>[A-Za-z]{3})?[\s]*$"#).unwrap();
+    pub static ref RE_SEQUENCE_NUMBER: Regex = Regex::new(r#"\{(?P<zeros>0+)\}"#).unwrap();
 }
 
+// ISO 4217 active codes
+pub const CURRENCY_CODES: &[&str] = &[
+    "AED", "AFN", "ALL", "AMD", "ANG", "AOA", "ARS", "AUD", "AWG", "AZN", "BAM", "BBD", "BDT", "BGN", "BHD", 
+    "BIF", "BMD", "BND", "BOB", "BOV", "BRL", "BSD", "BTN", "BWP", "BYN", "BZD", "CAD", "CDF", "CHE", "CHF", 
+    "CHW", "CLF", "CLP", "CNY", "COP", "COU", "CRC", "CUP", "CVE", "CZK", "DJF", "DKK", "DOP", "DZD", "EGP", 
+    "ERN", "ETB", "EUR", "FJD", "FKP", "GBP", "GEL", "GHS", "GIP", "GMD", "GNF", "GTQ", "GYD", "HKD", "HNL", 
+    "HTG", "HUF", "IDR", "ILS", "INR", "IQD", "IRR", "ISK", "JMD", "JOD", "JPY", "KES", "KGS", "KHR", "KMF", 
+    "KPW", "KRW", "KWD", "KYD", "KZT", "LAK", "LBP", "LKR", "LRD", "LSL", "LYD", "MAD", "MDL", "MGA", "MKD", 
+    "MMK", "MNT", "MOP", "MRU", "MUR", "MVR", "MWK", "MXN", "MXV", "MYR", "MZN", "NAD", "NGN", "NIO", "NOK", 
+    "NPR", "NZD", "OMR", "PAB", "PEN", "PGK", "PHP", "PKR", "PLN", "PYG", "QAR", "RON", "RSD", "RUB", "RWF", 
+    "SAR", "SBD", "SCR", "SDG", "SEK", "SGD", "SHP", "SLE", "SOS", "SRD", "SSP", "STN", "SVC", "SYP", "SZL", 
+    "THB", "TJS", "TMT", "TND", "TOP", "TRY", "TTD", "TWD", "TZS", "UAH", "UGX", "USD", "USN", "UYI", "UYU", 
+    "UYW", "UZS", "VED", "VES", "VND", "VUV", "WST", "XAF", "XAG", "XAU", "XCD", "XCG", "XDR", "XOF", "XPD", 
+    "XPF", "XPT", "YER", "ZAR", "ZMW", "ZWG",
+];
+
+// Space folder with exchange rates, one item per currency with rate against a common base currency 
+// (base has rate 1).
+pub const EXCHANGE_RATES_FOLDER: &str = "Exchange Rates";
+pub const EXCHANGE_RATES_COLUMN_CURRENCY: &str = "Currency";
+pub const EXCHANGE_RATES_COLUMN_RATE: &str = "Rate";
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct CheckBoxColumn {
     pub config: ColumnConfig
@@ -273,6 +298,8 @@ impl StorageColumn for CurrencyColumn {
         } else {
             field_config_map.insert(CURRENCY_SYMBOL.to_string(), CURRENCY_SYMBOL_DOLLAR.to_string());
         }
+        let currency = CurrencyColumn::get_currency_code(&self.config)?;
+        field_config_map.insert(CURRENCY.to_string(), currency);
         return Ok(field_config_map)
     }
     fn get_config(
@@ -291,6 +318,11 @@ impl StorageColumn for CurrencyColumn {
             let currency_symbol = currency_symbol.unwrap().clone();
             config.currency_symbol = Some(currency_symbol);
         }
+        let currency = field_config_map.get(CURRENCY);
+        if currency.is_some() {
+            let currency = currency.unwrap().clone();
+            config.currency = Some(currency);
+        }
         return Ok(config)
     }
     fn validate(&self, data: &Vec<String>) -> Result<Vec<String>, Vec<PlanetError>> {
@@ -305,7 +337,6 @@ impl StorageColumn for CurrencyColumn {
         }
         let column_name = config.name.unwrap_or_default();
         let number_decimals = config.number_decimals;
-        let currency_symbol = config.currency_symbol;
         if number_decimals.is_none() {
             let error = PlanetError::new(
                 500, 
@@ -322,67 +353,24 @@ impl StorageColumn for CurrencyColumn {
         }
         let number_decimals = number_decimals.unwrap();
         let number_decimals: u32 = number_decimals.to_u32().unwrap();
-        let currency_symbol = currency_symbol.unwrap();
-        let currency_symbol = currency_symbol.as_str();
+        let currency = CurrencyColumn::get_currency_code(&self.config);
+        if currency.is_err() {
+            let mut errors: Vec<PlanetError> = Vec::new();
+            errors.push(currency.unwrap_err());
+            return Err(errors);
+        }
+        let currency = currency.unwrap();
+        // $12.5, 12.5 EUR, EUR 12.5, 12.5€ => 12.50 EUR
         let mut data_new: Vec<String> = Vec::new();
-        let expr = &RE_CURRENCY;
-        for mut data_item in data {
-            let match_data = data_item.clone();
-            let is_valid = expr.is_match(&match_data);
-            if !is_valid {
-                let error = PlanetError::new(
-                    500, 
-                    Some(tr!("Validation error on currency \"{}\"", data_item.clone())),
-                );
+        for data_item in data {
+            let amount = CurrencyAmount::parse(&data_item, &currency);
+            if amount.is_err() {
                 let mut errors: Vec<PlanetError> = Vec::new();
-                errors.push(error);
+                errors.push(amount.unwrap_err());
                 return Err(errors);
             }
-            let captures = expr.captures(&match_data).unwrap();
-            let amount_wrap = captures.name("amount");
-            let symbol_pre_wrap = captures.name("symbol_pre");
-            let symbol_post_wrap = captures.name("symbol_post");
-            let mut amount_string: String = String::from("");
-            // eprintln!("CurrencyColumn.validate :: before symbol replace: {}", &data);
-            if symbol_pre_wrap.is_some() || symbol_post_wrap.is_some() {
-                // I have symbol on sent data
-                if symbol_pre_wrap.is_some() {
-                    let symbol_pre = symbol_pre_wrap.unwrap().as_str();
-                    // eprintln!("CurrencyColumn.validate :: [regex] symbol_pre: {}", symbol_pre);
-                    data_item = data_item.clone().replace(symbol_pre, "");
-                }
-                if symbol_post_wrap.is_some() {
-                    let symbol_post = symbol_post_wrap.unwrap().as_str();
-                    // eprintln!("CurrencyColumn.validate :: [regex] symbol_post: {}", symbol_post);
-                    data_item = data_item.clone().replace(symbol_post, "");
-                }
-            }
-            data_item = data_item.trim().to_string();
-            // eprintln!("CurrencyColumn.validate :: after symbol replace: {}", &data);
-            if amount_wrap.is_some() {
-                // Might be 7658.45 or 7658 or $7658. Need to get number without the currency symbol
-                let amount_str = amount_wrap.unwrap().as_str();
-                // eprintln!("CurrencyColumn.validate :: [regex] amount_str: {}", amount_str);
-                // 79876.45
-                // format amount to have number decimals from config
-                let amount = Decimal::from_str(amount_str);
-                if amount.is_err() {
-                    let error = PlanetError::new(
-                        500, 
-                        Some(tr!("Validation error on currency \"{}\"", data_item.clone())),
-                    );
-                    let mut errors: Vec<PlanetError> = Vec::new();
-                    errors.push(error);
-                    return Err(errors);
-                }
-                let amount = amount.unwrap().round_dp(number_decimals);
-                // amount_string = amount.to_string();
-                let number_decimals = number_decimals.to_usize().unwrap();
-                amount_string = format!("{:.1$}", &amount, number_decimals);
-            }
-            // eprintln!("CurrencyColumn.validate :: amount_string: {}", &amount_string);
-            // data needs to have right number of decimals and the currency symbol
-            data_new.push(format!("{}{}", currency_symbol, &amount_string));
+            let amount = amount.unwrap();
+            data_new.push(amount.serialize(number_decimals));
         }
         return Ok(data_new)
     }
@@ -401,6 +389,221 @@ impl StorageColumn for CurrencyColumn {
     }
 }
 
+impl CurrencyColumn {
+    // Currency option, or code from currency symbol for columns created with CurrencySymbol only
+    pub fn get_currency_code(config: &ColumnConfig) -> Result<String, PlanetError> {
+        let config = config.clone();
+        if config.currency.is_some() {
+            let currency = config.currency.unwrap().to_uppercase();
+            if !CURRENCY_CODES.contains(&currency.as_str()) {
+                return Err(
+                    PlanetError::new(
+                        500, 
+                        Some(tr!("Currency \"{}\" is not a valid ISO 4217 code.", &currency)),
+                    )
+                )
+            }
+            return Ok(currency)
+        }
+        let currency_symbol = config.currency_symbol.unwrap_or(CURRENCY_SYMBOL_DOLLAR.to_string());
+        let currency = get_currency_code_by_symbol(&currency_symbol);
+        if currency.is_some() {
+            return Ok(currency.unwrap())
+        }
+        return Ok(CURRENCY_CODE_DEFAULT.to_string())
+    }
+    // Currency code from folder column data, used when sorting items
+    pub fn get_column_currency_code(column: &BTreeMap<String, String>) -> Result<String, PlanetError> {
+        let mut config = ColumnConfig::defaults(None);
+        config.currency = column.get(CURRENCY).cloned();
+        config.currency_symbol = column.get(CURRENCY_SYMBOL).cloned();
+        return CurrencyColumn::get_currency_code(&config)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CurrencyAmount {
+    pub amount: Decimal,
+    pub currency: String,
+}
+impl CurrencyAmount {
+    pub fn parse(value: &String, default_currency: &String) -> Result<Self, PlanetError> {
+        let value = value.replace("\"", "");
+        let expr = &RE_CURRENCY_AMOUNT;
+        let captures = expr.captures(value.as_str());
+        if captures.is_none() {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Validation error on currency \"{}\"", &value)),
+                )
+            )
+        }
+        let captures = captures.unwrap();
+        let amount = Decimal::from_str(captures.name("amount").unwrap().as_str());
+        if amount.is_err() {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Validation error on currency \"{}\"", &value)),
+                )
+            )
+        }
+        let amount = amount.unwrap();
+        let mut codes: Vec<String> = Vec::new();
+        for name in ["code_pre", "code_post"] {
+            let code = captures.name(name);
+            if code.is_some() {
+                codes.push(code.unwrap().as_str().to_uppercase());
+            }
+        }
+        for name in ["symbol_pre", "symbol_post"] {
+            let symbol = captures.name(name);
+            if symbol.is_some() {
+                let symbol = symbol.unwrap().as_str().to_string();
+                let code = get_currency_code_by_symbol(&symbol);
+                if code.is_none() {
+                    return Err(
+                        PlanetError::new(
+                            500, 
+                            Some(tr!("Currency symbol \"{}\" not supported, use ISO 4217 code.", &symbol)),
+                        )
+                    )
+                }
+                codes.push(code.unwrap());
+            }
+        }
+        let mut currency = default_currency.to_uppercase();
+        if codes.len() > 0 {
+            currency = codes[0].clone();
+        }
+        for code in &codes {
+            if *code != currency {
+                return Err(
+                    PlanetError::new(
+                        500, 
+                        Some(tr!("Currency \"{}\" has more than one currency.", &value)),
+                    )
+                )
+            }
+        }
+        if !CURRENCY_CODES.contains(&currency.as_str()) {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Currency \"{}\" is not a valid ISO 4217 code.", &currency)),
+                )
+            )
+        }
+        let obj = Self{
+            amount: amount,
+            currency: currency,
+        };
+        return Ok(obj)
+    }
+    pub fn serialize(&self, number_decimals: u32) -> String {
+        let amount = self.amount.round_dp(number_decimals);
+        let number_decimals = number_decimals.to_usize().unwrap();
+        return format!("{:.1$} {2}", &amount, number_decimals, &self.currency)
+    }
+    pub fn convert(
+        &self, 
+        currency: &String, 
+        exchange_rates: &BTreeMap<String, Decimal>
+    ) -> Result<CurrencyAmount, PlanetError> {
+        let currency = currency.to_uppercase();
+        if currency == self.currency {
+            return Ok(self.clone())
+        }
+        let rate_from = get_exchange_rate(exchange_rates, &self.currency)?;
+        let rate_to = get_exchange_rate(exchange_rates, &currency)?;
+        let amount = self.amount.checked_mul(rate_to);
+        let amount = amount.and_then(|amount| amount.checked_div(rate_from));
+        if amount.is_none() {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Could not convert \"{}\" from \"{}\" into \"{}\".", 
+                        &self.amount, &self.currency, &currency
+                    )),
+                )
+            )
+        }
+        let obj = Self{
+            amount: amount.unwrap(),
+            currency: currency,
+        };
+        return Ok(obj)
+    }
+    // Amounts in different currencies sort together by value in base currency of exchange rates. Amounts
+    // with no rate for their currency are not compared with converted ones, they go after these, by 
+    // currency code and amount.
+    pub fn get_sort_key(&self, exchange_rates: &BTreeMap<String, Decimal>) -> String {
+        let rate = get_exchange_rate(exchange_rates, &self.currency);
+        if rate.is_ok() {
+            let amount = self.amount.checked_div(rate.unwrap());
+            if amount.is_some() {
+                return format!("0:{}", CurrencyAmount::get_sort_number(&amount.unwrap()))
+            }
+        }
+        return format!("1:{}:{}", &self.currency, CurrencyAmount::get_sort_number(&self.amount))
+    }
+    // Thousandths shifted to positive and zero padded, so strings sort like numbers
+    fn get_sort_number(amount: &Decimal) -> String {
+        let number = amount.checked_mul(Decimal::from(1000)).and_then(|number| number.to_i64());
+        let number = number.unwrap_or_default() as i128 - i64::MIN as i128;
+        return format!("{:020}", number)
+    }
+}
+
+pub fn get_currency_code_by_symbol(symbol: &String) -> Option<String> {
+    let symbol = symbol.trim();
+    let code = match symbol {
+        "$" | "US$" => "USD",
+        "€" => "EUR",
+        "£" => "GBP",
+        "¥" => "JPY",
+        "₹" => "INR",
+        "₽" => "RUB",
+        "₩" => "KRW",
+        "₺" => "TRY",
+        "₪" => "ILS",
+        "₱" => "PHP",
+        "₫" => "VND",
+        "₴" => "UAH",
+        "₦" => "NGN",
+        "฿" => "THB",
+        "zł" => "PLN",
+        _ => {
+            let code = symbol.to_uppercase();
+            if CURRENCY_CODES.contains(&code.as_str()) {
+                return Some(code)
+            }
+            return None
+        }
+    };
+    return Some(code.to_string())
+}
+
+pub fn get_exchange_rate(
+    exchange_rates: &BTreeMap<String, Decimal>, 
+    currency: &String
+) -> Result<Decimal, PlanetError> {
+    let rate = exchange_rates.get(currency);
+    if rate.is_some() {
+        let rate = rate.unwrap().clone();
+        if !rate.is_zero() {
+            return Ok(rate)
+        }
+    }
+    return Err(
+        PlanetError::new(
+            500, 
+            Some(tr!("No exchange rate for currency \"{}\" in folder \"{}\".", currency, EXCHANGE_RATES_FOLDER)),
+        )
+    )
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct PercentageColumn {
     pub config: ColumnConfig
@@ -509,6 +712,7 @@ pub struct GenerateNumberColumn {
     pub config: ColumnConfig,
     pub folder: Option<DbData>,
     pub db_folder: Option<TreeFolder>,
+    pub context_time_zone: Option<String>,
 }
 impl GenerateNumberColumn {
     pub fn defaults(
@@ -520,18 +724,118 @@ impl GenerateNumberColumn {
         let field_obj = Self{
             config: field_config,
             folder: folder,
-            db_folder: db_folder
+            db_folder: db_folder,
+            context_time_zone: None,
         };
         return field_obj
     }
 }
+impl GenerateNumberColumn {
+    pub fn get_sort_number(value: &String) -> i64 {
+        // Formatted sequences like INV-2024-00001 sort by their digits
+        let digits: String = value.chars().filter(|c| c.is_ascii_digit()).collect();
+        let number: i64 = FromStr::from_str(digits.as_str()).unwrap_or_default();
+        return number
+    }
+    fn get_option_number(
+        value: &Option<String>, 
+        default: i64, 
+        option: &str, 
+        column_name: &String
+    ) -> Result<i64, PlanetError> {
+        if value.is_none() {
+            return Ok(default)
+        }
+        let value = value.clone().unwrap();
+        let number: Result<i64, _> = FromStr::from_str(value.trim());
+        if number.is_err() {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Option \"{}\" for column \"{}\" must be a number, found \"{}\".", option, column_name, &value)),
+                )
+            )
+        }
+        return Ok(number.unwrap())
+    }
+    fn get_period(reset: &String, time_zone: &Option<String>) -> Option<String> {
+        let now = Utc::now().with_timezone(&get_time_zone(&None, time_zone));
+        match reset.as_str() {
+            SEQUENCE_RESET_YEARLY => Some(now.format("%Y").to_string()),
+            SEQUENCE_RESET_MONTHLY => Some(now.format("%Y-%m").to_string()),
+            _ => None,
+        }
+    }
+    pub fn format_sequence(format: &Option<String>, sequence: i64, time_zone: &Option<String>) -> String {
+        if format.is_none() {
+            return sequence.to_string()
+        }
+        let format = format.clone().unwrap();
+        let now = Utc::now().with_timezone(&get_time_zone(&None, time_zone));
+        let mut value = format.replace("{YYYY}", now.format("%Y").to_string().as_str());
+        value = value.replace("{YY}", now.format("%y").to_string().as_str());
+        value = value.replace("{MM}", now.format("%m").to_string().as_str());
+        value = value.replace("{DD}", now.format("%d").to_string().as_str());
+        let expr = &RE_SEQUENCE_NUMBER;
+        let value = expr.replace_all(&value, |captures: &regex::Captures| {
+            let width = captures.name("zeros").unwrap().as_str().len();
+            format!("{:0>width$}", sequence, width=width)
+        });
+        return value.to_string()
+    }
+}
 impl StorageColumn for GenerateNumberColumn {
     fn create_config(
         &mut self, 
         field_config_map: &BTreeMap<String, String>,
     ) -> Result<BTreeMap<String, String>, PlanetError> {
         let mut field_config_map = field_config_map.clone();
-        field_config_map.insert(SEQUENCE.to_string(), String::from("0"));
+        let config = self.config.clone();
+        let column_name = config.name.clone().unwrap_or_default();
+        let sequence_format = config.sequence_format;
+        if sequence_format.is_some() {
+            let sequence_format = sequence_format.unwrap();
+            if !RE_SEQUENCE_NUMBER.is_match(&sequence_format) {
+                return Err(
+                    PlanetError::new(
+                        500, 
+                        Some(tr!("Sequence format \"{}\" needs a number placeholder like \"{}\".", &sequence_format, "{00000}")),
+                    )
+                )
+            }
+            field_config_map.insert(SEQUENCE_FORMAT.to_string(), sequence_format);
+        }
+        let sequence_start = GenerateNumberColumn::get_option_number(
+            &config.sequence_start, 1, WITH_SEQUENCE_START, &column_name
+        )?;
+        let sequence_step = GenerateNumberColumn::get_option_number(
+            &config.sequence_step, 1, WITH_SEQUENCE_STEP, &column_name
+        )?;
+        if sequence_step < 1 {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Option \"{}\" for column \"{}\" must be greater than zero.", WITH_SEQUENCE_STEP, &column_name)),
+                )
+            )
+        }
+        field_config_map.insert(SEQUENCE_START.to_string(), sequence_start.to_string());
+        field_config_map.insert(SEQUENCE_STEP.to_string(), sequence_step.to_string());
+        let sequence_reset = config.sequence_reset.unwrap_or(SEQUENCE_RESET_NEVER.to_string());
+        let sequence_reset = sequence_reset.as_str();
+        match sequence_reset {
+            SEQUENCE_RESET_NEVER | SEQUENCE_RESET_YEARLY | SEQUENCE_RESET_MONTHLY => {
+                field_config_map.insert(SEQUENCE_RESET.to_string(), sequence_reset.to_string());
+            },
+            _ => {
+                return Err(
+                    PlanetError::new(
+                        500, 
+                        Some(tr!("Sequence reset must be one of Never, Yearly or Monthly, found \"{}\".", sequence_reset)),
+                    )
+                )
+            }
+        }
         return Ok(field_config_map)
     }
     fn get_config(
@@ -544,53 +848,31 @@ impl StorageColumn for GenerateNumberColumn {
             let sequence = sequence.unwrap().clone();
             config.sequence = Some(sequence);
         }
+        let sequence_format = field_config_map.get(SEQUENCE_FORMAT);
+        if sequence_format.is_some() {
+            let sequence_format = sequence_format.unwrap().clone();
+            config.sequence_format = Some(sequence_format);
+        }
+        let sequence_start = field_config_map.get(SEQUENCE_START);
+        if sequence_start.is_some() {
+            let sequence_start = sequence_start.unwrap().clone();
+            config.sequence_start = Some(sequence_start);
+        }
+        let sequence_step = field_config_map.get(SEQUENCE_STEP);
+        if sequence_step.is_some() {
+            let sequence_step = sequence_step.unwrap().clone();
+            config.sequence_step = Some(sequence_step);
+        }
+        let sequence_reset = field_config_map.get(SEQUENCE_RESET);
+        if sequence_reset.is_some() {
+            let sequence_reset = sequence_reset.unwrap().clone();
+            config.sequence_reset = Some(sequence_reset);
+        }
         return Ok(config)
     }
     fn validate(&self, _data: &Vec<String>) -> Result<Vec<String>, Vec<PlanetError>> {
-        let config = self.config.clone();
-        let mut folder = self.folder.clone().unwrap();
-        let db_folder = self.db_folder.clone().unwrap();
-        let mut data = folder.data.unwrap();
-        let column_id = config.id.unwrap();
-        let column_name = config.name.unwrap();
-        let columns = data.get(COLUMNS);
-        let mut columns_new: Vec<BTreeMap<String, String>> = Vec::new();
-        let mut sequence_list: Vec<String> = Vec::new();
-        if columns.is_some() {
-            let columns = columns.unwrap().clone();
-            for mut column in columns {
-                let column_target_id = column.get(ID);
-                if column_target_id.is_some() {
-                    let column_target_id = column_target_id.unwrap().clone();
-                    if column_id == column_target_id {
-                        let sequence = column.get(SEQUENCE);
-                        if sequence.is_some() {
-                            let sequence = sequence.unwrap();
-                            let mut sequence: usize = FromStr::from_str(sequence).unwrap();
-                            sequence += 1;
-                            column.insert(SEQUENCE.to_string(), sequence.to_string());
-                            columns_new.push(column);
-                            sequence_list.push(sequence.to_string());
-                        }            
-                    } else {
-                        columns_new.push(column);
-                    }
-                }
-            }
-            data.insert(COLUMNS.to_string(), columns_new.clone());
-            folder.data = Some(data.clone());
-            let result = db_folder.update(&folder);
-            if result.is_ok() {
-                return Ok(sequence_list)
-            }
-        }
-        let error = PlanetError::new(
-            500, 
-            Some(tr!("Error generating new number for column \"{}\".", &column_name)),
-        );
-        let mut errors: Vec<PlanetError> = Vec::new();
-        errors.push(error);
-        return Err(errors)
+        let number = self.next_number()?;
+        return Ok(vec![number.value])
     }
     fn get_yaml_out(&self, yaml_string: &String, value: &String) -> String {
         let field_config = self.config.clone();
@@ -606,6 +888,101 @@ impl StorageColumn for GenerateNumberColumn {
         return yaml_string;
     }
 }
+impl GenerateNumberColumn {
+    pub fn next_number(&self) -> Result<SequenceNumber, Vec<PlanetError>> {
+        let config = self.config.clone();
+        let folder = self.folder.clone().unwrap();
+        let db_folder = self.db_folder.clone().unwrap();
+        let folder_id = folder.id.unwrap_or_default();
+        let column_id = config.id.unwrap_or_default();
+        let column_name = config.name.unwrap_or_default();
+        let mut errors: Vec<PlanetError> = Vec::new();
+        let sequence_start = GenerateNumberColumn::get_option_number(
+            &config.sequence_start, 1, WITH_SEQUENCE_START, &column_name
+        );
+        let sequence_step = GenerateNumberColumn::get_option_number(
+            &config.sequence_step, 1, WITH_SEQUENCE_STEP, &column_name
+        );
+        if sequence_start.is_err() || sequence_step.is_err() {
+            errors.push(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Error generating new number for column \"{}\".", &column_name)),
+                )
+            );
+            return Err(errors)
+        }
+        let mut sequence_start = sequence_start.unwrap();
+        let sequence_step = sequence_step.unwrap();
+        let sequence_reset = config.sequence_reset.unwrap_or(SEQUENCE_RESET_NEVER.to_string());
+        let period = GenerateNumberColumn::get_period(&sequence_reset, &self.context_time_zone);
+        let key: String;
+        if period.is_some() {
+            key = format!("{}:{}", &column_id, period.unwrap());
+        } else {
+            key = column_id.clone();
+            // Columns created before sequences had their own tree keep counting from the schema sequence
+            let sequence = GenerateNumberColumn::get_option_number(
+                &config.sequence, 0, WITH_SEQUENCE, &column_name
+            ).unwrap_or_default();
+            if sequence > 0 {
+                sequence_start = sequence + sequence_step;
+            }
+        }
+        let result = db_folder.next_sequence(
+            &folder_id, 
+            &key, 
+            sequence_start, 
+            sequence_step
+        );
+        if result.is_err() {
+            errors.push(result.unwrap_err());
+            errors.push(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Error generating new number for column \"{}\".", &column_name)),
+                )
+            );
+            return Err(errors)
+        }
+        let sequence = result.unwrap();
+        let value = GenerateNumberColumn::format_sequence(
+            &config.sequence_format, sequence, &self.context_time_zone
+        );
+        let number = SequenceNumber{
+            folder_id: folder_id,
+            key: key,
+            sequence: sequence,
+            start: sequence_start,
+            step: sequence_step,
+            value: value,
+        };
+        return Ok(number)
+    }
+    // Numbers of failed inserts are given back latest first. When other insert took a later number
+    // meanwhile the number is lost and sequence has a gap.
+    pub fn release_numbers(db_folder: &TreeFolder, numbers: &Vec<SequenceNumber>) {
+        for number in numbers.iter().rev() {
+            let _ = db_folder.release_sequence(
+                &number.folder_id, 
+                &number.key, 
+                number.sequence, 
+                number.start, 
+                number.step
+            );
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SequenceNumber {
+    pub folder_id: String,
+    pub key: String,
+    pub sequence: i64,
+    pub start: i64,
+    pub step: i64,
+    pub value: String,
+}
 
 
 #[derive(Debug, Clone)]
@@ -747,6 +1124,7 @@ pub struct StatsColumn {
     pub db_folder: Option<TreeFolder>,
     pub properties_map: Option<HashMap<String, ColumnConfig>>,
     pub data_map: Option<BTreeMap<String, Vec<BTreeMap<String, String>>>>,
+    pub formula_env: Option<FormulaEnv>,
 }
 impl StatsColumn {
     pub fn defaults(
@@ -777,6 +1155,7 @@ impl StatsColumn {
             db_folder: db_folder,
             properties_map: properties_map,
             data_map: data_map,
+            formula_env: None,
         };
         return field_obj
     }
@@ -805,6 +1184,19 @@ impl StorageColumn for StatsColumn {
                 )
             }
         }
+        let currency = config.currency;
+        if currency.is_some() {
+            let currency = currency.unwrap().to_uppercase();
+            if !CURRENCY_CODES.contains(&currency.as_str()) {
+                return Err(
+                    PlanetError::new(
+                        500, 
+                        Some(tr!("Currency \"{}\" is not a valid ISO 4217 code.", &currency)),
+                    )
+                )
+            }
+            column_config_map.insert(CURRENCY.to_string(), currency);
+        }
         let related_column = config.related_column;
         if related_column.is_some() {
             let related_column = related_column.unwrap();
@@ -843,6 +1235,11 @@ impl StorageColumn for StatsColumn {
                 )
             }
         }
+        let currency = column_config_map.get(CURRENCY);
+        if currency.is_some() {
+            let currency = currency.unwrap();
+            config.currency = Some(currency.clone());
+        }
         let related_column = column_config_map.get(RELATED_COLUMN);
         if related_column.is_some() {
             let related_column = related_column.unwrap();
@@ -908,7 +1305,14 @@ impl StorageColumn for StatsColumn {
             // eprintln!("StatsColumn.validate :: stats_function: {}", &stats_function);
             let stats_function = stats_function.as_str();
             let formula: String;
-            let formula_format = String::from(FORMULA_FORMAT_NUMBER);
+            let mut formula_format = String::from(FORMULA_FORMAT_NUMBER);
+            // SUM({Amount}, "EUR") : Currency amounts converted into the target currency
+            let currency = config.currency.clone();
+            let mut currency_arg = String::from("");
+            if currency.is_some() {
+                currency_arg = format!(", \"{}\"", currency.unwrap());
+                formula_format = String::from(FORMULA_FORMAT_TEXT);
+            }
             match stats_function {
                 STATS_FUNCTION_COUNT => {
                     formula = format!("COUNT({{{}}})", &related_column);
@@ -926,10 +1330,10 @@ impl StorageColumn for StatsColumn {
                     formula = format!("MIN({{{}}})", &related_column);
                 },
                 STATS_FUNCTION_AVG => {
-                    formula = format!("AVG({{{}}})", &related_column);
+                    formula = format!("AVG({{{}}}{})", &related_column, &currency_arg);
                 },
                 STATS_FUNCTION_SUM => {
-                    formula = format!("SUM({{{}}})", &related_column);
+                    formula = format!("SUM({{{}}}{})", &related_column, &currency_arg);
                 },
                 _ => {
                     let error = PlanetError::new(
@@ -960,7 +1364,8 @@ impl StorageColumn for StatsColumn {
                 errors.push(error);
                 return Err(errors)
             }
-            let formula_compiled = formula_compiled.unwrap();
+            let mut formula_compiled = formula_compiled.unwrap();
+            formula_compiled.env = self.formula_env.clone();
             let formula_result = execute_formula(
                 &formula_compiled, 
                 &data_map, 
@@ -993,3 +1398,107 @@ impl StorageColumn for StatsColumn {
         return yaml_string;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_amount(value: &str) -> Result<CurrencyAmount, PlanetError> {
+        return CurrencyAmount::parse(&value.to_string(), &CURRENCY_CODE_DEFAULT.to_string())
+    }
+
+    fn get_rates() -> BTreeMap<String, Decimal> {
+        let mut rates: BTreeMap<String, Decimal> = BTreeMap::new();
+        rates.insert("USD".to_string(), Decimal::from_str("1").unwrap());
+        rates.insert("EUR".to_string(), Decimal::from_str("0.5").unwrap());
+        rates.insert("GBP".to_string(), Decimal::from_str("0.25").unwrap());
+        return rates
+    }
+
+    #[test]
+    fn parse_code_and_symbol() {
+        let amount = parse_amount("12.50 EUR").unwrap();
+        assert_eq!(amount.amount, Decimal::from_str("12.50").unwrap());
+        assert_eq!(amount.currency, "EUR");
+        let amount = parse_amount("eur 3").unwrap();
+        assert_eq!(amount.currency, "EUR");
+        let amount = parse_amount("€10").unwrap();
+        assert_eq!(amount.currency, "EUR");
+        let amount = parse_amount("-4.25 £").unwrap();
+        assert_eq!(amount.amount, Decimal::from_str("-4.25").unwrap());
+        assert_eq!(amount.currency, "GBP");
+        let amount = parse_amount("\"$ 7 USD\"").unwrap();
+        assert_eq!(amount.currency, "USD");
+    }
+
+    #[test]
+    fn parse_default_currency() {
+        let amount = CurrencyAmount::parse(&"100".to_string(), &"gbp".to_string()).unwrap();
+        assert_eq!(amount.amount, Decimal::from_str("100").unwrap());
+        assert_eq!(amount.currency, "GBP");
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert!(parse_amount("abc").is_err());
+        assert!(parse_amount("10 XYZ").is_err());
+        assert!(parse_amount("€10 USD").is_err());
+        assert!(parse_amount("10 ¤").is_err());
+    }
+
+    #[test]
+    fn serialize_rounds_decimals() {
+        let amount = parse_amount("12.346 EUR").unwrap();
+        assert_eq!(amount.serialize(2), "12.35 EUR");
+        let amount = parse_amount("3 USD").unwrap();
+        assert_eq!(amount.serialize(2), "3.00 USD");
+        assert_eq!(amount.serialize(0), "3 USD");
+    }
+
+    #[test]
+    fn convert_between_currencies() {
+        let rates = get_rates();
+        let amount = parse_amount("10 EUR").unwrap();
+        let converted = amount.convert(&"usd".to_string(), &rates).unwrap();
+        assert_eq!(converted.amount, Decimal::from_str("20").unwrap());
+        assert_eq!(converted.currency, "USD");
+        let converted = amount.convert(&"GBP".to_string(), &rates).unwrap();
+        assert_eq!(converted.amount, Decimal::from_str("5").unwrap());
+        let converted = amount.convert(&"EUR".to_string(), &rates).unwrap();
+        assert_eq!(converted.amount, amount.amount);
+        assert!(amount.convert(&"JPY".to_string(), &rates).is_err());
+    }
+
+    #[test]
+    fn sort_key_in_base_currency() {
+        let rates = get_rates();
+        let euros = parse_amount("10 EUR").unwrap();
+        let dollars = parse_amount("15 USD").unwrap();
+        let dollars_more = parse_amount("20 USD").unwrap();
+        let negative = parse_amount("-5 USD").unwrap();
+        assert!(dollars.get_sort_key(&rates) < euros.get_sort_key(&rates));
+        assert_eq!(dollars_more.get_sort_key(&rates), euros.get_sort_key(&rates));
+        assert!(negative.get_sort_key(&rates) < dollars.get_sort_key(&rates));
+    }
+
+    #[test]
+    fn sort_key_without_rate_after_converted() {
+        let rates = get_rates();
+        let dollars = parse_amount("100000 USD").unwrap();
+        let yen = parse_amount("500 JPY").unwrap();
+        let yen_more = parse_amount("10000 JPY").unwrap();
+        let francs = parse_amount("1 CHF").unwrap();
+        assert!(dollars.get_sort_key(&rates) < yen.get_sort_key(&rates));
+        assert!(yen.get_sort_key(&rates) < yen_more.get_sort_key(&rates));
+        // By currency code first, then amount
+        assert!(francs.get_sort_key(&rates) < yen.get_sort_key(&rates));
+    }
+
+    #[test]
+    fn currency_code_by_symbol() {
+        assert_eq!(get_currency_code_by_symbol(&"US$".to_string()), Some("USD".to_string()));
+        assert_eq!(get_currency_code_by_symbol(&" zł ".to_string()), Some("PLN".to_string()));
+        assert_eq!(get_currency_code_by_symbol(&"chf".to_string()), Some("CHF".to_string()));
+        assert_eq!(get_currency_code_by_symbol(&"¤".to_string()), None);
+    }
+}
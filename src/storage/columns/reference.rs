@@ -1,4 +1,5 @@
 use std::collections::BTreeMap;
+use std::str::FromStr;
 use colored::Colorize;
 use tr::tr;
 
@@ -9,7 +10,9 @@ use crate::planet::constants::*;
 use crate::storage::columns::*;
 use crate::storage::folder::*;
 use crate::storage::space::*;
-use crate::functions::Formula;
+use crate::storage::ConfigStorageColumn;
+use crate::storage::columns::number::StatsColumn;
+use crate::functions::{execute_formula, Formula, FormulaEnv};
 
 #[derive(Debug, Clone)]
 pub struct LinkColumn<'gb> {
@@ -53,6 +56,7 @@ impl<'gb> ObjectStorageColumn<'gb> for LinkColumn<'gb> {
         let many = config.many;
         let linked_folder = config.linked_folder;
         let delete_on_link_drop = config.delete_on_link_drop;
+        let on_delete = config.on_delete;
         let folder_name = self.folder_name.clone();
         // linked folder id is required for a link Column
         if linked_folder.is_none() {
@@ -99,6 +103,10 @@ impl<'gb> ObjectStorageColumn<'gb> for LinkColumn<'gb> {
             }
             column_config_map.insert(DELETE_ON_LINK_DROP.to_string(), delete_on_link_drop_string);
         }
+        if on_delete.is_some() {
+            let on_delete = on_delete.unwrap();
+            column_config_map.insert(ON_DELETE.to_string(), on_delete);
+        }
         return Ok(column_config_map)
     }
     fn get_config(
@@ -110,6 +118,7 @@ impl<'gb> ObjectStorageColumn<'gb> for LinkColumn<'gb> {
         let many = column_config_map.get(MANY);
         let linked_folder = column_config_map.get(LINKED_FOLDER);
         let delete_on_link_drop = column_config_map.get(DELETE_ON_LINK_DROP);
+        let on_delete = column_config_map.get(ON_DELETE);
         if many.is_some() {
             let many = many.unwrap().clone().to_lowercase();
             if many == String::from("1") || many == String::from(TRUE) {
@@ -130,6 +139,10 @@ impl<'gb> ObjectStorageColumn<'gb> for LinkColumn<'gb> {
                 config.delete_on_link_drop = Some(false);
             }
         }
+        if on_delete.is_some() {
+            let on_delete = on_delete.unwrap().clone();
+            config.on_delete = Some(on_delete);
+        }
         return Ok(config)
     }
     fn validate(
@@ -202,6 +215,16 @@ impl<'gb> ObjectStorageColumn<'gb> for LinkColumn<'gb> {
         // I will check I am able to fetch the link remote by id and fetch name
         for item_id in data.clone() {
             eprintln!("LinkColumn.validate  :: item_id: {}", &item_id);
+            let id_db = xid::Id::from_str(item_id.as_str());
+            if id_db.is_err() {
+                let error = PlanetError::new(
+                    500, 
+                    Some(tr!("Linked item id \"{}\" is not valid.", &item_id)),
+                );
+                let mut errors: Vec<PlanetError> = Vec::new();
+                errors.push(error);
+                return Err(errors);
+            }
             let item = db_folder_item.get(
                 &folder_name, 
                 GetItemOption::ById(item_id), 
@@ -429,4 +452,532 @@ impl<'gb> ObjectStorageColumn<'gb> for ReferenceColumn<'gb> {
         yaml_string.push_str(format!("  {field}: {value}\n", field=field, value=value).as_str());
         return yaml_string;
     }
-}
\ No newline at end of file
+}
+// Link column config, remote folder and remote column type used by rollup and lookup columns
+fn get_link_remote_config(
+    config: &ColumnConfig,
+    column_config_map: &BTreeMap<String, String>,
+    properties_map: &HashMap<String, ColumnConfig>,
+    db_folder: &TreeFolder,
+) -> Result<(BTreeMap<String, String>, DbData), PlanetError> {
+    let mut column_config_map = column_config_map.clone();
+    let name = config.name.clone().unwrap_or_default();
+    let link_column = config.link_column.clone();
+    if link_column.is_none() {
+        return Err(
+            PlanetError::new(
+                500, 
+                Some(tr!("Column \"{}\" needs \"LinkColumn\".", &name)),
+            )
+        );
+    }
+    let link_column = link_column.unwrap();
+    let link = properties_map.get(&link_column);
+    if link.is_none() {
+        return Err(
+            PlanetError::new(
+                500, 
+                Some(tr!("Link Column \"{}\" not found.", &link_column)),
+            )
+        );
+    }
+    let link = link.unwrap().clone();
+    let link_type = link.column_type.clone().unwrap_or_default();
+    if link_type.as_str() != COLUMN_TYPE_LINK || link.linked_folder.is_none() {
+        return Err(
+            PlanetError::new(
+                500, 
+                Some(tr!("Column \"{}\" is not a link column.", &link_column)),
+            )
+        );
+    }
+    let linked_folder = link.linked_folder.unwrap();
+    let linked_folder_obj = db_folder.get_by_name(&linked_folder)?;
+    if linked_folder_obj.is_none() {
+        return Err(
+            PlanetError::new(
+                500, 
+                Some(tr!("Folder \"{}\" not found.", &linked_folder)),
+            )
+        );
+    }
+    let linked_folder_obj = linked_folder_obj.unwrap();
+    column_config_map.insert(LINK_COLUMN.to_string(), link_column);
+    let remote_column = config.remote_column.clone();
+    if remote_column.is_some() {
+        let remote_column = remote_column.unwrap();
+        let column = TreeFolder::get_column_by_name(
+            &remote_column, 
+            &linked_folder_obj
+        );
+        if column.is_err() {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Column \"{}\" does not exist at \"{}\" folder.", 
+                        &remote_column, &linked_folder
+                    )),
+                )
+            );
+        }
+        let column = column.unwrap();
+        let remote_column_type = column.get(COLUMN_TYPE).unwrap().clone();
+        column_config_map.insert(String::from(REMOTE_COLUMN), remote_column);
+        column_config_map.insert(String::from(REMOTE_COLUMN_TYPE), remote_column_type);
+    }
+    return Ok((column_config_map, linked_folder_obj))
+}
+
+fn get_link_remote_column_config(
+    config: &ColumnConfig,
+    column_config_map: &BTreeMap<String, String>,
+) -> ColumnConfig {
+    let mut config = config.clone();
+    let link_column = column_config_map.get(LINK_COLUMN);
+    if link_column.is_some() {
+        config.link_column = Some(link_column.unwrap().clone());
+    }
+    let remote_column = column_config_map.get(REMOTE_COLUMN);
+    if remote_column.is_some() {
+        config.remote_column = Some(remote_column.unwrap().clone());
+    }
+    let remote_column_type = column_config_map.get(REMOTE_COLUMN_TYPE);
+    if remote_column_type.is_some() {
+        config.remote_column_type = Some(remote_column_type.unwrap().clone());
+    }
+    return config
+}
+
+// Items linked through the link column of the rollup or lookup column, with the linked folder column config.
+fn get_linked_items(
+    planet_context: &PlanetContext,
+    context: &Context,
+    config: &ColumnConfig,
+    db_folder: &TreeFolder,
+    space_database: &SpaceDatabase,
+    column_config_map: &BTreeMap<String, ColumnConfig>,
+    data_map: &BTreeMap<String, Vec<BTreeMap<String, String>>>,
+) -> Result<(DbData, BTreeMap<String, ColumnConfig>, Vec<DbData>), PlanetError> {
+    let link_column = config.link_column.clone().unwrap_or_default();
+    let link = column_config_map.get(&link_column);
+    if link.is_none() {
+        return Err(
+            PlanetError::new(
+                500, 
+                Some(tr!("Link Column \"{}\" not found.", &link_column)),
+            )
+        );
+    }
+    let link = link.unwrap().clone();
+    let link_id = link.id.clone().unwrap_or_default();
+    let linked_folder = link.linked_folder.clone().unwrap_or_default();
+    let folder = db_folder.get_by_name(&linked_folder)?;
+    if folder.is_none() {
+        return Err(
+            PlanetError::new(
+                500, 
+                Some(tr!("Folder \"{}\" not found.", &linked_folder)),
+            )
+        );
+    }
+    let folder = folder.unwrap();
+    let folder_id = folder.id.clone().unwrap_or_default();
+    let remote_column_config_map = ColumnConfig::get_column_config_map(
+        planet_context,
+        context,
+        &folder
+    )?;
+    let mut items: Vec<DbData> = Vec::new();
+    let list = data_map.get(&link_id);
+    if list.is_none() {
+        return Ok((folder, remote_column_config_map, items))
+    }
+    let list = list.unwrap();
+    let home_dir = db_folder.home_dir.clone().unwrap_or_default();
+    let account_id = db_folder.account_id.clone().unwrap_or_default();
+    let space_id = db_folder.space_id.clone().unwrap_or_default();
+    let mut db_row: TreeFolderItem = TreeFolderItem::defaults(
+        space_database.connection_pool.clone(),
+        home_dir.as_str(),
+        &account_id,
+        space_id.as_str(),
+        db_folder.site_id.clone(),
+        folder_id.as_str(),
+        db_folder,
+    )?;
+    for item_object in list {
+        let item_id = item_object.get(ID);
+        if item_id.is_none() {
+            continue
+        }
+        let item_id = item_id.unwrap().clone();
+        // Linked item may have been removed
+        let item = db_row.get(&linked_folder, GetItemOption::ById(item_id), None);
+        if item.is_ok() {
+            items.push(item.unwrap());
+        }
+    }
+    return Ok((folder, remote_column_config_map, items))
+}
+
+// Remote column values for linked items, or the formula result for each linked item.
+fn get_linked_values(
+    config: &ColumnConfig,
+    remote_column_config_map: &BTreeMap<String, ColumnConfig>,
+    items: &Vec<DbData>,
+    formula_env: &FormulaEnv,
+) -> Result<Vec<String>, PlanetError> {
+    let mut values: Vec<String> = Vec::new();
+    let formula_compiled = config.formula_compiled.clone();
+    if formula_compiled.is_some() {
+        let formula_compiled = formula_compiled.unwrap();
+        let mut formula_compiled: Formula = serde_yaml::from_str(
+            formula_compiled.as_str()
+        ).unwrap();
+        formula_compiled.env = Some(formula_env.clone());
+        for item in items {
+            let data_map = item.data.clone().unwrap_or_default();
+            let result = execute_formula(
+                &formula_compiled, 
+                &data_map, 
+                None, 
+                remote_column_config_map
+            )?;
+            values.push(result.result);
+        }
+        return Ok(values)
+    }
+    let remote_column = config.remote_column.clone().unwrap_or_default();
+    let remote_column_config = remote_column_config_map.get(&remote_column);
+    if remote_column_config.is_none() {
+        return Err(
+            PlanetError::new(
+                500, 
+                Some(tr!("Could not fetch remote column \"{}\".", &remote_column)),
+            )
+        );
+    }
+    let remote_column_id = remote_column_config.unwrap().id.clone().unwrap_or_default();
+    for item in items {
+        let data_map = item.data.clone().unwrap_or_default();
+        let list = data_map.get(&remote_column_id);
+        if list.is_none() {
+            continue
+        }
+        for value_obj in list.unwrap() {
+            let value = value_obj.get(VALUE);
+            if value.is_some() {
+                values.push(value.unwrap().clone());
+            }
+        }
+    }
+    return Ok(values)
+}
+
+#[derive(Debug, Clone)]
+pub struct RollupColumn<'gb> {
+    pub config: ColumnConfig,
+    pub planet_context: &'gb PlanetContext<'gb>,
+    pub context: &'gb Context<'gb>,
+    pub db_folder: Option<TreeFolder>,
+    pub space_database: Option<SpaceDatabase>,
+}
+impl<'gb> RollupColumn<'gb> {
+    pub fn defaults(
+        planet_context: &'gb PlanetContext, 
+        context: &'gb Context, 
+        column_config: &ColumnConfig,
+        db_folder: Option<TreeFolder>,
+        space_database: Option<SpaceDatabase>,
+    ) -> Self {
+        let column_config = column_config.clone();
+        let field_obj = Self{
+            config: column_config,
+            planet_context: planet_context,
+            context: context,
+            db_folder: db_folder,
+            space_database: space_database,
+        };
+        return field_obj
+    }
+    pub fn compute(
+        &self,
+        column_config_map: &BTreeMap<String, ColumnConfig>,
+        data_map: &BTreeMap<String, Vec<BTreeMap<String, String>>>,
+        formula_env: &FormulaEnv,
+    ) -> Result<Vec<String>, Vec<PlanetError>> {
+        let config = self.config.clone();
+        let db_folder = self.db_folder.clone().unwrap();
+        let space_database = self.space_database.clone().unwrap();
+        let result = get_linked_items(
+            self.planet_context, 
+            self.context, 
+            &config, 
+            &db_folder, 
+            &space_database, 
+            column_config_map, 
+            data_map
+        );
+        if result.is_err() {
+            return Err(vec![result.unwrap_err()])
+        }
+        let (linked_folder, remote_column_config_map, items) = result.unwrap();
+        let values = get_linked_values(
+            &config, &remote_column_config_map, &items, formula_env
+        );
+        if values.is_err() {
+            return Err(vec![values.unwrap_err()])
+        }
+        let values = values.unwrap();
+        // Linked values are aggregated by the stats column as a reference collection named as this column
+        let name = config.name.clone().unwrap_or_default();
+        let id = config.id.clone().unwrap_or_default();
+        let mut value_column_type = config.remote_column_type.clone().unwrap_or_default();
+        if config.formula_compiled.is_some() {
+            value_column_type = COLUMN_TYPE_NUMBER.to_string();
+            let formula_format = config.formula_format.clone().unwrap_or_default();
+            if formula_format.as_str() != FORMULA_FORMAT_NUMBER {
+                value_column_type = COLUMN_TYPE_SMALL_TEXT.to_string();
+            }
+        }
+        let mut values_config = ColumnConfig::defaults(None);
+        values_config.id = Some(id.clone());
+        values_config.name = Some(name.clone());
+        values_config.column_type = Some(COLUMN_TYPE_REFERENCE.to_string());
+        values_config.remote_column_type = Some(value_column_type);
+        values_config.many = Some(true);
+        let mut values_config_map: BTreeMap<String, ColumnConfig> = BTreeMap::new();
+        values_config_map.insert(name.clone(), values_config.clone());
+        let mut properties_map: HashMap<String, ColumnConfig> = HashMap::new();
+        properties_map.insert(name.clone(), values_config);
+        let mut values_data_map: BTreeMap<String, Vec<BTreeMap<String, String>>> = BTreeMap::new();
+        let mut value_list: Vec<BTreeMap<String, String>> = Vec::new();
+        for value in values {
+            let mut value_obj: BTreeMap<String, String> = BTreeMap::new();
+            value_obj.insert(VALUE.to_string(), value);
+            value_list.push(value_obj);
+        }
+        values_data_map.insert(id, value_list);
+        let mut stats_config = config.clone();
+        stats_config.related_column = Some(name);
+        let folder_name = linked_folder.name.clone().unwrap_or_default();
+        let mut stats_column = StatsColumn::defaults(
+            &stats_config,
+            Some(values_config_map),
+            Some(folder_name),
+            Some(db_folder),
+            Some(properties_map),
+            Some(values_data_map)
+        );
+        stats_column.formula_env = Some(formula_env.clone());
+        return stats_column.validate(&vec![])
+    }
+}
+impl<'gb> ObjectStorageColumn<'gb> for RollupColumn<'gb> {
+    fn create_config(
+        &mut self, 
+        column_config_map: &BTreeMap<String, String>,
+        properties_map: &HashMap<String, ColumnConfig>,
+        _: &String,
+    ) -> Result<BTreeMap<String, String>, PlanetError> {
+        let db_folder = self.db_folder.clone().unwrap();
+        let config = self.config.clone();
+        let (mut column_config_map, linked_folder) = get_link_remote_config(
+            &config, 
+            column_config_map, 
+            properties_map, 
+            &db_folder
+        )?;
+        // Stats function and currency are validated by the stats column
+        let mut stats_config = config.clone();
+        stats_config.related_column = config.name.clone();
+        let mut stats_column = StatsColumn::defaults(
+            &stats_config,
+            None,
+            None,
+            None,
+            None,
+            None
+        );
+        column_config_map = stats_column.create_config(&column_config_map)?;
+        column_config_map.remove(RELATED_COLUMN);
+        // Formula is executed for each linked item, on the linked folder columns
+        let formula = config.formula;
+        if formula.is_some() {
+            let formula = formula.unwrap();
+            let formula_format = config.formula_format.unwrap_or_default();
+            let linked_folder_name = linked_folder.name.clone().unwrap_or_default();
+            let remote_columns = ColumnConfig::get_config(
+                self.planet_context, 
+                self.context, 
+                &linked_folder
+            )?;
+            let mut remote_properties_map: HashMap<String, ColumnConfig> = HashMap::new();
+            for remote_column in remote_columns {
+                let remote_column_name = remote_column.name.clone().unwrap_or_default();
+                remote_properties_map.insert(remote_column_name, remote_column);
+            }
+            let formula_compiled = Formula::defaults(
+                &formula,
+                &formula_format,
+                None,
+                Some(remote_properties_map),
+                Some(db_folder.clone()),
+                Some(linked_folder_name),
+                false,
+                None
+            )?;
+            column_config_map.insert(String::from(FORMULA), formula);
+            column_config_map.insert(String::from(FORMULA_FORMAT), formula_format);
+            let formula_serialized = serde_yaml::to_string(&formula_compiled).unwrap();
+            column_config_map.insert(String::from(FORMULA_COMPILED), formula_serialized);
+        }
+        return Ok(column_config_map)
+    }
+    fn get_config(
+        &mut self, 
+        column_config_map: &BTreeMap<String, String>,
+    ) -> Result<ColumnConfig, PlanetError> {
+        let mut config = get_link_remote_column_config(&self.config, column_config_map);
+        let stats_function = column_config_map.get(STATS_FUNCTION);
+        if stats_function.is_some() {
+            config.stats_function = Some(stats_function.unwrap().clone());
+        }
+        let currency = column_config_map.get(CURRENCY);
+        if currency.is_some() {
+            config.currency = Some(currency.unwrap().clone());
+        }
+        let formula_compiled = column_config_map.get(FORMULA_COMPILED);
+        if formula_compiled.is_some() {
+            config.formula = column_config_map.get(FORMULA).cloned();
+            config.formula_format = column_config_map.get(FORMULA_FORMAT).cloned();
+            config.formula_compiled = Some(formula_compiled.unwrap().clone());
+        }
+        return Ok(config)
+    }
+    fn validate(
+        &self, 
+        data: &Vec<String>, 
+    ) -> Result<Vec<String>, Vec<PlanetError>> {
+        let data = data.clone();
+        return Ok(data);
+    }
+    fn get_yaml_out(&self, yaml_string: &String, value: &String) -> String {
+        let column_config = self.config.clone();
+        let field_name = column_config.name.unwrap();
+        let mut yaml_string = yaml_string.clone();
+        let field = &field_name.truecolor(
+            YAML_COLOR_BLUE[0], YAML_COLOR_BLUE[1], YAML_COLOR_BLUE[2]
+        );
+        let value = format!("{}", value.to_string().truecolor(
+            YAML_COLOR_YELLOW[0], YAML_COLOR_YELLOW[1], YAML_COLOR_YELLOW[2]
+        ));
+        yaml_string.push_str(format!("  {field}: {value}\n", field=field, value=value).as_str());
+        return yaml_string;
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LookupColumn<'gb> {
+    pub config: ColumnConfig,
+    pub planet_context: &'gb PlanetContext<'gb>,
+    pub context: &'gb Context<'gb>,
+    pub db_folder: Option<TreeFolder>,
+    pub space_database: Option<SpaceDatabase>,
+}
+impl<'gb> LookupColumn<'gb> {
+    pub fn defaults(
+        planet_context: &'gb PlanetContext, 
+        context: &'gb Context, 
+        column_config: &ColumnConfig,
+        db_folder: Option<TreeFolder>,
+        space_database: Option<SpaceDatabase>,
+    ) -> Self {
+        let column_config = column_config.clone();
+        let field_obj = Self{
+            config: column_config,
+            planet_context: planet_context,
+            context: context,
+            db_folder: db_folder,
+            space_database: space_database,
+        };
+        return field_obj
+    }
+    pub fn compute(
+        &self,
+        column_config_map: &BTreeMap<String, ColumnConfig>,
+        data_map: &BTreeMap<String, Vec<BTreeMap<String, String>>>,
+        formula_env: &FormulaEnv,
+    ) -> Result<Vec<String>, Vec<PlanetError>> {
+        let config = self.config.clone();
+        let db_folder = self.db_folder.clone().unwrap();
+        let space_database = self.space_database.clone().unwrap();
+        let result = get_linked_items(
+            self.planet_context, 
+            self.context, 
+            &config, 
+            &db_folder, 
+            &space_database, 
+            column_config_map, 
+            data_map
+        );
+        if result.is_err() {
+            return Err(vec![result.unwrap_err()])
+        }
+        let (_, remote_column_config_map, items) = result.unwrap();
+        let values = get_linked_values(
+            &config, &remote_column_config_map, &items, formula_env
+        );
+        if values.is_err() {
+            return Err(vec![values.unwrap_err()])
+        }
+        return Ok(values.unwrap())
+    }
+}
+impl<'gb> ObjectStorageColumn<'gb> for LookupColumn<'gb> {
+    fn create_config(
+        &mut self, 
+        column_config_map: &BTreeMap<String, String>,
+        properties_map: &HashMap<String, ColumnConfig>,
+        _: &String,
+    ) -> Result<BTreeMap<String, String>, PlanetError> {
+        let db_folder = self.db_folder.clone().unwrap();
+        let (mut column_config_map, _) = get_link_remote_config(
+            &self.config, 
+            column_config_map, 
+            properties_map, 
+            &db_folder
+        )?;
+        // Lookups are always a list of values
+        column_config_map.insert(String::from(MANY), String::from(TRUE));
+        return Ok(column_config_map)
+    }
+    fn get_config(
+        &mut self, 
+        column_config_map: &BTreeMap<String, String>,
+    ) -> Result<ColumnConfig, PlanetError> {
+        let mut config = get_link_remote_column_config(&self.config, column_config_map);
+        config.many = Some(true);
+        return Ok(config)
+    }
+    fn validate(
+        &self, 
+        data: &Vec<String>, 
+    ) -> Result<Vec<String>, Vec<PlanetError>> {
+        let data = data.clone();
+        return Ok(data);
+    }
+    fn get_yaml_out(&self, yaml_string: &String, value: &String) -> String {
+        let column_config = self.config.clone();
+        let field_name = column_config.name.unwrap();
+        let mut yaml_string = yaml_string.clone();
+        let field = &field_name.truecolor(
+            YAML_COLOR_BLUE[0], YAML_COLOR_BLUE[1], YAML_COLOR_BLUE[2]
+        );
+        let value = format!("{}", value.to_string().truecolor(
+            YAML_COLOR_BLUE[0], YAML_COLOR_BLUE[1], YAML_COLOR_BLUE[2]
+        ));
+        yaml_string.push_str(format!("  {field}: {value}\n", field=field, value=value).as_str());
+        return yaml_string;
+    }
+}
@@ -1,14 +1,23 @@
 use yaml_rust;
 use json;
 use std::collections::BTreeMap;
+use regex::Regex;
 use colored::Colorize;
 use serde::{Deserialize, Serialize};
+use serde_yaml;
+use lazy_static::lazy_static;
+use chrono::{DateTime, Utc};
 
 use crate::planet::PlanetError;
 use crate::statements::folder::schema::*;
 use crate::storage::constants::*;
+use crate::functions::{execute_formula, Formula, FormulaEnv};
 use crate::storage::columns::*;
 
+lazy_static! {
+    pub static ref RE_WORKFLOW_TRANSITION: Regex = Regex::new(r#"^[\s]*(?P<From>[^/\s]+)[\s]*/[\s]*(?P<To>[^/\s]+)([\s]+WHEN[\s]+(?P<Guard>[\s\S]+))?$"#).unwrap();
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SetColumn {
     pub config: ColumnConfig
@@ -84,6 +93,520 @@ impl StorageColumn for SetColumn {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct TagsColumn {
+    pub config: ColumnConfig,
+    pub db_folder: Option<TreeFolder>,
+}
+impl TagsColumn {
+    pub fn defaults(column_config: &ColumnConfig, db_folder: Option<TreeFolder>) -> Self {
+        let column_config = column_config.clone();
+        let column_obj = Self{
+            config: column_config,
+            db_folder: db_folder,
+        };
+        return column_obj
+    }
+    // " Project/Alpha//design " : project/alpha/design
+    pub fn normalize(value: &String) -> Result<String, PlanetError> {
+        let mut segments: Vec<String> = Vec::new();
+        for segment in value.split(TAG_SEPARATOR) {
+            let segment = segment.trim().to_lowercase();
+            if segment != "" {
+                segments.push(segment);
+            }
+        }
+        if segments.len() == 0 {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Tag \"{}\" is not valid.", value))
+                )
+            )
+        }
+        return Ok(segments.join(TAG_SEPARATOR))
+    }
+    // project/alpha/design : project, project/alpha, project/alpha/design
+    pub fn get_ancestors(tag: &String) -> Vec<String> {
+        let mut ancestors: Vec<String> = Vec::new();
+        let segments: Vec<&str> = tag.split(TAG_SEPARATOR).collect();
+        for index in 1..segments.len()+1 {
+            ancestors.push(segments[..index].join(TAG_SEPARATOR));
+        }
+        return ancestors
+    }
+    pub fn is_descendant(tag: &String, parent: &String) -> bool {
+        if tag == parent {
+            return true
+        }
+        return tag.starts_with(format!("{}{}", parent, TAG_SEPARATOR).as_str())
+    }
+    pub fn has_tag(tags: &Vec<String>, parent: &String) -> bool {
+        for tag in tags {
+            if TagsColumn::is_descendant(tag, parent) {
+                return true
+            }
+        }
+        return false
+    }
+    // project/alpha/design at depth 2 : project/alpha
+    pub fn get_tag_at_depth(tag: &String, depth: usize) -> String {
+        let segments: Vec<&str> = tag.split(TAG_SEPARATOR).collect();
+        if depth == 0 || segments.len() <= depth {
+            return tag.clone()
+        }
+        return segments[..depth].join(TAG_SEPARATOR)
+    }
+    // project/alpha/design renaming project/alpha to archive/alpha : archive/alpha/design
+    pub fn rename(tag: &String, tag_from: &String, tag_to: &String) -> Option<String> {
+        if !TagsColumn::is_descendant(tag, tag_from) {
+            return None
+        }
+        return Some(format!("{}{}", tag_to, &tag[tag_from.len()..]))
+    }
+}
+impl StorageColumn for TagsColumn {
+    fn create_config(
+        &mut self, 
+        field_config_map: &BTreeMap<String, String>,
+    ) -> Result<BTreeMap<String, String>, PlanetError> {
+        let mut field_config_map = field_config_map.clone();
+        // Tags are stored as a set of paths
+        field_config_map.insert(IS_SET.to_string(), String::from("true"));
+        return Ok(field_config_map)
+    }
+    fn get_config(
+        &mut self, 
+        field_config_map: &BTreeMap<String, String>,
+    ) -> Result<ColumnConfig, PlanetError> {
+        let _ = field_config_map;
+        let mut config = self.config.clone();
+        config.is_set = Some(String::from("true"));
+        return Ok(config)
+    }
+    fn validate(&self, data: &Vec<String>) -> Result<Vec<String>, Vec<PlanetError>> {
+        let column_config = self.config.clone();
+        let name = column_config.name.clone().unwrap_or_default();
+        let required = column_config.required.unwrap_or_default();
+        let mut errors: Vec<PlanetError> = Vec::new();
+        let mut tags: Vec<String> = Vec::new();
+        for data_item in data {
+            for value in data_item.split(",") {
+                if value.trim() == "" {
+                    continue
+                }
+                let tag = TagsColumn::normalize(&value.to_string());
+                if tag.is_err() {
+                    errors.push(tag.unwrap_err());
+                    return Err(errors)
+                }
+                let tag = tag.unwrap();
+                if !tags.contains(&tag) {
+                    tags.push(tag);
+                }
+            }
+        }
+        if tags.len() == 0 && required {
+            errors.push(
+                PlanetError::new(
+                    500, 
+                    Some(tr!(
+                        "Field {}{}{} is required", 
+                        String::from("\"").blue(), &name.blue(), String::from("\"").blue()
+                    )),
+                )
+            );
+            return Err(errors)
+        }
+        let set_validate = validate_set(&column_config, &tags);
+        if set_validate.is_err() {
+            errors.push(set_validate.unwrap_err());
+            return Err(errors)
+        }
+        if self.db_folder.is_some() {
+            let db_folder = self.db_folder.clone().unwrap();
+            let result = db_folder.register_tags(&tags);
+            if result.is_err() {
+                errors.push(result.unwrap_err());
+                return Err(errors)
+            }
+        }
+        return Ok(tags)
+    }
+    fn get_yaml_out(&self, yaml_string: &String, value: &String) -> String {
+        let field_config = self.config.clone();
+        let field_name = field_config.name.unwrap();
+        let mut yaml_string = yaml_string.clone();
+        let field = &field_name.truecolor(
+            YAML_COLOR_BLUE[0], YAML_COLOR_BLUE[1], YAML_COLOR_BLUE[2]
+        );
+        let value = format!("{}", value.to_string().truecolor(
+            YAML_COLOR_ORANGE[0], YAML_COLOR_ORANGE[1], YAML_COLOR_ORANGE[2]
+        ));
+        yaml_string.push_str(format!("  {field}: {value}\n", field=field, value=value).as_str());
+        return yaml_string;
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WorkflowTransition {
+    pub from: String,
+    pub to: String,
+    pub guard: Option<String>,
+    pub guard_compiled: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WorkflowColumn {
+    pub config: ColumnConfig
+}
+impl WorkflowColumn {
+    pub fn defaults(column_config: &ColumnConfig) -> Self {
+        let column_config = column_config.clone();
+        let column_obj = Self{
+            config: column_config
+        };
+        return column_obj
+    }
+    pub fn create_config(
+        &mut self, 
+        field_config_map: &BTreeMap<String, String>,
+        properties_map: &HashMap<String, ColumnConfig>,
+        db_folder: &TreeFolder,
+        folder_name: &String,
+    ) -> Result<BTreeMap<String, String>, PlanetError> {
+        let mut field_config_map = field_config_map.clone();
+        let config = self.config.clone();
+        let column_name = config.name.clone().unwrap_or_default();
+        let states = config.states.clone().unwrap_or_default();
+        if states.len() == 0 {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Workflow column \"{}\" needs at least one state.", &column_name))
+                )
+            )
+        }
+        field_config_map.insert(STATES.to_string(), states.join(","));
+        let transitions_source = config.transitions.clone().unwrap_or_default();
+        let mut transitions: Vec<WorkflowTransition> = Vec::new();
+        for transition_source in transitions_source {
+            let expr = &RE_WORKFLOW_TRANSITION;
+            let captures = expr.captures(&transition_source);
+            if captures.is_none() {
+                return Err(
+                    PlanetError::new(
+                        500, 
+                        Some(tr!("Transition \"{}\" is not valid, use From/To or From/To WHEN formula.", &transition_source))
+                    )
+                )
+            }
+            let captures = captures.unwrap();
+            let mut transition_states: Vec<String> = Vec::new();
+            for state_name in ["From", "To"] {
+                let state = captures.name(state_name).unwrap().as_str().to_string();
+                let state_config = self.get_state(&state);
+                if state_config.is_none() {
+                    return Err(
+                        PlanetError::new(
+                            500, 
+                            Some(tr!(
+                                "Transition \"{}\" has state \"{}\" not found in workflow column \"{}\".", 
+                                &transition_source, &state, &column_name
+                            ))
+                        )
+                    )
+                }
+                transition_states.push(state_config.unwrap());
+            }
+            let mut transition = WorkflowTransition{
+                from: transition_states[0].clone(),
+                to: transition_states[1].clone(),
+                guard: None,
+                guard_compiled: None,
+            };
+            let guard = captures.name("Guard");
+            if guard.is_some() {
+                let guard = guard.unwrap().as_str().trim().to_string();
+                let formula_compiled = Formula::defaults(
+                    &guard,
+                    &FORMULA_FORMAT_CHECK.to_string(),
+                    None,
+                    Some(properties_map.clone()),
+                    Some(db_folder.clone()),
+                    Some(folder_name.clone()),
+                    false,
+                    None
+                );
+                if formula_compiled.is_err() {
+                    let error = formula_compiled.unwrap_err();
+                    return Err(
+                        PlanetError::new(
+                            500, 
+                            Some(tr!(
+                                "Transition \"{}\" condition could not be compiled: {}", 
+                                &transition_source, &error.message
+                            ))
+                        )
+                    )
+                }
+                let formula_compiled = formula_compiled.unwrap();
+                transition.guard = Some(guard);
+                transition.guard_compiled = Some(serde_yaml::to_string(&formula_compiled).unwrap());
+            }
+            transitions.push(transition);
+        }
+        let transitions_serialized = serde_yaml::to_string(&transitions).unwrap();
+        field_config_map.insert(TRANSITIONS.to_string(), transitions_serialized);
+        return Ok(field_config_map)
+    }
+    pub fn get_config(
+        &mut self, 
+        field_config_map: &BTreeMap<String, String>,
+    ) -> Result<ColumnConfig, PlanetError> {
+        let mut config = self.config.clone();
+        let states = field_config_map.get(STATES);
+        if states.is_some() {
+            let states = states.unwrap();
+            let states: Vec<String> = states.split(",").map(|state| state.to_string()).collect();
+            config.states = Some(states);
+        }
+        let transitions = field_config_map.get(TRANSITIONS);
+        if transitions.is_some() {
+            let transitions_serialized = transitions.unwrap().clone();
+            let transitions: Result<Vec<WorkflowTransition>, serde_yaml::Error> = serde_yaml::from_str(
+                transitions_serialized.as_str()
+            );
+            if transitions.is_err() {
+                return Err(
+                    PlanetError::new(
+                        500, 
+                        Some(tr!("Workflow transitions are not correctly compiled."))
+                    )
+                )
+            }
+            let transitions = transitions.unwrap();
+            let mut transitions_source: Vec<String> = Vec::new();
+            for transition in transitions {
+                let mut transition_source = format!("{}{}{}", &transition.from, TRANSITION_SEPARATOR, &transition.to);
+                if transition.guard.is_some() {
+                    transition_source = format!("{} WHEN {}", &transition_source, transition.guard.unwrap());
+                }
+                transitions_source.push(transition_source);
+            }
+            config.transitions = Some(transitions_source);
+            config.transitions_compiled = Some(transitions_serialized);
+        }
+        return Ok(config)
+    }
+    pub fn get_state(&self, value: &String) -> Option<String> {
+        let states = self.config.states.clone().unwrap_or_default();
+        for state in states {
+            if state.to_lowercase() == value.trim().to_lowercase() {
+                return Some(state)
+            }
+        }
+        return None
+    }
+    fn get_transitions(&self) -> Vec<WorkflowTransition> {
+        let transitions_compiled = self.config.transitions_compiled.clone();
+        if transitions_compiled.is_some() {
+            let transitions_compiled = transitions_compiled.unwrap();
+            let transitions: Result<Vec<WorkflowTransition>, serde_yaml::Error> = serde_yaml::from_str(
+                transitions_compiled.as_str()
+            );
+            if transitions.is_ok() {
+                return transitions.unwrap()
+            }
+        }
+        return Vec::new()
+    }
+    pub fn validate(&self, data: &Vec<String>) -> Result<Vec<String>, Vec<PlanetError>> {
+        let config = self.config.clone();
+        let column_name = config.name.clone().unwrap_or_default();
+        let required = config.required.unwrap_or_default();
+        let mut errors: Vec<PlanetError> = Vec::new();
+        let mut data_new: Vec<String> = Vec::new();
+        for data_item in data {
+            if data_item.trim() == "" {
+                if required {
+                    errors.push(
+                        PlanetError::new(
+                            500, 
+                            Some(tr!(
+                                "Field {}{}{} is required", 
+                                String::from("\"").blue(), &column_name.blue(), String::from("\"").blue()
+                            )),
+                        )
+                    );
+                    return Err(errors)
+                }
+                data_new.push(data_item.clone());
+                continue
+            }
+            let state = self.get_state(data_item);
+            if state.is_none() {
+                errors.push(
+                    PlanetError::new(
+                        500, 
+                        Some(tr!(
+                            "State \"{}\" not found in workflow column \"{}\".", data_item, &column_name
+                        )),
+                    )
+                );
+                return Err(errors)
+            }
+            data_new.push(state.unwrap());
+        }
+        return Ok(data_new)
+    }
+    // Items start at the first state when transitions are configured
+    pub fn validate_initial_state(&self, state: &String) -> Result<(), PlanetError> {
+        let column_name = self.config.name.clone().unwrap_or_default();
+        let transitions = self.get_transitions();
+        let states = self.config.states.clone().unwrap_or_default();
+        if transitions.len() == 0 || states.len() == 0 || states[0] == *state {
+            return Ok(())
+        }
+        return Err(
+            PlanetError::new(
+                500, 
+                Some(tr!(
+                    "State \"{}\" not allowed at column \"{}\", items start at state \"{}\".", 
+                    state, &column_name, &states[0]
+                ))
+            )
+        )
+    }
+    // Changes with no transitions configured are all allowed. Conditions run on item data with the new state.
+    pub fn validate_transition(
+        &self,
+        state_from: &Option<String>,
+        state_to: &String,
+        data_map: &BTreeMap<String, Vec<BTreeMap<String, String>>>,
+        column_config_map: &BTreeMap<String, ColumnConfig>,
+        formula_env: &FormulaEnv,
+    ) -> Result<(), PlanetError> {
+        let column_name = self.config.name.clone().unwrap_or_default();
+        if state_from.is_none() {
+            return self.validate_initial_state(state_to)
+        }
+        let state_from = state_from.clone().unwrap();
+        if state_from == *state_to {
+            return Ok(())
+        }
+        let transitions = self.get_transitions();
+        if transitions.len() == 0 {
+            return Ok(())
+        }
+        for transition in transitions {
+            if transition.from != state_from || transition.to != *state_to {
+                continue
+            }
+            if transition.guard_compiled.is_none() {
+                return Ok(())
+            }
+            let guard = transition.guard.clone().unwrap_or_default();
+            let guard_compiled = transition.guard_compiled.clone().unwrap();
+            let formula: Result<Formula, serde_yaml::Error> = serde_yaml::from_str(guard_compiled.as_str());
+            if formula.is_err() {
+                return Err(
+                    PlanetError::new(
+                        500, 
+                        Some(tr!("Transition condition \"{}\" is not correctly compiled.", &guard))
+                    )
+                )
+            }
+            let mut formula = formula.unwrap();
+            formula.env = Some(formula_env.clone());
+            let formula_result = execute_formula(&formula, data_map, None, column_config_map)?;
+            let result = formula_result.result.to_uppercase();
+            if formula_result.matched || result == String::from("TRUE") {
+                return Ok(())
+            }
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!(
+                        "Transition from \"{}\" to \"{}\" at column \"{}\" not allowed, condition failed: {}", 
+                        &state_from, state_to, &column_name, &guard
+                    ))
+                )
+            )
+        }
+        return Err(
+            PlanetError::new(
+                500, 
+                Some(tr!(
+                    "Transition from \"{}\" to \"{}\" not allowed at column \"{}\".", 
+                    &state_from, state_to, &column_name
+                ))
+            )
+        )
+    }
+    pub fn get_history_key(column_id: &String) -> String {
+        return format!("{}__{}", column_id, STATE_HISTORY)
+    }
+    pub fn record_state(
+        data: &BTreeMap<String, Vec<BTreeMap<String, String>>>,
+        column_id: &String,
+        state: &String,
+    ) -> BTreeMap<String, Vec<BTreeMap<String, String>>> {
+        let mut data = data.clone();
+        let history_key = WorkflowColumn::get_history_key(column_id);
+        let mut history = data.get(&history_key).cloned().unwrap_or_default();
+        let mut map: BTreeMap<String, String> = BTreeMap::new();
+        map.insert(VALUE.to_string(), state.clone());
+        map.insert(STATE_CHANGED_ON.to_string(), Utc::now().to_rfc3339());
+        history.push(map);
+        data.insert(history_key, history);
+        return data
+    }
+    // Seconds the item spent in state, the current state counts until now
+    pub fn get_time_in_state(
+        history: &Vec<BTreeMap<String, String>>,
+        state: &String,
+        now: &DateTime<Utc>,
+    ) -> i64 {
+        let mut seconds: i64 = 0;
+        for (index, item) in history.iter().enumerate() {
+            let value = item.get(VALUE).cloned().unwrap_or_default();
+            if value.to_lowercase() != state.to_lowercase() {
+                continue
+            }
+            let changed_on = item.get(STATE_CHANGED_ON).cloned().unwrap_or_default();
+            let start = DateTime::parse_from_rfc3339(&changed_on);
+            if start.is_err() {
+                continue
+            }
+            let start = start.unwrap().with_timezone(&Utc);
+            let mut end = now.clone();
+            if index + 1 < history.len() {
+                let next_changed_on = history[index + 1].get(STATE_CHANGED_ON).cloned().unwrap_or_default();
+                let next = DateTime::parse_from_rfc3339(&next_changed_on);
+                if next.is_ok() {
+                    end = next.unwrap().with_timezone(&Utc);
+                }
+            }
+            seconds += (end - start).num_seconds();
+        }
+        return seconds
+    }
+    pub fn get_yaml_out(&self, yaml_string: &String, value: &String) -> String {
+        let field_config = self.config.clone();
+        let field_name = field_config.name.unwrap();
+        let mut yaml_string = yaml_string.clone();
+        let field = &field_name.truecolor(
+            YAML_COLOR_BLUE[0], YAML_COLOR_BLUE[1], YAML_COLOR_BLUE[2]
+        );
+        let value = format!("{}", value.to_string().truecolor(
+            YAML_COLOR_ORANGE[0], YAML_COLOR_ORANGE[1], YAML_COLOR_ORANGE[2]
+        ));
+        yaml_string.push_str(format!("  {field}: {value}\n", field=field, value=value).as_str());
+        return yaml_string;
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ObjectColumn {
     pub config: ColumnConfig
@@ -111,6 +634,18 @@ impl StorageColumn for ObjectColumn {
         } else {
             field_config_map.insert(MODE.to_string(), MODE_YAML.to_string());
         }
+        let object_schema = config.object_schema;
+        if object_schema.is_some() {
+            let object_schema = object_schema.unwrap();
+            // Compile at create time so schema errors are reported on CREATE FOLDER
+            let _ = ObjectSchema::compile(&object_schema)?;
+            field_config_map.insert(OBJECT_SCHEMA.to_string(), object_schema);
+        }
+        let indexed_paths = config.indexed_paths;
+        if indexed_paths.is_some() {
+            let indexed_paths = indexed_paths.unwrap();
+            field_config_map.insert(INDEXED_PATHS.to_string(), indexed_paths.join(","));
+        }
         return Ok(field_config_map.clone())
     }
     fn get_config(
@@ -130,6 +665,20 @@ impl StorageColumn for ObjectColumn {
                 )
             )
         }
+        let object_schema = field_config_map.get(OBJECT_SCHEMA);
+        if object_schema.is_some() {
+            let object_schema = object_schema.unwrap();
+            config.object_schema = Some(object_schema.clone());
+        }
+        let indexed_paths = field_config_map.get(INDEXED_PATHS);
+        if indexed_paths.is_some() {
+            let indexed_paths = indexed_paths.unwrap();
+            let mut paths: Vec<String> = Vec::new();
+            for path in indexed_paths.split(",") {
+                paths.push(path.to_string());
+            }
+            config.indexed_paths = Some(paths);
+        }
         return Ok(config)
     }
     fn validate(&self, data: &Vec<String>) -> Result<Vec<String>, Vec<PlanetError>> {
@@ -145,9 +694,20 @@ impl StorageColumn for ObjectColumn {
             return Err(errors)
         }
         let mode = mode.unwrap();
+        let mut object_schema: Option<ObjectSchema> = None;
+        if config.object_schema.is_some() {
+            let result = ObjectSchema::compile(&config.object_schema.unwrap());
+            if result.is_err() {
+                let mut errors: Vec<PlanetError> = Vec::new();
+                errors.push(result.unwrap_err());
+                return Err(errors)
+            }
+            object_schema = Some(result.unwrap());
+        }
         let data = data.clone();
         for data_item in data.clone() {
             // Check weather use json or yaml from config
+            let mut value: json::JsonValue = json::JsonValue::Null;
             if mode == MODE_YAML.to_string() {
                 let result = yaml_rust::YamlLoader::load_from_str(&data_item);
                 if result.is_err() {
@@ -159,6 +719,10 @@ impl StorageColumn for ObjectColumn {
                     errors.push(error);
                     return Err(errors)
                 }
+                let documents = result.unwrap();
+                if documents.len() > 0 {
+                    value = yaml_to_json(&documents[0]);
+                }
             } else if mode == MODE_JSON.to_string() {
                 let result = json::parse(&data_item);
                 if result.is_err() {
@@ -170,6 +734,22 @@ impl StorageColumn for ObjectColumn {
                     errors.push(error);
                     return Err(errors)
                 }
+                value = result.unwrap();
+            }
+            if object_schema.is_some() {
+                let schema_errors = object_schema.clone().unwrap().validate(&value);
+                if schema_errors.len() > 0 {
+                    let mut errors: Vec<PlanetError> = Vec::new();
+                    for schema_error in schema_errors {
+                        errors.push(
+                            PlanetError::new(
+                                500, 
+                                Some(tr!("Object validation error: {}", schema_error))
+                            )
+                        );
+                    }
+                    return Err(errors)
+                }
             }
         }
         return Ok(data.clone())
@@ -191,3 +771,574 @@ impl StorageColumn for ObjectColumn {
         return yaml_string;
     }
 }
+
+impl ObjectColumn {
+    // Values found at path like customer.tier or lines.0.sku. Lists are flattened, so lines.sku returns 
+    // sku for all lines.
+    pub fn get_path_values(source: &String, path: &String) -> Vec<String> {
+        let mut values: Vec<String> = Vec::new();
+        let documents = yaml_rust::YamlLoader::load_from_str(source);
+        if documents.is_err() {
+            return values
+        }
+        let documents = documents.unwrap();
+        if documents.len() == 0 {
+            return values
+        }
+        let mut nodes: Vec<yaml_rust::Yaml> = Vec::new();
+        nodes.push(documents[0].clone());
+        let segments: Vec<&str> = path.split(".").collect();
+        for segment in segments {
+            let segment = segment.trim();
+            if segment == "" {
+                continue
+            }
+            let mut next_nodes: Vec<yaml_rust::Yaml> = Vec::new();
+            for node in nodes {
+                ObjectColumn::get_path_children(&node, segment, &mut next_nodes);
+            }
+            nodes = next_nodes;
+        }
+        for node in nodes {
+            match node {
+                yaml_rust::Yaml::Array(list) => {
+                    for item in list {
+                        let value = ObjectColumn::get_scalar(&item);
+                        if value.is_some() {
+                            values.push(value.unwrap());
+                        }
+                    }
+                },
+                _ => {
+                    let value = ObjectColumn::get_scalar(&node);
+                    if value.is_some() {
+                        values.push(value.unwrap());
+                    }
+                }
+            }
+        }
+        return values
+    }
+    fn get_path_children(node: &yaml_rust::Yaml, segment: &str, children: &mut Vec<yaml_rust::Yaml>) {
+        match node {
+            yaml_rust::Yaml::Hash(map) => {
+                let key = yaml_rust::Yaml::String(segment.to_string());
+                let child = map.get(&key);
+                if child.is_some() {
+                    children.push(child.unwrap().clone());
+                }
+            },
+            yaml_rust::Yaml::Array(list) => {
+                let index: Result<usize, _> = segment.parse();
+                if index.is_ok() {
+                    let index = index.unwrap();
+                    if index < list.len() {
+                        children.push(list[index].clone());
+                    }
+                } else {
+                    for item in list {
+                        ObjectColumn::get_path_children(item, segment, children);
+                    }
+                }
+            },
+            _ => {}
+        }
+    }
+    fn get_scalar(node: &yaml_rust::Yaml) -> Option<String> {
+        match node {
+            yaml_rust::Yaml::String(text) => Some(text.clone()),
+            yaml_rust::Yaml::Integer(number) => Some(number.to_string()),
+            yaml_rust::Yaml::Real(number) => Some(number.clone()),
+            yaml_rust::Yaml::Boolean(flag) => Some(flag.to_string()),
+            _ => None,
+        }
+    }
+    // Path values are kept at paths index the way WHERE compares them: numbers by value, so 5 and 5.0 
+    // are the same, and text without case.
+    pub fn get_path_index_value(value: &String) -> String {
+        let number: Result<f64, _> = value.parse();
+        if number.is_ok() {
+            return number.unwrap().to_string()
+        }
+        return value.to_lowercase()
+    }
+    // Sort key for path values: numbers are encoded to sort numerically and before text, items without 
+    // value go last.
+    pub fn get_path_sort_value(source: &String, path: &String) -> String {
+        let values = ObjectColumn::get_path_values(source, path);
+        if values.len() == 0 {
+            return String::from("2")
+        }
+        let value = values[0].clone();
+        let number: Result<f64, _> = value.parse();
+        if number.is_ok() {
+            let number = number.unwrap();
+            let bits = number.to_bits();
+            let key: u64;
+            if number >= 0.0 {
+                key = bits ^ (1 << 63);
+            } else {
+                key = !bits;
+            }
+            return format!("0{:016x}", key)
+        }
+        return format!("1{}", value)
+    }
+}
+
+pub const OBJECT_SCHEMA_TYPES: [&str; 7] = [
+    "string", "number", "integer", "boolean", "object", "array", "null"
+];
+pub const OBJECT_SCHEMA_IGNORED_KEYWORDS: [&str; 7] = [
+    "$schema", "$id", "$comment", "title", "description", "default", "examples"
+];
+
+#[derive(Debug, Clone)]
+pub struct ObjectSchema {
+    pub types: Option<Vec<String>>,
+    pub properties: BTreeMap<String, ObjectSchema>,
+    pub required: Vec<String>,
+    pub additional_properties: Option<bool>,
+    pub items: Option<Box<ObjectSchema>>,
+    pub enum_values: Option<Vec<json::JsonValue>>,
+    pub pattern: Option<Regex>,
+    pub min_length: Option<usize>,
+    pub max_length: Option<usize>,
+    pub minimum: Option<f64>,
+    pub maximum: Option<f64>,
+    pub min_items: Option<usize>,
+    pub max_items: Option<usize>,
+}
+impl ObjectSchema {
+    pub fn compile(schema: &String) -> Result<ObjectSchema, PlanetError> {
+        let schema_json = json::parse(schema);
+        if schema_json.is_err() {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Object schema is not valid JSON."))
+                )
+            )
+        }
+        let schema_json = schema_json.unwrap();
+        return ObjectSchema::compile_node(&schema_json, &String::from(""))
+    }
+    fn get_path(path: &String, key: &str) -> String {
+        if path.len() == 0 {
+            return key.to_string()
+        }
+        return format!("{}.{}", path, key)
+    }
+    fn get_path_display(path: &String) -> String {
+        if path.len() == 0 {
+            return String::from("(root)")
+        }
+        return path.clone()
+    }
+    fn get_usize(node: &json::JsonValue, keyword: &str, path: &String) -> Result<Option<usize>, PlanetError> {
+        let value = &node[keyword];
+        if value.is_null() {
+            return Ok(None)
+        }
+        let number = value.as_usize();
+        if number.is_none() {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!(
+                        "Object schema error at \"{}\": \"{}\" must be a positive integer.", 
+                        ObjectSchema::get_path_display(path), keyword
+                    ))
+                )
+            )
+        }
+        return Ok(number)
+    }
+    fn get_f64(node: &json::JsonValue, keyword: &str, path: &String) -> Result<Option<f64>, PlanetError> {
+        let value = &node[keyword];
+        if value.is_null() {
+            return Ok(None)
+        }
+        let number = value.as_f64();
+        if number.is_none() {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!(
+                        "Object schema error at \"{}\": \"{}\" must be a number.", 
+                        ObjectSchema::get_path_display(path), keyword
+                    ))
+                )
+            )
+        }
+        return Ok(number)
+    }
+    fn compile_node(node: &json::JsonValue, path: &String) -> Result<ObjectSchema, PlanetError> {
+        let path_display = ObjectSchema::get_path_display(path);
+        if !node.is_object() {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Object schema error at \"{}\": schema must be an object.", &path_display))
+                )
+            )
+        }
+        let supported = vec![
+            "type", "properties", "required", "additionalProperties", "items", "enum", "pattern", 
+            "minLength", "maxLength", "minimum", "maximum", "minItems", "maxItems"
+        ];
+        for (keyword, _) in node.entries() {
+            if !supported.contains(&keyword) && !OBJECT_SCHEMA_IGNORED_KEYWORDS.contains(&keyword) {
+                return Err(
+                    PlanetError::new(
+                        500, 
+                        Some(tr!(
+                            "Object schema error at \"{}\": keyword \"{}\" is not supported.", 
+                            &path_display, keyword
+                        ))
+                    )
+                )
+            }
+        }
+        // type
+        let mut types: Option<Vec<String>> = None;
+        let type_node = &node["type"];
+        if !type_node.is_null() {
+            let mut type_list: Vec<String> = Vec::new();
+            if type_node.is_string() {
+                type_list.push(type_node.as_str().unwrap().to_string());
+            } else if type_node.is_array() {
+                for member in type_node.members() {
+                    if member.is_string() {
+                        type_list.push(member.as_str().unwrap().to_string());
+                    }
+                }
+            }
+            if type_list.len() == 0 {
+                return Err(
+                    PlanetError::new(
+                        500, 
+                        Some(tr!("Object schema error at \"{}\": \"type\" must be a string or list of strings.", &path_display))
+                    )
+                )
+            }
+            for item_type in &type_list {
+                if !OBJECT_SCHEMA_TYPES.contains(&item_type.as_str()) {
+                    return Err(
+                        PlanetError::new(
+                            500, 
+                            Some(tr!("Object schema error at \"{}\": type \"{}\" not supported.", &path_display, item_type))
+                        )
+                    )
+                }
+            }
+            types = Some(type_list);
+        }
+        // properties
+        let mut properties: BTreeMap<String, ObjectSchema> = BTreeMap::new();
+        let properties_node = &node["properties"];
+        if !properties_node.is_null() {
+            if !properties_node.is_object() {
+                return Err(
+                    PlanetError::new(
+                        500, 
+                        Some(tr!("Object schema error at \"{}\": \"properties\" must be an object.", &path_display))
+                    )
+                )
+            }
+            for (key, property_node) in properties_node.entries() {
+                let property_path = ObjectSchema::get_path(path, key);
+                let property = ObjectSchema::compile_node(property_node, &property_path)?;
+                properties.insert(key.to_string(), property);
+            }
+        }
+        // required
+        let mut required: Vec<String> = Vec::new();
+        let required_node = &node["required"];
+        if !required_node.is_null() {
+            if !required_node.is_array() {
+                return Err(
+                    PlanetError::new(
+                        500, 
+                        Some(tr!("Object schema error at \"{}\": \"required\" must be a list.", &path_display))
+                    )
+                )
+            }
+            for member in required_node.members() {
+                if member.is_string() {
+                    required.push(member.as_str().unwrap().to_string());
+                }
+            }
+        }
+        // additionalProperties, only booleans supported
+        let mut additional_properties: Option<bool> = None;
+        let additional_node = &node["additionalProperties"];
+        if !additional_node.is_null() {
+            if !additional_node.is_boolean() {
+                return Err(
+                    PlanetError::new(
+                        500, 
+                        Some(tr!("Object schema error at \"{}\": \"additionalProperties\" must be true or false.", &path_display))
+                    )
+                )
+            }
+            additional_properties = additional_node.as_bool();
+        }
+        // items
+        let mut items: Option<Box<ObjectSchema>> = None;
+        let items_node = &node["items"];
+        if !items_node.is_null() {
+            let items_path = format!("{}[]", path);
+            let items_schema = ObjectSchema::compile_node(items_node, &items_path)?;
+            items = Some(Box::new(items_schema));
+        }
+        // enum
+        let mut enum_values: Option<Vec<json::JsonValue>> = None;
+        let enum_node = &node["enum"];
+        if !enum_node.is_null() {
+            if !enum_node.is_array() || enum_node.len() == 0 {
+                return Err(
+                    PlanetError::new(
+                        500, 
+                        Some(tr!("Object schema error at \"{}\": \"enum\" must be a list with values.", &path_display))
+                    )
+                )
+            }
+            let mut values: Vec<json::JsonValue> = Vec::new();
+            for member in enum_node.members() {
+                values.push(member.clone());
+            }
+            enum_values = Some(values);
+        }
+        // pattern
+        let mut pattern: Option<Regex> = None;
+        let pattern_node = &node["pattern"];
+        if !pattern_node.is_null() {
+            let pattern_str = pattern_node.as_str();
+            if pattern_str.is_none() {
+                return Err(
+                    PlanetError::new(
+                        500, 
+                        Some(tr!("Object schema error at \"{}\": \"pattern\" must be a string.", &path_display))
+                    )
+                )
+            }
+            let expr = Regex::new(pattern_str.unwrap());
+            if expr.is_err() {
+                return Err(
+                    PlanetError::new(
+                        500, 
+                        Some(tr!("Object schema error at \"{}\": \"pattern\" is not a valid regular expression.", &path_display))
+                    )
+                )
+            }
+            pattern = Some(expr.unwrap());
+        }
+        let schema = ObjectSchema{
+            types: types,
+            properties: properties,
+            required: required,
+            additional_properties: additional_properties,
+            items: items,
+            enum_values: enum_values,
+            pattern: pattern,
+            min_length: ObjectSchema::get_usize(node, "minLength", path)?,
+            max_length: ObjectSchema::get_usize(node, "maxLength", path)?,
+            minimum: ObjectSchema::get_f64(node, "minimum", path)?,
+            maximum: ObjectSchema::get_f64(node, "maximum", path)?,
+            min_items: ObjectSchema::get_usize(node, "minItems", path)?,
+            max_items: ObjectSchema::get_usize(node, "maxItems", path)?,
+        };
+        return Ok(schema)
+    }
+    fn matches_type(value: &json::JsonValue, item_type: &str) -> bool {
+        match item_type {
+            "string" => value.is_string(),
+            "number" => value.is_number(),
+            "integer" => {
+                if !value.is_number() {
+                    return false
+                }
+                let number = value.as_f64().unwrap_or_default();
+                number.fract() == 0.0
+            },
+            "boolean" => value.is_boolean(),
+            "object" => value.is_object(),
+            "array" => value.is_array(),
+            "null" => value.is_null(),
+            _ => false,
+        }
+    }
+    pub fn validate(&self, value: &json::JsonValue) -> Vec<String> {
+        let mut errors: Vec<String> = Vec::new();
+        self.validate_node(value, &String::from(""), &mut errors);
+        return errors
+    }
+    fn validate_node(&self, value: &json::JsonValue, path: &String, errors: &mut Vec<String>) {
+        let path_display = ObjectSchema::get_path_display(path);
+        if self.types.is_some() {
+            let types = self.types.clone().unwrap();
+            let mut matched = false;
+            for item_type in &types {
+                if ObjectSchema::matches_type(value, item_type.as_str()) {
+                    matched = true;
+                    break
+                }
+            }
+            if !matched {
+                errors.push(tr!("\"{}\" must be of type {}.", &path_display, types.join(" or ")));
+                return
+            }
+        }
+        if self.enum_values.is_some() {
+            let enum_values = self.enum_values.clone().unwrap();
+            if !enum_values.contains(value) {
+                let mut values: Vec<String> = Vec::new();
+                for enum_value in enum_values {
+                    values.push(enum_value.dump());
+                }
+                errors.push(tr!("\"{}\" must be one of {}.", &path_display, values.join(", ")));
+            }
+        }
+        if value.is_string() {
+            let value_str = value.as_str().unwrap();
+            let length = value_str.chars().count();
+            if self.min_length.is_some() && length < self.min_length.unwrap() {
+                errors.push(tr!("\"{}\" must have at least {} characters.", &path_display, self.min_length.unwrap()));
+            }
+            if self.max_length.is_some() && length > self.max_length.unwrap() {
+                errors.push(tr!("\"{}\" must have at most {} characters.", &path_display, self.max_length.unwrap()));
+            }
+            if self.pattern.is_some() {
+                let pattern = self.pattern.clone().unwrap();
+                if !pattern.is_match(value_str) {
+                    errors.push(tr!("\"{}\" does not match pattern \"{}\".", &path_display, pattern.as_str()));
+                }
+            }
+        }
+        if value.is_number() {
+            let number = value.as_f64().unwrap_or_default();
+            if self.minimum.is_some() && number < self.minimum.unwrap() {
+                errors.push(tr!("\"{}\" must be greater or equal than {}.", &path_display, self.minimum.unwrap()));
+            }
+            if self.maximum.is_some() && number > self.maximum.unwrap() {
+                errors.push(tr!("\"{}\" must be lower or equal than {}.", &path_display, self.maximum.unwrap()));
+            }
+        }
+        if value.is_object() {
+            for key in &self.required {
+                if !value.has_key(key.as_str()) {
+                    let key_path = ObjectSchema::get_path(path, key);
+                    errors.push(tr!("\"{}\" is required.", &key_path));
+                }
+            }
+            for (key, item_value) in value.entries() {
+                let key_path = ObjectSchema::get_path(path, key);
+                let property = self.properties.get(key);
+                if property.is_some() {
+                    property.unwrap().validate_node(item_value, &key_path, errors);
+                } else if self.additional_properties.is_some() && !self.additional_properties.unwrap() {
+                    errors.push(tr!("\"{}\" is not allowed.", &key_path));
+                }
+            }
+        }
+        if value.is_array() {
+            let length = value.len();
+            if self.min_items.is_some() && length < self.min_items.unwrap() {
+                errors.push(tr!("\"{}\" must have at least {} items.", &path_display, self.min_items.unwrap()));
+            }
+            if self.max_items.is_some() && length > self.max_items.unwrap() {
+                errors.push(tr!("\"{}\" must have at most {} items.", &path_display, self.max_items.unwrap()));
+            }
+            if self.items.is_some() {
+                let items = self.items.clone().unwrap();
+                let mut index = 0;
+                for member in value.members() {
+                    let member_path = format!("{}[{}]", path, index);
+                    items.validate_node(member, &member_path, errors);
+                    index += 1;
+                }
+            }
+        }
+    }
+}
+
+pub fn yaml_to_json(value: &yaml_rust::Yaml) -> json::JsonValue {
+    match value {
+        yaml_rust::Yaml::Real(number) => {
+            let number: f64 = number.parse().unwrap_or_default();
+            json::JsonValue::from(number)
+        },
+        yaml_rust::Yaml::Integer(number) => json::JsonValue::from(*number),
+        yaml_rust::Yaml::String(text) => json::JsonValue::from(text.clone()),
+        yaml_rust::Yaml::Boolean(flag) => json::JsonValue::from(*flag),
+        yaml_rust::Yaml::Array(list) => {
+            let mut array = json::JsonValue::new_array();
+            for item in list {
+                let _ = array.push(yaml_to_json(item));
+            }
+            array
+        },
+        yaml_rust::Yaml::Hash(map) => {
+            let mut object = json::JsonValue::new_object();
+            for (key, item) in map {
+                let key = match key {
+                    yaml_rust::Yaml::String(text) => text.clone(),
+                    yaml_rust::Yaml::Integer(number) => number.to_string(),
+                    yaml_rust::Yaml::Real(number) => number.clone(),
+                    yaml_rust::Yaml::Boolean(flag) => flag.to_string(),
+                    _ => String::from(""),
+                };
+                object[key.as_str()] = yaml_to_json(item);
+            }
+            object
+        },
+        _ => json::JsonValue::Null,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rename_tag(tag: &str, tag_from: &str, tag_to: &str) -> Option<String> {
+        return TagsColumn::rename(&tag.to_string(), &tag_from.to_string(), &tag_to.to_string())
+    }
+
+    #[test]
+    fn rename_exact_tag() {
+        assert_eq!(rename_tag("project/alpha", "project/alpha", "archive/alpha"), Some("archive/alpha".to_string()));
+        assert_eq!(rename_tag("urgent", "urgent", "priority"), Some("priority".to_string()));
+    }
+
+    #[test]
+    fn rename_descendants() {
+        assert_eq!(
+            rename_tag("project/alpha/design", "project/alpha", "archive/alpha"), 
+            Some("archive/alpha/design".to_string())
+        );
+        assert_eq!(
+            rename_tag("project/alpha/design/ui", "project", "work"), 
+            Some("work/alpha/design/ui".to_string())
+        );
+        assert_eq!(
+            rename_tag("project/alpha", "project", "work/clients"), 
+            Some("work/clients/alpha".to_string())
+        );
+    }
+
+    #[test]
+    fn rename_skips_other_tags() {
+        assert_eq!(rename_tag("project/alphabet", "project/alpha", "archive/alpha"), None);
+        assert_eq!(rename_tag("project", "project/alpha", "archive/alpha"), None);
+        assert_eq!(rename_tag("other/project/alpha", "project/alpha", "archive/alpha"), None);
+    }
+
+    #[test]
+    fn rename_normalized_tags() {
+        let tag = TagsColumn::normalize(&" Project/Alpha//Design ".to_string()).unwrap();
+        let tag_from = TagsColumn::normalize(&"project/ALPHA".to_string()).unwrap();
+        let tag_to = TagsColumn::normalize(&"Archive/Alpha".to_string()).unwrap();
+        assert_eq!(TagsColumn::rename(&tag, &tag_from, &tag_to), Some("archive/alpha/design".to_string()));
+    }
+}
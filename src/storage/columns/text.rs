@@ -10,6 +10,13 @@ use lazy_static::lazy_static;
 use regex::Regex;
 use lingua::{Language, LanguageDetector, LanguageDetectorBuilder};
 use rust_stemmers::{Algorithm, Stemmer};
+use chacha20poly1305::{ChaCha20Poly1305, Key, KeyInit, Nonce};
+use chacha20poly1305::aead::{Aead, AeadCore, OsRng};
+use pbkdf2::pbkdf2_hmac;
+use rand::RngCore;
+use sha2::Sha256;
+use url::Url;
+use pulldown_cmark::{html, Event, Options, Parser, Tag};
 
 use crate::planet::constants::ID;
 use crate::planet::PlanetError;
@@ -22,9 +29,7 @@ use crate::storage::columns::*;
 
 lazy_static! {
     pub static ref RE_TEXT: Regex = Regex::new(r#"[a-zA-Z0-9]+"#).unwrap();
-    pub static ref RE_PHONE: Regex = Regex::new(r#"^(\+\d{1,2}\s)?\(?\d{2,3}\)?[\s.-]\d{2,3}[\s.-]\d{2,4}([\s.-]\d{2,4})?$"#).unwrap();
     pub static ref RE_EMAIL: Regex = Regex::new(r#"^(?:[a-z0-9!#$%&'*+/=?^_`{|}~-]+(?:\.[a-z0-9!#$%&'*+/=?^_`{|}~-]+)*|"(?:[\x01-\x08\x0b\x0c\x0e-\x1f\x21\x23-\x5b\x5d-\x7f]|\\[\x01-\x09\x0b\x0c\x0e-\x7f])*")@(?:(?:[a-z0-9](?:[a-z0-9-]*[a-z0-9])?\.)+[a-z0-9](?:[a-z0-9-]*[a-z0-9])?|\[(?:(?:(2(5[0-5]|[0-4][0-9])|1[0-9][0-9]|[1-9]?[0-9]))\.){3}(?:(2(5[0-5]|[0-4][0-9])|1[0-9][0-9]|[1-9]?[0-9])|[a-z0-9-]*[a-z0-9]:(?:[\x01-\x08\x0b\x0c\x0e-\x1f\x21-\x5a\x53-\x7f]|\\[\x01-\x09\x0b\x0c\x0e-\x7f])+)\])$"#).unwrap();
-    pub static ref RE_URL: Regex = Regex::new(r#"^https?:[/][/](www\.)?[-a-zA-Z0-9@:%._\+~#=]{1,256}\.[a-zA-Z0-9()]{1,6}\b([-a-zA-Z0-9()@:%_\+.~#?&//=]*)$"#).unwrap();
 }
 
 
@@ -53,6 +58,8 @@ impl StorageColumn for SmallTextColumn {
             let max_length = max_length.unwrap();
             column_config_map.insert(String::from(MAX_LENGTH), max_length);
         }
+        let translatable = config.translatable.unwrap_or_default();
+        column_config_map.insert(TRANSLATABLE.to_string(), translatable.to_string());
         return Ok(column_config_map)
     }
     fn get_config(
@@ -65,6 +72,11 @@ impl StorageColumn for SmallTextColumn {
             let max_length = max_length.unwrap();
             config.max_length = Some(max_length.clone());
         }
+        let translatable = column_config_map.get(TRANSLATABLE);
+        if translatable.is_some() {
+            let translatable = translatable.unwrap().clone();
+            config.translatable = Some(translatable == String::from("true"));
+        }
         return Ok(config)
     }
     fn validate(&self, data: &Vec<String>) -> Result<Vec<String>, Vec<PlanetError>> {
@@ -149,16 +161,21 @@ impl StorageColumn for LongTextColumn {
         &mut self, 
         column_config_map: &BTreeMap<String, String>,
     ) -> Result<BTreeMap<String, String>, PlanetError> {
-        let column_config_map = column_config_map.clone();
-        // No special attributes so far for small text field
+        let mut column_config_map = column_config_map.clone();
+        let translatable = self.config.translatable.unwrap_or_default();
+        column_config_map.insert(TRANSLATABLE.to_string(), translatable.to_string());
         return Ok(column_config_map)
     }
     fn get_config(
         &mut self, 
-        _: &BTreeMap<String, String>,
+        column_config_map: &BTreeMap<String, String>,
     ) -> Result<ColumnConfig, PlanetError> {
-        let config = self.config.clone();
-        // No special attributes so far for small text field
+        let mut config = self.config.clone();
+        let translatable = column_config_map.get(TRANSLATABLE);
+        if translatable.is_some() {
+            let translatable = translatable.unwrap().clone();
+            config.translatable = Some(translatable == String::from("true"));
+        }
         return Ok(config)
     }
     fn validate(&self, data: &Vec<String>) -> Result<Vec<String>, Vec<PlanetError>> {
@@ -206,11 +223,195 @@ impl StorageColumn for LongTextColumn {
     }
 }
 
+// Markdown text. Raw html and links with script urls are removed on insert, SELECT outputs markdown or 
+// rendered html, and text index gets the plain text without formatting.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RichTextColumn {
+    pub config: ColumnConfig
+}
+impl RichTextColumn {
+    pub fn defaults(column_config: &ColumnConfig) -> Self {
+        let column_config = column_config.clone();
+        let column_obj = Self{
+            config: column_config
+        };
+        return column_obj
+    }
+    fn get_parser(markdown: &str) -> Parser {
+        let mut options = Options::empty();
+        options.insert(Options::ENABLE_TABLES);
+        options.insert(Options::ENABLE_STRIKETHROUGH);
+        options.insert(Options::ENABLE_TASKLISTS);
+        return Parser::new_ext(markdown, options)
+    }
+    fn is_unsafe_url(url: &str) -> bool {
+        let url = url.trim().to_lowercase();
+        return url.starts_with("javascript:") || url.starts_with("vbscript:") || url.starts_with("data:") || 
+            url.starts_with("file:")
+    }
+    fn sanitize_once(markdown: &String) -> String {
+        let mut ranges: Vec<(usize, usize)> = Vec::new();
+        for (event, range) in RichTextColumn::get_parser(markdown.as_str()).into_offset_iter() {
+            match event {
+                Event::Html(_) | Event::InlineHtml(_) => {
+                    ranges.push((range.start, range.end));
+                },
+                Event::Start(Tag::Link{dest_url, ..}) | Event::Start(Tag::Image{dest_url, ..}) => {
+                    if RichTextColumn::is_unsafe_url(&dest_url) {
+                        ranges.push((range.start, range.end));
+                    }
+                },
+                _ => {}
+            }
+        }
+        let mut sanitized = String::from("");
+        let mut position: usize = 0;
+        for (start, end) in ranges {
+            if start < position {
+                continue
+            }
+            sanitized.push_str(&markdown[position..start]);
+            position = end;
+        }
+        sanitized.push_str(&markdown[position..]);
+        return sanitized
+    }
+    pub fn sanitize(markdown: &String) -> String {
+        // Removing html can join pieces into new html, <scr<b></b>ipt>, so we sanitize until nothing changes
+        let mut markdown = markdown.replace("\r\n", "\n");
+        loop {
+            let sanitized = RichTextColumn::sanitize_once(&markdown);
+            if sanitized == markdown {
+                break
+            }
+            markdown = sanitized;
+        }
+        return markdown
+    }
+    pub fn get_html(markdown: &String) -> String {
+        let parser = RichTextColumn::get_parser(markdown.as_str()).map(|event| match event {
+            Event::Html(text) | Event::InlineHtml(text) => Event::Text(text),
+            _ => event,
+        });
+        let mut html_output = String::from("");
+        html::push_html(&mut html_output, parser);
+        return html_output
+    }
+    pub fn get_plain_text(markdown: &String) -> String {
+        let mut text = String::from("");
+        for event in RichTextColumn::get_parser(markdown.as_str()) {
+            match event {
+                Event::Text(value) | Event::Code(value) => {
+                    text.push_str(&value);
+                },
+                Event::SoftBreak | Event::HardBreak | Event::Rule | Event::End(_) => {
+                    text.push_str(" ");
+                },
+                _ => {}
+            }
+        }
+        let words: Vec<&str> = text.split_whitespace().collect();
+        return words.join(" ")
+    }
+    pub fn get_display_value(&self, value: &String) -> String {
+        let output = self.config.output.clone().unwrap_or(OUTPUT_MARKDOWN.to_string());
+        if output.as_str() == OUTPUT_HTML {
+            return RichTextColumn::get_html(value)
+        }
+        return value.clone()
+    }
+}
+impl StorageColumn for RichTextColumn {
+    fn create_config(
+        &mut self, 
+        column_config_map: &BTreeMap<String, String>,
+    ) -> Result<BTreeMap<String, String>, PlanetError> {
+        let mut column_config_map = column_config_map.clone();
+        let output = self.config.output.clone().unwrap_or(OUTPUT_MARKDOWN.to_string());
+        let output = output.as_str();
+        match output {
+            OUTPUT_MARKDOWN | OUTPUT_HTML => {
+                column_config_map.insert(OUTPUT.to_string(), output.to_string());
+            },
+            _ => {
+                return Err(
+                    PlanetError::new(
+                        500, 
+                        Some(tr!("Output must be one of Markdown or Html, found \"{}\".", output)),
+                    )
+                )
+            }
+        }
+        return Ok(column_config_map)
+    }
+    fn get_config(
+        &mut self, 
+        column_config_map: &BTreeMap<String, String>,
+    ) -> Result<ColumnConfig, PlanetError> {
+        let mut config = self.config.clone();
+        let output = column_config_map.get(OUTPUT);
+        if output.is_some() {
+            let output = output.unwrap().clone();
+            config.output = Some(output);
+        }
+        return Ok(config)
+    }
+    fn validate(&self, data: &Vec<String>) -> Result<Vec<String>, Vec<PlanetError>> {
+        let data = data.clone();
+        let config = self.config.clone();
+        let set_validate = validate_set(&config, &data);
+        if set_validate.is_err() {
+            let error = set_validate.unwrap_err();
+            let mut errors: Vec<PlanetError> = Vec::new();
+            errors.push(error);
+            return Err(errors)
+        }
+        let required = config.required.unwrap_or_default();
+        let name = config.name.unwrap_or_default();
+        let mut data_new: Vec<String> = Vec::new();
+        for data_item in data {
+            let data_item = RichTextColumn::sanitize(&data_item);
+            if data_item.trim() == "" && required == true {
+                let error = PlanetError::new(
+                    500, 
+                    Some(tr!(
+                        "Field {}{}{} is required", 
+                        String::from("\"").blue(), name.blue(), String::from("\"").blue()
+                    )),
+                );
+                let mut errors: Vec<PlanetError> = Vec::new();
+                errors.push(error);
+                return Err(errors);
+            }
+            data_new.push(data_item);
+        }
+        return Ok(data_new)
+    }
+    fn get_yaml_out(&self, yaml_string: &String, value: &String) -> String {
+        let column_config = self.config.clone();
+        let column_name = column_config.name.unwrap();
+        let mut yaml_string = yaml_string.clone();
+        let field = &column_name.truecolor(
+            YAML_COLOR_BLUE[0], YAML_COLOR_BLUE[1], YAML_COLOR_BLUE[2]
+        );
+        yaml_string.push_str(format!("  {field}: |\n", field=field).as_str());
+        let value = self.get_display_value(value);
+        for line in value.lines() {
+            let line = format!("{}", 
+                line.truecolor(YAML_COLOR_ORANGE[0], YAML_COLOR_ORANGE[1], YAML_COLOR_ORANGE[2]), 
+            );
+            yaml_string.push_str(format!("    {line}\n", line=line).as_str());
+        }
+        return yaml_string;
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SelectColumn {
     pub config: ColumnConfig,
     pub options_id_map: Option<BTreeMap<String, String>>,
     pub options_name_map: Option<BTreeMap<String, String>>,
+    pub archived_ids: Option<Vec<String>>,
 }
 impl SelectColumn {
     pub fn defaults(column_config: &ColumnConfig, folder: Option<&DbData>) -> Self {
@@ -219,11 +420,13 @@ impl SelectColumn {
             config: column_config,
             options_id_map: None,
             options_name_map: None,
+            archived_ids: None,
         };
         if folder.is_some() {
             let folder = folder.unwrap();
             let mut options_id_map: BTreeMap<String, String> = BTreeMap::new();
             let mut options_name_map: BTreeMap<String, String> = BTreeMap::new();
+            let mut archived_ids: Vec<String> = Vec::new();
             let column_config = &column_obj.config;
             let column_name = column_config.name.clone().unwrap();
             for data in folder.data.iter() {
@@ -254,6 +457,9 @@ impl SelectColumn {
                                     option.get(VALUE).unwrap().clone(), 
                                     option.get(KEY).unwrap().clone()
                                 );
+                                if SelectColumn::is_option_archived(option) {
+                                    archived_ids.push(option.get(KEY).unwrap().clone());
+                                }
                             }
                         }
                     }
@@ -261,9 +467,106 @@ impl SelectColumn {
             }
             column_obj.options_id_map = Some(options_id_map);
             column_obj.options_name_map = Some(options_name_map);
+            column_obj.archived_ids = Some(archived_ids);
         }
         return column_obj;
     }
+    pub fn is_option_archived(option: &BTreeMap<String, String>) -> bool {
+        let archived = option.get(SELECT_OPTION_ARCHIVED);
+        if archived.is_some() {
+            let archived = archived.unwrap().to_lowercase();
+            return archived == TRUE || archived == "1"
+        }
+        return false
+    }
+    pub fn get_option_order(option: &BTreeMap<String, String>, default: usize) -> usize {
+        let order = option.get(SELECT_OPTION_ORDER);
+        if order.is_some() {
+            let order: Result<usize, _> = FromStr::from_str(order.unwrap().as_str());
+            if order.is_ok() {
+                return order.unwrap()
+            }
+        }
+        return default
+    }
+    // Options sorted by configured order, options without order (created before ordering) keep list position
+    pub fn sort_options(options: &Vec<BTreeMap<String, String>>) -> Vec<BTreeMap<String, String>> {
+        let mut options_sorted: Vec<(usize, BTreeMap<String, String>)> = Vec::new();
+        let mut position: usize = 0;
+        for option in options {
+            let order = SelectColumn::get_option_order(option, position);
+            options_sorted.push((order, option.clone()));
+            position += 1;
+        }
+        options_sorted.sort_by_key(|item| item.0);
+        let mut options: Vec<BTreeMap<String, String>> = Vec::new();
+        for (_, option) in options_sorted {
+            options.push(option);
+        }
+        return options
+    }
+    // Sort position for option id among all select columns in folder. Many select values sort by first option.
+    pub fn get_sort_order(folder: &DbData, value: &String) -> Option<usize> {
+        let option_id = value.split(",").next().unwrap_or_default().trim().to_string();
+        let data = folder.data.clone();
+        if data.is_none() {
+            return None
+        }
+        let data = data.unwrap();
+        for (key, options) in &data {
+            if !key.ends_with(&format!("__{}", SELECT_OPTIONS)) {
+                continue
+            }
+            let mut position: usize = 0;
+            for option in options {
+                let key = option.get(KEY);
+                if key.is_some() && *key.unwrap() == option_id {
+                    return Some(SelectColumn::get_option_order(option, position))
+                }
+                position += 1;
+            }
+        }
+        return None
+    }
+    // Keep ids from existing options by name, so items keep pointing to their options. Existing options 
+    // not found in new options are kept as archived.
+    pub fn merge_options(
+        options: &Vec<BTreeMap<String, String>>,
+        options_existing: Option<&Vec<BTreeMap<String, String>>>,
+    ) -> Vec<BTreeMap<String, String>> {
+        let mut options = options.clone();
+        if options_existing.is_none() {
+            return options
+        }
+        let options_existing = options_existing.unwrap();
+        let mut order = options.len();
+        for option_existing in options_existing {
+            let value_existing = option_existing.get(VALUE).unwrap().clone();
+            let mut found = false;
+            for option in options.iter_mut() {
+                let value = option.get(VALUE).unwrap().clone();
+                if value.to_lowercase() == value_existing.to_lowercase() {
+                    option.insert(KEY.to_string(), option_existing.get(KEY).unwrap().clone());
+                    if option.get(SELECT_OPTION_COLOR).is_none() && option_existing.get(SELECT_OPTION_COLOR).is_some() {
+                        option.insert(
+                            SELECT_OPTION_COLOR.to_string(), 
+                            option_existing.get(SELECT_OPTION_COLOR).unwrap().clone()
+                        );
+                    }
+                    found = true;
+                    break
+                }
+            }
+            if !found {
+                let mut option_archived = option_existing.clone();
+                option_archived.insert(SELECT_OPTION_ARCHIVED.to_string(), TRUE.to_string());
+                option_archived.insert(SELECT_OPTION_ORDER.to_string(), order.to_string());
+                options.push(option_archived);
+                order += 1;
+            }
+        }
+        return options
+    }
 }
 impl StorageColumn for SelectColumn {
     fn create_config(
@@ -337,6 +640,22 @@ impl StorageColumn for SelectColumn {
                 let options = column_config.options.unwrap();
                 let options_name_map = &self.options_name_map.clone().unwrap();
                 let options_id_map = &self.options_id_map.clone().unwrap();
+                let archived_ids = self.archived_ids.clone().unwrap_or_default();
+                for item_select_id in &id_list {
+                    if archived_ids.contains(&item_select_id.to_string()) {
+                        let error = PlanetError::new(
+                            500, 
+                            Some(tr!(
+                                "Option {}{}{} is archived for field {}{}{}", 
+                                String::from("\"").blue(), item_select_id, String::from("\"").blue(),
+                                String::from("\"").blue(), &name.blue(), String::from("\"").blue(),
+                            )),
+                        );
+                        let mut errors: Vec<PlanetError> = Vec::new();
+                        errors.push(error);
+                        return Err(errors);
+                    }
+                }
                 let mut verified = false;
                 if *&id_list.len() == 1 {
                     for select_option in options {
@@ -771,9 +1090,35 @@ impl TextColumn {
                 // COLUMN_TYPE_EMAIL => {
                 //     values_wrap = self.do_text_basic(data_map, &column_id);
                 // },
-                // COLUMN_TYPE_FILE => {
-                //     values_wrap = self.do_text_basic(data_map, &column_id);
-                // },
+                COLUMN_TYPE_FILE => {
+                    // Text extracted from documents, file ids are not words
+                    let key = format!("{}__text", column_id);
+                    values_wrap = self.do_text_basic(data_map, &key);
+                },
+                COLUMN_TYPE_SECRET => {
+                    // Never indexed
+                    continue
+                },
+                COLUMN_TYPE_RICH_TEXT => {
+                    // Plain text, markdown syntax would pollute stems
+                    let values = self.do_text_basic(data_map, &column_id);
+                    if values.is_some() {
+                        let values: Vec<String> = values.unwrap().iter().map(
+                            |value| RichTextColumn::get_plain_text(value)
+                        ).collect();
+                        values_wrap = Some(values);
+                    }
+                },
+                COLUMN_TYPE_TAGS => {
+                    // Tag segments as words, project/alpha => project alpha
+                    let values = self.do_text_basic(data_map, &column_id);
+                    if values.is_some() {
+                        let values: Vec<String> = values.unwrap().iter().map(
+                            |value| value.replace(TAG_SEPARATOR, " ")
+                        ).collect();
+                        values_wrap = Some(values);
+                    }
+                },
                 _ => {
                     values_wrap = self.do_text_basic(data_map, &column_id);
                 },
@@ -787,20 +1132,7 @@ impl TextColumn {
                         if value == String::from("") {
                             continue
                         }
-                        // Do ascii folding
-                        value = fold_to_ascii(value.as_str());
-                        // Through regex, parse only words and numbers, remove duplicate whitespace
-                        let expr = &RE_TEXT;
-                        let mut value_new = String::from("");
-                        let words = expr.captures_iter(value.as_str());
-                        for word in words {
-                            let word = word.get(0).unwrap().as_str();
-                            value_new = format!("{} {}", value_new, word);
-                        }
-                        let value_new = value_new.trim().to_string();
-                        value = value_new;
-                        // Convert to lower
-                        value = value.to_lowercase();
+                        value = self.get_index_text(&value);
                         column_text = format!("{} {}", &column_text, &value).trim().to_string();
                         text = format!("{} {}", &text, &value).trim().to_string();
                     }
@@ -810,11 +1142,46 @@ impl TextColumn {
                 continue
             }
         }
+        // Translations go apart as {column_id}@{language}, indexed with the stemmer for its language
+        let translations = data_map.get(TRANSLATIONS);
+        if translations.is_some() {
+            let translations = translations.unwrap();
+            for translation in translations {
+                let column_id = translation.get(COLUMN_ID);
+                let language = translation.get(LANGUAGE);
+                let value = translation.get(VALUE);
+                if column_id.is_none() || language.is_none() || value.is_none() {
+                    continue
+                }
+                let key = format!(
+                    "{}{}{}", column_id.unwrap(), TRANSLATION_SEPARATOR, language.unwrap()
+                );
+                let value = self.get_index_text(value.unwrap());
+                if value != String::from("") {
+                    map.insert(key, value);
+                }
+            }
+        }
         // add text to map
         // let text = text.trim().to_string();
         map.insert(text_column_id, text);
         return Ok(map.clone())
     }
+    fn get_index_text(&self, value: &String) -> String {
+        // Do ascii folding
+        let value = fold_to_ascii(value.as_str());
+        // Through regex, parse only words and numbers, remove duplicate whitespace
+        let expr = &RE_TEXT;
+        let mut value_new = String::from("");
+        let words = expr.captures_iter(value.as_str());
+        for word in words {
+            let word = word.get(0).unwrap().as_str();
+            value_new = format!("{} {}", value_new, word);
+        }
+        let value_new = value_new.trim().to_string();
+        // Convert to lower
+        return value_new.to_lowercase()
+    }
     pub fn get_yaml_out(&self, yaml_string: &String, value: &String) -> String {
         let column_config = self.config.clone();
         let column_name = column_config.name.unwrap();
@@ -830,6 +1197,262 @@ impl TextColumn {
     }
 }
 
+pub const SECRET_MASK: &str = "********";
+pub const SECRET_KEY_ROUNDS: u32 = 100_000;
+pub const SECRET_SALT_LENGTH: usize = 16;
+pub const SECRET_CHECK_VALUE: &str = "achiever-secret-check";
+
+// Secret values are encrypted with a key held by the user on top of the folder encryption. Key is derived 
+// from the user key with PBKDF2 and the column salt, values stored as "{nonce}:{ciphertext}" in hex. Key 
+// derivation is slow on purpose, so cipher is created once per column object and reused for all values. 
+// Column keeps a known value encrypted with the key it was created with, values are only written with a 
+// key that can decrypt it.
+#[derive(Clone)]
+pub struct SecretColumn {
+    pub config: ColumnConfig,
+    pub secret_key: Option<String>,
+    cipher: Option<ChaCha20Poly1305>,
+}
+impl SecretColumn {
+    pub fn defaults(column_config: &ColumnConfig, secret_key: &Option<String>) -> Self {
+        let column_config = column_config.clone();
+        let cipher = SecretColumn::get_cipher(&column_config, secret_key);
+        let column_obj = Self{
+            config: column_config,
+            secret_key: secret_key.clone(),
+            cipher: cipher,
+        };
+        return column_obj
+    }
+    pub fn generate_salt() -> String {
+        let mut salt = [0u8; SECRET_SALT_LENGTH];
+        OsRng.fill_bytes(&mut salt);
+        return encode_hex(&salt)
+    }
+    fn get_cipher(config: &ColumnConfig, secret_key: &Option<String>) -> Option<ChaCha20Poly1305> {
+        let secret_key = secret_key.clone();
+        if secret_key.is_none() {
+            return None
+        }
+        let secret_key = secret_key.unwrap();
+        if secret_key == String::from("") {
+            return None
+        }
+        let salt = config.secret_salt.clone();
+        if salt.is_none() {
+            return None
+        }
+        let salt = decode_hex(salt.unwrap().as_str());
+        if salt.is_none() {
+            return None
+        }
+        let salt = salt.unwrap();
+        let mut key = [0u8; 32];
+        pbkdf2_hmac::<Sha256>(secret_key.as_bytes(), salt.as_slice(), SECRET_KEY_ROUNDS, &mut key);
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(&key));
+        return Some(cipher)
+    }
+    pub fn encrypt(&self, value: &String) -> Result<String, PlanetError> {
+        let column_name = self.config.name.clone().unwrap_or_default();
+        let cipher = self.cipher.as_ref();
+        if cipher.is_none() {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Secret key is needed in context for column \"{}\".", &column_name)),
+                )
+            )
+        }
+        let cipher = cipher.unwrap();
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = cipher.encrypt(&nonce, value.as_bytes());
+        if ciphertext.is_err() {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Could not encrypt value for column \"{}\".", &column_name)),
+                )
+            )
+        }
+        let ciphertext = ciphertext.unwrap();
+        let value = format!("{}:{}", encode_hex(nonce.as_slice()), encode_hex(ciphertext.as_slice()));
+        return Ok(value)
+    }
+    pub fn check_key(&self) -> Result<(), PlanetError> {
+        let column_name = self.config.name.clone().unwrap_or_default();
+        let check = self.config.secret_check.clone();
+        if check.is_none() || self.cipher.is_none() {
+            return Ok(())
+        }
+        let check = self.decrypt(&check.unwrap());
+        if check.is_none() || check.unwrap() != SECRET_CHECK_VALUE.to_string() {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Secret key in context is not the key for column \"{}\".", &column_name)),
+                )
+            )
+        }
+        return Ok(())
+    }
+    pub fn decrypt(&self, value: &String) -> Option<String> {
+        let cipher = self.cipher.as_ref();
+        if cipher.is_none() {
+            return None
+        }
+        let cipher = cipher.unwrap();
+        let pieces: Vec<&str> = value.split(":").collect();
+        if pieces.len() != 2 {
+            return None
+        }
+        let nonce = decode_hex(pieces[0]);
+        let ciphertext = decode_hex(pieces[1]);
+        if nonce.is_none() || ciphertext.is_none() {
+            return None
+        }
+        let nonce = nonce.unwrap();
+        if nonce.len() != 12 {
+            return None
+        }
+        let ciphertext = ciphertext.unwrap();
+        // Wrong key fails authentication
+        let plaintext = cipher.decrypt(Nonce::from_slice(nonce.as_slice()), ciphertext.as_slice());
+        if plaintext.is_err() {
+            return None
+        }
+        let plaintext = String::from_utf8(plaintext.unwrap());
+        if plaintext.is_err() {
+            return None
+        }
+        return Some(plaintext.unwrap())
+    }
+    pub fn get_display_value(&self, value: &String) -> String {
+        let plaintext = self.decrypt(value);
+        if plaintext.is_some() {
+            return plaintext.unwrap()
+        }
+        return SECRET_MASK.to_string()
+    }
+}
+impl StorageColumn for SecretColumn {
+    fn create_config(
+        &mut self, 
+        column_config_map: &BTreeMap<String, String>,
+    ) -> Result<BTreeMap<String, String>, PlanetError> {
+        let mut column_config_map = column_config_map.clone();
+        // Salt is kept when column is modified, otherwise values already stored could not be decrypted
+        let salt = self.config.secret_salt.clone().unwrap_or(SecretColumn::generate_salt());
+        column_config_map.insert(SECRET_SALT.to_string(), salt.clone());
+        let mut check = self.config.secret_check.clone();
+        if check.is_none() && self.config.secret_salt.is_none() {
+            let column_name = self.config.name.clone().unwrap_or_default();
+            let mut config = self.config.clone();
+            config.secret_salt = Some(salt);
+            let obj = SecretColumn::defaults(&config, &self.secret_key);
+            let value = obj.encrypt(&SECRET_CHECK_VALUE.to_string());
+            if value.is_err() {
+                return Err(
+                    PlanetError::new(
+                        500, 
+                        Some(tr!("Secret key is needed in context to create column \"{}\".", &column_name)),
+                    )
+                )
+            }
+            check = Some(value.unwrap());
+        }
+        if check.is_some() {
+            column_config_map.insert(SECRET_CHECK.to_string(), check.unwrap());
+        }
+        return Ok(column_config_map)
+    }
+    fn get_config(
+        &mut self, 
+        column_config_map: &BTreeMap<String, String>,
+    ) -> Result<ColumnConfig, PlanetError> {
+        let mut config = self.config.clone();
+        let salt = column_config_map.get(SECRET_SALT);
+        if salt.is_some() {
+            let salt = salt.unwrap().clone();
+            config.secret_salt = Some(salt);
+        }
+        config.secret_check = column_config_map.get(SECRET_CHECK).cloned();
+        return Ok(config)
+    }
+    fn validate(&self, data: &Vec<String>) -> Result<Vec<String>, Vec<PlanetError>> {
+        let data = data.clone();
+        let config = self.config.clone();
+        let column_name = config.name.clone().unwrap_or_default();
+        let required = config.required.unwrap_or_default();
+        let mut data_new: Vec<String> = Vec::new();
+        if data.iter().any(|data_item| *data_item != String::from("")) {
+            let result = self.check_key();
+            if result.is_err() {
+                return Err(vec![result.unwrap_err()])
+            }
+        }
+        for data_item in data {
+            if data_item == String::from("") {
+                if required {
+                    let error = PlanetError::new(
+                        500, 
+                        Some(tr!(
+                            "Field {}{}{} is required", 
+                            String::from("\"").blue(), &column_name.blue(), String::from("\"").blue()
+                        )),
+                    );
+                    return Err(vec![error])
+                }
+                continue
+            }
+            let value = self.encrypt(&data_item);
+            if value.is_err() {
+                return Err(vec![value.unwrap_err()])
+            }
+            data_new.push(value.unwrap());
+        }
+        return Ok(data_new)
+    }
+    fn get_yaml_out(&self, yaml_string: &String, value: &String) -> String {
+        let column_config = self.config.clone();
+        let column_name = column_config.name.unwrap();
+        let mut yaml_string = yaml_string.clone();
+        let field = &column_name.truecolor(
+            YAML_COLOR_BLUE[0], YAML_COLOR_BLUE[1], YAML_COLOR_BLUE[2]
+        );
+        let value = self.get_display_value(value);
+        let value = format!("{}", 
+            value.truecolor(YAML_COLOR_ORANGE[0], YAML_COLOR_ORANGE[1], YAML_COLOR_ORANGE[2]), 
+        );
+        yaml_string.push_str(format!("  {field}: {value}\n", field=field, value=value).as_str());
+        return yaml_string;
+    }
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    let mut hex = String::from("");
+    for byte in bytes {
+        hex.push_str(format!("{:02x}", byte).as_str());
+    }
+    return hex
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return None
+    }
+    let mut bytes: Vec<u8> = Vec::new();
+    let mut index = 0;
+    while index < hex.len() {
+        let byte = u8::from_str_radix(&hex[index..index+2], 16);
+        if byte.is_err() {
+            return None
+        }
+        bytes.push(byte.unwrap());
+        index += 2;
+    }
+    return Some(bytes)
+}
+
 #[derive(Debug, Clone)]
 pub struct GenerateIdColumn {
     pub config: ColumnConfig,
@@ -897,6 +1520,116 @@ impl StorageColumn for GenerateIdColumn {
     }
 }
 
+// Calling code, trunk prefix, national number lengths and display groups for major countries
+#[derive(Debug, Clone)]
+pub struct PhoneCountry {
+    pub country: &'static str,
+    pub calling_code: &'static str,
+    pub trunk_prefix: &'static str,
+    pub min_length: usize,
+    pub max_length: usize,
+    pub groups: &'static [usize],
+}
+
+pub const PHONE_COUNTRIES: &[PhoneCountry] = &[
+    PhoneCountry{country: "US", calling_code: "1", trunk_prefix: "", min_length: 10, max_length: 10, groups: &[3, 3, 4]},
+    PhoneCountry{country: "CA", calling_code: "1", trunk_prefix: "", min_length: 10, max_length: 10, groups: &[3, 3, 4]},
+    PhoneCountry{country: "MX", calling_code: "52", trunk_prefix: "", min_length: 10, max_length: 10, groups: &[2, 4, 4]},
+    PhoneCountry{country: "BR", calling_code: "55", trunk_prefix: "0", min_length: 10, max_length: 11, groups: &[2, 5, 4]},
+    PhoneCountry{country: "AR", calling_code: "54", trunk_prefix: "0", min_length: 10, max_length: 11, groups: &[2, 4, 4]},
+    PhoneCountry{country: "CL", calling_code: "56", trunk_prefix: "", min_length: 9, max_length: 9, groups: &[1, 4, 4]},
+    PhoneCountry{country: "CO", calling_code: "57", trunk_prefix: "", min_length: 10, max_length: 10, groups: &[3, 3, 4]},
+    PhoneCountry{country: "PE", calling_code: "51", trunk_prefix: "0", min_length: 8, max_length: 9, groups: &[3, 3, 3]},
+    PhoneCountry{country: "GB", calling_code: "44", trunk_prefix: "0", min_length: 9, max_length: 10, groups: &[2, 4, 4]},
+    PhoneCountry{country: "IE", calling_code: "353", trunk_prefix: "0", min_length: 7, max_length: 9, groups: &[2, 3, 4]},
+    PhoneCountry{country: "ES", calling_code: "34", trunk_prefix: "", min_length: 9, max_length: 9, groups: &[3, 2, 2, 2]},
+    PhoneCountry{country: "PT", calling_code: "351", trunk_prefix: "", min_length: 9, max_length: 9, groups: &[3, 3, 3]},
+    PhoneCountry{country: "FR", calling_code: "33", trunk_prefix: "0", min_length: 9, max_length: 9, groups: &[1, 2, 2, 2, 2]},
+    PhoneCountry{country: "DE", calling_code: "49", trunk_prefix: "0", min_length: 6, max_length: 11, groups: &[]},
+    PhoneCountry{country: "IT", calling_code: "39", trunk_prefix: "", min_length: 6, max_length: 11, groups: &[]},
+    PhoneCountry{country: "NL", calling_code: "31", trunk_prefix: "0", min_length: 9, max_length: 9, groups: &[1, 8]},
+    PhoneCountry{country: "BE", calling_code: "32", trunk_prefix: "0", min_length: 8, max_length: 9, groups: &[]},
+    PhoneCountry{country: "CH", calling_code: "41", trunk_prefix: "0", min_length: 9, max_length: 9, groups: &[2, 3, 2, 2]},
+    PhoneCountry{country: "AT", calling_code: "43", trunk_prefix: "0", min_length: 4, max_length: 13, groups: &[]},
+    PhoneCountry{country: "SE", calling_code: "46", trunk_prefix: "0", min_length: 7, max_length: 9, groups: &[]},
+    PhoneCountry{country: "NO", calling_code: "47", trunk_prefix: "", min_length: 8, max_length: 8, groups: &[3, 2, 3]},
+    PhoneCountry{country: "DK", calling_code: "45", trunk_prefix: "", min_length: 8, max_length: 8, groups: &[2, 2, 2, 2]},
+    PhoneCountry{country: "FI", calling_code: "358", trunk_prefix: "0", min_length: 5, max_length: 10, groups: &[]},
+    PhoneCountry{country: "PL", calling_code: "48", trunk_prefix: "", min_length: 9, max_length: 9, groups: &[3, 3, 3]},
+    PhoneCountry{country: "RU", calling_code: "7", trunk_prefix: "8", min_length: 10, max_length: 10, groups: &[3, 3, 2, 2]},
+    PhoneCountry{country: "TR", calling_code: "90", trunk_prefix: "0", min_length: 10, max_length: 10, groups: &[3, 3, 2, 2]},
+    PhoneCountry{country: "IN", calling_code: "91", trunk_prefix: "0", min_length: 10, max_length: 10, groups: &[5, 5]},
+    PhoneCountry{country: "CN", calling_code: "86", trunk_prefix: "0", min_length: 10, max_length: 11, groups: &[3, 4, 4]},
+    PhoneCountry{country: "JP", calling_code: "81", trunk_prefix: "0", min_length: 9, max_length: 10, groups: &[]},
+    PhoneCountry{country: "KR", calling_code: "82", trunk_prefix: "0", min_length: 8, max_length: 10, groups: &[]},
+    PhoneCountry{country: "AU", calling_code: "61", trunk_prefix: "0", min_length: 9, max_length: 9, groups: &[1, 4, 4]},
+    PhoneCountry{country: "NZ", calling_code: "64", trunk_prefix: "0", min_length: 8, max_length: 10, groups: &[]},
+    PhoneCountry{country: "ZA", calling_code: "27", trunk_prefix: "0", min_length: 9, max_length: 9, groups: &[2, 3, 4]},
+];
+
+impl PhoneCountry {
+    pub fn get_by_country(country: &String) -> Option<PhoneCountry> {
+        let country = country.trim().to_uppercase();
+        for phone_country in PHONE_COUNTRIES {
+            if phone_country.country == country.as_str() {
+                return Some(phone_country.clone())
+            }
+        }
+        return None
+    }
+    pub fn get_by_number(digits: &String) -> Option<PhoneCountry> {
+        // digits after the plus sign, first country sharing calling code wins (US for +1)
+        for phone_country in PHONE_COUNTRIES {
+            if digits.starts_with(phone_country.calling_code) {
+                return Some(phone_country.clone())
+            }
+        }
+        return None
+    }
+    fn has_length(&self, national: &String) -> bool {
+        let length = national.len();
+        return length >= self.min_length && length <= self.max_length
+    }
+    fn get_national(&self, digits: &String) -> String {
+        // 020 ... or +44 (0)20 ..., trunk prefix removed when number without it is still valid
+        let national = digits.clone();
+        if self.trunk_prefix != "" && national.starts_with(self.trunk_prefix) {
+            let national_trunk = national[self.trunk_prefix.len()..].to_string();
+            if self.has_length(&national_trunk) {
+                return national_trunk
+            }
+        }
+        return national
+    }
+    pub fn get_groups(&self, national: &String) -> Vec<String> {
+        let mut sizes: Vec<usize> = self.groups.to_vec();
+        let total: usize = sizes.iter().sum();
+        if total != national.len() {
+            // Groups of three, last group takes the remaining digits
+            sizes = Vec::new();
+            let mut remaining = national.len();
+            while remaining > 4 {
+                sizes.push(3);
+                remaining -= 3;
+            }
+            sizes.push(remaining);
+        }
+        let mut groups: Vec<String> = Vec::new();
+        let mut position: usize = 0;
+        for size in sizes {
+            if size == 0 {
+                continue
+            }
+            groups.push(national[position..position+size].to_string());
+            position += size;
+        }
+        return groups
+    }
+}
+
+pub const PHONE_MIN_DIGITS: usize = 6;
+pub const PHONE_MAX_DIGITS: usize = 15;
+
 #[derive(Debug, Clone)]
 pub struct PhoneColumn {
     pub config: ColumnConfig,
@@ -911,20 +1644,159 @@ impl PhoneColumn {
         };
         return field_obj
     }
+    pub fn normalize(value: &String, default_country: &Option<String>) -> Result<String, PlanetError> {
+        // National or international number into E.164: +34600112233
+        let mut digits = String::from("");
+        let mut is_international = false;
+        for character in value.trim().chars() {
+            if character == '+' && digits.len() == 0 && !is_international {
+                is_international = true;
+            } else if character.is_ascii_digit() {
+                digits.push(character);
+            } else if !" -.()/".contains(character) {
+                return Err(
+                    PlanetError::new(
+                        500, 
+                        Some(tr!("Phone number \"{}\" has invalid characters.", value)),
+                    )
+                )
+            }
+        }
+        if !is_international && digits.starts_with("00") {
+            is_international = true;
+            digits = digits[2..].to_string();
+        }
+        if is_international {
+            let phone_country = PhoneCountry::get_by_number(&digits);
+            if phone_country.is_some() {
+                let phone_country = phone_country.unwrap();
+                let national = digits[phone_country.calling_code.len()..].to_string();
+                let national = phone_country.get_national(&national);
+                if phone_country.has_length(&national) {
+                    return Ok(format!("+{}{}", phone_country.calling_code, national))
+                }
+            } else if digits.len() >= 8 && digits.len() <= PHONE_MAX_DIGITS {
+                return Ok(format!("+{}", digits))
+            }
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Phone number \"{}\" is not valid.", value)),
+                )
+            )
+        }
+        if default_country.is_none() {
+            // Without default country, national numbers are kept as digits like before normalization
+            if digits.len() >= PHONE_MIN_DIGITS && digits.len() <= PHONE_MAX_DIGITS {
+                return Ok(digits)
+            }
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Phone number \"{}\" is not valid.", value)),
+                )
+            )
+        }
+        let default_country = default_country.clone().unwrap();
+        let phone_country = PhoneCountry::get_by_country(&default_country);
+        if phone_country.is_none() {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Country \"{}\" not supported for phone numbers.", &default_country)),
+                )
+            )
+        }
+        let phone_country = phone_country.unwrap();
+        let mut national = digits.clone();
+        // 34600112233, calling code without plus sign
+        if national.len() > phone_country.max_length && national.starts_with(phone_country.calling_code) {
+            national = national[phone_country.calling_code.len()..].to_string();
+        }
+        let national = phone_country.get_national(&national);
+        if !phone_country.has_length(&national) {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Phone number \"{}\" is not valid for country \"{}\".", value, &default_country)),
+                )
+            )
+        }
+        return Ok(format!("+{}{}", phone_country.calling_code, national))
+    }
+    pub fn get_display_value(&self, value: &String) -> String {
+        let phone_format = self.config.phone_format.clone().unwrap_or(PHONE_FORMAT_E164.to_string());
+        if phone_format.as_str() == PHONE_FORMAT_E164 || !value.starts_with("+") {
+            return value.clone()
+        }
+        let digits = value[1..].to_string();
+        let phone_country = PhoneCountry::get_by_number(&digits);
+        if phone_country.is_none() {
+            return value.clone()
+        }
+        let phone_country = phone_country.unwrap();
+        let national = digits[phone_country.calling_code.len()..].to_string();
+        let groups = phone_country.get_groups(&national).join(" ");
+        if phone_format.as_str() == PHONE_FORMAT_NATIONAL {
+            return format!("{}{}", phone_country.trunk_prefix, groups)
+        }
+        return format!("+{} {}", phone_country.calling_code, groups)
+    }
 }
 impl StorageColumn for PhoneColumn {
     fn create_config(
         &mut self, 
         column_config_map: &BTreeMap<String, String>,
     ) -> Result<BTreeMap<String, String>, PlanetError> {
-        let column_config_map = column_config_map.clone();
+        let mut column_config_map = column_config_map.clone();
+        let config = self.config.clone();
+        let default_country = config.default_country;
+        if default_country.is_some() {
+            let default_country = default_country.unwrap();
+            let phone_country = PhoneCountry::get_by_country(&default_country);
+            if phone_country.is_none() {
+                return Err(
+                    PlanetError::new(
+                        500, 
+                        Some(tr!("Country \"{}\" not supported for phone numbers.", &default_country)),
+                    )
+                )
+            }
+            let phone_country = phone_country.unwrap();
+            column_config_map.insert(DEFAULT_COUNTRY.to_string(), phone_country.country.to_string());
+        }
+        let phone_format = config.phone_format.unwrap_or(PHONE_FORMAT_E164.to_string());
+        let phone_format = phone_format.as_str();
+        match phone_format {
+            PHONE_FORMAT_E164 | PHONE_FORMAT_INTERNATIONAL | PHONE_FORMAT_NATIONAL => {
+                column_config_map.insert(PHONE_FORMAT.to_string(), phone_format.to_string());
+            },
+            _ => {
+                return Err(
+                    PlanetError::new(
+                        500, 
+                        Some(tr!("Phone format must be one of E164, International or National, found \"{}\".", phone_format)),
+                    )
+                )
+            }
+        }
         return Ok(column_config_map)
     }
     fn get_config(
         &mut self, 
-        _: &BTreeMap<String, String>,
+        column_config_map: &BTreeMap<String, String>,
     ) -> Result<ColumnConfig, PlanetError> {
-        let config = self.config.clone();
+        let mut config = self.config.clone();
+        let default_country = column_config_map.get(DEFAULT_COUNTRY);
+        if default_country.is_some() {
+            let default_country = default_country.unwrap().clone();
+            config.default_country = Some(default_country);
+        }
+        let phone_format = column_config_map.get(PHONE_FORMAT);
+        if phone_format.is_some() {
+            let phone_format = phone_format.unwrap().clone();
+            config.phone_format = Some(phone_format);
+        }
         return Ok(config)
     }
     fn validate(
@@ -943,18 +1815,19 @@ impl StorageColumn for PhoneColumn {
         let column_name = config.name.unwrap_or_default();
         let mut data_new: Vec<String> = Vec::new();
         for data_item in data {
-            let expr = &RE_PHONE;
-            let is_found = expr.is_match(&data_item);
-            if is_found == true {
-                data_new.push(data_item);
+            let result = PhoneColumn::normalize(&data_item, &config.default_country);
+            if result.is_ok() {
+                data_new.push(result.unwrap());
             } else {
-                let error = PlanetError::new(
-                    500, 
-                    Some(tr!("Error validating phone column \"{}\" with value \"{}\".", 
-                    &column_name, &data_item))
-                );
                 let mut errors: Vec<PlanetError> = Vec::new();
-                errors.push(error);
+                errors.push(result.unwrap_err());
+                errors.push(
+                    PlanetError::new(
+                        500, 
+                        Some(tr!("Error validating phone column \"{}\" with value \"{}\".", 
+                        &column_name, &data_item))
+                    )
+                );
                 return Err(errors);
             }    
         }
@@ -967,6 +1840,7 @@ impl StorageColumn for PhoneColumn {
         let field = &column_name.truecolor(
             YAML_COLOR_BLUE[0], YAML_COLOR_BLUE[1], YAML_COLOR_BLUE[2]
         );
+        let value = self.get_display_value(value);
         let value = format!("{}", 
             value.truecolor(YAML_COLOR_ORANGE[0], YAML_COLOR_ORANGE[1], YAML_COLOR_ORANGE[2]), 
         );
@@ -989,6 +1863,41 @@ impl EmailColumn {
         };
         return field_obj
     }
+    pub fn normalize(value: &String) -> Result<String, PlanetError> {
+        // Local part as written, domain lower-cased and IDNs in punycode: jo@Bücher.de => jo@xn--bcher-kva.de
+        let value = value.trim();
+        let position = value.rfind("@");
+        if position.is_none() {
+            return Err(
+                PlanetError::new(500, Some(tr!("Email \"{}\" is not valid.", value)))
+            )
+        }
+        let position = position.unwrap();
+        let local = &value[..position];
+        let domain = value[position+1..].trim_end_matches(".");
+        let domain = idna::domain_to_ascii(domain);
+        if domain.is_err() {
+            return Err(
+                PlanetError::new(500, Some(tr!("Email \"{}\" has an invalid domain.", value)))
+            )
+        }
+        let domain = domain.unwrap();
+        let email_check = format!("{}@{}", local.to_lowercase(), &domain);
+        if !RE_EMAIL.is_match(&email_check) {
+            return Err(
+                PlanetError::new(500, Some(tr!("Email \"{}\" is not valid.", value)))
+            )
+        }
+        return Ok(format!("{}@{}", local, &domain))
+    }
+    pub fn get_domain(value: &String) -> String {
+        let position = value.rfind("@");
+        if position.is_none() {
+            return String::from("")
+        }
+        let position = position.unwrap();
+        return value[position+1..].trim().to_lowercase()
+    }
 }
 impl StorageColumn for EmailColumn {
     fn create_config(
@@ -1021,11 +1930,9 @@ impl StorageColumn for EmailColumn {
         let column_name = config.name.unwrap_or_default();
         let mut data_new: Vec<String> = Vec::new();
         for data_item in data {
-            let expr = &RE_EMAIL;
-            let is_found = expr.is_match(&data_item);
-            if is_found == true {
-                // return Ok(value.clone())
-                data_new.push(data_item);
+            let result = EmailColumn::normalize(&data_item);
+            if result.is_ok() {
+                data_new.push(result.unwrap());
             } else {
                 let error = PlanetError::new(
                     500, 
@@ -1068,20 +1975,80 @@ impl UrlColumn {
         };
         return field_obj
     }
+    pub fn parse(value: &String) -> Result<Url, PlanetError> {
+        // Web and file transfer schemes with a host, https when written without scheme: www.example.com/pricing
+        let mut value = value.trim().to_string();
+        if value.find("://").is_none() {
+            value = format!("https://{}", &value);
+        }
+        let url = Url::parse(&value);
+        if url.is_err() {
+            return Err(
+                PlanetError::new(500, Some(tr!("Url \"{}\" is not valid.", &value)))
+            )
+        }
+        let url = url.unwrap();
+        if !URL_ALLOWED_SCHEMES.contains(&url.scheme()) {
+            return Err(
+                PlanetError::new(500, Some(tr!("Url \"{}\" has a scheme not allowed.", &value)))
+            )
+        }
+        if url.host_str().is_none() {
+            return Err(
+                PlanetError::new(500, Some(tr!("Url \"{}\" has no host.", &value)))
+            )
+        }
+        return Ok(url)
+    }
+    pub fn is_tracking_param(name: &str) -> bool {
+        let name = name.to_lowercase();
+        return name.starts_with("utm_") || URL_TRACKING_PARAMS.contains(&name.as_str())
+    }
+    pub fn normalize(value: &String, strip_tracking: bool) -> Result<String, PlanetError> {
+        // Scheme and host lower-cased, IDN hosts in punycode
+        let mut url = UrlColumn::parse(value)?;
+        if strip_tracking && url.query().is_some() {
+            let pairs: Vec<(String, String)> = url.query_pairs().map(
+                |(name, value)| (name.into_owned(), value.into_owned())
+            ).collect();
+            let pairs_kept: Vec<(String, String)> = pairs.iter().filter(
+                |(name, _)| !UrlColumn::is_tracking_param(name)
+            ).cloned().collect();
+            if pairs_kept.len() == 0 {
+                url.set_query(None);
+            } else if pairs_kept.len() != pairs.len() {
+                url.query_pairs_mut().clear().extend_pairs(pairs_kept);
+            }
+        }
+        return Ok(url.to_string())
+    }
 }
+
+pub const URL_ALLOWED_SCHEMES: [&str; 4] = ["http", "https", "ftp", "ftps"];
+pub const URL_TRACKING_PARAMS: [&str; 15] = [
+    "fbclid", "gclid", "dclid", "gbraid", "wbraid", "msclkid", "mc_cid", "mc_eid", "yclid", "igshid", 
+    "_ga", "_gl", "_hsenc", "_hsmi", "mkt_tok",
+];
 impl StorageColumn for UrlColumn {
     fn create_config(
         &mut self, 
         column_config_map: &BTreeMap<String, String>,
     ) -> Result<BTreeMap<String, String>, PlanetError> {
-        let column_config_map = column_config_map.clone();
+        let mut column_config_map = column_config_map.clone();
+        let strip_tracking = self.config.strip_tracking.unwrap_or_default();
+        column_config_map.insert(STRIP_TRACKING.to_string(), strip_tracking.to_string());
         return Ok(column_config_map)
     }
     fn get_config(
         &mut self, 
-        _: &BTreeMap<String, String>,
+        column_config_map: &BTreeMap<String, String>,
     ) -> Result<ColumnConfig, PlanetError> {
-        let config = self.config.clone();
+        let mut config = self.config.clone();
+        let strip_tracking = column_config_map.get(STRIP_TRACKING);
+        if strip_tracking.is_some() {
+            let strip_tracking = strip_tracking.unwrap().clone();
+            config.strip_tracking = Some(strip_tracking == String::from("true"));
+        }
         return Ok(config)
     }
     fn validate(
@@ -1099,11 +2066,11 @@ impl StorageColumn for UrlColumn {
         }
         let mut data_new: Vec<String> = Vec::new();
         let column_name = config.name.unwrap_or_default();
+        let strip_tracking = config.strip_tracking.unwrap_or_default();
         for data_item in data {
-            let expr = &RE_URL;
-            let is_found = expr.is_match(&data_item);
-            if is_found == true {
-                data_new.push(data_item);
+            let result = UrlColumn::normalize(&data_item, strip_tracking);
+            if result.is_ok() {
+                data_new.push(result.unwrap());
             } else {
                 let error = PlanetError::new(
                     500, 
@@ -1201,3 +2168,110 @@ pub fn get_stemmer_by_language(language_code: &str) -> Stemmer {
     }
     return stemmer;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn normalize_phone(value: &str, country: Option<&str>) -> Result<String, PlanetError> {
+        return PhoneColumn::normalize(&value.to_string(), &country.map(|country| country.to_string()))
+    }
+
+    #[test]
+    fn phone_international() {
+        assert_eq!(normalize_phone("+34 600 11 22 33", None).unwrap(), "+34600112233");
+        assert_eq!(normalize_phone("0034 600-11-22-33", None).unwrap(), "+34600112233");
+        assert_eq!(normalize_phone("+44 (0)20 7946 0958", None).unwrap(), "+442079460958");
+        assert_eq!(normalize_phone("+999 1234 5678", None).unwrap(), "+99912345678");
+        assert!(normalize_phone("+34 600", None).is_err());
+    }
+
+    #[test]
+    fn phone_national_with_country() {
+        assert_eq!(normalize_phone("600 11 22 33", Some("ES")).unwrap(), "+34600112233");
+        assert_eq!(normalize_phone("34600112233", Some("es")).unwrap(), "+34600112233");
+        assert_eq!(normalize_phone("020 7946 0958", Some("GB")).unwrap(), "+442079460958");
+        assert_eq!(normalize_phone("(212) 555-0100", Some("US")).unwrap(), "+12125550100");
+        assert!(normalize_phone("600 11 22", Some("ES")).is_err());
+        assert!(normalize_phone("600112233", Some("XX")).is_err());
+    }
+
+    #[test]
+    fn phone_national_without_country() {
+        assert_eq!(normalize_phone("600 11 22 33", None).unwrap(), "600112233");
+        assert!(normalize_phone("12345", None).is_err());
+        assert!(normalize_phone("600-ABC-123", None).is_err());
+    }
+
+    #[test]
+    fn rich_text_keeps_markdown() {
+        let markdown = String::from("# Title\n\n- item **bold** and [link](https://example.com)\n");
+        assert_eq!(RichTextColumn::sanitize(&markdown), markdown);
+        let markdown = String::from("line one\r\nline two");
+        assert_eq!(RichTextColumn::sanitize(&markdown), "line one\nline two");
+    }
+
+    #[test]
+    fn rich_text_removes_html() {
+        let sanitized = RichTextColumn::sanitize(&String::from("Hello <script>alert(1)</script> **world**"));
+        assert!(!sanitized.contains("<"), "{}", &sanitized);
+        assert!(sanitized.contains("**world**"));
+        let sanitized = RichTextColumn::sanitize(&String::from("<div onclick=\"alert(1)\">\nblock\n</div>\n\ntext"));
+        assert!(!sanitized.contains("<"), "{}", &sanitized);
+        assert!(sanitized.contains("text"));
+    }
+
+    #[test]
+    fn rich_text_removes_html_built_from_pieces() {
+        let sanitized = RichTextColumn::sanitize(&String::from("<scr<b></b>ipt>alert(1)</scr<i></i>ipt>"));
+        assert!(!sanitized.contains("<script"), "{}", &sanitized);
+        assert!(!sanitized.contains("</script"), "{}", &sanitized);
+    }
+
+    #[test]
+    fn rich_text_removes_script_urls() {
+        let sanitized = RichTextColumn::sanitize(
+            &String::from("[click](javascript:alert(1)) and [ok](https://example.com)")
+        );
+        assert!(!sanitized.to_lowercase().contains("javascript"), "{}", &sanitized);
+        assert!(sanitized.contains("[ok](https://example.com)"));
+        let sanitized = RichTextColumn::sanitize(&String::from("![x](data:text/html;base64,PHNjcmlwdD4=) [y](VBScript:run)"));
+        assert!(!sanitized.contains("data:"), "{}", &sanitized);
+        assert!(!sanitized.to_lowercase().contains("vbscript"), "{}", &sanitized);
+    }
+
+    fn create_secret_column(secret_key: Option<&str>) -> Result<ColumnConfig, PlanetError> {
+        let mut config = ColumnConfig::defaults(None);
+        config.name = Some(String::from("Token"));
+        let secret_key = secret_key.map(|secret_key| secret_key.to_string());
+        let mut obj = SecretColumn::defaults(&config, &secret_key);
+        let map = obj.create_config(&BTreeMap::new())?;
+        return obj.get_config(&map)
+    }
+
+    fn validate_secret(config: &ColumnConfig, secret_key: &str, value: &str) -> Result<Vec<String>, Vec<PlanetError>> {
+        let obj = SecretColumn::defaults(config, &Some(secret_key.to_string()));
+        return obj.validate(&vec![value.to_string()])
+    }
+
+    #[test]
+    fn secret_check_on_create() {
+        let config = create_secret_column(Some("key one")).unwrap();
+        assert!(config.secret_salt.is_some());
+        assert!(config.secret_check.is_some());
+        assert!(create_secret_column(None).is_err());
+    }
+
+    #[test]
+    fn secret_column_key() {
+        let config = create_secret_column(Some("key one")).unwrap();
+        let values = validate_secret(&config, "key one", "value").unwrap();
+        let obj = SecretColumn::defaults(&config, &Some(String::from("key one")));
+        assert_eq!(obj.get_display_value(&values[0]), "value");
+        assert!(validate_secret(&config, "key two", "value").is_err());
+        // Empty values are not encrypted, key is not needed
+        assert!(validate_secret(&config, "key two", "").unwrap().is_empty());
+        let obj = SecretColumn::defaults(&config, &Some(String::from("key two")));
+        assert_eq!(obj.get_display_value(&values[0]), SECRET_MASK);
+    }
+}
@@ -40,11 +40,17 @@ pub const COLUMN_ID: &str = "column_id";
 pub const SCORE: &str = "score";
 pub const LANGUAGE_CODES: &str = "language_codes";
 pub const LANGUAGE_DEFAULT: &str = "language_default";
+pub const LANGUAGE: &str = "language";
+pub const TRANSLATIONS: &str = "translations";
+pub const TRANSLATION_SEPARATOR: &str = "@";
 pub const TEXT_SEARCH_COLUMN_RELEVANCE: &str = "text_search_column_relevance";
 
 pub const PROPERTIES: &str = "properties";
 pub const COLUMN_IDS: &str = "column_ids";
 pub const SELECT_OPTIONS: &str = "select_options";
+pub const SELECT_OPTION_COLOR: &str = "color";
+pub const SELECT_OPTION_ORDER: &str = "order";
+pub const SELECT_OPTION_ARCHIVED: &str = "archived";
 pub const KEY: &str = "key";
 pub const NAME_CAMEL: &str = "Name";
 pub const FOLDER_NAME: &str = "folder_name";
@@ -64,8 +70,10 @@ pub const FORMULA_FORMAT: &str = "formula_format";
 pub const FORMULA_COMPILED: &str = "formula_compiled";
 pub const DATE_FORMAT: &str = "date_format";
 pub const TIME_FORMAT: &str = "time_format";
+pub const DURATION_FORMAT: &str = "duration_format";
 pub const NUMBER_DECIMALS: &str = "number_decimals";
 pub const CURRENCY_SYMBOL: &str = "currency_symbol";
+pub const CURRENCY: &str = "currency";
 pub const LINKED_FOLDER: &str = "linked_folder";
 pub const DELETE_ON_LINK_DROP: &str = "delete_on_link_drop";
 pub const LINK_COLUMN: &str = "link_column";
@@ -76,19 +84,66 @@ pub const LANGUAGE_COLUMN: &str = "Language";
 pub const TEXT_COLUMN: &str = "Text";
 pub const WHERE: &str = "where";
 pub const SEQUENCE: &str = "sequence";
+pub const SEQUENCE_FORMAT: &str = "sequence_format";
+pub const SEQUENCE_START: &str = "sequence_start";
+pub const SEQUENCE_STEP: &str = "sequence_step";
+pub const SEQUENCE_RESET: &str = "sequence_reset";
+pub const SEQUENCE_RESET_NEVER: &str = "Never";
+pub const SEQUENCE_RESET_YEARLY: &str = "Yearly";
+pub const SEQUENCE_RESET_MONTHLY: &str = "Monthly";
+pub const DEFAULT_COUNTRY: &str = "default_country";
+pub const PHONE_FORMAT: &str = "phone_format";
+pub const PHONE_FORMAT_E164: &str = "E164";
+pub const PHONE_FORMAT_INTERNATIONAL: &str = "International";
+pub const PHONE_FORMAT_NATIONAL: &str = "National";
+pub const STRIP_TRACKING: &str = "strip_tracking";
+pub const OUTPUT: &str = "output";
+pub const SECRET_SALT: &str = "secret_salt";
+pub const SECRET_CHECK: &str = "secret_check";
+pub const OUTPUT_MARKDOWN: &str = "Markdown";
+pub const OUTPUT_HTML: &str = "Html";
+pub const TRANSLATABLE: &str = "translatable";
+pub const STATES: &str = "states";
+pub const TRANSITIONS: &str = "transitions";
+pub const STATE_HISTORY: &str = "state_history";
+pub const STATE_CHANGED_ON: &str = "changed_on";
+pub const TRANSITION_SEPARATOR: &str = "/";
+pub const THUMBNAILS: &str = "thumbnails";
+pub const LOCATION_COLUMN: &str = "location_column";
+pub const THUMBNAIL_SIZE_MAX: u32 = 2048;
+pub const THUMBNAIL_CONTENT_TYPE: &str = "image/jpeg";
+pub const THUMBNAIL_OF: &str = "thumbnail_of";
+// Space database tree with file contents by SHA-256 hash, stored once and reference counted
+pub const FILE_CONTENT_DB: &str = "files/content.db";
+pub const FILE_CONTENT_WAIT_ATTEMPTS: u32 = 1000;
 pub const MAXIMUM: &str = "maximum";
 pub const MINIMUM: &str = "minmum";
 pub const SET_MAXIMUM: &str = "set_maximum";
 pub const SET_MINIMUM: &str = "set_minmum";
 pub const MAX_LENGTH: &str = "max_length";
 pub const IS_SET: &str = "is_set";
+pub const TAG_SEPARATOR: &str = "/";
+pub const TAGS_GROUP_SEPARATOR: &str = "\u{1e}";
 pub const OBJECT_SCHEMA: &str = "object_schema";
 pub const STATS_FUNCTION: &str = "stats_function";
 pub const MODE: &str = "mode";
 pub const CONTENT_TYPES: &str = "content_types";
 pub const STATEMENTS: &str = "statements";
+pub const UNIQUE: &str = "unique";
+pub const UNIQUE_KEYS: &str = "unique_keys";
+pub const CHECKS: &str = "checks";
+pub const ON_DELETE: &str = "on_delete";
+pub const INDEXED_PATHS: &str = "indexed_paths";
+pub const TIME_ZONE: &str = "time_zone";
+pub const OBJECT_PATH_SEPARATOR: &str = "->";
 
 pub const CURRENCY_SYMBOL_DOLLAR: &str = "$";
+pub const CURRENCY_CODE_DEFAULT: &str = "USD";
+
+// Link delete rules
+pub const ON_DELETE_CASCADE: &str = "Cascade";
+pub const ON_DELETE_SET_NULL: &str = "SetNull";
+pub const ON_DELETE_RESTRICT: &str = "Restrict";
 
 // Column Types
 pub const COLUMN_TYPE_SMALL_TEXT: &str = "SmallText";
@@ -120,12 +175,24 @@ pub const COLUMN_TYPE_OBJECT: &str = "Object";
 pub const COLUMN_TYPE_STATS: &str = "Stats";
 pub const COLUMN_TYPE_FILE: &str = "File";
 pub const COLUMN_TYPE_STATEMENT: &str = "Statement";
+pub const COLUMN_TYPE_RECURRENCE: &str = "Recurrence";
+pub const COLUMN_TYPE_LOCATION: &str = "Location";
+pub const COLUMN_TYPE_ROLLUP: &str = "Rollup";
+pub const COLUMN_TYPE_LOOKUP: &str = "Lookup";
+pub const COLUMN_TYPE_SECRET: &str = "Secret";
+pub const COLUMN_TYPE_RICH_TEXT: &str = "RichText";
+pub const COLUMN_TYPE_TAGS: &str = "Tags";
+pub const COLUMN_TYPE_WORKFLOW: &str = "Workflow";
 
 // Date Format
 pub const DATE_FORMAT_FRIENDLY: &str = "Friendly";
 pub const DATE_FORMAT_US: &str = "US";
 pub const DATE_FORMAT_EUROPEAN: &str = "European";
 pub const DATE_FORMAT_ISO: &str = "ISO";
+pub const DURATION_FORMAT_CLOCK: &str = "Clock";
+pub const DURATION_FORMAT_COMPACT: &str = "Compact";
+pub const DURATION_FORMAT_LONG: &str = "Long";
+pub const DURATION_FORMAT_ISO: &str = "ISO";
 
 // Stats Functions
 pub const STATS_FUNCTION_COUNT: &str = "COUNT";
@@ -160,3 +227,8 @@ pub const FILE_PROP_SUBJECT: &str = "Subject";
 pub const FILE_PROP_DESCRIPTION: &str = "Description";
 pub const FILE_PROP_CATEGORY: &str = "Category";
 pub const FILE_PROP_METADATA: &str = "Metadata";
+pub const FILE_PROP_CAMERA: &str = "Camera";
+pub const FILE_PROP_TAKEN_TIME: &str = "Taken Time";
+pub const FILE_PROP_LOCATION: &str = "Location";
+pub const FILE_PROP_THUMBNAIL: &str = "Thumbnail";
+pub const FILE_PROP_HASH: &str = "Hash";
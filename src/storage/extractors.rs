@@ -0,0 +1,508 @@
+extern crate zip;
+extern crate pdf_extract;
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::fs::File;
+use std::io::Read;
+use std::panic;
+use tr::tr;
+use regex::Regex;
+use lazy_static::lazy_static;
+use json;
+use reqwest::blocking::Client;
+
+use crate::planet::{PlanetError, TikaConfig};
+use crate::planet::constants::{TIKA_HOST, TIKA_PORT};
+use crate::storage::constants::*;
+use crate::storage::columns::text::RichTextColumn;
+
+lazy_static! {
+    pub static ref RE_XML_TAG: Regex = Regex::new(r#"<[^>]+>"#).unwrap();
+    pub static ref RE_HTML_NO_TEXT: Regex = Regex::new(r#"(?is)<(script|style|head)[^>]*>.*?</(script|style|head)>"#).unwrap();
+    pub static ref RE_HTML_TITLE: Regex = Regex::new(r#"(?is)<title[^>]*>(?P<Title>.*?)</title>"#).unwrap();
+    pub static ref RE_HTML_META: Regex = Regex::new(r#"(?is)<meta[\s]+name="(?P<Name>[^"]+)"[\s]+content="(?P<Content>[^"]*)""#).unwrap();
+}
+
+pub const CONTENT_TYPE_TEXT: &str = "text/plain";
+pub const CONTENT_TYPE_MARKDOWN: &str = "text/markdown";
+pub const CONTENT_TYPE_MARKDOWN_X: &str = "text/x-markdown";
+pub const CONTENT_TYPE_CSV: &str = "text/csv";
+pub const CONTENT_TYPE_JSON: &str = "application/json";
+pub const CONTENT_TYPE_HTML: &str = "text/html";
+pub const CONTENT_TYPE_DOCX: &str = "application/vnd.openxmlformats-officedocument.wordprocessingml.document";
+pub const CONTENT_TYPE_ODT: &str = "application/vnd.oasis.opendocument.text";
+pub const CONTENT_TYPE_PDF: &str = "application/pdf";
+
+// Maximum bytes we read from a document part inside zip files (docx, odt)
+pub const ZIP_ENTRY_MAX_SIZE: u64 = 50*1024*1024;
+
+// What an extractor gets out of a file: text for the index, file properties (FILE_PROP_*), keywords
+// we store as tags and raw metadata when backend provides it.
+#[derive(Debug, Clone)]
+pub struct ExtractedDocument {
+    pub text: Option<String>,
+    pub properties: BTreeMap<String, String>,
+    pub keywords: Vec<String>,
+    pub metadata: Option<String>,
+}
+impl ExtractedDocument {
+    pub fn defaults() -> Self {
+        let document = Self{
+            text: None,
+            properties: BTreeMap::new(),
+            keywords: Vec::new(),
+            metadata: None,
+        };
+        return document
+    }
+}
+
+pub trait Extractor {
+    fn accepts(&self, content_type: &String) -> bool;
+    fn extract(&self, path: &String, content_type: &String) -> Result<ExtractedDocument, PlanetError>;
+}
+
+// Native extractors go first, then Tika when configured in planet context. Files no extractor
+// accepts get no text, only the properties we get from the file itself.
+pub fn extract_document(
+    path: &String,
+    content_type: &String,
+    tika: &Option<TikaConfig>
+) -> Result<ExtractedDocument, PlanetError> {
+    let mut extractors: Vec<Box<dyn Extractor>> = Vec::new();
+    extractors.push(Box::new(PlainTextExtractor{}));
+    extractors.push(Box::new(HtmlExtractor{}));
+    extractors.push(Box::new(OfficeExtractor{}));
+    extractors.push(Box::new(PdfExtractor{}));
+    extractors.push(Box::new(ImageExtractor{}));
+    if tika.is_some() {
+        let tika = tika.clone().unwrap();
+        extractors.push(Box::new(TikaExtractor::defaults(&tika)));
+    }
+    for extractor in extractors {
+        if extractor.accepts(content_type) {
+            return extractor.extract(path, content_type)
+        }
+    }
+    return Ok(ExtractedDocument::defaults())
+}
+
+pub struct PlainTextExtractor {}
+impl Extractor for PlainTextExtractor {
+    fn accepts(&self, content_type: &String) -> bool {
+        let content_type = content_type.as_str();
+        return content_type == CONTENT_TYPE_TEXT ||
+            content_type == CONTENT_TYPE_MARKDOWN ||
+            content_type == CONTENT_TYPE_MARKDOWN_X ||
+            content_type == CONTENT_TYPE_CSV ||
+            content_type == CONTENT_TYPE_JSON
+    }
+    fn extract(&self, path: &String, content_type: &String) -> Result<ExtractedDocument, PlanetError> {
+        let content = read_text_file(path)?;
+        let mut document = ExtractedDocument::defaults();
+        let text: String;
+        match content_type.as_str() {
+            CONTENT_TYPE_MARKDOWN | CONTENT_TYPE_MARKDOWN_X => {
+                text = RichTextColumn::get_plain_text(&content);
+            },
+            CONTENT_TYPE_CSV => {
+                text = content.replace(",", " ").replace(";", " ").replace("\"", " ");
+            },
+            CONTENT_TYPE_JSON => {
+                let json_document = json::parse(&content);
+                if json_document.is_err() {
+                    return Err(
+                        PlanetError::new(
+                            500,
+                            Some(tr!("File at \"{}\" is not valid JSON.", path)),
+                        )
+                    );
+                }
+                let json_document = json_document.unwrap();
+                let mut values: Vec<String> = Vec::new();
+                get_json_values(&json_document, &mut values);
+                text = values.join(" ");
+            },
+            _ => {
+                text = content;
+            }
+        }
+        document.text = Some(text);
+        return Ok(document)
+    }
+}
+
+pub struct HtmlExtractor {}
+impl Extractor for HtmlExtractor {
+    fn accepts(&self, content_type: &String) -> bool {
+        return content_type.as_str() == CONTENT_TYPE_HTML
+    }
+    fn extract(&self, path: &String, _content_type: &String) -> Result<ExtractedDocument, PlanetError> {
+        let content = read_text_file(path)?;
+        let mut document = ExtractedDocument::defaults();
+        let title = RE_HTML_TITLE.captures(&content);
+        if title.is_some() {
+            let title = title.unwrap().name("Title").unwrap().as_str();
+            let title = decode_entities(&title.trim().to_string());
+            if title.len() > 0 {
+                document.properties.insert(FILE_PROP_TITLE.to_string(), title);
+            }
+        }
+        for captures in RE_HTML_META.captures_iter(&content) {
+            let name = captures.name("Name").unwrap().as_str().to_lowercase();
+            let meta_content = decode_entities(
+                &captures.name("Content").unwrap().as_str().to_string()
+            );
+            match name.as_str() {
+                "description" => {
+                    document.properties.insert(FILE_PROP_DESCRIPTION.to_string(), meta_content);
+                },
+                "author" => {
+                    document.properties.insert(FILE_PROP_CREATOR.to_string(), meta_content);
+                },
+                "keywords" => {
+                    document.keywords = get_keywords(&meta_content);
+                },
+                _ => {}
+            }
+        }
+        let body = RE_HTML_NO_TEXT.replace_all(&content, " ").to_string();
+        document.text = Some(get_markup_text(&body));
+        return Ok(document)
+    }
+}
+
+// DOCX and ODT are zip files with XML documents for content and properties
+pub struct OfficeExtractor {}
+impl Extractor for OfficeExtractor {
+    fn accepts(&self, content_type: &String) -> bool {
+        let content_type = content_type.as_str();
+        return content_type == CONTENT_TYPE_DOCX || content_type == CONTENT_TYPE_ODT
+    }
+    fn extract(&self, path: &String, content_type: &String) -> Result<ExtractedDocument, PlanetError> {
+        let file = File::open(path);
+        if file.is_err() {
+            let error = file.unwrap_err();
+            return Err(
+                PlanetError::new(
+                    500,
+                    Some(tr!("Could not open File at \"{}\". Error: \"{}\".", path, &error)),
+                )
+            );
+        }
+        let archive = zip::ZipArchive::new(file.unwrap());
+        if archive.is_err() {
+            return Err(
+                PlanetError::new(
+                    500,
+                    Some(tr!("File at \"{}\" is not a valid office document.", path)),
+                )
+            );
+        }
+        let mut archive = archive.unwrap();
+        let mut document = ExtractedDocument::defaults();
+        let is_docx = content_type.as_str() == CONTENT_TYPE_DOCX;
+        let content_path: &str;
+        let meta_path: &str;
+        let paragraph_ends: Vec<&str>;
+        if is_docx {
+            content_path = "word/document.xml";
+            meta_path = "docProps/core.xml";
+            paragraph_ends = vec!["</w:p>", "<w:tab/>", "<w:br/>"];
+        } else {
+            content_path = "content.xml";
+            meta_path = "meta.xml";
+            paragraph_ends = vec!["</text:p>", "</text:h>", "<text:tab/>", "<text:line-break/>"];
+        }
+        let content = get_zip_entry(&mut archive, content_path);
+        if content.is_none() {
+            return Err(
+                PlanetError::new(
+                    500,
+                    Some(tr!("File at \"{}\" has no document content.", path)),
+                )
+            );
+        }
+        let mut content = content.unwrap();
+        for paragraph_end in paragraph_ends {
+            content = content.replace(paragraph_end, &format!("{} ", paragraph_end));
+        }
+        document.text = Some(get_markup_text(&content));
+        let meta = get_zip_entry(&mut archive, meta_path);
+        if meta.is_some() {
+            let meta = meta.unwrap();
+            let mut elements: Vec<(&str, &str)> = vec![
+                ("dc:title", FILE_PROP_TITLE),
+                ("dc:subject", FILE_PROP_SUBJECT),
+                ("dc:description", FILE_PROP_DESCRIPTION),
+            ];
+            if is_docx {
+                elements.push(("dc:creator", FILE_PROP_CREATOR));
+                elements.push(("dcterms:created", FILE_PROP_CREATED_TIME));
+                elements.push(("dcterms:modified", FILE_PROP_LAST_MODIFIED_TIME));
+                elements.push(("cp:category", FILE_PROP_CATEGORY));
+            } else {
+                elements.push(("meta:initial-creator", FILE_PROP_CREATOR));
+                elements.push(("meta:creation-date", FILE_PROP_CREATED_TIME));
+                elements.push(("dc:date", FILE_PROP_LAST_MODIFIED_TIME));
+            }
+            for (element, property) in elements {
+                let value = get_xml_element_values(&meta, element);
+                if value.len() > 0 {
+                    document.properties.insert(property.to_string(), value[0].clone());
+                }
+            }
+            if is_docx {
+                let keywords = get_xml_element_values(&meta, "cp:keywords");
+                if keywords.len() > 0 {
+                    document.keywords = get_keywords(&keywords[0]);
+                }
+            } else {
+                document.keywords = get_xml_element_values(&meta, "meta:keyword");
+            }
+        }
+        return Ok(document)
+    }
+}
+
+pub struct PdfExtractor {}
+impl Extractor for PdfExtractor {
+    fn accepts(&self, content_type: &String) -> bool {
+        return content_type.as_str() == CONTENT_TYPE_PDF
+    }
+    fn extract(&self, path: &String, _content_type: &String) -> Result<ExtractedDocument, PlanetError> {
+        // Malformed files can make the PDF parser panic
+        let text = panic::catch_unwind(|| pdf_extract::extract_text(path));
+        if text.is_err() {
+            return Err(
+                PlanetError::new(
+                    500,
+                    Some(tr!("Could not extract text from PDF at \"{}\".", path)),
+                )
+            );
+        }
+        let text = text.unwrap();
+        if text.is_err() {
+            let error = text.unwrap_err();
+            return Err(
+                PlanetError::new(
+                    500,
+                    Some(tr!("Could not extract text from PDF at \"{}\". Error: \"{}\".", path, &error)),
+                )
+            );
+        }
+        let mut document = ExtractedDocument::defaults();
+        document.text = Some(text.unwrap());
+        return Ok(document)
+    }
+}
+
+// Only dimensions, EXIF data is read by the File column
+pub struct ImageExtractor {}
+impl Extractor for ImageExtractor {
+    fn accepts(&self, content_type: &String) -> bool {
+        return content_type.starts_with("image/")
+    }
+    fn extract(&self, path: &String, _content_type: &String) -> Result<ExtractedDocument, PlanetError> {
+        let mut document = ExtractedDocument::defaults();
+        let dimensions = image::image_dimensions(path);
+        if dimensions.is_ok() {
+            let (width, height) = dimensions.unwrap();
+            document.properties.insert(FILE_PROP_IMAGE_WIDTH.to_string(), width.to_string());
+            document.properties.insert(FILE_PROP_IMAGE_HEIGHT.to_string(), height.to_string());
+        }
+        return Ok(document)
+    }
+}
+
+pub struct TikaExtractor {
+    pub host: String,
+    pub port: String,
+}
+impl TikaExtractor {
+    pub fn defaults(config: &TikaConfig) -> Self {
+        let host = config.host.clone().unwrap_or(TIKA_HOST.to_string());
+        let port = config.port.clone().unwrap_or(TIKA_PORT.to_string());
+        let extractor = Self{
+            host: host,
+            port: port,
+        };
+        return extractor
+    }
+}
+impl Extractor for TikaExtractor {
+    fn accepts(&self, _content_type: &String) -> bool {
+        return true
+    }
+    fn extract(&self, path: &String, _content_type: &String) -> Result<ExtractedDocument, PlanetError> {
+        let file = File::open(path);
+        if file.is_err() {
+            let error = file.unwrap_err();
+            return Err(
+                PlanetError::new(
+                    500,
+                    Some(tr!("Could not open File at \"{}\". Error: \"{}\".", path, &error)),
+                )
+            );
+        }
+        let client = Client::new();
+        let url = format!("http://{host}:{port}/rmeta/text", host=&self.host, port=&self.port);
+        let res = client.put(&url)
+        .body(file.unwrap())
+        .send();
+        if res.is_err() {
+            let error = res.unwrap_err();
+            return Err(
+                PlanetError::new(
+                    500,
+                    Some(tr!(
+                        "Error processing file metadata, Tika at \"{}:{}\" not available: \"{}\".",
+                        &self.host, &self.port, &error
+                    )),
+                )
+            );
+        }
+        let response = res.unwrap().text().unwrap_or_default();
+        let json_document = json::parse(&response);
+        if json_document.is_err() {
+            return Err(
+                PlanetError::new(
+                    500,
+                    Some(tr!("Error processing metadata response.")),
+                )
+            );
+        }
+        let json_document = json_document.unwrap();
+        let main_document = &json_document[0];
+        let mut document = ExtractedDocument::defaults();
+        let elements: Vec<(&str, &str)> = vec![
+            ("dc:title", FILE_PROP_TITLE),
+            ("dcterms:created", FILE_PROP_CREATED_TIME),
+            ("dcterms:modified", FILE_PROP_LAST_MODIFIED_TIME),
+            ("dc:creator", FILE_PROP_CREATOR),
+            ("dc:description", FILE_PROP_DESCRIPTION),
+            ("cp:category", FILE_PROP_CATEGORY),
+        ];
+        for (element, property) in elements {
+            let value = &main_document[element];
+            if !value.is_null() {
+                document.properties.insert(property.to_string(), value.to_string());
+            }
+        }
+        let subject = &main_document["dc:subject"];
+        if !subject.is_null() {
+            if subject.is_string() {
+                document.properties.insert(FILE_PROP_SUBJECT.to_string(), subject.to_string());
+            } else {
+                document.properties.insert(FILE_PROP_SUBJECT.to_string(), subject[0].to_string());
+                document.keywords = get_keywords(&subject[1].to_string());
+            }
+        }
+        let dimensions: Vec<(&str, &str)> = vec![
+            ("Image Width", FILE_PROP_IMAGE_WIDTH),
+            ("Image Height", FILE_PROP_IMAGE_HEIGHT),
+        ];
+        for (element, property) in dimensions {
+            let value = &main_document[element];
+            if !value.is_null() {
+                let value = value.to_string();
+                let fields: Vec<&str> = value.split(" pixels").collect();
+                document.properties.insert(property.to_string(), fields[0].to_string());
+            }
+        }
+        let text = &main_document["X-TIKA:content"];
+        if !text.is_null() {
+            document.text = Some(text.to_string());
+        }
+        document.metadata = Some(response);
+        return Ok(document)
+    }
+}
+
+fn read_text_file(path: &String) -> Result<String, PlanetError> {
+    let content = fs::read(path);
+    if content.is_err() {
+        let error = content.unwrap_err();
+        return Err(
+            PlanetError::new(
+                500,
+                Some(tr!("Could not open File at \"{}\". Error: \"{}\".", path, &error)),
+            )
+        );
+    }
+    let content = content.unwrap();
+    return Ok(String::from_utf8_lossy(&content).to_string())
+}
+
+fn get_zip_entry(archive: &mut zip::ZipArchive<File>, name: &str) -> Option<String> {
+    let entry = archive.by_name(name);
+    if entry.is_err() {
+        return None
+    }
+    // Size in zip header is not trusted, we read up to the limit
+    let entry = entry.unwrap();
+    let mut content: Vec<u8> = Vec::new();
+    let result = entry.take(ZIP_ENTRY_MAX_SIZE).read_to_end(&mut content);
+    if result.is_err() {
+        return None
+    }
+    return Some(String::from_utf8_lossy(&content).to_string())
+}
+
+fn get_xml_element_values(xml: &String, element: &str) -> Vec<String> {
+    let expr = format!(r#"(?s)<{element}(\s[^>]*)?>(?P<Value>.*?)</{element}>"#, element=regex::escape(element));
+    let expr = Regex::new(&expr).unwrap();
+    let mut values: Vec<String> = Vec::new();
+    for captures in expr.captures_iter(xml) {
+        let value = captures.name("Value").unwrap().as_str();
+        let value = decode_entities(&value.trim().to_string());
+        if value.len() > 0 {
+            values.push(value);
+        }
+    }
+    return values
+}
+
+fn get_markup_text(markup: &String) -> String {
+    let text = RE_XML_TAG.replace_all(markup, " ").to_string();
+    let text = decode_entities(&text);
+    let words: Vec<&str> = text.split_whitespace().collect();
+    return words.join(" ")
+}
+
+fn decode_entities(text: &String) -> String {
+    let text = text.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+    return text
+}
+
+fn get_keywords(keywords: &String) -> Vec<String> {
+    let mut list: Vec<String> = Vec::new();
+    for keyword in keywords.split(",") {
+        let keyword = keyword.trim();
+        if keyword.len() > 0 {
+            list.push(keyword.to_string());
+        }
+    }
+    return list
+}
+
+fn get_json_values(value: &json::JsonValue, values: &mut Vec<String>) {
+    if value.is_object() {
+        for (_key, item) in value.entries() {
+            get_json_values(item, values);
+        }
+    } else if value.is_array() {
+        for item in value.members() {
+            get_json_values(item, values);
+        }
+    } else if value.is_string() || value.is_number() {
+        values.push(value.to_string());
+    }
+}
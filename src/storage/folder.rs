@@ -3,7 +3,7 @@ extern crate slug;
 extern crate rust_stemmers;
 
 use std::fs;
-use std::io::{Read, Write};
+use std::io::{Read, Write, Seek, SeekFrom};
 use std::str::FromStr;
 use std::thread;
 use std::sync::{Arc, Mutex};
@@ -24,6 +24,7 @@ use std::fs::{File, remove_file, create_dir_all};
 use chacha20poly1305::{ChaCha20Poly1305, KeyInit}; // Or `XChaCha20Poly1305`
 use chacha20poly1305::aead::stream;
 use anyhow::anyhow;
+use sha2::{Sha256, Digest};
 
 use crate::planet::constants::*;
 use crate::statements::folder::schema::ColumnConfig;
@@ -35,6 +36,8 @@ use crate::storage::columns::*;
 use crate::storage::columns::text::{
     get_stop_words_by_language, get_stemmer_by_language, get_default_language_code
 };
+use crate::storage::columns::structure::{ObjectColumn, TagsColumn};
+use crate::storage::columns::geo::{Location, GEOHASH_PRECISION};
 
 
 pub trait FolderSchema {
@@ -151,6 +154,7 @@ pub struct DbFile {
     pub context: Option<BTreeMap<String, String>>,
     pub content: Option<Vec<u8>>,
     pub path: Option<String>,
+    pub hash: Option<String>,
 }
 impl DbFile {
     pub fn defaults(
@@ -185,9 +189,12 @@ impl DbFile {
             }
             routing_map.insert(String::from(IPFS_CID), ipfs_cid);
         }
+        let hash: String;
         if size > MAX_FILE_DB {
-            // .achiever-planet/private/files/{file_id}.achieverenc
-            // .achiever-planet/sites/{site_id}/spaces/{space_id}/files/{file_id}.achieverenc
+            hash = DbFile::get_file_hash(file)?;
+            // Encrypted content is stored once per space, file name is content hash
+            // .achiever-planet/private/files/{hash}.achieverenc
+            // .achiever-planet/sites/{site_id}/spaces/{space_id}/files/{hash}.achieverenc
             let space_id = routing_map.get(SPACE_ID);
             let site_id = routing_map.get(SITE_ID);
             if space_id.is_some() && site_id.is_some() {
@@ -196,16 +203,16 @@ impl DbFile {
                 let path_string: String;
                 if space_id == PRIVATE {
                     path_string = format!(
-                        "{home}/{private}/files/{file_id}.achieverenc", 
+                        "{home}/{private}/files/{hash}.achieverenc", 
                         home=&home_dir, 
-                        file_id=file_id,
+                        hash=&hash,
                         private=PRIVATE
                     );
                 } else {
                     path_string = format!(
-                        "{home}/sites/{site_id}/spaces/{space_id}/files/{file_id}.achieverenc", 
+                        "{home}/sites/{site_id}/spaces/{space_id}/files/{hash}.achieverenc", 
                         home=&home_dir, 
-                        file_id=file_id,
+                        hash=&hash,
                         site_id=site_id,
                         space_id=space_id
                     );
@@ -226,6 +233,7 @@ impl DbFile {
                     )
                 );
             }
+            hash = DbFile::get_content_hash(&contents);
             content = Some(contents);
         }
         let obj = Self{
@@ -239,9 +247,46 @@ impl DbFile {
             size: Some(size),
             content_type: Some(content_type.clone()),
             file_type: Some(file_type.clone()),
+            hash: Some(hash),
         };
         return Ok(obj)
     }
+    pub fn get_content_hash(content: &Vec<u8>) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(content);
+        return format!("{:x}", hasher.finalize())
+    }
+    pub fn get_file_hash(file: &mut File) -> Result<String, PlanetError> {
+        let mut hasher = Sha256::new();
+        let mut buffer = [0u8; 8192];
+        loop {
+            let read_count = file.read(&mut buffer);
+            if read_count.is_err() {
+                return Err(
+                    PlanetError::new(
+                        500, 
+                        Some(tr!("Error reading file.")),
+                    )
+                );
+            }
+            let read_count = read_count.unwrap();
+            if read_count == 0 {
+                break;
+            }
+            hasher.update(&buffer[..read_count]);
+        }
+        // File is read again when encrypted
+        let result = file.seek(SeekFrom::Start(0));
+        if result.is_err() {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Error reading file.")),
+                )
+            );
+        }
+        return Ok(format!("{:x}", hasher.finalize()))
+    }
     pub fn write_file(
         &mut self,
         file: &mut File,
@@ -258,13 +303,41 @@ impl DbFile {
             );
         }
         let path = path.unwrap();
+        if self.hash.is_some() {
+            // Encrypted file is written for first reference to content, in the same update of the content 
+            // record, so a release of same content cannot delete it meanwhile
+            return db_folder_item.write_file_blob(&self, Some(file))
+        }
+        DbFile::encrypt_file(file, &path)?;
+        return self.write_file_reference(&mut db_folder_item, &path, false)
+    }
+    pub fn encrypt_file(file: &mut File, path: &String) -> Result<(), PlanetError> {
+        // File may be encrypted again when content record update is retried
+        let result = file.seek(SeekFrom::Start(0));
+        if result.is_err() {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Error reading file.")),
+                )
+            );
+        }
         let cipher = ChaCha20Poly1305::new(CHILD_PRIVATE_KEY_ARRAY.as_ref().into());
         let mut stream_encryptor = stream::EncryptorBE32::from_aead(
             cipher, CHILD_NONCE.as_ref().into()
         );
         const BUFFER_LEN: usize = 500;
         let mut buffer = [0u8; BUFFER_LEN];
-        let mut enc_file = File::create(&path).unwrap();
+        let enc_file = File::create(path);
+        if enc_file.is_err() {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Could not create encrypted file at \"{}\".", path)),
+                )
+            );
+        }
+        let mut enc_file = enc_file.unwrap();
         loop {
             let read_count = file.read(&mut buffer).unwrap();
             if read_count == BUFFER_LEN {
@@ -272,6 +345,7 @@ impl DbFile {
                     .encrypt_next(buffer.as_slice())
                     .map_err(|err| anyhow!("Encrypting large file: {}", err));
                 if ciphertext.is_err() {
+                    let _ = remove_file(path);
                     return Err(
                         PlanetError::new(
                             500, 
@@ -286,6 +360,7 @@ impl DbFile {
                     .encrypt_last(&buffer[..read_count])
                     .map_err(|err| anyhow!("Encrypting large file: {}", err));
                 if ciphertext.is_err() {
+                    let _ = remove_file(path);
                     return Err(
                         PlanetError::new(
                             500, 
@@ -298,14 +373,24 @@ impl DbFile {
                 break;
             }
         }
+        return Ok(())
+    }
+    fn write_file_reference(
+        &mut self,
+        db_folder_item: &mut TreeFolderItem,
+        path: &String,
+        exists: bool,
+    ) -> Result<String, PlanetError> {
         // Write into file database the path to encrypted file
         let file_id = db_folder_item.write_file(&self);
         if file_id.is_ok() {
             let file_id = file_id.unwrap();
             return Ok(file_id.clone())
         } else {
-            // remove file from path
-            let _ = remove_file(&path);
+            // remove file from path, only when we wrote it
+            if !exists {
+                let _ = remove_file(path);
+            }
             return Err(
                 PlanetError::new(
                     500, 
@@ -463,6 +548,75 @@ impl DbFile {
             ()
         )
     }
+    // Hash of decrypted content for files written before contents were stored by hash
+    pub fn get_encrypted_file_hash(&self) -> Result<String, PlanetError> {
+        let path = self.path.clone();
+        if path.is_none() {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("No path defined for file.")),
+                )
+            );
+        }
+        let path = path.unwrap();
+        let cipher = ChaCha20Poly1305::new(CHILD_PRIVATE_KEY_ARRAY.as_ref().into());
+        let mut stream_decryptor = stream::DecryptorBE32::from_aead(
+            cipher, 
+            CHILD_NONCE.as_ref().into()
+        );
+        const BUFFER_LEN: usize = 500 + 16;
+        let mut buffer = [0u8; BUFFER_LEN];
+        let encrypted_file = File::open(&path);
+        if encrypted_file.is_err() {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Could not open encrypted file.")),
+                )
+            );
+        }
+        let mut encrypted_file = encrypted_file.unwrap();
+        let mut hasher = Sha256::new();
+        loop {
+            let read_count = encrypted_file.read(&mut buffer);
+            if read_count.is_err() {
+                return Err(
+                    PlanetError::new(
+                        500, 
+                        Some(tr!("Error reading file.")),
+                    )
+                );
+            }
+            let read_count = read_count.unwrap();
+            if read_count == 0 {
+                break;
+            }
+            let plaintext: Result<Vec<u8>, anyhow::Error>;
+            if read_count == BUFFER_LEN {
+                plaintext = stream_decryptor
+                    .decrypt_next(buffer.as_slice())
+                    .map_err(|err| anyhow!("Decrypting large file: {}", err));
+            } else {
+                plaintext = stream_decryptor
+                    .decrypt_last(&buffer[..read_count])
+                    .map_err(|err| anyhow!("Decrypting large file: {}", err));
+            }
+            if plaintext.is_err() {
+                return Err(
+                    PlanetError::new(
+                        500, 
+                        Some(tr!("Problem decrypting file.")),
+                    )
+                );
+            }
+            hasher.update(plaintext.unwrap());
+            if read_count < BUFFER_LEN {
+                break;
+            }
+        }
+        return Ok(format!("{:x}", hasher.finalize()))
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Validate, Clone)]
@@ -601,6 +755,41 @@ impl SerdeEncryptSharedKey for DbFile {
     type S = BincodeSerializer<Self>;  // you can specify serializer implementation (or implement it by yourself).
 }
 
+// File content by SHA-256 hash, one per space. Files in folder files databases reference it by hash,
+// content is removed when last reference is removed.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DbFileContent {
+    pub hash: String,
+    pub size: u64,
+    pub references: u64,
+    pub content: Option<Vec<u8>>,
+    pub path: Option<String>,
+}
+
+impl SerdeEncryptSharedKey for DbFileContent {
+    type S = BincodeSerializer<Self>;
+}
+impl DbFileContent {
+    pub fn decode(value: Option<&[u8]>, shared_key: &SharedKey) -> Result<Option<Self>, PlanetError> {
+        if value.is_none() {
+            return Ok(None)
+        }
+        let item_ = EncryptedMessage::deserialize(value.unwrap().to_vec());
+        if item_.is_err() {
+            return Err(
+                PlanetError::new(500, Some(tr!("Error decrypting file.")))
+            )
+        }
+        let item_ = DbFileContent::decrypt_owned(&item_.unwrap(), shared_key);
+        if item_.is_err() {
+            return Err(
+                PlanetError::new(500, Some(tr!("Error decrypting file.")))
+            )
+        }
+        return Ok(Some(item_.unwrap()))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct TreeFolder {
     pub home_dir: Option<String>,
@@ -1130,6 +1319,207 @@ impl TreeFolder {
         }
         return item_data
     }
+    pub fn next_sequence(
+        &self,
+        folder_id: &String,
+        key: &String,
+        start: i64,
+        step: i64,
+    ) -> Result<i64, PlanetError> {
+        // folders/c7c815is1s406kaf3j30/sequences
+        let tree = self.open_sequences(folder_id)?;
+        let result = tree.fetch_and_update(key.as_bytes(), |current| {
+            let next = TreeFolder::get_next_sequence(current, start, step);
+            Some(next.to_be_bytes().to_vec())
+        });
+        if result.is_err() {
+            return Err(
+                PlanetError::new(500, Some(tr!("Could not update sequence \"{}\".", key)))
+            )
+        }
+        let previous = result.unwrap();
+        let next = TreeFolder::get_next_sequence(previous.as_deref(), start, step);
+        let _ = tree.flush();
+        return Ok(next)
+    }
+    fn open_sequences(&self, folder_id: &String) -> Result<sled::Tree, PlanetError> {
+        let path = format!(
+            "folders/{folder_id}/sequences",
+            folder_id=folder_id,
+        );
+        let tree = self.database.open_tree(path);
+        if tree.is_err() {
+            return Err(
+                PlanetError::new(500, Some(tr!("Could not open sequences database.")))
+            )
+        }
+        return Ok(tree.unwrap())
+    }
+    // Sequence keys are column id, or column id and period, so these are remapped when target folder has 
+    // new column ids
+    pub fn copy_sequences(
+        &self, 
+        folder_id: &String, 
+        target: &TreeFolder, 
+        target_folder_id: &String,
+        column_id_map: &BTreeMap<String, String>,
+    ) -> Result<(), PlanetError> {
+        let source_tree = self.open_sequences(folder_id)?;
+        let target_tree = target.open_sequences(target_folder_id)?;
+        for result in source_tree.iter() {
+            if result.is_err() {
+                return Err(
+                    PlanetError::new(500, Some(tr!("Could not read sequences database.")))
+                )
+            }
+            let (key, value) = result.unwrap();
+            let key = String::from_utf8(key.to_vec()).unwrap_or_default();
+            let mut fields: Vec<String> = key.splitn(2, ":").map(|field| field.to_string()).collect();
+            let column_id = column_id_map.get(&fields[0]);
+            if column_id.is_some() {
+                fields[0] = column_id.unwrap().clone();
+            }
+            let key = fields.join(":");
+            let result = target_tree.insert(key.as_bytes(), value);
+            if result.is_err() {
+                return Err(
+                    PlanetError::new(500, Some(tr!("Could not write into sequences database.")))
+                )
+            }
+        }
+        let _ = target_tree.flush();
+        return Ok(())
+    }
+    pub fn drop_sequences(&self, folder_id: &String) -> Result<(), PlanetError> {
+        let path = format!(
+            "folders/{folder_id}/sequences",
+            folder_id=folder_id,
+        );
+        let result = self.database.drop_tree(path.clone());
+        if result.is_err() {
+            return Err(
+                PlanetError::new(500, Some(tr!("Error deleting sequences \"{}\".", &path)))
+            )
+        }
+        return Ok(())
+    }
+    // Gives back a number from a failed insert, only possible when no later number was taken
+    pub fn release_sequence(
+        &self,
+        folder_id: &String,
+        key: &String,
+        sequence: i64,
+        start: i64,
+        step: i64,
+    ) -> Result<bool, PlanetError> {
+        let tree = self.open_sequences(folder_id)?;
+        let current = sequence.to_be_bytes().to_vec();
+        let mut previous: Option<Vec<u8>> = None;
+        if sequence != start {
+            previous = Some((sequence - step).to_be_bytes().to_vec());
+        }
+        let result = tree.compare_and_swap(key.as_bytes(), Some(current), previous);
+        if result.is_err() {
+            return Err(
+                PlanetError::new(500, Some(tr!("Could not update sequence \"{}\".", key)))
+            )
+        }
+        let released = result.unwrap().is_ok();
+        let _ = tree.flush();
+        return Ok(released)
+    }
+    fn get_next_sequence(current: Option<&[u8]>, start: i64, step: i64) -> i64 {
+        if current.is_none() {
+            return start
+        }
+        let current = current.unwrap();
+        if current.len() != 8 {
+            return start
+        }
+        let mut bytes: [u8; 8] = [0; 8];
+        bytes.copy_from_slice(current);
+        return i64::from_be_bytes(bytes) + step
+    }
+    fn open_tags(&self) -> Result<sled::Tree, PlanetError> {
+        // Space wide tag registry, tag hash => encrypted tag
+        let tree = self.database.open_tree("tags");
+        if tree.is_err() {
+            return Err(
+                PlanetError::new(500, Some(tr!("Could not open tags database.")))
+            )
+        }
+        return Ok(tree.unwrap())
+    }
+    fn get_tag_key_db(tag: &String) -> Vec<u8> {
+        let mut hasher = Sha256::new();
+        hasher.update(tag.as_bytes());
+        return hasher.finalize().to_vec()
+    }
+    fn insert_tag(tree: &sled::Tree, tag: &String) -> Result<(), PlanetError> {
+        let shared_key: SharedKey = SharedKey::from_array(CHILD_PRIVATE_KEY_ARRAY);
+        // project/alpha/design also registers project and project/alpha
+        for ancestor in TagsColumn::get_ancestors(tag) {
+            let name_tree = NameTree{
+                name: ancestor.clone()
+            };
+            let encoded: Vec<u8> = name_tree.encrypt(&shared_key).unwrap().serialize();
+            let result = tree.insert(TreeFolder::get_tag_key_db(&ancestor), encoded);
+            if result.is_err() {
+                return Err(
+                    PlanetError::new(500, Some(tr!("Could not register tag \"{}\".", &ancestor)))
+                )
+            }
+        }
+        return Ok(())
+    }
+    pub fn register_tags(&self, tags: &Vec<String>) -> Result<(), PlanetError> {
+        let tree = self.open_tags()?;
+        for tag in tags {
+            TreeFolder::insert_tag(&tree, tag)?;
+        }
+        let _ = tree.flush();
+        return Ok(())
+    }
+    pub fn get_tags(&self) -> Result<Vec<String>, PlanetError> {
+        let tree = self.open_tags()?;
+        let shared_key: SharedKey = SharedKey::from_array(CHILD_PRIVATE_KEY_ARRAY);
+        let mut tags: Vec<String> = Vec::new();
+        for result in tree.iter() {
+            if result.is_err() {
+                return Err(
+                    PlanetError::new(500, Some(tr!("Could not fetch tags from database.")))
+                )
+            }
+            let (_, value) = result.unwrap();
+            let message = EncryptedMessage::deserialize(value.to_vec());
+            if message.is_err() {
+                continue
+            }
+            let name_tree = NameTree::decrypt_owned(&message.unwrap(), &shared_key);
+            if name_tree.is_ok() {
+                tags.push(name_tree.unwrap().name);
+            }
+        }
+        tags.sort();
+        return Ok(tags)
+    }
+    pub fn replace_tags(&self, tag_map: &BTreeMap<String, String>) -> Result<(), PlanetError> {
+        // tag_map: tag => new tag. Descendants move with their tag.
+        let tree = self.open_tags()?;
+        let tags = self.get_tags()?;
+        for tag in tags {
+            for (tag_from, tag_to) in tag_map {
+                let tag_new = TagsColumn::rename(&tag, tag_from, tag_to);
+                if tag_new.is_some() {
+                    let _ = tree.remove(TreeFolder::get_tag_key_db(&tag));
+                    TreeFolder::insert_tag(&tree, &tag_new.unwrap())?;
+                    break
+                }
+            }
+        }
+        let _ = tree.flush();
+        return Ok(())
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -1178,6 +1568,13 @@ pub enum GetItemOption {
     ByName(String),
 }
 
+#[derive(Debug, Clone, PartialEq)]
+enum TreeKind {
+    Data,
+    Files,
+    Raw,
+}
+
 #[derive(Debug, Clone)]
 pub struct TreeFolderItem {
     pub database: sled::Db,
@@ -1191,6 +1588,9 @@ pub struct TreeFolderItem {
     pub index: Option<sled::Tree>,
     pub files_db: Option<sled::Tree>,
     pub tree_partitions: Option<sled::Tree>,
+    pub unique_index: Option<sled::Tree>,
+    pub path_index: Option<sled::Tree>,
+    pub geo_index: Option<sled::Tree>,
 }
 
 impl TreeFolderItem {
@@ -1252,6 +1652,19 @@ impl TreeFolderItem {
         return Ok(())
     }
 
+    pub fn reindex_item(
+        &mut self,
+        item: &DbData,
+        text_map: &BTreeMap<String, String>,
+    ) -> Result<DbData, PlanetError> {
+        let item_id = item.id.clone().unwrap_or_default();
+        let items = self.open_partition_by_item(&item_id)?;
+        self.tree = Some(items.0);
+        self.index = Some(items.1);
+        let result = self.index(item, text_map)?;
+        return Ok(result)
+    }
+
     pub fn reindex_default_language(
         &mut self
     ) -> Result<(), PlanetError> {
@@ -1509,6 +1922,41 @@ impl TreeFolderItem {
                 )
             }
         }
+        let path_unique = format!(
+            "folders/{folder_id}/unique.index",
+            folder_id=folder_id,
+        );
+        let unique_result = self.database.drop_tree(path_unique.clone());
+        if unique_result.is_err() {
+            return Err(
+                PlanetError::new(500, Some(tr!("Error deleting unique index \"{}\".", &path_unique)))
+            )
+        }
+        self.unique_index = None;
+        let path_paths = format!(
+            "folders/{folder_id}/paths.index",
+            folder_id=folder_id,
+        );
+        let paths_result = self.database.drop_tree(path_paths.clone());
+        if paths_result.is_err() {
+            return Err(
+                PlanetError::new(500, Some(tr!("Error deleting paths index \"{}\".", &path_paths)))
+            )
+        }
+        self.path_index = None;
+        let path_geo = format!(
+            "folders/{folder_id}/geo.index",
+            folder_id=folder_id,
+        );
+        let geo_result = self.database.drop_tree(path_geo.clone());
+        if geo_result.is_err() {
+            return Err(
+                PlanetError::new(500, Some(tr!("Error deleting geo index \"{}\".", &path_geo)))
+            )
+        }
+        self.geo_index = None;
+        // Sequences are kept in folders database, not space database
+        self.tree_folder.drop_sequences(&folder_id.to_string())?;
         return Ok(())
     }
 
@@ -1539,6 +1987,7 @@ impl TreeFolderItem {
             tree = tree_.unwrap();
         }
         let mut path_list: Vec<String> = Vec::new();
+        let mut hash_list: Vec<String> = Vec::new();
         for result in tree.iter() {
             let tuple = result.unwrap();
             let item_db = tuple.1.to_vec();
@@ -1548,7 +1997,9 @@ impl TreeFolderItem {
                 &shared_key);
             let item = item_.unwrap();
             let path = item.path;
-            if path.is_some() {
+            if item.hash.is_some() {
+                hash_list.push(item.hash.unwrap());
+            } else if path.is_some() {
                 let path = path.unwrap();
                 path_list.push(path);
             }
@@ -1563,6 +2014,11 @@ impl TreeFolderItem {
                 )))
             )
         }
+        self.files_db = None;
+        // Content shared by hash is deleted when last reference is released
+        for hash in hash_list {
+            self.release_file(&hash)?;
+        }
         // Delete big files from files folder
         for path in path_list {
             let result = fs::remove_file(path.clone());
@@ -1822,37 +2278,30 @@ impl TreeFolderItem {
         return Ok(relevance_int)
     }
     pub fn write_file(&mut self, db_file: &DbFile) -> Result<String, PlanetError> {
+        return self.write_file_blob(db_file, None)
+    }
+    // Blob is plain file encrypted into file path when content is referenced for the first time
+    pub fn write_file_blob(&mut self, db_file: &DbFile, blob: Option<&mut File>) -> Result<String, PlanetError> {
         // box/base/folder/c7c815is1s406kaf3j30/files.db
         let shared_key: SharedKey = SharedKey::from_array(CHILD_PRIVATE_KEY_ARRAY);
-        let db_file = db_file.clone();
-        let encrypted_data = db_file.encrypt(&shared_key).unwrap();
-        let encoded = encrypted_data.serialize();
+        let mut db_file = db_file.clone();
         let id = db_file.id.clone().unwrap();
         let id_db = xid::Id::from_str(id.as_str()).unwrap();
         let id_db = id_db.as_bytes();
-        let folder_id = self.folder_id.clone().unwrap_or_default();
-        let folder_id = folder_id.as_str();
-        let file_name = db_file.name.unwrap_or_default();
-        let path_db = format!(
-            "folders/{folder_id}/files.db",
-            folder_id=folder_id,
-        );
-        let db: Tree;
-        if self.files_db.is_some() {
-            db = self.files_db.clone().unwrap();
-        } else {
-            let db_ = self.database.open_tree(path_db);
-            if db_.is_err() {
-                return Err(
-                    PlanetError::new(
-                        500, 
-                        Some(tr!("Could not find language column.")),
-                    )
-                )
+        let file_name = db_file.name.clone().unwrap_or_default();
+        let db = self.open_files_db()?;
+        let mut referenced = false;
+        if db_file.hash.is_some() {
+            // Content goes to space file contents, new files add a reference
+            let exists = db.contains_key(id_db).unwrap_or_default();
+            if !exists {
+                db_file = self.reference_file_blob(&db_file, blob)?;
+                referenced = true;
             }
-            db = db_.unwrap();
-            self.files_db = Some(db.clone());
+            db_file.content = None;
         }
+        let encrypted_data = db_file.encrypt(&shared_key).unwrap();
+        let encoded = encrypted_data.serialize();
         let response = &db.insert(id_db, encoded);
         match response {
             Ok(_) => {
@@ -1860,6 +2309,9 @@ impl TreeFolderItem {
                 return Ok(id.clone())
             },
             Err(_) => {
+                if referenced {
+                    let _ = self.release_file(&db_file.hash.clone().unwrap());
+                }
                 return Err(
                     PlanetError::new(
                         500, 
@@ -1873,10 +2325,9 @@ impl TreeFolderItem {
             }
         }    
     }
-    pub fn export_file(&mut self, id: &String) -> Result<usize, PlanetError> {
+    pub fn get_file(&mut self, id: &String) -> Result<DbFile, PlanetError> {
         let shared_key: SharedKey = SharedKey::from_array(CHILD_PRIVATE_KEY_ARRAY);
         let folder_id = self.folder_id.clone().unwrap_or_default();
-        let folder_id = folder_id.as_str();
         let path_db = format!(
             "folders/{folder_id}/files.db",
             folder_id=folder_id,
@@ -1890,7 +2341,7 @@ impl TreeFolderItem {
                 return Err(
                     PlanetError::new(
                         500, 
-                        Some(tr!("Could not find language column.")),
+                        Some(tr!("Could not open file database.")),
                     )
                 )
             }
@@ -1900,149 +2351,1801 @@ impl TreeFolderItem {
         let id_db = xid::Id::from_str(&id).unwrap();
         let id_db = id_db.as_bytes();
         let result = db.get(id_db);
-        let file_size: usize;
-        match result {
-            Ok(_) => {
-                let result = result.unwrap();
-                if result.is_none() {
-                    return Err(
-                        PlanetError::new(
-                            500, 
-                            Some(tr!("Item not found in file database.")),
-                        )
-                    )
-                }
-                let item_db = result.unwrap().to_vec();
-                let item_ = EncryptedMessage::deserialize(
-                    item_db
-                ).unwrap();
-                let item_ = DbFile::decrypt_owned(
-                    &item_, 
-                    &shared_key
-                );
-                if item_.is_err() {
-                    return Err(
-                        PlanetError::new(
-                            500, 
-                            Some(tr!("Error decrypting file.")),
+        if result.is_err() {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Could not find item in file database.")),
+                )
+            )
+        }
+        let result = result.unwrap();
+        if result.is_none() {
+            return Err(
+                PlanetError::new(
+                    404, 
+                    Some(tr!("Item not found in file database.")),
+                )
+            )
+        }
+        let item_db = result.unwrap().to_vec();
+        let item_ = EncryptedMessage::deserialize(
+            item_db
+        ).unwrap();
+        let item_ = DbFile::decrypt_owned(
+            &item_, 
+            &shared_key
+        );
+        if item_.is_err() {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Error decrypting file.")),
+                )
+            )
+        }
+        let mut db_file = item_.unwrap();
+        if db_file.hash.is_some() && db_file.content.is_none() && db_file.path.is_none() {
+            let hash = db_file.hash.clone().unwrap();
+            let file_content = self.get_file_content(&hash)?;
+            if file_content.is_none() {
+                return Err(
+                    PlanetError::new(
+                        404, 
+                        Some(tr!("Content for file \"{}\" not found.", id)),
+                    )
+                )
+            }
+            db_file.content = file_content.unwrap().content;
+        }
+        return Ok(db_file)
+    }
+    pub fn copy_file(
+        &mut self, 
+        id: &String, 
+        target: &mut TreeFolderItem
+    ) -> Result<String, PlanetError> {
+        let mut db_file = self.get_file(id)?;
+        let file_id = generate_id().unwrap_or_default();
+        let mut path_copied: Option<String> = None;
+        if db_file.path.is_some() && db_file.hash.is_none() {
+            // Files written before contents were stored by hash have file id in path. Copy is referenced 
+            // by content hash, so it does not depend on source file.
+            db_file.hash = Some(db_file.get_encrypted_file_hash()?);
+        }
+        if db_file.path.is_some() {
+            // Encrypted content is copied only when target space does not have it. Key and nonce are 
+            // same for all files, so encrypted content for a hash is same in any space.
+            let hash = db_file.hash.clone().unwrap();
+            let path = db_file.path.clone().unwrap();
+            let mut routing: BTreeMap<String, String> = BTreeMap::new();
+            routing.insert(SPACE_ID.to_string(), target.space_id.clone().unwrap_or_default());
+            if target.site_id.is_some() {
+                routing.insert(SITE_ID.to_string(), target.site_id.clone().unwrap());
+            }
+            let path_new = target.get_file_path_moved(&hash, &routing);
+            if path_new != path && !std::path::Path::new(&path_new).exists() {
+                let result = fs::copy(&path, &path_new);
+                if result.is_err() {
+                    return Err(
+                        PlanetError::new(500, Some(tr!("Could not copy file \"{}\".", &path)))
+                    )
+                }
+                path_copied = Some(path_new.clone());
+            }
+            db_file.path = Some(path_new);
+        }
+        // Thumbnails are files in same files database
+        let mut options = db_file.options.clone().unwrap_or_default();
+        let thumbnails = options.clone();
+        for (key, thumbnail_id) in thumbnails {
+            if key.starts_with(FILE_PROP_THUMBNAIL) {
+                let thumbnail_id = self.copy_file(&thumbnail_id, target)?;
+                options.insert(key, thumbnail_id);
+            }
+        }
+        if db_file.options.is_some() {
+            db_file.options = Some(options);
+        }
+        db_file.id = Some(file_id.clone());
+        let result = target.write_file(&db_file);
+        if result.is_err() {
+            if path_copied.is_some() {
+                let _ = remove_file(path_copied.unwrap());
+            }
+            return Err(result.unwrap_err())
+        }
+        return Ok(file_id)
+    }
+    pub fn export_file(&mut self, id: &String) -> Result<usize, PlanetError> {
+        let mut file = self.get_file(id)?;
+        if file.content.is_none() {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Item not found in file database.")),
+                )
+            )
+        }
+        let content = file.clone().content.unwrap();
+        let path = file.get_home_path();
+        let path = path.unwrap();
+        let mut file = File::create(path).unwrap();
+        let result = file.write(&content);
+        if result.is_err() {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Error writing into home directory.")),
+                )
+            )
+        }
+        let file_size = result.unwrap();
+        return Ok(file_size)
+    }
+    pub fn export_thumbnail(&mut self, id: &String, size: u32) -> Result<usize, PlanetError> {
+        let db_file = self.get_file(id)?;
+        let options = db_file.options.unwrap_or_default();
+        let key = format!("{} {}", FILE_PROP_THUMBNAIL, size);
+        let thumbnail_id = options.get(&key);
+        if thumbnail_id.is_none() {
+            return Err(
+                PlanetError::new(
+                    404, 
+                    Some(tr!("Thumbnail of size {} not found for file \"{}\".", size, id)),
+                )
+            )
+        }
+        let thumbnail_id = thumbnail_id.unwrap().clone();
+        return self.export_file(&thumbnail_id)
+    }
+
+    fn open_files_db(&mut self) -> Result<Tree, PlanetError> {
+        if self.files_db.is_some() {
+            return Ok(self.files_db.clone().unwrap())
+        }
+        let folder_id = self.folder_id.clone().unwrap_or_default();
+        let path_db = format!(
+            "folders/{folder_id}/files.db",
+            folder_id=folder_id,
+        );
+        let db = self.database.open_tree(path_db);
+        if db.is_err() {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Could not open file database.")),
+                )
+            )
+        }
+        let db = db.unwrap();
+        self.files_db = Some(db.clone());
+        return Ok(db)
+    }
+    fn open_file_content_db(&mut self) -> Result<Tree, PlanetError> {
+        let db = self.database.open_tree(FILE_CONTENT_DB);
+        if db.is_err() {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Could not open file content database.")),
+                )
+            )
+        }
+        return Ok(db.unwrap())
+    }
+    pub fn get_file_content(&mut self, hash: &String) -> Result<Option<DbFileContent>, PlanetError> {
+        let shared_key: SharedKey = SharedKey::from_array(CHILD_PRIVATE_KEY_ARRAY);
+        let db = self.open_file_content_db()?;
+        let result = db.get(hash.as_bytes());
+        if result.is_err() {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Could not read file content database.")),
+                )
+            )
+        }
+        let result = result.unwrap();
+        return DbFileContent::decode(result.as_deref(), &shared_key)
+    }
+    // Reference count changes run as one atomic update on the record, so concurrent inserts and deletes
+    // of same content cannot lose an update. Returns record before and after update.
+    fn update_file_content<F>(
+        &mut self, 
+        hash: &String, 
+        mut update: F
+    ) -> Result<(Option<DbFileContent>, Option<DbFileContent>), PlanetError> 
+    where F: FnMut(Option<DbFileContent>) -> Option<DbFileContent> {
+        let shared_key: SharedKey = SharedKey::from_array(CHILD_PRIVATE_KEY_ARRAY);
+        let db = self.open_file_content_db()?;
+        let mut failed = false;
+        let mut updated: Option<DbFileContent> = None;
+        let result = db.fetch_and_update(hash.as_bytes(), |current| {
+            failed = false;
+            let file_content = DbFileContent::decode(current, &shared_key);
+            if file_content.is_err() {
+                // Record is kept as it is
+                failed = true;
+                return current.map(|value| value.to_vec())
+            }
+            updated = update(file_content.unwrap());
+            if updated.is_none() {
+                return None
+            }
+            let encoded = updated.clone().unwrap().encrypt(&shared_key).unwrap().serialize();
+            return Some(encoded)
+        });
+        if result.is_err() || failed {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Could not update file content database.")),
+                )
+            )
+        }
+        let previous = DbFileContent::decode(result.unwrap().as_deref(), &shared_key)?;
+        return Ok((previous, updated))
+    }
+    // Adds a reference to file content by hash, new content is written only for first reference in space.
+    // Encrypted file for blob is written in the record update, when record is new or file is missing. 
+    // Records with no references have their encrypted file being deleted, we wait until they are removed.
+    fn add_file_reference(
+        &mut self, 
+        file_content: &DbFileContent, 
+        blob: Option<&mut File>
+    ) -> Result<Option<DbFileContent>, PlanetError> {
+        let file_content = file_content.clone();
+        let mut blob = blob;
+        let mut attempts = 0;
+        loop {
+            let mut deleting = false;
+            let mut blob_error: Option<PlanetError> = None;
+            let (previous, _) = self.update_file_content(&file_content.hash.clone(), |current| {
+                deleting = false;
+                blob_error = None;
+                let mut record = file_content.clone();
+                record.references = 0;
+                if current.is_some() {
+                    record = current.clone().unwrap();
+                    if record.references == 0 {
+                        deleting = true;
+                        return current
+                    }
+                }
+                if blob.is_some() && record.path.is_some() {
+                    let path = record.path.clone().unwrap();
+                    if !std::path::Path::new(&path).exists() {
+                        let result = DbFile::encrypt_file(blob.as_mut().unwrap(), &path);
+                        if result.is_err() {
+                            blob_error = Some(result.unwrap_err());
+                            return current
+                        }
+                    }
+                }
+                record.references += 1;
+                return Some(record)
+            })?;
+            if blob_error.is_some() {
+                return Err(blob_error.unwrap())
+            }
+            if !deleting {
+                return Ok(previous)
+            }
+            attempts += 1;
+            if attempts > FILE_CONTENT_WAIT_ATTEMPTS {
+                return Err(
+                    PlanetError::new(
+                        500, 
+                        Some(tr!("Content for file \"{}\" is being deleted.", &file_content.hash)),
+                    )
+                )
+            }
+            thread::sleep(std::time::Duration::from_millis(1));
+        }
+    }
+    pub fn reference_file(&mut self, db_file: &DbFile) -> Result<DbFile, PlanetError> {
+        return self.reference_file_blob(db_file, None)
+    }
+    fn reference_file_blob(&mut self, db_file: &DbFile, blob: Option<&mut File>) -> Result<DbFile, PlanetError> {
+        let mut db_file = db_file.clone();
+        let hash = db_file.hash.clone().unwrap_or_default();
+        if db_file.content.is_none() && db_file.path.is_none() && self.get_file_content(&hash)?.is_none() {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("No content found for file \"{}\".", &hash)),
+                )
+            )
+        }
+        let file_content = DbFileContent{
+            hash: hash.clone(),
+            size: db_file.size.unwrap_or_default(),
+            references: 1,
+            content: db_file.content.clone(),
+            path: db_file.path.clone(),
+        };
+        let previous = self.add_file_reference(&file_content, blob)?;
+        if previous.is_some() {
+            let previous = previous.unwrap();
+            if previous.path.is_some() {
+                db_file.path = previous.path.clone();
+            }
+        }
+        db_file.content = None;
+        return Ok(db_file)
+    }
+    // Removes a reference, orphan content is deleted from database and OS files dir. Last reference to an 
+    // encrypted file leaves the record with no references while file is deleted, new references wait for 
+    // it. Record is removed after, only if it still has no references.
+    pub fn release_file(&mut self, hash: &String) -> Result<(), PlanetError> {
+        let (previous, updated) = self.update_file_content(hash, |current| {
+            if current.is_none() {
+                return None
+            }
+            let mut current = current.unwrap();
+            if current.references == 0 {
+                // Already being deleted
+                return Some(current)
+            }
+            if current.references > 1 || current.path.is_some() {
+                current.references -= 1;
+                return Some(current)
+            }
+            return None
+        })?;
+        if previous.is_none() || updated.is_none() {
+            return Ok(())
+        }
+        let previous = previous.unwrap();
+        let updated = updated.unwrap();
+        if previous.references == 0 || updated.references > 0 {
+            return Ok(())
+        }
+        let path = updated.path.unwrap();
+        let result = fs::remove_file(path.clone());
+        self.update_file_content(hash, |current| {
+            if current.is_some() && current.clone().unwrap().references == 0 {
+                return None
+            }
+            return current
+        })?;
+        if result.is_err() {
+            return Err(
+                PlanetError::new(500, Some(tr!(
+                    "Error deleting file \"{}\"", &path
+                )))
+            )
+        }
+        return Ok(())
+    }
+    pub fn delete_file(&mut self, id: &String) -> Result<(), PlanetError> {
+        let db_file = self.get_file(id);
+        if db_file.is_err() {
+            let error = db_file.unwrap_err();
+            if error.error_code == 404 {
+                return Ok(())
+            }
+            return Err(error)
+        }
+        let db_file = db_file.unwrap();
+        let db = self.open_files_db()?;
+        let id_db = xid::Id::from_str(id.as_str()).unwrap();
+        let result = db.remove(id_db.as_bytes());
+        if result.is_err() {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Could not delete file \"{}\" from file database.", id)),
+                )
+            )
+        }
+        let options = db_file.options.clone().unwrap_or_default();
+        for (key, thumbnail_id) in options {
+            if key.starts_with(FILE_PROP_THUMBNAIL) {
+                self.delete_file(&thumbnail_id)?;
+            }
+        }
+        if db_file.hash.is_some() {
+            let hash = db_file.hash.unwrap();
+            self.release_file(&hash)?;
+        } else if db_file.path.is_some() {
+            let _ = fs::remove_file(db_file.path.unwrap());
+        }
+        return Ok(())
+    }
+    pub fn delete_files(&mut self, ids: &Vec<String>) -> Result<(), PlanetError> {
+        for id in ids {
+            self.delete_file(id)?;
+        }
+        return Ok(())
+    }
+    fn remove_files(&mut self, folder: &DbData, item: &DbData) -> Result<(), PlanetError> {
+        let folder_data = folder.data.clone().unwrap_or_default();
+        let item_data = item.data.clone().unwrap_or_default();
+        let columns = folder_data.get(COLUMNS);
+        if columns.is_none() {
+            return Ok(())
+        }
+        let columns = columns.unwrap();
+        for column in columns {
+            let column_type = column.get(COLUMN_TYPE);
+            let column_id = column.get(ID);
+            if column_type.is_none() || column_id.is_none() {
+                continue
+            }
+            if column_type.unwrap().as_str() != COLUMN_TYPE_FILE {
+                continue
+            }
+            let values = item_data.get(column_id.unwrap());
+            if values.is_none() {
+                continue
+            }
+            for value in values.unwrap() {
+                let mut file_id = value.get(ID);
+                if file_id.is_none() {
+                    file_id = value.get(VALUE);
+                }
+                if file_id.is_none() {
+                    continue
+                }
+                self.delete_file(file_id.unwrap())?;
+            }
+        }
+        return Ok(())
+    }
+
+    pub fn get_value(column_id: &String, item: &DbData) -> Result<String, PlanetError> {
+        let item = item.clone();
+        let data_map = item.clone().data.unwrap();
+        let column_id = column_id.clone();
+        let item_values = data_map.get(&column_id);
+        if item_values.is_some() {
+            let item_values = item_values.unwrap();
+            let value = get_value_list(item_values);
+            if value.is_some() {
+                let value = value.unwrap();
+                return Ok(value)
+            }
+        }
+        return Err(
+            PlanetError::new(
+                500, 
+                Some(tr!("Could not find item in file database.")),
+            )
+        )
+    }
+
+    pub fn get_index_item(
+        index_tree: sled::Tree, 
+        item_id: &IVec
+    ) -> Result<DbData, PlanetError> {
+        let item_id = item_id.clone();
+        let shared_key: SharedKey = SharedKey::from_array(CHILD_PRIVATE_KEY_ARRAY);
+        let index_item = index_tree.get(item_id);
+        if index_item.is_err() {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Could not find item in index database.")),
+                )
+            )
+        }
+        let index_item = index_item.unwrap();
+        if index_item.is_none() {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Index item is empty.")),
+                )
+            )
+        }
+        let index_item = index_item.unwrap().to_vec();
+        let index_item_ = EncryptedMessage::deserialize(
+            index_item
+        ).unwrap();
+        let index_item_ = DbData::decrypt_owned(
+            &index_item_, 
+            &shared_key);
+        if index_item_.is_err() {
+            return Err(
+                PlanetError::new(
+                    500, 
+                    Some(tr!("Error decrypting the index item object.")),
+                )
+            )
+        }
+        let index_item = index_item_.unwrap();
+        return Ok(index_item)
+    }
+
+    pub fn get_language_code_by_config(
+        column_config_map: &BTreeMap<String, ColumnConfig>,
+        data_map: &BTreeMap<String, Vec<BTreeMap<String, String>>>,
+    ) -> String {
+        let mut language_code = LANGUAGE_CODE_ENGLISH.to_string();
+        for (_column_name, v) in column_config_map.clone() {
+            let column_type = v.column_type.unwrap();
+            let column_type = column_type.as_str();
+            let config_column_id = v.id.unwrap();
+            if column_type == COLUMN_TYPE_LANGUAGE {
+                let language_code_ = data_map.get(&config_column_id);
+                if language_code_.is_some() {
+                    let language_code_ = language_code_.unwrap();
+                    let language_code_ = get_value_list(language_code_);
+                    if language_code_.is_some() {
+                        let language_code_ = language_code_.unwrap();
+                        language_code = language_code_;
+                    }
+                }
+            }
+        }
+        return language_code
+
+    }
+
+    pub fn get_translation_language_codes(
+        data_map: &BTreeMap<String, Vec<BTreeMap<String, String>>>,
+    ) -> Vec<String> {
+        let mut language_codes: Vec<String> = Vec::new();
+        let translations = data_map.get(TRANSLATIONS);
+        if translations.is_some() {
+            let translations = translations.unwrap();
+            for translation in translations {
+                let language = translation.get(LANGUAGE);
+                if language.is_some() {
+                    let language = language.unwrap().clone();
+                    if !language_codes.contains(&language) {
+                        language_codes.push(language);
+                    }
+                }
+            }
+        }
+        return language_codes
+    }
+
+    fn open_unique_index(&mut self) -> Result<sled::Tree, PlanetError> {
+        if self.unique_index.is_some() {
+            let tree = self.unique_index.clone().unwrap();
+            return Ok(tree)
+        }
+        let folder_id = self.folder_id.clone().unwrap_or_default();
+        // folders/c7c815is1s406kaf3j30/unique.index
+        let path = format!(
+            "folders/{folder_id}/unique.index",
+            folder_id=folder_id,
+        );
+        let result = self.database.open_tree(path);
+        if result.is_err() {
+            return Err(
+                PlanetError::new(500, Some(tr!("Could not open unique index.")))
+            )
+        }
+        let tree = result.unwrap();
+        self.unique_index = Some(tree.clone());
+        return Ok(tree)
+    }
+
+    fn get_unique_value(column_type: &str, value: &String) -> String {
+        let value = value.trim();
+        if column_type == COLUMN_TYPE_EMAIL {
+            return value.to_lowercase()
+        }
+        return value.to_string()
+    }
+
+    // Returns list of (label, key) for unique columns and unique keys found in item data. Keys are hashed
+    // before writing to the index, so values are not stored in clear text.
+    pub fn get_unique_entries(folder: &DbData, db_data: &DbData) -> Vec<(String, String)> {
+        let mut entries: Vec<(String, String)> = Vec::new();
+        let folder_data = folder.data.clone();
+        let item_data = db_data.data.clone();
+        if folder_data.is_none() || item_data.is_none() {
+            return entries
+        }
+        let folder_data = folder_data.unwrap();
+        let item_data = item_data.unwrap();
+        let mut column_type_map: BTreeMap<String, String> = BTreeMap::new();
+        let columns = folder_data.get(COLUMNS);
+        if columns.is_some() {
+            let columns = columns.unwrap();
+            for column in columns {
+                let column_id = column.get(ID);
+                let column_type = column.get(COLUMN_TYPE);
+                if column_id.is_none() || column_type.is_none() {
+                    continue
+                }
+                let column_id = column_id.unwrap();
+                let column_type = column_type.unwrap();
+                column_type_map.insert(column_id.clone(), column_type.clone());
+                let unique = column.get(UNIQUE);
+                if unique.is_none() || unique.unwrap().clone() != TRUE.to_string() {
+                    continue
+                }
+                let column_name = column.get(NAME).unwrap().clone();
+                let values = item_data.get(column_id);
+                if values.is_some() {
+                    let values = values.unwrap();
+                    for value_map in values {
+                        let value = value_map.get(VALUE);
+                        if value.is_some() {
+                            let value = TreeFolderItem::get_unique_value(
+                                column_type.as_str(), value.unwrap()
+                            );
+                            if value == String::from("") {
+                                continue
+                            }
+                            let key = format!("{}:{}", column_id, &value);
+                            entries.push((column_name.clone(), key));
+                        }
+                    }
+                }
+            }
+        }
+        let unique_keys = folder_data.get(UNIQUE_KEYS);
+        if unique_keys.is_some() {
+            let unique_keys = unique_keys.unwrap();
+            for unique_key in unique_keys {
+                let key_name = unique_key.get(NAME).unwrap().clone();
+                let column_ids = unique_key.get(COLUMN_IDS).unwrap().clone();
+                let column_ids: Vec<&str> = column_ids.split(",").collect();
+                let mut key_values: Vec<String> = Vec::new();
+                for column_id in column_ids.clone() {
+                    let values = item_data.get(column_id);
+                    if values.is_some() {
+                        let value = get_value_list(values.unwrap());
+                        if value.is_some() {
+                            let column_type = column_type_map.get(column_id).unwrap().clone();
+                            let value = TreeFolderItem::get_unique_value(
+                                column_type.as_str(), &value.unwrap()
+                            );
+                            if value != String::from("") {
+                                key_values.push(value);
+                            }
+                        }
+                    }
+                }
+                // Items without all key columns informed do not take part in the unique key
+                if key_values.len() != column_ids.len() {
+                    continue
+                }
+                let key = format!("{}:{}", &key_name, key_values.join("\u{1f}"));
+                entries.push((key_name.clone(), key));
+            }
+        }
+        return entries
+    }
+
+    fn get_unique_key_db(key: &String) -> Vec<u8> {
+        let mut hasher = Sha256::new();
+        hasher.update(key.as_bytes());
+        let key_db = hasher.finalize().to_vec();
+        return key_db
+    }
+
+    fn release_unique_keys(tree: &sled::Tree, keys: &Vec<Vec<u8>>) {
+        for key_db in keys {
+            let _ = tree.remove(key_db.clone());
+        }
+    }
+
+    // Keys are claimed with compare and swap, so two items can't take the same value at once. Keys of 
+    // previous item data no longer used are removed after new ones are claimed.
+    pub fn write_unique(
+        &mut self, 
+        folder: &DbData, 
+        db_data: &DbData, 
+        previous: Option<DbData>
+    ) -> Result<(), PlanetError> {
+        let item_id = db_data.id.clone().unwrap_or_default();
+        let tree = self.open_unique_index()?;
+        let entries = TreeFolderItem::get_unique_entries(folder, db_data);
+        let mut keys: Vec<Vec<u8>> = Vec::new();
+        let mut keys_claimed: Vec<Vec<u8>> = Vec::new();
+        for (label, key) in entries {
+            let key_db = TreeFolderItem::get_unique_key_db(&key);
+            keys.push(key_db.clone());
+            let result = tree.compare_and_swap(
+                key_db.clone(), 
+                None as Option<&[u8]>, 
+                Some(item_id.as_bytes())
+            );
+            if result.is_err() {
+                TreeFolderItem::release_unique_keys(&tree, &keys_claimed);
+                return Err(
+                    PlanetError::new(500, Some(tr!("Error writing into unique index.")))
+                )
+            }
+            let result = result.unwrap();
+            if result.is_ok() {
+                keys_claimed.push(key_db);
+                continue
+            }
+            let current = result.unwrap_err().current;
+            let unique_item_id = String::from_utf8(current.map(|value| value.to_vec()).unwrap_or_default()).unwrap_or_default();
+            if unique_item_id != item_id {
+                TreeFolderItem::release_unique_keys(&tree, &keys_claimed);
+                return Err(
+                    PlanetError::new(
+                        500, 
+                        Some(tr!(
+                            "Unique constraint {}{}{} failed, value already exists in folder.", 
+                            String::from("\"").blue(), &label.blue(), String::from("\"").blue()
+                        ))
+                    )
+                )
+            }
+        }
+        if previous.is_some() {
+            let previous = previous.unwrap();
+            let entries = TreeFolderItem::get_unique_entries(folder, &previous);
+            for (_label, key) in entries {
+                let key_db = TreeFolderItem::get_unique_key_db(&key);
+                if keys.contains(&key_db) {
+                    continue
+                }
+                let _ = tree.compare_and_swap(
+                    key_db, 
+                    Some(item_id.as_bytes()), 
+                    None as Option<&[u8]>
+                );
+            }
+        }
+        return Ok(())
+    }
+
+    pub fn remove_unique(&mut self, folder: &DbData, db_data: &DbData) -> Result<(), PlanetError> {
+        return self.release_unique(folder, db_data, None)
+    }
+
+    // Releases keys claimed for item data when item could not be written. Keys also used by previous item 
+    // data are kept, since item still holds those values.
+    pub fn release_unique(
+        &mut self, 
+        folder: &DbData, 
+        db_data: &DbData, 
+        previous: Option<DbData>
+    ) -> Result<(), PlanetError> {
+        let item_id = db_data.id.clone().unwrap_or_default();
+        let tree = self.open_unique_index()?;
+        let mut keys_previous: Vec<Vec<u8>> = Vec::new();
+        if previous.is_some() {
+            let previous = previous.unwrap();
+            let entries = TreeFolderItem::get_unique_entries(folder, &previous);
+            for (_label, key) in entries {
+                keys_previous.push(TreeFolderItem::get_unique_key_db(&key));
+            }
+        }
+        let entries = TreeFolderItem::get_unique_entries(folder, db_data);
+        for (_label, key) in entries {
+            let key_db = TreeFolderItem::get_unique_key_db(&key);
+            if keys_previous.contains(&key_db) {
+                continue
+            }
+            // Only remove entries owned by this item
+            let _ = tree.compare_and_swap(
+                key_db, 
+                Some(item_id.as_bytes()), 
+                None as Option<&[u8]>
+            );
+        }
+        return Ok(())
+    }
+
+    fn open_path_index(&mut self) -> Result<sled::Tree, PlanetError> {
+        if self.path_index.is_some() {
+            let tree = self.path_index.clone().unwrap();
+            return Ok(tree)
+        }
+        let folder_id = self.folder_id.clone().unwrap_or_default();
+        // folders/c7c815is1s406kaf3j30/paths.index
+        let path = format!(
+            "folders/{folder_id}/paths.index",
+            folder_id=folder_id,
+        );
+        let result = self.database.open_tree(path);
+        if result.is_err() {
+            return Err(
+                PlanetError::new(500, Some(tr!("Could not open paths index.")))
+            )
+        }
+        let tree = result.unwrap();
+        self.path_index = Some(tree.clone());
+        return Ok(tree)
+    }
+
+    // Paths index keys are hash of column id, path and value, followed by item id, so we can get items 
+    // for a path value with a prefix scan.
+    fn get_path_key_prefix(column_id: &String, path: &String, value: &String) -> Vec<u8> {
+        let key = format!("{}:{}:{}", column_id, path, value);
+        return TreeFolderItem::get_unique_key_db(&key)
+    }
+
+    pub fn get_path_entries(folder: &DbData, db_data: &DbData) -> Vec<Vec<u8>> {
+        let mut entries: Vec<Vec<u8>> = Vec::new();
+        let folder_data = folder.data.clone();
+        let item_data = db_data.data.clone();
+        let item_id = db_data.id.clone().unwrap_or_default();
+        let item_id_db = xid::Id::from_str(item_id.as_str());
+        if folder_data.is_none() || item_data.is_none() || item_id_db.is_err() {
+            return entries
+        }
+        let item_id_db = item_id_db.unwrap();
+        let folder_data = folder_data.unwrap();
+        let item_data = item_data.unwrap();
+        let columns = folder_data.get(COLUMNS);
+        if columns.is_none() {
+            return entries
+        }
+        let columns = columns.unwrap();
+        for column in columns {
+            let indexed_paths = column.get(INDEXED_PATHS);
+            let column_id = column.get(ID);
+            if indexed_paths.is_none() || column_id.is_none() {
+                continue
+            }
+            let column_id = column_id.unwrap();
+            let values = item_data.get(column_id);
+            if values.is_none() {
+                continue
+            }
+            let source = get_value_list(values.unwrap());
+            if source.is_none() {
+                continue
+            }
+            let source = source.unwrap();
+            for path in indexed_paths.unwrap().split(",") {
+                let path = path.to_string();
+                let path_values = ObjectColumn::get_path_values(&source, &path);
+                for path_value in path_values {
+                    let path_value = ObjectColumn::get_path_index_value(&path_value);
+                    let mut key = TreeFolderItem::get_path_key_prefix(
+                        column_id, &path, &path_value
+                    );
+                    key.extend_from_slice(item_id_db.as_bytes());
+                    if !entries.contains(&key) {
+                        entries.push(key);
+                    }
+                }
+            }
+        }
+        return entries
+    }
+
+    pub fn write_path_index(
+        &mut self, 
+        folder: &DbData, 
+        db_data: &DbData, 
+        previous: Option<DbData>
+    ) -> Result<(), PlanetError> {
+        let item_id = db_data.id.clone().unwrap_or_default();
+        if previous.is_some() {
+            self.remove_path_index(folder, &previous.unwrap())?;
+        }
+        let entries = TreeFolderItem::get_path_entries(folder, db_data);
+        if entries.len() == 0 {
+            return Ok(())
+        }
+        let tree = self.open_path_index()?;
+        for key in entries {
+            let result = tree.insert(key, item_id.as_bytes());
+            if result.is_err() {
+                return Err(
+                    PlanetError::new(500, Some(tr!("Error writing into paths index.")))
+                )
+            }
+        }
+        return Ok(())
+    }
+
+    pub fn remove_path_index(&mut self, folder: &DbData, db_data: &DbData) -> Result<(), PlanetError> {
+        let entries = TreeFolderItem::get_path_entries(folder, db_data);
+        if entries.len() == 0 {
+            return Ok(())
+        }
+        let tree = self.open_path_index()?;
+        for key in entries {
+            let _ = tree.remove(key);
+        }
+        return Ok(())
+    }
+
+    pub fn rebuild_path_index(&mut self, folder: &DbData) -> Result<(), PlanetError> {
+        let tree = self.open_path_index()?;
+        let result = tree.clear();
+        if result.is_err() {
+            return Err(
+                PlanetError::new(500, Some(tr!("Error writing into paths index.")))
+            )
+        }
+        let items = self.get_all_items()?;
+        for item in items {
+            self.write_path_index(folder, &item, None)?;
+        }
+        return Ok(())
+    }
+
+    pub fn get_path_index_ids(
+        &mut self, 
+        column_id: &String, 
+        path: &String, 
+        value: &String
+    ) -> Result<Vec<String>, PlanetError> {
+        let tree = self.open_path_index()?;
+        let prefix = TreeFolderItem::get_path_key_prefix(column_id, path, value);
+        let mut ids: Vec<String> = Vec::new();
+        for result in tree.scan_prefix(prefix) {
+            if result.is_err() {
+                return Err(
+                    PlanetError::new(500, Some(tr!("Could not read paths index.")))
+                )
+            }
+            let (_, item_id) = result.unwrap();
+            let item_id = String::from_utf8(item_id.to_vec()).unwrap_or_default();
+            ids.push(item_id);
+        }
+        return Ok(ids)
+    }
+
+    fn open_geo_index(&mut self) -> Result<sled::Tree, PlanetError> {
+        if self.geo_index.is_some() {
+            let tree = self.geo_index.clone().unwrap();
+            return Ok(tree)
+        }
+        let folder_id = self.folder_id.clone().unwrap_or_default();
+        // folders/c7c815is1s406kaf3j30/geo.index
+        let path = format!(
+            "folders/{folder_id}/geo.index",
+            folder_id=folder_id,
+        );
+        let result = self.database.open_tree(path);
+        if result.is_err() {
+            return Err(
+                PlanetError::new(500, Some(tr!("Could not open geo index.")))
+            )
+        }
+        let tree = result.unwrap();
+        self.geo_index = Some(tree.clone());
+        return Ok(tree)
+    }
+
+    // Geo index keys are hash of column id and geohash cell, followed by item id. We write one entry for 
+    // each geohash precision, so locations are not stored in clear and we can still get items in a cell 
+    // of any size with a prefix scan.
+    fn get_geo_key_prefix(column_id: &String, cell: &String) -> Vec<u8> {
+        let key = format!("{}:{}", column_id, cell);
+        return TreeFolderItem::get_unique_key_db(&key)
+    }
+
+    pub fn get_geo_entries(folder: &DbData, db_data: &DbData) -> Vec<Vec<u8>> {
+        let mut entries: Vec<Vec<u8>> = Vec::new();
+        let folder_data = folder.data.clone();
+        let item_data = db_data.data.clone();
+        let item_id = db_data.id.clone().unwrap_or_default();
+        let item_id_db = xid::Id::from_str(item_id.as_str());
+        if folder_data.is_none() || item_data.is_none() || item_id_db.is_err() {
+            return entries
+        }
+        let item_id_db = item_id_db.unwrap();
+        let folder_data = folder_data.unwrap();
+        let item_data = item_data.unwrap();
+        let columns = folder_data.get(COLUMNS);
+        if columns.is_none() {
+            return entries
+        }
+        let columns = columns.unwrap();
+        for column in columns {
+            let column_type = column.get(COLUMN_TYPE);
+            let column_id = column.get(ID);
+            if column_type.is_none() || column_id.is_none() {
+                continue
+            }
+            if column_type.unwrap().as_str() != COLUMN_TYPE_LOCATION {
+                continue
+            }
+            let column_id = column_id.unwrap();
+            let values = item_data.get(column_id);
+            if values.is_none() {
+                continue
+            }
+            for value in values.unwrap() {
+                let value = value.get(VALUE);
+                if value.is_none() {
+                    continue
+                }
+                let location = Location::parse(value.unwrap());
+                if location.is_err() {
+                    continue
+                }
+                let geohash = location.unwrap().get_geohash(GEOHASH_PRECISION);
+                for precision in 1..GEOHASH_PRECISION+1 {
+                    let cell = geohash[..precision].to_string();
+                    let mut key = TreeFolderItem::get_geo_key_prefix(column_id, &cell);
+                    key.extend_from_slice(item_id_db.as_bytes());
+                    if !entries.contains(&key) {
+                        entries.push(key);
+                    }
+                }
+            }
+        }
+        return entries
+    }
+
+    pub fn write_geo_index(
+        &mut self, 
+        folder: &DbData, 
+        db_data: &DbData, 
+        previous: Option<DbData>
+    ) -> Result<(), PlanetError> {
+        let item_id = db_data.id.clone().unwrap_or_default();
+        if previous.is_some() {
+            self.remove_geo_index(folder, &previous.unwrap())?;
+        }
+        let entries = TreeFolderItem::get_geo_entries(folder, db_data);
+        if entries.len() == 0 {
+            return Ok(())
+        }
+        let tree = self.open_geo_index()?;
+        for key in entries {
+            let result = tree.insert(key, item_id.as_bytes());
+            if result.is_err() {
+                return Err(
+                    PlanetError::new(500, Some(tr!("Error writing into geo index.")))
+                )
+            }
+        }
+        return Ok(())
+    }
+
+    pub fn remove_geo_index(&mut self, folder: &DbData, db_data: &DbData) -> Result<(), PlanetError> {
+        let entries = TreeFolderItem::get_geo_entries(folder, db_data);
+        if entries.len() == 0 {
+            return Ok(())
+        }
+        let tree = self.open_geo_index()?;
+        for key in entries {
+            let _ = tree.remove(key);
+        }
+        return Ok(())
+    }
+
+    pub fn get_geo_index_ids(
+        &mut self, 
+        column_id: &String, 
+        cells: &Vec<String>
+    ) -> Result<Vec<String>, PlanetError> {
+        let tree = self.open_geo_index()?;
+        let mut ids: Vec<String> = Vec::new();
+        for cell in cells {
+            let prefix = TreeFolderItem::get_geo_key_prefix(column_id, cell);
+            for result in tree.scan_prefix(prefix) {
+                if result.is_err() {
+                    return Err(
+                        PlanetError::new(500, Some(tr!("Could not read geo index.")))
+                    )
+                }
+                let (_, item_id) = result.unwrap();
+                let item_id = String::from_utf8(item_id.to_vec()).unwrap_or_default();
+                if !ids.contains(&item_id) {
+                    ids.push(item_id);
+                }
+            }
+        }
+        return Ok(ids)
+    }
+
+    pub fn get_item_tuple(&mut self, item_id: &String) -> Result<(u16, (IVec, IVec), sled::Tree), PlanetError> {
+        let partition = self.get_partition(item_id)?;
+        self.tree = None;
+        self.index = None;
+        let (db, index) = self.open_partition(&partition)?;
+        let id_db = xid::Id::from_str(item_id.as_str()).unwrap();
+        let id_db = id_db.as_bytes();
+        let result = db.get(id_db);
+        if result.is_err() {
+            return Err(
+                PlanetError::new(500, Some(tr!("Could not fetch item from database")))
+            )
+        }
+        let result = result.unwrap();
+        if result.is_none() {
+            return Err(
+                PlanetError::new(404, Some(tr!("Item \"{}\" not found.", item_id)))
+            )
+        }
+        let key = IVec::from(id_db.to_vec());
+        return Ok((partition, (key, result.unwrap()), index))
+    }
+
+    fn get_folder_item(&self, folder_id: &String) -> TreeFolderItem {
+        let mut folder_item = self.clone();
+        folder_item.folder_id = Some(folder_id.clone());
+        folder_item.tree = None;
+        folder_item.index = None;
+        folder_item.files_db = None;
+        folder_item.tree_partitions = None;
+        folder_item.unique_index = None;
+        folder_item.path_index = None;
+        folder_item.geo_index = None;
+        return folder_item
+    }
+
+    pub fn get_link_rule(column: &BTreeMap<String, String>) -> String {
+        let on_delete = column.get(ON_DELETE);
+        if on_delete.is_some() {
+            return on_delete.unwrap().clone()
+        }
+        // Older folders only have DeleteOnLinkDrop
+        let delete_on_link_drop = column.get(DELETE_ON_LINK_DROP);
+        if delete_on_link_drop.is_some() {
+            let delete_on_link_drop = delete_on_link_drop.unwrap().clone();
+            if delete_on_link_drop == String::from("1") || delete_on_link_drop == String::from(TRUE) {
+                return ON_DELETE_CASCADE.to_string()
+            }
+        }
+        return ON_DELETE_SET_NULL.to_string()
+    }
+
+    pub fn get_link_ids(column_id: &String, item: &DbData) -> Vec<String> {
+        let mut ids: Vec<String> = Vec::new();
+        let data = item.data.clone();
+        if data.is_none() {
+            return ids
+        }
+        let data = data.unwrap();
+        let list = data.get(column_id);
+        if list.is_some() {
+            let list = list.unwrap();
+            for item_object in list {
+                let id = item_object.get(ID);
+                if id.is_some() {
+                    ids.push(id.unwrap().clone());
+                }
+            }
+        }
+        return ids
+    }
+
+    pub fn get_all_items(&mut self) -> Result<Vec<DbData>, PlanetError> {
+        let shared_key: SharedKey = SharedKey::from_array(CHILD_PRIVATE_KEY_ARRAY);
+        let mut items: Vec<DbData> = Vec::new();
+        let partitions = self.get_partitions()?;
+        for partition in partitions {
+            // open_partition keeps the last opened tree, reset so every partition is read
+            self.tree = None;
+            self.index = None;
+            let (db, _) = self.open_partition(&partition)?;
+            for db_result in db.iter() {
+                if db_result.is_err() {
+                    continue
+                }
+                let (_, db_item) = db_result.unwrap();
+                let item_ = EncryptedMessage::deserialize(db_item.to_vec());
+                if item_.is_err() {
+                    continue
+                }
+                let item_ = DbData::decrypt_owned(
+                    &item_.unwrap(), 
+                    &shared_key
+                );
+                if item_.is_ok() {
+                    items.push(item_.unwrap());
+                }
+            }
+        }
+        self.tree = None;
+        self.index = None;
+        return Ok(items)
+    }
+
+    fn remove_link(
+        &mut self, 
+        column_id: &String, 
+        item_id: &String, 
+        linked_id: &String
+    ) -> Result<(), PlanetError> {
+        let folder_id = self.folder_id.clone().unwrap_or_default();
+        let folder = self.tree_folder.get(&folder_id)?;
+        let folder_name = folder.name.unwrap_or_default();
+        let item = self.get(&folder_name, GetItemOption::ById(item_id.clone()), None);
+        if item.is_err() {
+            // Item already removed
+            return Ok(())
+        }
+        let mut item = item.unwrap();
+        let mut data = item.data.clone().unwrap_or_default();
+        let list = data.get(column_id);
+        if list.is_none() {
+            return Ok(())
+        }
+        let list = list.unwrap().clone();
+        let mut list_new: Vec<BTreeMap<String, String>> = Vec::new();
+        for item_object in list {
+            let id = item_object.get(ID);
+            if id.is_some() && *id.unwrap() == *linked_id {
+                continue
+            }
+            list_new.push(item_object);
+        }
+        if list_new.len() == 0 {
+            data.remove(column_id);
+        } else {
+            data.insert(column_id.clone(), list_new);
+        }
+        item.data = Some(data);
+        self.update(&item)?;
+        return Ok(())
+    }
+
+    pub fn add_link(
+        &mut self, 
+        column_id: &String, 
+        item_id: &String, 
+        linked_id: &String
+    ) -> Result<(), PlanetError> {
+        let folder_id = self.folder_id.clone().unwrap_or_default();
+        let folder = self.tree_folder.get(&folder_id)?;
+        let folder_name = folder.name.unwrap_or_default();
+        let mut item = self.get(&folder_name, GetItemOption::ById(item_id.clone()), None)?;
+        let mut data = item.data.clone().unwrap_or_default();
+        let mut list: Vec<BTreeMap<String, String>> = Vec::new();
+        let list_wrap = data.get(column_id);
+        if list_wrap.is_some() {
+            list = list_wrap.unwrap().clone();
+        }
+        for item_object in &list {
+            let id = item_object.get(ID);
+            if id.is_some() && *id.unwrap() == *linked_id {
+                return Ok(())
+            }
+        }
+        let mut item_object: BTreeMap<String, String> = BTreeMap::new();
+        item_object.insert(ID.to_string(), linked_id.clone());
+        list.push(item_object);
+        data.insert(column_id.clone(), list);
+        item.data = Some(data);
+        self.update(&item)?;
+        return Ok(())
+    }
+
+    // Links from items at linked folders to a new item, as (linked folder id, column id, linked item id). Links 
+    // already written are removed when one of them fails.
+    pub fn add_reverse_links(
+        &mut self, 
+        item_id: &String, 
+        links: &Vec<(String, String, String)>
+    ) -> Result<(), PlanetError> {
+        let mut links_added: Vec<(String, String, String)> = Vec::new();
+        for (linked_folder_id, column_id, linked_id) in links {
+            let mut linked_folder_item = self.get_folder_item(linked_folder_id);
+            let result = linked_folder_item.add_link(column_id, linked_id, item_id);
+            if result.is_err() {
+                let _ = self.remove_reverse_links(item_id, &links_added);
+                return Err(result.unwrap_err())
+            }
+            links_added.push((linked_folder_id.clone(), column_id.clone(), linked_id.clone()));
+        }
+        return Ok(())
+    }
+
+    pub fn remove_reverse_links(
+        &mut self, 
+        item_id: &String, 
+        links: &Vec<(String, String, String)>
+    ) -> Result<(), PlanetError> {
+        for (linked_folder_id, column_id, linked_id) in links {
+            let mut linked_folder_item = self.get_folder_item(linked_folder_id);
+            linked_folder_item.remove_link(column_id, linked_id, item_id)?;
+        }
+        return Ok(())
+    }
+
+    // Items just inserted are removed with their index, unique, path, geo and file entries
+    pub fn undo_insert(&mut self, items: &Vec<DbData>) -> Result<(), PlanetError> {
+        let folder_id = self.folder_id.clone().unwrap_or_default();
+        let folder = self.tree_folder.get(&folder_id)?;
+        for item in items {
+            self.remove_item(&folder, item)?;
+        }
+        return Ok(())
+    }
+
+    fn get_routing_moved(
+        routing_db: Option<BTreeMap<String, String>>, 
+        routing: &BTreeMap<String, String>
+    ) -> Option<BTreeMap<String, String>> {
+        let mut routing_db = routing_db.unwrap_or_default();
+        for (key, value) in routing {
+            routing_db.insert(key.clone(), value.clone());
+        }
+        if !routing.contains_key(SITE_ID) {
+            routing_db.remove(SITE_ID);
+        }
+        return Some(routing_db)
+    }
+
+    fn get_file_path_moved(&self, file_id: &String, routing: &BTreeMap<String, String>) -> String {
+        let home_dir = self.home_dir.clone().unwrap_or_default();
+        let space_id = routing.get(SPACE_ID).unwrap().clone();
+        let path_dir: String;
+        if space_id.as_str() == PRIVATE {
+            path_dir = format!(
+                "{home}/{private}/files", 
+                home=&home_dir, 
+                private=PRIVATE
+            );
+        } else {
+            let site_id = routing.get(SITE_ID).unwrap().clone();
+            path_dir = format!(
+                "{home}/sites/{site_id}/spaces/{space_id}/files", 
+                home=&home_dir, 
+                site_id=site_id,
+                space_id=space_id
+            );
+        }
+        let _ = create_dir_all(&path_dir);
+        return format!("{path_dir}/{file_id}.achieverenc", path_dir=path_dir, file_id=file_id)
+    }
+
+    fn get_folder_tree_names(&self) -> Vec<String> {
+        let folder_id = self.folder_id.clone().unwrap_or_default();
+        let prefix = format!("folders/{folder_id}/", folder_id=folder_id);
+        let mut tree_names: Vec<String> = Vec::new();
+        for tree_name in self.database.tree_names() {
+            let tree_name = String::from_utf8(tree_name.to_vec()).unwrap_or_default();
+            if tree_name.starts_with(&prefix) {
+                tree_names.push(tree_name);
+            }
+        }
+        return tree_names
+    }
+
+    pub fn remove_folder_trees(&mut self) -> Result<(), PlanetError> {
+        for tree_name in self.get_folder_tree_names() {
+            let result = self.database.drop_tree(tree_name.clone());
+            if result.is_err() {
+                return Err(
+                    PlanetError::new(500, Some(tr!("Error deleting database tree \"{}\".", &tree_name)))
+                )
+            }
+        }
+        self.tree = None;
+        self.index = None;
+        self.files_db = None;
+        self.tree_partitions = None;
+        self.unique_index = None;
+        self.path_index = None;
+        self.geo_index = None;
+        return Ok(())
+    }
+
+    // Folder trees with items we decrypt to route into target space, other trees (unique, paths and geo
+    // indexes) hold keys and ids and are copied as they are.
+    fn get_tree_kind(tree_name: &String, prefix: &String) -> TreeKind {
+        let name = tree_name.replacen(prefix.as_str(), "", 1);
+        if name.as_str() == "files.db" {
+            return TreeKind::Files
+        }
+        if name.as_str() == "partitions" {
+            return TreeKind::Data
+        }
+        let fields: Vec<&str> = name.split(".").collect();
+        if fields.len() == 2 && fields[0].len() == 4 && fields[0].chars().all(|c| c.is_ascii_digit()) && 
+            (fields[1] == "db" || fields[1] == "index") {
+            return TreeKind::Data
+        }
+        return TreeKind::Raw
+    }
+
+    fn copy_trees(
+        &mut self, 
+        target: &mut TreeFolderItem, 
+        routing: &BTreeMap<String, String>,
+        moved_files: &mut Vec<(String, String)>,
+        moved_hashes: &mut Vec<String>,
+    ) -> Result<(), PlanetError> {
+        let shared_key: SharedKey = SharedKey::from_array(CHILD_PRIVATE_KEY_ARRAY);
+        let folder_id = self.folder_id.clone().unwrap_or_default();
+        let prefix = format!("folders/{folder_id}/", folder_id=folder_id);
+        // partitions, db and index for each partition, unique index and files
+        for tree_name in self.get_folder_tree_names() {
+            let tree_kind = TreeFolderItem::get_tree_kind(&tree_name, &prefix);
+            let source_tree = self.database.open_tree(tree_name.clone());
+            let target_tree = target.database.open_tree(tree_name.clone());
+            if source_tree.is_err() || target_tree.is_err() {
+                return Err(
+                    PlanetError::new(500, Some(tr!("Could not open database tree \"{}\".", &tree_name)))
+                )
+            }
+            let source_tree = source_tree.unwrap();
+            let target_tree = target_tree.unwrap();
+            for result in source_tree.iter() {
+                if result.is_err() {
+                    return Err(
+                        PlanetError::new(500, Some(tr!("Could not read database tree \"{}\".", &tree_name)))
+                    )
+                }
+                let (key, value) = result.unwrap();
+                let mut value = value.to_vec();
+                if tree_kind == TreeKind::Files {
+                    let item_ = EncryptedMessage::deserialize(value.clone());
+                    if item_.is_err() {
+                        return Err(
+                            PlanetError::new(500, Some(tr!("Error decrypting file.")))
+                        )
+                    }
+                    let db_file = DbFile::decrypt_owned(&item_.unwrap(), &shared_key);
+                    if db_file.is_err() {
+                        return Err(
+                            PlanetError::new(500, Some(tr!("Error decrypting file.")))
+                        )
+                    }
+                    let mut db_file = db_file.unwrap();
+                    db_file.routing = TreeFolderItem::get_routing_moved(db_file.routing.clone(), routing);
+                    if db_file.hash.is_some() {
+                        // Content is referenced in target space once trees are copied
+                        let hash = db_file.hash.clone().unwrap();
+                        if db_file.path.is_some() {
+                            db_file.path = Some(self.get_file_path_moved(&hash, routing));
+                        }
+                        moved_hashes.push(hash);
+                    } else if db_file.path.is_some() {
+                        let path = db_file.path.clone().unwrap();
+                        let file_id = db_file.id.clone().unwrap_or_default();
+                        let path_new = self.get_file_path_moved(&file_id, routing);
+                        let result = fs::copy(&path, &path_new);
+                        if result.is_err() {
+                            return Err(
+                                PlanetError::new(500, Some(tr!("Could not move file \"{}\".", &path)))
+                            )
+                        }
+                        moved_files.push((path, path_new.clone()));
+                        db_file.path = Some(path_new);
+                    }
+                    value = db_file.encrypt(&shared_key).unwrap().serialize();
+                } else if tree_kind == TreeKind::Data {
+                    let item_ = EncryptedMessage::deserialize(value.clone());
+                    if item_.is_err() {
+                        return Err(
+                            PlanetError::new(500, Some(tr!("Could not fetch item from database")))
                         )
-                    )
+                    }
+                    let item = DbData::decrypt_owned(&item_.unwrap(), &shared_key);
+                    if item.is_err() {
+                        return Err(
+                            PlanetError::new(500, Some(tr!("Could not fetch item from database")))
+                        )
+                    }
+                    let mut item = item.unwrap();
+                    item.routing = TreeFolderItem::get_routing_moved(item.routing.clone(), routing);
+                    value = item.encrypt(&shared_key).unwrap().serialize();
                 }
-                let mut file = item_.unwrap();
-                let content = file.clone().content.unwrap();
-                let path = file.get_home_path();
-                let path = path.unwrap();
-                let mut file = File::create(path).unwrap();
-                let result = file.write(&content);
+                let result = target_tree.insert(key, value);
                 if result.is_err() {
                     return Err(
-                        PlanetError::new(
-                            500, 
-                            Some(tr!("Error writing into home directory.")),
-                        )
+                        PlanetError::new(500, Some(tr!("Could not write into database tree \"{}\".", &tree_name)))
                     )
                 }
-                file_size = result.unwrap();
-            },
-            Err(_) => {
-                return Err(
-                    PlanetError::new(
-                        500, 
-                        Some(tr!("Could not find item in file database.")),
-                    )
-                )
             }
         }
-        return Ok(file_size)
+        return Ok(())
     }
 
-    pub fn get_value(column_id: &String, item: &DbData) -> Result<String, PlanetError> {
-        let item = item.clone();
-        let data_map = item.clone().data.unwrap();
-        let column_id = column_id.clone();
-        let item_values = data_map.get(&column_id);
-        if item_values.is_some() {
-            let item_values = item_values.unwrap();
-            let value = get_value_list(item_values);
-            if value.is_some() {
-                let value = value.unwrap();
-                return Ok(value)
+    pub fn move_to(
+        &mut self, 
+        target: &mut TreeFolderItem, 
+        routing: &BTreeMap<String, String>
+    ) -> Result<(), PlanetError> {
+        let mut moved_files: Vec<(String, String)> = Vec::new();
+        let mut moved_hashes: Vec<String> = Vec::new();
+        let mut copied_contents: Vec<String> = Vec::new();
+        let mut result = self.copy_trees(target, routing, &mut moved_files, &mut moved_hashes);
+        if result.is_ok() {
+            result = self.move_file_contents(target, routing, &moved_hashes, &mut copied_contents);
+        }
+        // Sequences live in folders database (workspace or site), shared when both spaces are in same site
+        let folder_id = self.folder_id.clone().unwrap_or_default();
+        let shared_folder_db = self.tree_folder.site_id == target.tree_folder.site_id;
+        if result.is_ok() && !shared_folder_db {
+            result = self.tree_folder.copy_sequences(
+                &folder_id, 
+                &target.tree_folder, 
+                &folder_id, 
+                &BTreeMap::new()
+            );
+        }
+        if result.is_err() {
+            // Leave target space as it was
+            let _ = target.remove_folder_trees();
+            for (_, path_new) in moved_files {
+                let _ = remove_file(path_new);
+            }
+            for path_new in copied_contents {
+                let _ = remove_file(path_new);
             }
+            if !shared_folder_db {
+                let _ = target.tree_folder.drop_sequences(&folder_id);
+            }
+            return Err(result.unwrap_err())
         }
-        return Err(
-            PlanetError::new(
-                500, 
-                Some(tr!("Could not find item in file database.")),
-            )
-        )
+        self.remove_folder_trees()?;
+        if !shared_folder_db {
+            self.tree_folder.drop_sequences(&folder_id)?;
+        }
+        for (path, _) in moved_files {
+            let _ = remove_file(path);
+        }
+        for hash in moved_hashes {
+            self.release_file(&hash)?;
+        }
+        return Ok(())
     }
 
-    pub fn get_index_item(
-        index_tree: sled::Tree, 
-        item_id: &IVec
-    ) -> Result<DbData, PlanetError> {
-        let item_id = item_id.clone();
-        let shared_key: SharedKey = SharedKey::from_array(CHILD_PRIVATE_KEY_ARRAY);
-        let index_item = index_tree.get(item_id);
-        if index_item.is_err() {
-            return Err(
-                PlanetError::new(
-                    500, 
-                    Some(tr!("Could not find item in index database.")),
-                )
-            )
+    fn move_file_contents(
+        &mut self, 
+        target: &mut TreeFolderItem, 
+        routing: &BTreeMap<String, String>,
+        moved_hashes: &Vec<String>,
+        copied_contents: &mut Vec<String>,
+    ) -> Result<(), PlanetError> {
+        for hash in moved_hashes {
+            let file_content = self.get_file_content(hash)?;
+            if file_content.is_none() {
+                continue
+            }
+            let mut file_content = file_content.unwrap();
+            if file_content.path.is_some() {
+                let path = file_content.path.clone().unwrap();
+                let path_new = self.get_file_path_moved(hash, routing);
+                // Encrypted file is named by hash, copied when target space does not have it. Source content 
+                // is kept while referenced by other folders, released after move.
+                if !std::path::Path::new(&path_new).exists() {
+                    let result = fs::copy(&path, &path_new);
+                    if result.is_err() {
+                        return Err(
+                            PlanetError::new(500, Some(tr!("Could not move file \"{}\".", &path)))
+                        )
+                    }
+                    copied_contents.push(path_new.clone());
+                }
+                file_content.path = Some(path_new);
+            }
+            target.add_file_reference(&file_content, None)?;
         }
-        let index_item = index_item.unwrap();
-        if index_item.is_none() {
+        return Ok(())
+    }
+
+    fn remove_item(&mut self, folder: &DbData, item: &DbData) -> Result<(), PlanetError> {
+        let item_id = item.id.clone().unwrap_or_default();
+        let id_db = xid::Id::from_str(item_id.as_str()).unwrap();
+        let id_db = id_db.as_bytes();
+        // Routing in partitions tree is kept, partitions are assigned by number of items routed
+        let (db, index) = self.open_partition_by_item(&item_id)?;
+        let result = db.remove(id_db);
+        if result.is_err() {
             return Err(
-                PlanetError::new(
-                    500, 
-                    Some(tr!("Index item is empty.")),
-                )
+                PlanetError::new(500, Some(tr!("Could not delete item \"{}\".", &item_id)))
             )
         }
-        let index_item = index_item.unwrap().to_vec();
-        let index_item_ = EncryptedMessage::deserialize(
-            index_item
-        ).unwrap();
-        let index_item_ = DbData::decrypt_owned(
-            &index_item_, 
-            &shared_key);
-        if index_item_.is_err() {
+        let result = index.remove(id_db);
+        if result.is_err() {
             return Err(
-                PlanetError::new(
-                    500, 
-                    Some(tr!("Error decrypting the index item object.")),
-                )
+                PlanetError::new(500, Some(tr!("Could not delete item \"{}\" from index.", &item_id)))
             )
         }
-        let index_item = index_item_.unwrap();
-        return Ok(index_item)
+        self.remove_unique(folder, item)?;
+        self.remove_path_index(folder, item)?;
+        self.remove_geo_index(folder, item)?;
+        self.remove_files(folder, item)?;
+        return Ok(())
     }
 
-    pub fn get_language_code_by_config(
-        column_config_map: &BTreeMap<String, ColumnConfig>,
-        data_map: &BTreeMap<String, Vec<BTreeMap<String, String>>>,
-    ) -> String {
-        let mut language_code = LANGUAGE_CODE_ENGLISH.to_string();
-        for (_column_name, v) in column_config_map.clone() {
-            let column_type = v.column_type.unwrap();
-            let column_type = column_type.as_str();
-            let config_column_id = v.id.unwrap();
-            if column_type == COLUMN_TYPE_LANGUAGE {
-                let language_code_ = data_map.get(&config_column_id);
-                if language_code_.is_some() {
-                    let language_code_ = language_code_.unwrap();
-                    let language_code_ = get_value_list(language_code_);
-                    if language_code_.is_some() {
-                        let language_code_ = language_code_.unwrap();
-                        language_code = language_code_;
+    pub fn delete_item(
+        &mut self, 
+        id: &String, 
+        skip_folder_id: Option<String>,
+        visited: &mut Vec<String>,
+    ) -> Result<DbData, PlanetError> {
+        let folder_id = self.folder_id.clone().unwrap_or_default();
+        let folder = self.tree_folder.get(&folder_id)?;
+        let folder_name = folder.name.clone().unwrap_or_default();
+        let item = self.get(&folder_name, GetItemOption::ById(id.clone()), None)?;
+        let visited_key = format!("{}:{}", &folder_id, id);
+        if visited.contains(&visited_key) {
+            return Ok(item)
+        }
+        visited.push(visited_key);
+        // Check rules for items linked to this item, Restrict aborts before anything is removed
+        let mut rules: Vec<(String, String, String, Vec<String>)> = Vec::new();
+        let folder_data = folder.data.clone().unwrap_or_default();
+        let columns = folder_data.get(COLUMNS);
+        if columns.is_some() {
+            let columns = columns.unwrap();
+            for column in columns {
+                let column_type = column.get(COLUMN_TYPE);
+                if column_type.is_none() || column_type.unwrap().as_str() != COLUMN_TYPE_LINK {
+                    continue
+                }
+                let column_id = column.get(ID).unwrap().clone();
+                let linked_folder_name = column.get(LINKED_FOLDER);
+                if linked_folder_name.is_none() {
+                    continue
+                }
+                let linked_folder_name = linked_folder_name.unwrap();
+                let linked_folder = self.tree_folder.get_by_name(linked_folder_name)?;
+                if linked_folder.is_none() {
+                    continue
+                }
+                let linked_folder = linked_folder.unwrap();
+                let linked_folder_id = linked_folder.id.clone().unwrap_or_default();
+                if skip_folder_id.is_some() && skip_folder_id.clone().unwrap() == linked_folder_id {
+                    continue
+                }
+                let mut linked_ids: Vec<String> = Vec::new();
+                for linked_id in TreeFolderItem::get_link_ids(&column_id, &item) {
+                    let linked_key = format!("{}:{}", &linked_folder_id, &linked_id);
+                    if !visited.contains(&linked_key) {
+                        linked_ids.push(linked_id);
                     }
                 }
+                if linked_ids.len() == 0 {
+                    continue
+                }
+                // Rule is defined at the column of the linked folder pointing to this folder
+                let mut rule = ON_DELETE_SET_NULL.to_string();
+                let remote_column = TreeFolder::get_column_by_id(&column_id, &linked_folder);
+                if remote_column.is_ok() {
+                    rule = TreeFolderItem::get_link_rule(&remote_column.unwrap());
+                }
+                if rule.as_str() == ON_DELETE_RESTRICT {
+                    return Err(
+                        PlanetError::new(
+                            500, 
+                            Some(tr!(
+                                "Item \"{}\" cannot be deleted, it is linked from folder \"{}\".", 
+                                id, linked_folder_name
+                            ))
+                        )
+                    )
+                }
+                rules.push((linked_folder_id, column_id, rule, linked_ids));
             }
         }
-        return language_code
+        for (linked_folder_id, column_id, rule, linked_ids) in rules {
+            let mut linked_folder_item = self.get_folder_item(&linked_folder_id);
+            for linked_id in linked_ids {
+                if rule.as_str() == ON_DELETE_CASCADE {
+                    let result = linked_folder_item.delete_item(
+                        &linked_id, 
+                        skip_folder_id.clone(), 
+                        visited
+                    );
+                    if result.is_err() {
+                        let error = result.unwrap_err();
+                        if error.error_code != 404 {
+                            return Err(error)
+                        }
+                    }
+                } else {
+                    linked_folder_item.remove_link(&column_id, &linked_id, id)?;
+                }
+            }
+        }
+        self.remove_item(&folder, &item)?;
+        return Ok(item)
+    }
 
+    pub fn drop_links(&mut self) -> Result<(), PlanetError> {
+        let folder_id = self.folder_id.clone().unwrap_or_default();
+        let folder = self.tree_folder.get(&folder_id)?;
+        let folder_name = folder.name.clone().unwrap_or_default();
+        let folders = self.tree_folder.list()?;
+        // (folder id, column id, rule, items linking to folder being dropped)
+        let mut rules: Vec<(String, String, String, Vec<DbData>)> = Vec::new();
+        for linked_folder in folders {
+            let linked_folder_id = linked_folder.id.clone().unwrap_or_default();
+            if linked_folder_id == folder_id {
+                continue
+            }
+            let linked_folder_data = linked_folder.data.clone().unwrap_or_default();
+            let columns = linked_folder_data.get(COLUMNS);
+            if columns.is_none() {
+                continue
+            }
+            let columns = columns.unwrap();
+            let mut linked_folder_item = self.get_folder_item(&linked_folder_id);
+            let mut linked_items: Option<Vec<DbData>> = None;
+            for column in columns {
+                let column_type = column.get(COLUMN_TYPE);
+                if column_type.is_none() || column_type.unwrap().as_str() != COLUMN_TYPE_LINK {
+                    continue
+                }
+                let column_linked_folder = column.get(LINKED_FOLDER);
+                if column_linked_folder.is_none() || 
+                    column_linked_folder.unwrap().to_lowercase() != folder_name.to_lowercase() {
+                    continue
+                }
+                let column_id = column.get(ID).unwrap().clone();
+                let column_name = column.get(NAME).unwrap().clone();
+                let linked_folder_name = linked_folder.name.clone().unwrap_or_default();
+                if linked_items.is_none() {
+                    linked_items = Some(linked_folder_item.get_all_items()?);
+                }
+                let mut items: Vec<DbData> = Vec::new();
+                for item in linked_items.clone().unwrap() {
+                    if TreeFolderItem::get_link_ids(&column_id, &item).len() > 0 {
+                        items.push(item);
+                    }
+                }
+                let rule = TreeFolderItem::get_link_rule(column);
+                if rule.as_str() == ON_DELETE_RESTRICT && items.len() > 0 {
+                    return Err(
+                        PlanetError::new(
+                            500, 
+                            Some(tr!(
+                                "Folder \"{}\" cannot be dropped, it is linked from column \"{}\" at folder \"{}\".", 
+                                &folder_name, &column_name, &linked_folder_name
+                            ))
+                        )
+                    )
+                }
+                rules.push((linked_folder_id.clone(), column_id, rule, items));
+            }
+        }
+        let mut visited: Vec<String> = Vec::new();
+        for (linked_folder_id, column_id, rule, items) in rules {
+            let mut linked_folder_item = self.get_folder_item(&linked_folder_id);
+            let mut linked_folder = self.tree_folder.get(&linked_folder_id)?;
+            let linked_folder_name = linked_folder.name.clone().unwrap_or_default();
+            for item in items {
+                let item_id = item.id.clone().unwrap_or_default();
+                if rule.as_str() == ON_DELETE_CASCADE {
+                    let result = linked_folder_item.delete_item(
+                        &item_id, 
+                        Some(folder_id.clone()), 
+                        &mut visited
+                    );
+                    if result.is_err() {
+                        let error = result.unwrap_err();
+                        if error.error_code != 404 {
+                            return Err(error)
+                        }
+                    }
+                } else {
+                    let item = linked_folder_item.get(
+                        &linked_folder_name, 
+                        GetItemOption::ById(item_id.clone()), 
+                        None
+                    );
+                    if item.is_err() {
+                        continue
+                    }
+                    let mut item = item.unwrap();
+                    let mut data = item.data.clone().unwrap_or_default();
+                    data.remove(&column_id);
+                    item.data = Some(data);
+                    linked_folder_item.update(&item)?;
+                }
+            }
+            // Link column no longer has a folder to point to
+            linked_folder = self.tree_folder.get(&linked_folder_id)?;
+            let mut linked_folder_data = linked_folder.data.clone().unwrap_or_default();
+            let columns = linked_folder_data.get(COLUMNS);
+            if columns.is_some() {
+                let mut columns_new: Vec<BTreeMap<String, String>> = Vec::new();
+                for column in columns.unwrap() {
+                    let id = column.get(ID);
+                    if id.is_some() && *id.unwrap() == column_id {
+                        continue
+                    }
+                    columns_new.push(column.clone());
+                }
+                linked_folder_data.insert(COLUMNS.to_string(), columns_new);
+                linked_folder.data = Some(linked_folder_data);
+                self.tree_folder.update(&linked_folder)?;
+            }
+        }
+        return Ok(())
     }
 
 }
@@ -2077,6 +4180,9 @@ impl FolderItem for TreeFolderItem {
             index: None,
             tree_partitions: None,
             files_db: None,
+            unique_index: None,
+            path_index: None,
+            geo_index: None,
         };
         Ok(db_row)
     }
@@ -2102,10 +4208,12 @@ impl FolderItem for TreeFolderItem {
             // TODO: Insert missing data as None in db
             let tree_folder = self.tree_folder.clone();
             let folder = tree_folder.get_by_name(folder_name);
+            let mut folder_wrap: Option<DbData> = None;
             if folder.is_ok() {
                 let folder = folder.unwrap();
                 if folder.is_some() {
                     let folder = folder.unwrap();
+                    folder_wrap = Some(folder.clone());
                     let folder_data = folder.data.unwrap();
                     let columns = folder_data.get(COLUMNS);
                     if columns.is_some() {
@@ -2114,6 +4222,15 @@ impl FolderItem for TreeFolderItem {
                     }
                 }
             }
+            if folder_wrap.is_some() {
+                let folder = folder_wrap.clone().unwrap();
+                let unique_response = self.write_unique(&folder, &db_data, None);
+                if unique_response.is_err() {
+                    let error = unique_response.unwrap_err();
+                    errors.push(error);
+                    continue
+                }
+            }
             let encrypted_data = db_data.encrypt(&shared_key).unwrap();
             let encoded: Vec<u8> = encrypted_data.serialize();
             let id = db_data.id.clone().unwrap();
@@ -2139,6 +4256,19 @@ impl FolderItem for TreeFolderItem {
                     match item_ {
                         Ok(_) => {
                             let item = item_.unwrap();
+                            if folder_wrap.is_some() {
+                                let folder = folder_wrap.clone().unwrap();
+                                let paths_response = self.write_path_index(&folder, &item, None);
+                                if paths_response.is_err() {
+                                    let error = paths_response.unwrap_err();
+                                    errors.push(error);
+                                }
+                                let geo_response = self.write_geo_index(&folder, &item, None);
+                                if geo_response.is_err() {
+                                    let error = geo_response.unwrap_err();
+                                    errors.push(error);
+                                }
+                            }
                             let index_response = self.index(&item, &text_data);
                             if index_response.is_err() {
                                 let error = index_response.unwrap_err();
@@ -2153,6 +4283,9 @@ impl FolderItem for TreeFolderItem {
                     }
                 },
                 Err(_) => {
+                    if folder_wrap.is_some() {
+                        let _ = self.remove_unique(&folder_wrap.clone().unwrap(), &db_data);
+                    }
                     errors.push(PlanetError::new(500, Some(tr!("Could not insert data"))));
                 }
             }
@@ -2195,13 +4328,21 @@ impl FolderItem for TreeFolderItem {
         }
         let language_code = language_code.unwrap();
         // eprintln!("DbFolderItem.index :: language_code: {:#?}", &language_code);
-        let stop_words = get_stop_words_by_language(&language_code);
-        let stemmer = get_stemmer_by_language(&language_code);
         if map.is_some() {
             let map = map.unwrap();
             if map.len() == 1 {
                 let map = &map[0];
-                for (column_id, column_value) in map {
+                for (column_key, column_value) in map {
+                    // Translations come as {column_id}@{language}
+                    let mut column_id = column_key.clone();
+                    let mut column_language_code = language_code.clone();
+                    if column_key.find(TRANSLATION_SEPARATOR).is_some() {
+                        let fields: Vec<&str> = column_key.split(TRANSLATION_SEPARATOR).collect();
+                        column_id = fields[0].to_string();
+                        column_language_code = fields[1].to_string();
+                    }
+                    let stop_words = get_stop_words_by_language(&column_language_code);
+                    let stemmer = get_stemmer_by_language(&column_language_code);
                     let mut processed_words: Vec<&str> = Vec::new();
                     // eprintln!("DbFolderItem.index :: column_id: {} column_value: {}", column_id, column_value);
                     let words: Vec<&str> = column_value.split(" ").collect();
@@ -2315,13 +4456,36 @@ impl FolderItem for TreeFolderItem {
         let id_db = xid::Id::from_str(id.as_str()).unwrap();
         let id_db = id_db.as_bytes();
         let shared_key: SharedKey = SharedKey::from_array(CHILD_PRIVATE_KEY_ARRAY);
+        // unique columns and keys
+        let folder_id = self.folder_id.clone().unwrap_or_default();
+        let folder = self.tree_folder.get(&folder_id)?;
+        let (db, _) = self.open_partition_by_item(&id)?;
+        let mut previous: Option<DbData> = None;
+        let item_db = db.get(id_db);
+        if item_db.is_ok() {
+            let item_db = item_db.unwrap();
+            if item_db.is_some() {
+                let item = EncryptedMessage::deserialize(item_db.unwrap().to_vec());
+                if item.is_ok() {
+                    let item = DbData::decrypt_owned(&item.unwrap(), &shared_key);
+                    if item.is_ok() {
+                        previous = Some(item.unwrap());
+                    }
+                }
+            }
+        }
+        self.write_unique(&folder, &db_data, None)?;
         let encrypted_data = db_data.encrypt(&shared_key).unwrap();
         let encoded: Vec<u8> = encrypted_data.serialize();
-        let (db, _) = self.open_partition_by_item(&id)?;
         let response = &db.insert(id_db, encoded);
         match response {
             Ok(_) => {
                 let response = response.clone().unwrap();
+                if response.is_none() {
+                    self.write_path_index(&folder, &db_data, None)?;
+                    self.write_geo_index(&folder, &db_data, None)?;
+                    return Ok(db_data)
+                }
                 let item_db = response.unwrap().to_vec();
                 let item = EncryptedMessage::deserialize(
                     item_db
@@ -2333,6 +4497,9 @@ impl FolderItem for TreeFolderItem {
                 match item {
                     Ok(_) => {
                         let item = item.unwrap();
+                        self.write_unique(&folder, &db_data, Some(item.clone()))?;
+                        self.write_path_index(&folder, &db_data, Some(item.clone()))?;
+                        self.write_geo_index(&folder, &db_data, Some(item.clone()))?;
                         Ok(item)
                     },
                     Err(_) => {
@@ -2347,6 +4514,7 @@ impl FolderItem for TreeFolderItem {
                 }
             },
             Err(_) => {
+                let _ = self.release_unique(&folder, &db_data, previous);
                 Err(PlanetError::new(500, Some(tr!("Could not update data"))))
             }
         }
@@ -2761,3 +4929,273 @@ pub fn get_value_list(list: &Vec<BTreeMap<String, String>>) -> Option<String> {
     }
     return None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_database() -> sled::Db {
+        return sled::Config::new().temporary(true).open().unwrap()
+    }
+
+    fn get_tree_folder(database: &sled::Db) -> TreeFolder {
+        let tree_folder = TreeFolder{
+            database: database.clone(),
+            home_dir: Some(String::from("")),
+            account_id: Some(String::from("")),
+            space_id: Some(PRIVATE.to_string()),
+            site_id: None,
+            box_id: None,
+            tree: database.open_tree("folders.db").unwrap(),
+        };
+        return tree_folder
+    }
+
+    fn get_folder_item(database: &sled::Db, folder_id: &String, home_dir: &str) -> TreeFolderItem {
+        let mut connection_pool: HashMap<String, sled::Db> = HashMap::new();
+        connection_pool.insert(PRIVATE.to_string(), database.clone());
+        let tree_folder = get_tree_folder(database);
+        let folder_item = TreeFolderItem::defaults(
+            connection_pool, 
+            home_dir, 
+            "", 
+            PRIVATE, 
+            None, 
+            folder_id.as_str(), 
+            &tree_folder
+        ).unwrap();
+        return folder_item
+    }
+
+    fn get_unique_folder() -> DbData {
+        let mut column: BTreeMap<String, String> = BTreeMap::new();
+        column.insert(ID.to_string(), String::from("c1"));
+        column.insert(NAME.to_string(), String::from("Code"));
+        column.insert(COLUMN_TYPE.to_string(), COLUMN_TYPE_SMALL_TEXT.to_string());
+        column.insert(UNIQUE.to_string(), TRUE.to_string());
+        let mut data: BTreeMap<String, Vec<BTreeMap<String, String>>> = BTreeMap::new();
+        data.insert(COLUMNS.to_string(), vec![column]);
+        let folder = DbData::defaults(&String::from("Codes"), Some(data), None, None, None, None).unwrap();
+        return folder
+    }
+
+    fn get_code_item(id: &Option<String>, code: &str) -> DbData {
+        let mut value: BTreeMap<String, String> = BTreeMap::new();
+        value.insert(VALUE.to_string(), code.to_string());
+        let mut data: BTreeMap<String, Vec<BTreeMap<String, String>>> = BTreeMap::new();
+        data.insert(String::from("c1"), vec![value]);
+        let mut item = DbData::defaults(&String::from("Item"), Some(data), None, None, None, None).unwrap();
+        if id.is_some() {
+            item.id = id.clone();
+        }
+        return item
+    }
+
+    #[test]
+    fn release_unique_on_failed_update() {
+        let database = get_database();
+        let folder = get_unique_folder();
+        let mut folder_item = get_folder_item(&database, &folder.id.clone().unwrap(), "");
+        let previous = get_code_item(&None, "a");
+        folder_item.write_unique(&folder, &previous, None).unwrap();
+        // Update claims new value before write, then write fails
+        let item = get_code_item(&previous.id, "b");
+        folder_item.write_unique(&folder, &item, None).unwrap();
+        folder_item.release_unique(&folder, &item, Some(previous.clone())).unwrap();
+        let other = get_code_item(&None, "b");
+        assert!(folder_item.write_unique(&folder, &other, None).is_ok());
+        let other = get_code_item(&None, "a");
+        assert!(folder_item.write_unique(&folder, &other, None).is_err());
+        // Item keeps its value
+        assert!(folder_item.write_unique(&folder, &previous, None).is_ok());
+    }
+
+    #[test]
+    fn release_unique_keeps_unchanged_values() {
+        let database = get_database();
+        let folder = get_unique_folder();
+        let mut folder_item = get_folder_item(&database, &folder.id.clone().unwrap(), "");
+        let previous = get_code_item(&None, "a");
+        folder_item.write_unique(&folder, &previous, None).unwrap();
+        let item = get_code_item(&previous.id, "a");
+        folder_item.write_unique(&folder, &item, None).unwrap();
+        folder_item.release_unique(&folder, &item, Some(previous.clone())).unwrap();
+        let other = get_code_item(&None, "a");
+        assert!(folder_item.write_unique(&folder, &other, None).is_err());
+        folder_item.remove_unique(&folder, &previous).unwrap();
+        assert!(folder_item.write_unique(&folder, &other, None).is_ok());
+    }
+
+    fn get_home_dir() -> String {
+        let home_dir = std::env::temp_dir().join(generate_id().unwrap());
+        let home_dir = home_dir.to_str().unwrap().to_string();
+        create_dir_all(format!("{}/{}/files", &home_dir, PRIVATE)).unwrap();
+        return home_dir
+    }
+
+    fn get_routing() -> BTreeMap<String, String> {
+        let mut routing: BTreeMap<String, String> = BTreeMap::new();
+        routing.insert(SPACE_ID.to_string(), PRIVATE.to_string());
+        return routing
+    }
+
+    #[test]
+    fn copied_file_read_after_source_dropped() {
+        let database = get_database();
+        let home_dir = get_home_dir();
+        let mut source = get_folder_item(&database, &generate_id().unwrap(), home_dir.as_str());
+        let mut target = get_folder_item(&database, &generate_id().unwrap(), home_dir.as_str());
+        let content = b"copied file content".to_vec();
+        let db_file = DbFile{
+            id: generate_id(),
+            name: Some(String::from("notes.txt")),
+            size: Some(content.len() as u64),
+            content_type: Some(String::from("text/plain")),
+            file_type: None,
+            routing: Some(get_routing()),
+            options: None,
+            context: None,
+            content: Some(content.clone()),
+            path: None,
+            hash: Some(DbFile::get_content_hash(&content)),
+        };
+        let file_id = source.write_file(&db_file).unwrap();
+        let file_id_copied = source.copy_file(&file_id, &mut target).unwrap();
+        assert_ne!(file_id, file_id_copied);
+        source.drop_files().unwrap();
+        let db_file_copied = target.get_file(&file_id_copied).unwrap();
+        assert_eq!(db_file_copied.content, Some(content));
+        let _ = fs::remove_dir_all(&home_dir);
+    }
+
+    #[test]
+    fn copied_encrypted_file_read_after_source_dropped() {
+        let database = get_database();
+        let home_dir = get_home_dir();
+        let mut source = get_folder_item(&database, &generate_id().unwrap(), home_dir.as_str());
+        let mut target = get_folder_item(&database, &generate_id().unwrap(), home_dir.as_str());
+        let content: Vec<u8> = (0..2000).map(|index| (index % 251) as u8).collect();
+        let path_content = format!("{}/content.bin", &home_dir);
+        fs::write(&path_content, &content).unwrap();
+        // File written before contents were stored by hash, file id in path
+        let file_id = generate_id().unwrap();
+        let path = format!("{}/{}/files/{}.achieverenc", &home_dir, PRIVATE, &file_id);
+        let mut db_file = DbFile{
+            id: Some(file_id.clone()),
+            name: Some(String::from("content.bin")),
+            size: Some(content.len() as u64),
+            content_type: Some(String::from("application/octet-stream")),
+            file_type: None,
+            routing: Some(get_routing()),
+            options: None,
+            context: None,
+            content: None,
+            path: Some(path.clone()),
+            hash: None,
+        };
+        let mut file = File::open(&path_content).unwrap();
+        db_file.write_file(&mut file, &source).unwrap();
+        let file_id_copied = source.copy_file(&file_id, &mut target).unwrap();
+        source.drop_files().unwrap();
+        assert!(!std::path::Path::new(&path).exists());
+        let db_file_copied = target.get_file(&file_id_copied).unwrap();
+        let path_copied = db_file_copied.path.clone().unwrap();
+        assert_ne!(path_copied, path);
+        assert!(std::path::Path::new(&path_copied).exists());
+        assert_eq!(db_file_copied.get_encrypted_file_hash().unwrap(), DbFile::get_content_hash(&content));
+        let _ = fs::remove_dir_all(&home_dir);
+    }
+
+    #[test]
+    fn copied_sequences_continue_numbers() {
+        let database = get_database();
+        let tree_folder = get_tree_folder(&database);
+        let folder_id = generate_id().unwrap();
+        let target_folder_id = generate_id().unwrap();
+        let key = String::from("c1");
+        let key_period = String::from("c2:2026-10");
+        assert_eq!(tree_folder.next_sequence(&folder_id, &key, 1, 1).unwrap(), 1);
+        assert_eq!(tree_folder.next_sequence(&folder_id, &key, 1, 1).unwrap(), 2);
+        assert_eq!(tree_folder.next_sequence(&folder_id, &key_period, 10, 5).unwrap(), 10);
+        let mut column_id_map: BTreeMap<String, String> = BTreeMap::new();
+        column_id_map.insert(String::from("c1"), String::from("n1"));
+        column_id_map.insert(String::from("c2"), String::from("n2"));
+        tree_folder.copy_sequences(&folder_id, &tree_folder, &target_folder_id, &column_id_map).unwrap();
+        // Insert into copied folder takes next number after source last one
+        let key_target = String::from("n1");
+        let key_period_target = String::from("n2:2026-10");
+        assert_eq!(tree_folder.next_sequence(&target_folder_id, &key_target, 1, 1).unwrap(), 3);
+        assert_eq!(tree_folder.next_sequence(&target_folder_id, &key_period_target, 10, 5).unwrap(), 15);
+        // Source keeps its own sequence
+        assert_eq!(tree_folder.next_sequence(&folder_id, &key, 1, 1).unwrap(), 3);
+    }
+
+    fn get_encrypted_db_file(content: &Vec<u8>, home_dir: &String) -> DbFile {
+        let hash = DbFile::get_content_hash(content);
+        let db_file = DbFile{
+            id: generate_id(),
+            name: Some(String::from("content.bin")),
+            size: Some(content.len() as u64),
+            content_type: Some(String::from("application/octet-stream")),
+            file_type: None,
+            routing: Some(get_routing()),
+            options: None,
+            context: None,
+            content: None,
+            path: Some(format!("{}/{}/files/{}.achieverenc", home_dir, PRIVATE, &hash)),
+            hash: Some(hash),
+        };
+        return db_file
+    }
+
+    #[test]
+    fn released_file_blob_removed_with_last_reference() {
+        let database = get_database();
+        let home_dir = get_home_dir();
+        let mut folder_item = get_folder_item(&database, &generate_id().unwrap(), home_dir.as_str());
+        let content: Vec<u8> = (0..2000).map(|index| (index % 251) as u8).collect();
+        let path_content = format!("{}/content.bin", &home_dir);
+        fs::write(&path_content, &content).unwrap();
+        let mut db_file = get_encrypted_db_file(&content, &home_dir);
+        let mut db_file_other = get_encrypted_db_file(&content, &home_dir);
+        let hash = db_file.hash.clone().unwrap();
+        let path = db_file.path.clone().unwrap();
+        let file_id = db_file.write_file(&mut File::open(&path_content).unwrap(), &folder_item).unwrap();
+        let file_id_other = db_file_other.write_file(&mut File::open(&path_content).unwrap(), &folder_item).unwrap();
+        assert_eq!(folder_item.get_file_content(&hash).unwrap().unwrap().references, 2);
+        folder_item.delete_file(&file_id).unwrap();
+        assert!(std::path::Path::new(&path).exists());
+        folder_item.delete_file(&file_id_other).unwrap();
+        assert!(!std::path::Path::new(&path).exists());
+        assert!(folder_item.get_file_content(&hash).unwrap().is_none());
+        // Same content written again gets encrypted file back
+        let mut db_file = get_encrypted_db_file(&content, &home_dir);
+        let file_id = db_file.write_file(&mut File::open(&path_content).unwrap(), &folder_item).unwrap();
+        assert_eq!(folder_item.get_file_content(&hash).unwrap().unwrap().references, 1);
+        let db_file = folder_item.get_file(&file_id).unwrap();
+        assert_eq!(db_file.get_encrypted_file_hash().unwrap(), hash);
+        let _ = fs::remove_dir_all(&home_dir);
+    }
+
+    #[test]
+    fn file_reference_not_added_while_blob_deleted() {
+        let database = get_database();
+        let home_dir = get_home_dir();
+        let mut folder_item = get_folder_item(&database, &generate_id().unwrap(), home_dir.as_str());
+        let content: Vec<u8> = (0..2000).map(|index| (index % 251) as u8).collect();
+        let db_file = get_encrypted_db_file(&content, &home_dir);
+        let hash = db_file.hash.clone().unwrap();
+        // Last reference released, encrypted file still being deleted
+        let file_content = DbFileContent{
+            hash: hash.clone(),
+            size: content.len() as u64,
+            references: 0,
+            content: None,
+            path: db_file.path.clone(),
+        };
+        folder_item.update_file_content(&hash, |_| Some(file_content.clone())).unwrap();
+        assert!(folder_item.write_file(&db_file).is_err());
+        assert_eq!(folder_item.get_file_content(&hash).unwrap().unwrap().references, 0);
+        let _ = fs::remove_dir_all(&home_dir);
+    }
+}
@@ -4,6 +4,7 @@ pub mod folder;
 pub mod constants;
 pub mod columns;
 pub mod space;
+pub mod extractors;
 
 use std::collections::{BTreeMap,HashMap};
 
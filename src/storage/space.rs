@@ -110,4 +110,62 @@ impl SpaceDatabase {
         };
         return Ok(obj)
     }
+    pub fn with_space(
+        &self,
+        site_id: Option<String>, 
+        space_id: &str, 
+        home_dir: Option<&str>,
+    ) -> Result<Self, PlanetError> {
+        // Databases already open are shared, sled does not allow opening same database twice
+        let home_dir = home_dir.unwrap_or_default();
+        let mut connection_pool = self.connection_pool.clone();
+        let mut databases: Vec<(String, String)> = Vec::new();
+        if site_id.is_none() {
+            databases.push((
+                PRIVATE.to_string(), 
+                format!("{home}/private/database.db", home=&home_dir)
+            ));
+            databases.push((
+                WORKSPACE.to_string(), 
+                format!("{home}/workspace.db", home=&home_dir)
+            ));
+        } else {
+            let site_id = site_id.clone().unwrap();
+            databases.push((
+                space_id.to_string(), 
+                format!(
+                    "{home}/sites/{site_id}/spaces/{space_id}/database.db", 
+                    site_id=site_id, 
+                    space_id=&space_id,
+                    home=&home_dir
+                )
+            ));
+            databases.push((
+                site_id.clone(), 
+                format!("{home}/sites/{site_id}/site.db", site_id=site_id, home=&home_dir)
+            ));
+        }
+        for (key, path) in databases {
+            if connection_pool.contains_key(&key) {
+                continue
+            }
+            let config: sled::Config = sled::Config::default()
+            .use_compression(site_id.is_some())
+            .path(path);
+            let result= config.open();
+            if result.is_err() {
+                return Err(
+                    PlanetError::new(
+                        500, 
+                        Some(tr!("Error opening database.db on space \"{}\".", space_id)),
+                    )
+                )
+            }
+            connection_pool.insert(key, result.unwrap());
+        }
+        let obj = Self{
+            connection_pool: connection_pool
+        };
+        return Ok(obj)
+    }
 }
\ No newline at end of file